@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Wire values of `reason` on /api/ai and /api/ai/summarize responses. The
+// server owns the status and message for each code; both sides share the
+// names so neither has to match on strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    EmptyQuestion,
+    QuestionTooLong,
+    UnintelligibleInput,
+    EmbedTokenMissing,
+    EmbedTokenInvalid,
+    PerIpBurst,
+    PerIpMinute,
+    PerIpHour,
+    PerIpDay,
+    MinuteBudget,
+    HourBudget,
+    DayBudget,
+    MonthBudget,
+    DeadlineExceeded,
+    BackendError,
+    DryRun,
+    FaqMatch,
+    UnknownSection,
+    SectionUnavailable,
+    // Codes from a newer server than this build still parse.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ReasonCode {
+    pub const ALL: [ReasonCode; 20] = [
+        ReasonCode::EmptyQuestion,
+        ReasonCode::QuestionTooLong,
+        ReasonCode::UnintelligibleInput,
+        ReasonCode::EmbedTokenMissing,
+        ReasonCode::EmbedTokenInvalid,
+        ReasonCode::PerIpBurst,
+        ReasonCode::PerIpMinute,
+        ReasonCode::PerIpHour,
+        ReasonCode::PerIpDay,
+        ReasonCode::MinuteBudget,
+        ReasonCode::HourBudget,
+        ReasonCode::DayBudget,
+        ReasonCode::MonthBudget,
+        ReasonCode::DeadlineExceeded,
+        ReasonCode::BackendError,
+        ReasonCode::DryRun,
+        ReasonCode::FaqMatch,
+        ReasonCode::UnknownSection,
+        ReasonCode::SectionUnavailable,
+        ReasonCode::Unknown,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReasonCode::EmptyQuestion => "empty_question",
+            ReasonCode::QuestionTooLong => "question_too_long",
+            ReasonCode::UnintelligibleInput => "unintelligible_input",
+            ReasonCode::EmbedTokenMissing => "embed_token_missing",
+            ReasonCode::EmbedTokenInvalid => "embed_token_invalid",
+            ReasonCode::PerIpBurst => "per_ip_burst",
+            ReasonCode::PerIpMinute => "per_ip_minute",
+            ReasonCode::PerIpHour => "per_ip_hour",
+            ReasonCode::PerIpDay => "per_ip_day",
+            ReasonCode::MinuteBudget => "minute_budget",
+            ReasonCode::HourBudget => "hour_budget",
+            ReasonCode::DayBudget => "day_budget",
+            ReasonCode::MonthBudget => "month_budget",
+            ReasonCode::DeadlineExceeded => "deadline_exceeded",
+            ReasonCode::BackendError => "backend_error",
+            ReasonCode::DryRun => "dry_run",
+            ReasonCode::FaqMatch => "faq_match",
+            ReasonCode::UnknownSection => "unknown_section",
+            ReasonCode::SectionUnavailable => "section_unavailable",
+            ReasonCode::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_limit(self) -> bool {
+        matches!(
+            self,
+            ReasonCode::PerIpBurst
+                | ReasonCode::PerIpMinute
+                | ReasonCode::PerIpHour
+                | ReasonCode::PerIpDay
+                | ReasonCode::MinuteBudget
+                | ReasonCode::HourBudget
+                | ReasonCode::DayBudget
+                | ReasonCode::MonthBudget
+        )
+    }
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // No wildcard arm: a new variant does not compile until it has a slot
+    // here, and the test below then fails until it is listed in `ALL`.
+    fn position(code: ReasonCode) -> usize {
+        match code {
+            ReasonCode::EmptyQuestion => 0,
+            ReasonCode::QuestionTooLong => 1,
+            ReasonCode::UnintelligibleInput => 2,
+            ReasonCode::EmbedTokenMissing => 3,
+            ReasonCode::EmbedTokenInvalid => 4,
+            ReasonCode::PerIpBurst => 5,
+            ReasonCode::PerIpMinute => 6,
+            ReasonCode::PerIpHour => 7,
+            ReasonCode::PerIpDay => 8,
+            ReasonCode::MinuteBudget => 9,
+            ReasonCode::HourBudget => 10,
+            ReasonCode::DayBudget => 11,
+            ReasonCode::MonthBudget => 12,
+            ReasonCode::DeadlineExceeded => 13,
+            ReasonCode::BackendError => 14,
+            ReasonCode::DryRun => 15,
+            ReasonCode::FaqMatch => 16,
+            ReasonCode::UnknownSection => 17,
+            ReasonCode::SectionUnavailable => 18,
+            ReasonCode::Unknown => 19,
+        }
+    }
+
+    #[test]
+    fn all_lists_every_variant_once() {
+        for (index, code) in ReasonCode::ALL.iter().enumerate() {
+            assert_eq!(position(*code), index, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn wire_names_stay_stable() {
+        let names: Vec<String> = ReasonCode::ALL
+            .iter()
+            .map(|code| serde_json::to_string(code).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "\"empty_question\"",
+                "\"question_too_long\"",
+                "\"unintelligible_input\"",
+                "\"embed_token_missing\"",
+                "\"embed_token_invalid\"",
+                "\"per_ip_burst\"",
+                "\"per_ip_minute\"",
+                "\"per_ip_hour\"",
+                "\"per_ip_day\"",
+                "\"minute_budget\"",
+                "\"hour_budget\"",
+                "\"day_budget\"",
+                "\"month_budget\"",
+                "\"deadline_exceeded\"",
+                "\"backend_error\"",
+                "\"dry_run\"",
+                "\"faq_match\"",
+                "\"unknown_section\"",
+                "\"section_unavailable\"",
+                "\"unknown\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn every_code_round_trips_and_matches_as_str() {
+        for code in ReasonCode::ALL {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+            assert_eq!(code.to_string(), code.as_str());
+            let parsed: ReasonCode = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, code);
+        }
+    }
+
+    #[test]
+    fn unrecognised_codes_parse_as_unknown() {
+        let parsed: ReasonCode = serde_json::from_str("\"cached\"").unwrap();
+        assert_eq!(parsed, ReasonCode::Unknown);
+        let missing: Option<ReasonCode> = serde_json::from_str("null").unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn only_limiter_codes_count_as_limits() {
+        let limits: Vec<&str> = ReasonCode::ALL
+            .iter()
+            .filter(|code| code.is_limit())
+            .map(|code| code.as_str())
+            .collect();
+        assert_eq!(
+            limits,
+            [
+                "per_ip_burst",
+                "per_ip_minute",
+                "per_ip_hour",
+                "per_ip_day",
+                "minute_budget",
+                "hour_budget",
+                "day_budget",
+                "month_budget",
+            ]
+        );
+    }
+}
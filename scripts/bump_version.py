@@ -104,6 +104,7 @@ def main() -> int:
     version_file = repo_root / "VERSION"
     workspace_manifest = repo_root / "Cargo.toml"
     server_manifest = repo_root / "server" / "Cargo.toml"
+    model_manifest = repo_root / "model" / "Cargo.toml"
 
     try:
         current_version = read_current_version(version_file, workspace_manifest)
@@ -112,6 +113,7 @@ def main() -> int:
         if not update_manifest(workspace_manifest, new_version):
             raise ValueError(f"Failed to update version in {workspace_manifest}")
         ensure_workspace_version(server_manifest)
+        ensure_workspace_version(model_manifest)
     except Exception as exc:  # noqa: BLE001
         print(f"[bump-version] error: {exc}", file=sys.stderr)
         return 1
@@ -14,4 +14,18 @@ fn main() {
             }
         }
     }
+
+    if let Ok(output) = Command::new("date")
+        .args(["-u", "+%Y-%m-%dT%H:%M:%SZ"])
+        .output()
+    {
+        if output.status.success() {
+            if let Ok(stamp) = String::from_utf8(output.stdout) {
+                let trimmed = stamp.trim();
+                if !trimmed.is_empty() {
+                    println!("cargo:rustc-env=BUILD_TIMESTAMP={trimmed}");
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,2125 @@
+use crate::backend_stats::{BackendStats, BackendUsage};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::deadline::Deadline;
+use crate::generation::{GenerationMode, GenerationPreset, GenerationPresets};
+use crate::prompt::{
+    continuation_prompt, estimate_tokens, trim_history, AiPrompt, ConversationTurn, KnowledgeBase,
+    HISTORY_MAX_TOKENS,
+};
+use crate::provider_error::ProviderReason;
+use crate::summary::SUMMARY_MAX_COMPLETION_TOKENS;
+use crate::{backend_stats, provider_error};
+use anyhow::{anyhow, Context};
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const GOOGLE_MODEL_NAME: &str = "gemini-2.5-flash-lite";
+const GOOGLE_ENDPOINT: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash-lite:generateContent";
+pub const GROQ_MODEL_NAME: &str = "llama-3.1-8b-instant";
+const GROQ_ENDPOINT: &str = "https://api.groq.com/openai/v1/chat/completions";
+pub const OPENAI_MODEL_NAME: &str = "gpt-4o-mini";
+const OPENAI_ENDPOINT: &str = "https://api.openai.com/v1/chat/completions";
+pub const FIXTURE_BACKEND_NAME: &str = "fixture";
+pub const FIXTURE_MODEL_NAME: &str = "fixture-model";
+const GROQ_PRIORITY: i32 = 10;
+const GOOGLE_PRIORITY: i32 = 20;
+const OPENAI_PRIORITY: i32 = 30;
+const EXTRA_BACKEND_DEFAULT_PRIORITY: i32 = 100;
+const BUILTIN_BACKEND_NAMES: [&str; 3] = ["groq", "google", "openai"];
+pub const MAX_COMPLETION_TOKENS: usize = 384;
+pub const DEFAULT_GENERATION_PRESETS: GenerationPresets = GenerationPresets {
+    answer: GenerationPreset::new(MAX_COMPLETION_TOKENS),
+    summary: GenerationPreset::new(SUMMARY_MAX_COMPLETION_TOKENS),
+    follow_up: GenerationPreset::new(MAX_COMPLETION_TOKENS),
+};
+const INPUT_COST_EUR_PER_1K: f64 = 0.000552; // Converted from $0.0006 ≈ €0.000552 (fx ~0.92)
+const OUTPUT_COST_EUR_PER_1K: f64 = 0.002208; // Converted from $0.0024 ≈ €0.002208
+const OPENAI_PRICING: BackendPricing = BackendPricing {
+    input_per_1k: INPUT_COST_EUR_PER_1K,
+    output_per_1k: OUTPUT_COST_EUR_PER_1K,
+};
+const WARMUP_TIMEOUT: Duration = Duration::from_secs(5);
+const STREAM_TIMEOUT: Duration = Duration::from_secs(15);
+// Backends are skipped once less than this is left before the request deadline.
+const MIN_BACKEND_ATTEMPT: Duration = Duration::from_secs(3);
+pub const STREAM_TRUNCATION_MARKER: &str = "(response truncated)";
+const WARMUP_SYSTEM_PROMPT: &str = "Reply with the single word pong.";
+const WARMUP_PROMPT: &str = "ping";
+
+#[derive(Clone)]
+pub struct AiClient {
+    pub http: reqwest::Client,
+    pub backends: Vec<ApiBackend>,
+    pub stream_timeout: Duration,
+    pub min_attempt: Duration,
+    pub presets: GenerationPresets,
+    pub preset: GenerationPreset,
+    pub stats: Arc<std::sync::Mutex<BackendStats>>,
+    pub fixture: bool,
+    pub allow_paid_first: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackendProtocol {
+    OpenAiChat,
+    GoogleGenerate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BackendPricing {
+    input_per_1k: f64,
+    output_per_1k: f64,
+}
+
+#[derive(Clone)]
+pub struct ApiBackend {
+    pub name: String,
+    pub label: String,
+    aliases: &'static [&'static str],
+    protocol: BackendProtocol,
+    endpoint: String,
+    pub model: String,
+    api_key: Arc<String>,
+    pub priority: i32,
+    pricing: Option<BackendPricing>,
+    stream: bool,
+    pub breaker: Arc<CircuitBreaker>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WarmupReport {
+    pub backend: String,
+    pub reachable: bool,
+    pub latency_ms: u128,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtraBackendConfig {
+    name: String,
+    endpoint: String,
+    model: String,
+    api_key_env: String,
+    #[serde(default = "default_extra_backend_priority")]
+    priority: i32,
+    #[serde(default)]
+    cost_per_1k_in: f64,
+    #[serde(default)]
+    cost_per_1k_out: f64,
+    #[serde(default)]
+    stream: bool,
+}
+
+pub struct AiAnswer {
+    pub text: String,
+    pub backend: String,
+    pub model: String,
+    pub cost_eur: f64,
+    pub truncated: bool,
+}
+
+pub struct BackendReply {
+    pub text: String,
+    pub truncated: bool,
+    // Set when the stream stalled: the tokens actually produced, counted
+    // before the truncation marker is appended.
+    partial_tokens: Option<usize>,
+}
+
+impl AiClient {
+    pub fn new(
+        google_key: Option<String>,
+        groq_key: Option<String>,
+        openai_key: Option<String>,
+        extra_backends: Vec<ApiBackend>,
+    ) -> anyhow::Result<Self> {
+        let mut backends: Vec<ApiBackend> = [
+            groq_key.map(ApiBackend::groq),
+            google_key.map(ApiBackend::google),
+            openai_key.map(ApiBackend::openai),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(extra_backends)
+        .collect();
+        if backends.is_empty() {
+            return Err(anyhow!(
+                "No AI provider configured. Provide GOOGLE_API_KEY, GROQ_API_KEY, OPENAI_API_KEY, or AI_EXTRA_BACKENDS."
+            ));
+        }
+        backends.sort_by_key(|backend| backend.priority);
+        Self::with_backends(backends, false)
+    }
+
+    // Answers every question locally with `fixture_answer`, so browser and
+    // load tests can drive the real `/api/ai` path without keys or spend.
+    pub fn fixture() -> anyhow::Result<Self> {
+        Self::with_backends(Vec::new(), true)
+    }
+
+    // Still answers with `fixture_answer`, but prices every estimate like
+    // OpenAI so the load test spends the budgets as paid traffic would. The
+    // backend has no endpoint or key, so it can never be reached.
+    pub fn fixture_priced_like_openai() -> anyhow::Result<Self> {
+        let priced = ApiBackend {
+            endpoint: String::new(),
+            ..ApiBackend::openai(String::new())
+        };
+        Self::with_backends(vec![priced], true)
+    }
+
+    pub fn with_backends(backends: Vec<ApiBackend>, fixture: bool) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(20))
+            .build()?;
+
+        Ok(Self {
+            http,
+            backends,
+            stream_timeout: STREAM_TIMEOUT,
+            min_attempt: MIN_BACKEND_ATTEMPT,
+            presets: DEFAULT_GENERATION_PRESETS,
+            preset: DEFAULT_GENERATION_PRESETS.answer,
+            stats: Default::default(),
+            fixture,
+            allow_paid_first: false,
+        })
+    }
+
+    pub fn with_backend_order(self, raw: &str, allow_paid_first: bool) -> anyhow::Result<Self> {
+        Ok(Self {
+            backends: apply_backend_order(self.backends, raw, allow_paid_first)?,
+            allow_paid_first,
+            ..self
+        })
+    }
+
+    pub fn with_paid_first(self, allow_paid_first: bool) -> Self {
+        Self {
+            allow_paid_first,
+            ..self
+        }
+    }
+
+    pub fn with_presets(self, presets: GenerationPresets) -> Self {
+        Self {
+            presets,
+            preset: presets.answer,
+            ..self
+        }
+    }
+
+    // Same backends and circuit breakers, sampled with another mode's preset;
+    // summaries and follow-ups are billed against their own completion budget.
+    pub fn for_mode(&self, mode: GenerationMode) -> Self {
+        Self {
+            preset: self.presets.get(mode),
+            ..self.clone()
+        }
+    }
+
+    pub fn lock_stats(&self) -> std::sync::MutexGuard<'_, BackendStats> {
+        self.stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn usage(&self) -> Vec<BackendUsage> {
+        self.lock_stats().snapshot(Utc::now())
+    }
+
+    pub fn record_quota_error(&self, backend: &ApiBackend, error: &BackendError) {
+        let BackendError::ApiFailure(status, body) = error else {
+            return;
+        };
+        let Some(reason) = backend_stats::quota_reason(*status, body) else {
+            return;
+        };
+        let first_this_hour =
+            self.lock_stats()
+                .record_quota_error(&backend.name, reason, Utc::now());
+        if first_this_hour {
+            warn!(
+                target: "ai",
+                model = backend.model.as_str(),
+                reason = reason.as_str(),
+                "{} backend started returning quota errors",
+                backend.label
+            );
+        }
+    }
+
+    pub fn primary_model(&self) -> Option<&str> {
+        if self.fixture {
+            return Some(FIXTURE_MODEL_NAME);
+        }
+        self.backends.first().map(|backend| backend.model.as_str())
+    }
+
+    pub fn resolve_preferred(&self, name: &str) -> Option<&str> {
+        self.backends
+            .iter()
+            .find(|backend| backend.matches(name))
+            .map(|backend| backend.name.as_str())
+    }
+
+    // A visitor's preferred backend leads the chain, except that a paid one
+    // only moves ahead of the other paid backends unless paid-first is allowed.
+    pub fn backend_order(&self, preferred: Option<&str>) -> Vec<&ApiBackend> {
+        let mut order: Vec<&ApiBackend> = self.backends.iter().collect();
+        if let Some(preferred) = preferred {
+            if let Some(index) = order.iter().position(|backend| backend.name == preferred) {
+                let backend = order.remove(index);
+                let slot = if backend.is_paid() && !self.allow_paid_first {
+                    order
+                        .iter()
+                        .position(|backend| backend.is_paid())
+                        .unwrap_or(order.len())
+                } else {
+                    0
+                };
+                order.insert(slot, backend);
+            }
+        }
+        order
+    }
+
+    pub fn holds_back(&self, preferred: &str) -> bool {
+        self.backend_order(Some(preferred))
+            .first()
+            .is_some_and(|backend| backend.name != preferred)
+    }
+
+    // Backends that would answer first if a visitor preferred them, in the
+    // server's order; held-back paid backends and open breakers are left out.
+    pub fn leading_backends(&self) -> Vec<&str> {
+        self.backend_order(None)
+            .into_iter()
+            .map(|backend| backend.name.as_str())
+            .filter(|name| {
+                self.attempt_order(Some(name))
+                    .first()
+                    .is_some_and(|first| first.name == *name)
+            })
+            .collect()
+    }
+
+    // Backends with an open circuit are skipped unless every one is open.
+    pub fn attempt_order(&self, preferred: Option<&str>) -> Vec<&ApiBackend> {
+        let mut order = self.backend_order(preferred);
+        if order.iter().any(|backend| !backend.breaker.is_open()) {
+            order.retain(|backend| !backend.breaker.is_open());
+        }
+        order
+    }
+
+    pub fn estimate_cost(&self, input_tokens: usize, preferred: Option<&str>) -> f64 {
+        self.backend_order(preferred)
+            .first()
+            .map(|backend| backend.cost(input_tokens, self.preset.max_tokens))
+            .unwrap_or(0.0)
+    }
+
+    pub async fn ask(
+        &self,
+        knowledge: &KnowledgeBase,
+        prompt: AiPrompt<'_>,
+        input_tokens: usize,
+        preferred: Option<&str>,
+        deadline: &Deadline,
+    ) -> Result<AiAnswer, AiClientError> {
+        if self.fixture {
+            return Ok(fixture_answer(&prompt));
+        }
+        let mut failures = Vec::new();
+        let mut skipped = 0;
+        let user_prompt = prompt.user_prompt();
+        let question_chars = prompt.question.len();
+        let order = self.attempt_order(preferred);
+
+        for (position, backend) in order.iter().copied().enumerate() {
+            if !deadline.allows(self.min_attempt) {
+                info!(
+                    target: "ai",
+                    model = backend.model.as_str(),
+                    remaining_ms = u64::try_from(deadline.remaining().as_millis()).unwrap_or(u64::MAX),
+                    "Skipping {} backend: not enough time left before the request deadline",
+                    backend.label
+                );
+                skipped += 1;
+                continue;
+            }
+            let cost_eur = backend.cost(input_tokens, self.preset.max_tokens);
+            self.lock_stats().record_request(&backend.name, Utc::now());
+            let dispatch = self.dispatch(
+                backend,
+                &knowledge.system_prompt,
+                prompt.history,
+                &user_prompt,
+                question_chars,
+                cost_eur,
+            );
+            let result = tokio::time::timeout(deadline.remaining(), dispatch)
+                .await
+                .unwrap_or(Err(BackendError::DeadlineExceeded));
+
+            match result {
+                Ok(reply) => {
+                    backend.breaker.record_success();
+                    // A stream cut short only produced part of the completion,
+                    // so only that part is billed.
+                    let cost_eur = match reply.partial_tokens {
+                        Some(output_tokens) => backend.cost(input_tokens, output_tokens),
+                        None => cost_eur,
+                    };
+                    return Ok(AiAnswer {
+                        text: reply.text,
+                        backend: backend.name.clone(),
+                        model: backend.model.clone(),
+                        cost_eur,
+                        truncated: reply.truncated,
+                    });
+                }
+                Err(error) => {
+                    match order.get(position + 1) {
+                        Some(next) => warn!(
+                            target: "ai",
+                            model = backend.model.as_str(),
+                            error = %error,
+                            reason = error.provider_reason().map(ProviderReason::as_str),
+                            fallback = format!("{} fallback", next.label),
+                            "{} backend error",
+                            backend.label
+                        ),
+                        None => error!(
+                            target: "ai",
+                            model = backend.model.as_str(),
+                            error = %error,
+                            reason = error.provider_reason().map(ProviderReason::as_str),
+                            "{} backend failed with no fallback available",
+                            backend.label
+                        ),
+                    }
+                    // Running out of request time says nothing about the backend.
+                    if !matches!(error, BackendError::DeadlineExceeded) {
+                        backend.breaker.record_failure();
+                    }
+                    self.record_quota_error(backend, &error);
+                    failures.push(BackendFailure::new(backend.label.clone(), error));
+                }
+            }
+        }
+
+        if skipped > 0 || deadline.is_expired() {
+            Err(AiClientError::DeadlineExceeded(failures))
+        } else if failures.is_empty() {
+            Err(AiClientError::NoBackendConfigured)
+        } else {
+            Err(AiClientError::AllBackendsFailed(failures))
+        }
+    }
+
+    // One follow-up call to the backend that produced a truncated answer. The
+    // caller gates it on the spend limiter with `continuation_cost`.
+    pub async fn continue_answer(
+        &self,
+        knowledge: &KnowledgeBase,
+        prompt: &AiPrompt<'_>,
+        answer: &AiAnswer,
+        cost_eur: f64,
+    ) -> Result<BackendReply, BackendError> {
+        let backend = self
+            .backends
+            .iter()
+            .find(|backend| backend.name == answer.backend)
+            .ok_or(BackendError::EmptyAnswer)?;
+        let user_prompt = continuation_prompt(&prompt.user_prompt(), &answer.text);
+        self.for_mode(GenerationMode::FollowUp)
+            .dispatch(
+                backend,
+                &knowledge.system_prompt,
+                &[],
+                &user_prompt,
+                prompt.question.len(),
+                cost_eur,
+            )
+            .await
+    }
+
+    pub fn continuation_cost(&self, answer: &AiAnswer, input_tokens: usize) -> f64 {
+        self.backends
+            .iter()
+            .find(|backend| backend.name == answer.backend)
+            .map(|backend| {
+                backend.cost(
+                    input_tokens + estimate_tokens(&answer.text),
+                    self.presets.follow_up.max_tokens,
+                )
+            })
+            .unwrap_or(0.0)
+    }
+
+    pub async fn warm_up(&self) -> Vec<WarmupReport> {
+        let mut reports = Vec::with_capacity(self.backends.len());
+        for backend in &self.backends {
+            let started = std::time::Instant::now();
+            let probe = self.dispatch(
+                backend,
+                WARMUP_SYSTEM_PROMPT,
+                &[],
+                WARMUP_PROMPT,
+                WARMUP_PROMPT.len(),
+                0.0,
+            );
+            let outcome = match tokio::time::timeout(WARMUP_TIMEOUT, probe).await {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err(err)) => Err(err.to_string()),
+                Err(_) => Err(format!("no answer within {}s", WARMUP_TIMEOUT.as_secs())),
+            };
+            let latency_ms = started.elapsed().as_millis();
+            let reachable = match outcome {
+                Ok(()) => {
+                    backend.breaker.record_success();
+                    info!(
+                        target: "ai",
+                        backend = backend.label.as_str(),
+                        model = backend.model.as_str(),
+                        latency_ms = latency_ms as u64,
+                        "AI backend reachable"
+                    );
+                    true
+                }
+                Err(error) => {
+                    backend.breaker.trip();
+                    warn!(
+                        target: "ai",
+                        backend = backend.label.as_str(),
+                        model = backend.model.as_str(),
+                        latency_ms = latency_ms as u64,
+                        error = error.as_str(),
+                        "AI backend unreachable at startup; circuit opened"
+                    );
+                    false
+                }
+            };
+            reports.push(WarmupReport {
+                backend: backend.label.clone(),
+                reachable,
+                latency_ms,
+            });
+        }
+        reports
+    }
+
+    pub async fn dispatch(
+        &self,
+        backend: &ApiBackend,
+        system_prompt: &str,
+        history: &[ConversationTurn],
+        user_prompt: &str,
+        question_chars: usize,
+        cost_eur: f64,
+    ) -> Result<BackendReply, BackendError> {
+        // Only the Gemini payload carries earlier turns so far; chat
+        // backends still receive a single-turn request.
+        match backend.protocol {
+            BackendProtocol::OpenAiChat => {
+                self.ask_backend(
+                    backend,
+                    system_prompt,
+                    user_prompt,
+                    question_chars,
+                    cost_eur,
+                )
+                .await
+            }
+            BackendProtocol::GoogleGenerate => {
+                self.ask_google(backend, system_prompt, history, user_prompt, question_chars)
+                    .await
+            }
+        }
+    }
+
+    pub async fn ask_google(
+        &self,
+        backend: &ApiBackend,
+        system_prompt: &str,
+        history: &[ConversationTurn],
+        user_prompt: &str,
+        question_chars: usize,
+    ) -> Result<BackendReply, BackendError> {
+        let payload = GoogleGenerateRequest::new(system_prompt, history, user_prompt)
+            .with_preset(self.preset);
+        let response = self
+            .http
+            .post(backend.endpoint.as_str())
+            .header("x-goog-api-key", backend.api_key.as_str())
+            .json(&payload)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(BackendError::ApiFailure(status, detail));
+        }
+
+        let body: GoogleGenerateResponse = response.json().await?;
+        let answer = google_reply(body)?;
+
+        info!(
+            target: "ai",
+            cost_eur = 0.0,
+            chars = question_chars,
+            model = backend.model.as_str(),
+            msg = "AI response generated by backend"
+        );
+        Ok(answer)
+    }
+
+    pub async fn ask_backend(
+        &self,
+        backend: &ApiBackend,
+        system_prompt: &str,
+        user_prompt: &str,
+        question_chars: usize,
+        cost_eur: f64,
+    ) -> Result<BackendReply, BackendError> {
+        let mut payload =
+            ChatRequest::new(&backend.model, system_prompt, user_prompt).with_preset(self.preset);
+        payload.stream = backend.stream;
+        let response = self
+            .http
+            .post(backend.endpoint.as_str())
+            .bearer_auth(backend.api_key.as_str())
+            .json(&payload)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(BackendError::ApiFailure(status, detail));
+        }
+
+        let answer = if backend.stream {
+            self.read_chat_stream(backend, response).await?
+        } else {
+            let body: ChatResponse = response.json().await?;
+            chat_reply(body)?
+        };
+
+        info!(
+            target: "ai",
+            cost_eur,
+            chars = question_chars,
+            model = backend.model.as_str(),
+            msg = "AI response generated by backend"
+        );
+        Ok(answer)
+    }
+
+    // Reads an OpenAI-compatible SSE completion. When the backend stalls or
+    // drops the connection mid-answer, the tokens received so far are served
+    // with a truncation marker instead of failing the whole request.
+    pub async fn read_chat_stream(
+        &self,
+        backend: &ApiBackend,
+        mut response: reqwest::Response,
+    ) -> Result<BackendReply, BackendError> {
+        let deadline = tokio::time::Instant::now() + self.stream_timeout;
+        let mut stream = ChatStream::default();
+        let cut_short = loop {
+            match tokio::time::timeout_at(deadline, response.chunk()).await {
+                Ok(Ok(Some(bytes))) => {
+                    if stream.push(&bytes) {
+                        break None;
+                    }
+                }
+                Ok(Ok(None)) => break None,
+                Ok(Err(err)) => break Some(BackendError::Network(err)),
+                Err(_) => break Some(BackendError::StreamTimeout),
+            }
+        };
+        match cut_short {
+            None => stream.finish(),
+            Some(error) => {
+                let reply = stream.finish_partial().ok_or(error)?;
+                warn!(
+                    target: "ai",
+                    model = backend.model.as_str(),
+                    partial_chars = reply.text.chars().count(),
+                    "AI stream cut short; serving the partial answer"
+                );
+                Ok(reply)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChatStream {
+    buffer: Vec<u8>,
+    text: String,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamChunk {
+    choices: Vec<ChatStreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatStreamChoice {
+    #[serde(default)]
+    delta: Option<ChatChoiceMessage>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+impl ChatStream {
+    // Feeds raw bytes and returns true once the `[DONE]` event arrived.
+    fn push(&mut self, bytes: &[u8]) -> bool {
+        self.buffer.extend_from_slice(bytes);
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:") else {
+                continue;
+            };
+            let data = data.trim();
+            if data == "[DONE]" {
+                return true;
+            }
+            let Ok(chunk) = serde_json::from_str::<ChatStreamChunk>(data) else {
+                continue;
+            };
+            for choice in chunk.choices {
+                if let Some(content) = choice.delta.and_then(|delta| delta.content) {
+                    self.text.push_str(&content);
+                }
+                if choice.finish_reason.is_some() {
+                    self.finish_reason = choice.finish_reason;
+                }
+            }
+        }
+        false
+    }
+
+    fn finish(self) -> Result<BackendReply, BackendError> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return Err(BackendError::EmptyAnswer);
+        }
+        Ok(BackendReply {
+            text: text.to_string(),
+            truncated: is_truncation(self.finish_reason.as_deref()),
+            partial_tokens: None,
+        })
+    }
+
+    fn finish_partial(self) -> Option<BackendReply> {
+        let text = self.text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        Some(BackendReply {
+            text: format!("{text}\n\n{STREAM_TRUNCATION_MARKER}"),
+            truncated: false,
+            partial_tokens: Some(estimate_tokens(text)),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BackendFailure {
+    backend: String,
+    error: BackendError,
+}
+
+impl BackendFailure {
+    fn new(backend: String, error: BackendError) -> Self {
+        Self { backend, error }
+    }
+
+    fn detail(&self, include_raw: bool) -> BackendFailureDetail {
+        BackendFailureDetail {
+            backend: self.backend.clone(),
+            kind: self.error.kind(),
+            status: self.error.status(),
+            reason: self.error.provider_reason(),
+            detail: include_raw.then(|| self.error.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    Timeout,
+    Auth,
+    Quota,
+    Server,
+    Empty,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendFailureDetail {
+    pub backend: String,
+    pub kind: FailureKind,
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<ProviderReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl BackendPricing {
+    pub fn cost(&self, input_tokens: usize, output_tokens: usize) -> f64 {
+        let input_cost = self.input_per_1k * (input_tokens as f64 / 1000.0);
+        let output_cost = self.output_per_1k * (output_tokens as f64 / 1000.0);
+        (input_cost + output_cost).max(0.0)
+    }
+}
+
+impl ApiBackend {
+    fn groq(api_key: String) -> Self {
+        Self {
+            name: "groq".to_string(),
+            label: "Groq".to_string(),
+            aliases: &["llama"],
+            protocol: BackendProtocol::OpenAiChat,
+            endpoint: GROQ_ENDPOINT.to_string(),
+            model: GROQ_MODEL_NAME.to_string(),
+            api_key: Arc::new(api_key),
+            priority: GROQ_PRIORITY,
+            pricing: None,
+            stream: false,
+            breaker: Default::default(),
+        }
+    }
+
+    fn google(api_key: String) -> Self {
+        Self {
+            name: "google".to_string(),
+            label: "Google".to_string(),
+            aliases: &["gemini"],
+            protocol: BackendProtocol::GoogleGenerate,
+            endpoint: GOOGLE_ENDPOINT.to_string(),
+            model: GOOGLE_MODEL_NAME.to_string(),
+            api_key: Arc::new(api_key),
+            priority: GOOGLE_PRIORITY,
+            pricing: None,
+            stream: false,
+            breaker: Default::default(),
+        }
+    }
+
+    fn openai(api_key: String) -> Self {
+        Self {
+            name: "openai".to_string(),
+            label: "OpenAI".to_string(),
+            aliases: &["gpt"],
+            protocol: BackendProtocol::OpenAiChat,
+            endpoint: OPENAI_ENDPOINT.to_string(),
+            model: OPENAI_MODEL_NAME.to_string(),
+            api_key: Arc::new(api_key),
+            priority: OPENAI_PRIORITY,
+            pricing: Some(OPENAI_PRICING),
+            stream: false,
+            breaker: Default::default(),
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let name = name.trim().to_ascii_lowercase();
+        self.name == name
+            || self.model.eq_ignore_ascii_case(&name)
+            || self.aliases.contains(&name.as_str())
+    }
+
+    pub fn cost(&self, input_tokens: usize, output_tokens: usize) -> f64 {
+        self.pricing
+            .map(|pricing| pricing.cost(input_tokens, output_tokens))
+            .unwrap_or(0.0)
+    }
+
+    pub fn is_paid(&self) -> bool {
+        self.pricing.is_some()
+    }
+}
+
+// Canned markdown that only depends on the prompt, so identical requests get
+// identical answers.
+fn fixture_answer(prompt: &AiPrompt<'_>) -> AiAnswer {
+    let chunk_ids = prompt
+        .context
+        .unwrap_or_default()
+        .iter()
+        .map(|chunk| format!("`{}`", chunk.id))
+        .collect::<Vec<_>>();
+    let chunks = if chunk_ids.is_empty() {
+        "none".to_string()
+    } else {
+        chunk_ids.join(", ")
+    };
+    AiAnswer {
+        text: format!(
+            "**Fixture answer** to: {}\n\n- Context chunks: {chunks}\n- Language: {}\n\n_Served by AI_FIXTURE_MODE; no model was called._",
+            prompt.question.trim(),
+            prompt.language.code()
+        ),
+        backend: FIXTURE_BACKEND_NAME.to_string(),
+        model: FIXTURE_MODEL_NAME.to_string(),
+        cost_eur: 0.0,
+        truncated: false,
+    }
+}
+
+fn default_extra_backend_priority() -> i32 {
+    EXTRA_BACKEND_DEFAULT_PRIORITY
+}
+
+pub fn parse_extra_backends(
+    raw: &str,
+    lookup_key: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<Vec<ApiBackend>> {
+    if raw.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let configs: Vec<ExtraBackendConfig> = serde_json::from_str(raw)
+        .context("AI_EXTRA_BACKENDS must be a JSON array of backend objects")?;
+
+    let mut seen: Vec<String> = Vec::new();
+    let mut backends = Vec::with_capacity(configs.len());
+    for config in configs {
+        let name = config.name.trim().to_ascii_lowercase();
+        if name.is_empty() {
+            return Err(anyhow!("AI_EXTRA_BACKENDS entry is missing a name"));
+        }
+        if BUILTIN_BACKEND_NAMES.contains(&name.as_str()) || seen.contains(&name) {
+            return Err(anyhow!(
+                "AI_EXTRA_BACKENDS entry `{name}` duplicates an existing backend name"
+            ));
+        }
+        let endpoint = config.endpoint.trim();
+        if !(endpoint.starts_with("https://") || endpoint.starts_with("http://")) {
+            return Err(anyhow!(
+                "AI_EXTRA_BACKENDS entry `{name}` needs an http(s) endpoint"
+            ));
+        }
+        let model = config.model.trim();
+        if model.is_empty() {
+            return Err(anyhow!(
+                "AI_EXTRA_BACKENDS entry `{name}` is missing a model"
+            ));
+        }
+        if !(config.cost_per_1k_in.is_finite()
+            && config.cost_per_1k_out.is_finite()
+            && config.cost_per_1k_in >= 0.0
+            && config.cost_per_1k_out >= 0.0)
+        {
+            return Err(anyhow!(
+                "AI_EXTRA_BACKENDS entry `{name}` has invalid pricing"
+            ));
+        }
+        let api_key = lookup_key(config.api_key_env.trim())
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow!(
+                    "AI_EXTRA_BACKENDS entry `{name}` references unset key variable `{}`",
+                    config.api_key_env
+                )
+            })?;
+        let pricing = if config.cost_per_1k_in > 0.0 || config.cost_per_1k_out > 0.0 {
+            Some(BackendPricing {
+                input_per_1k: config.cost_per_1k_in,
+                output_per_1k: config.cost_per_1k_out,
+            })
+        } else {
+            None
+        };
+
+        seen.push(name.clone());
+        backends.push(ApiBackend {
+            label: config.name.trim().to_string(),
+            name,
+            aliases: &[],
+            protocol: BackendProtocol::OpenAiChat,
+            endpoint: endpoint.to_string(),
+            model: model.to_string(),
+            api_key: Arc::new(api_key),
+            priority: config.priority,
+            pricing,
+            stream: config.stream,
+            breaker: Default::default(),
+        });
+    }
+    Ok(backends)
+}
+
+// Backends named in `AI_BACKEND_ORDER` lead in that order; the rest keep
+// their priority order behind them. Built-ins without credentials are
+// skipped, but any other unknown or repeated name stops startup. Free
+// backends must come before paid ones unless `allow_paid_first` is set.
+fn apply_backend_order(
+    backends: Vec<ApiBackend>,
+    raw: &str,
+    allow_paid_first: bool,
+) -> anyhow::Result<Vec<ApiBackend>> {
+    let mut remaining = backends;
+    let mut ordered: Vec<ApiBackend> = Vec::with_capacity(remaining.len());
+    for entry in raw
+        .split(',')
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+    {
+        if let Some(index) = remaining.iter().position(|backend| backend.matches(&entry)) {
+            ordered.push(remaining.remove(index));
+        } else if let Some(placed) = ordered.iter().find(|backend| backend.matches(&entry)) {
+            return Err(anyhow!(
+                "AI_BACKEND_ORDER lists `{}` more than once",
+                placed.name
+            ));
+        } else if BUILTIN_BACKEND_NAMES.contains(&entry.as_str()) {
+            warn!(
+                target: "ai",
+                backend = entry.as_str(),
+                "AI_BACKEND_ORDER names a backend without credentials; skipping it"
+            );
+        } else {
+            let configured = ordered
+                .iter()
+                .chain(&remaining)
+                .map(|backend| backend.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(anyhow!(
+                "AI_BACKEND_ORDER names unknown backend `{entry}` (configured: {configured})"
+            ));
+        }
+    }
+    ordered.extend(remaining);
+
+    if !allow_paid_first {
+        if let Some(paid) = ordered.iter().position(ApiBackend::is_paid) {
+            if let Some(free) = ordered[paid..].iter().find(|backend| !backend.is_paid()) {
+                return Err(anyhow!(
+                    "AI_BACKEND_ORDER puts paid backend `{}` before free backend `{}`; set AI_ALLOW_PAID_FIRST=1 if that is intended",
+                    ordered[paid].name,
+                    free.name
+                ));
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackendError {
+    #[error("network error: {0}")]
+    Network(#[from] reqwest::Error),
+    #[error("api failure ({0}): {1}")]
+    ApiFailure(StatusCode, String),
+    #[error("AI response did not contain any answer")]
+    EmptyAnswer,
+    #[error("AI stream stalled before any answer arrived")]
+    StreamTimeout,
+    #[error("request deadline reached before the backend answered")]
+    DeadlineExceeded,
+}
+
+impl BackendError {
+    fn kind(&self) -> FailureKind {
+        match self {
+            BackendError::Network(err) if err.is_timeout() => FailureKind::Timeout,
+            BackendError::Network(_) => FailureKind::Server,
+            BackendError::ApiFailure(status, body) => {
+                match (*status, provider_error::parse(body)) {
+                    (_, Some(reason)) if reason.is_auth() => FailureKind::Auth,
+                    (_, Some(reason)) if reason.is_quota() => FailureKind::Quota,
+                    (status, _) => match status {
+                        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => FailureKind::Auth,
+                        StatusCode::TOO_MANY_REQUESTS | StatusCode::PAYMENT_REQUIRED => {
+                            FailureKind::Quota
+                        }
+                        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => {
+                            FailureKind::Timeout
+                        }
+                        _ => FailureKind::Server,
+                    },
+                }
+            }
+            BackendError::EmptyAnswer => FailureKind::Empty,
+            BackendError::StreamTimeout | BackendError::DeadlineExceeded => FailureKind::Timeout,
+        }
+    }
+
+    fn status(&self) -> Option<u16> {
+        match self {
+            BackendError::Network(err) => err.status().map(|status| status.as_u16()),
+            BackendError::ApiFailure(status, _) => Some(status.as_u16()),
+            BackendError::EmptyAnswer
+            | BackendError::StreamTimeout
+            | BackendError::DeadlineExceeded => None,
+        }
+    }
+
+    fn provider_reason(&self) -> Option<ProviderReason> {
+        match self {
+            BackendError::ApiFailure(_, body) => provider_error::parse(body),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AiClientError {
+    #[error("No AI backend is configured")]
+    NoBackendConfigured,
+    #[error("All AI backends failed: {}", summarize_failures(.0))]
+    AllBackendsFailed(Vec<BackendFailure>),
+    #[error("AI request deadline exceeded: {}", summarize_failures(.0))]
+    DeadlineExceeded(Vec<BackendFailure>),
+}
+
+impl AiClientError {
+    pub fn failure_details(&self, include_raw: bool) -> Vec<BackendFailureDetail> {
+        match self {
+            AiClientError::NoBackendConfigured => Vec::new(),
+            AiClientError::AllBackendsFailed(failures)
+            | AiClientError::DeadlineExceeded(failures) => failures
+                .iter()
+                .map(|failure| failure.detail(include_raw))
+                .collect(),
+        }
+    }
+}
+
+fn summarize_failures(failures: &[BackendFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("{} backend failed: {}", failure.backend, failure.error))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoogleGenerateRequest<'a> {
+    pub contents: Vec<GoogleContent<'a>>,
+    pub system_instruction: GoogleContent<'a>,
+    pub generation_config: GoogleGenerationConfig,
+}
+
+#[derive(Serialize)]
+pub struct GoogleContent<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'a str>,
+    parts: [GooglePart<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct GooglePart<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoogleGenerationConfig {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    max_output_tokens: u32,
+}
+
+impl<'a> GoogleGenerateRequest<'a> {
+    // Gemini expects strictly alternating user/model turns ending on the user,
+    // so every kept turn contributes one of each before the new question.
+    pub fn new(
+        system_prompt: &'a str,
+        history: &'a [ConversationTurn],
+        user_prompt: &'a str,
+    ) -> Self {
+        let history = trim_history(history, HISTORY_MAX_TOKENS);
+        let mut contents = Vec::with_capacity(history.len() * 2 + 1);
+        for turn in history {
+            contents.push(GoogleContent::user(turn.question.trim()));
+            contents.push(GoogleContent::model(turn.answer.trim()));
+        }
+        contents.push(GoogleContent::user(user_prompt));
+        Self {
+            contents,
+            system_instruction: GoogleContent::instruction(system_prompt),
+            generation_config: GoogleGenerationConfig::new(DEFAULT_GENERATION_PRESETS.answer),
+        }
+    }
+}
+
+impl GoogleGenerateRequest<'_> {
+    pub fn with_preset(mut self, preset: GenerationPreset) -> Self {
+        self.generation_config = GoogleGenerationConfig::new(preset);
+        self
+    }
+}
+
+impl<'a> GoogleContent<'a> {
+    fn instruction(text: &'a str) -> Self {
+        Self {
+            role: None,
+            parts: [GooglePart { text }],
+        }
+    }
+
+    fn user(text: &'a str) -> Self {
+        Self {
+            role: Some("user"),
+            parts: [GooglePart { text }],
+        }
+    }
+
+    fn model(text: &'a str) -> Self {
+        Self {
+            role: Some("model"),
+            parts: [GooglePart { text }],
+        }
+    }
+}
+
+impl GoogleGenerationConfig {
+    fn new(preset: GenerationPreset) -> Self {
+        Self {
+            temperature: preset.temperature,
+            top_p: preset.top_p,
+            max_output_tokens: preset.max_tokens as u32,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    max_tokens: usize,
+    messages: [ChatMessage<'a>; 2],
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+impl<'a> ChatRequest<'a> {
+    fn new(model: &'a str, system_prompt: &'a str, user_prompt: &'a str) -> Self {
+        Self {
+            model,
+            temperature: DEFAULT_GENERATION_PRESETS.answer.temperature,
+            top_p: None,
+            max_tokens: DEFAULT_GENERATION_PRESETS.answer.max_tokens,
+            messages: [
+                ChatMessage {
+                    role: "system",
+                    content: system_prompt,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: user_prompt,
+                },
+            ],
+            stream: false,
+        }
+    }
+
+    fn with_preset(mut self, preset: GenerationPreset) -> Self {
+        self.temperature = preset.temperature;
+        self.top_p = preset.top_p;
+        self.max_tokens = preset.max_tokens;
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GoogleGenerateResponse {
+    candidates: Option<Vec<GoogleCandidate>>,
+}
+
+#[derive(Deserialize)]
+struct GoogleCandidate {
+    content: Option<GoogleCandidateContent>,
+    #[serde(default, rename = "finishReason")]
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GoogleCandidateContent {
+    parts: Option<Vec<GoogleCandidatePart>>,
+}
+
+#[derive(Deserialize)]
+struct GoogleCandidatePart {
+    text: Option<String>,
+}
+
+impl GoogleCandidate {
+    fn into_reply(self) -> Option<BackendReply> {
+        let truncated = is_truncation(self.finish_reason.as_deref());
+        self.into_text().map(|text| BackendReply {
+            text,
+            truncated,
+            partial_tokens: None,
+        })
+    }
+
+    fn into_text(self) -> Option<String> {
+        self.content.and_then(|content| {
+            content
+                .parts
+                .unwrap_or_default()
+                .into_iter()
+                .find_map(|part| {
+                    part.text
+                        .map(|text| text.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                })
+        })
+    }
+}
+
+// OpenAI-compatible APIs report `length`, Gemini reports `MAX_TOKENS` when the
+// completion stopped at the token ceiling instead of finishing on its own.
+fn is_truncation(finish_reason: Option<&str>) -> bool {
+    finish_reason.is_some_and(|reason| {
+        reason.eq_ignore_ascii_case("length") || reason.eq_ignore_ascii_case("max_tokens")
+    })
+}
+
+fn chat_reply(body: ChatResponse) -> Result<BackendReply, BackendError> {
+    body.choices
+        .into_iter()
+        .find_map(|choice| {
+            let truncated = is_truncation(choice.finish_reason.as_deref());
+            choice
+                .message
+                .content
+                .map(|content| content.trim().to_string())
+                .filter(|text| !text.is_empty())
+                .map(|text| BackendReply {
+                    text,
+                    truncated,
+                    partial_tokens: None,
+                })
+        })
+        .ok_or(BackendError::EmptyAnswer)
+}
+
+fn google_reply(body: GoogleGenerateResponse) -> Result<BackendReply, BackendError> {
+    body.candidates
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(GoogleCandidate::into_reply)
+        .ok_or(BackendError::EmptyAnswer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer_language::AnswerLanguage;
+    use crate::deadline::REQUEST_DEADLINE;
+    use crate::handlers::AiRequest;
+    use crate::rag::ContextChunk;
+    use crate::test_support::{
+        all_builtin_client, chat_completion, extra_backend_keys, mock_client, serialized_contents,
+        spawn_mock_backend, turn,
+    };
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::sync::atomic::Ordering;
+    use tokio::net::TcpListener;
+
+    fn backend_names<'a>(client: &'a AiClient, preferred: Option<&str>) -> Vec<&'a str> {
+        client
+            .backend_order(preferred)
+            .into_iter()
+            .map(|backend| backend.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn primary_model_falls_back_through_backends() {
+        let client = AiClient::new(
+            Some("google-key".to_string()),
+            Some("groq-key".to_string()),
+            Some("openai-key".to_string()),
+            Vec::new(),
+        )
+        .expect("client should construct");
+        assert_eq!(client.primary_model(), Some(GROQ_MODEL_NAME));
+
+        let client = AiClient::new(
+            Some("google-key".to_string()),
+            None,
+            Some("openai-key".to_string()),
+            Vec::new(),
+        )
+        .expect("client should construct without Groq");
+        assert_eq!(client.primary_model(), Some(GOOGLE_MODEL_NAME));
+
+        let client = AiClient::new(None, None, Some("openai-key".to_string()), Vec::new())
+            .expect("OpenAI only");
+        assert_eq!(client.primary_model(), Some(OPENAI_MODEL_NAME));
+    }
+
+    #[test]
+    fn preferred_backend_is_tried_first() {
+        let client = AiClient::new(
+            Some("google-key".to_string()),
+            Some("groq-key".to_string()),
+            Some("openai-key".to_string()),
+            Vec::new(),
+        )
+        .expect("client should construct");
+        assert_eq!(
+            backend_names(&client, Some("google")),
+            vec!["google", "groq", "openai"],
+            "Preferred backend should lead the fallback chain"
+        );
+        let preferred = client.resolve_preferred("gpt");
+        assert_eq!(preferred, Some("openai"));
+        assert_eq!(
+            backend_names(&client, preferred),
+            vec!["groq", "google", "openai"],
+            "A paid preference must not jump ahead of free backends"
+        );
+        assert!(client.holds_back("openai"));
+        assert!(!client.holds_back("google"));
+        let paid_first = client.clone().with_paid_first(true);
+        assert_eq!(
+            backend_names(&paid_first, preferred),
+            vec!["openai", "groq", "google"],
+            "AI_ALLOW_PAID_FIRST lets a paid preference lead"
+        );
+        assert!(!paid_first.holds_back("openai"));
+        assert_eq!(client.leading_backends(), vec!["groq", "google"]);
+        assert_eq!(
+            paid_first.leading_backends(),
+            vec!["groq", "google", "openai"]
+        );
+        assert_eq!(
+            client.resolve_preferred(GOOGLE_MODEL_NAME),
+            Some("google"),
+            "Full model names should map to their backend"
+        );
+    }
+
+    #[test]
+    fn unknown_or_unconfigured_preferred_model_is_ignored() {
+        let client = AiClient::new(None, Some("groq-key".to_string()), None, Vec::new())
+            .expect("client should construct");
+        assert_eq!(client.resolve_preferred("claude"), None);
+        assert_eq!(
+            client.resolve_preferred("openai"),
+            None,
+            "Backends without credentials should not be selectable"
+        );
+        assert_eq!(backend_names(&client, None), vec!["groq"]);
+
+        let request: AiRequest =
+            serde_json::from_value(json!({ "question": "Hi" })).expect("request should parse");
+        assert!(request.preferred_model.is_none());
+    }
+
+    #[test]
+    fn backend_order_env_leads_and_keeps_unlisted_backends() {
+        let client = all_builtin_client()
+            .with_backend_order(" Google , groq ", false)
+            .expect("valid order");
+        assert_eq!(
+            backend_names(&client, None),
+            vec!["google", "groq", "openai"]
+        );
+        assert_eq!(client.primary_model(), Some(GOOGLE_MODEL_NAME));
+
+        let client = all_builtin_client()
+            .with_backend_order("gemini", false)
+            .expect("aliases select backends");
+        assert_eq!(
+            backend_names(&client, None),
+            vec!["google", "groq", "openai"]
+        );
+
+        let client = AiClient::new(
+            Some("google-key".to_string()),
+            Some("groq-key".to_string()),
+            None,
+            Vec::new(),
+        )
+        .expect("client should construct")
+        .with_backend_order("google,groq,openai", false)
+        .expect("built-ins without keys are skipped");
+        assert_eq!(backend_names(&client, None), vec!["google", "groq"]);
+    }
+
+    #[test]
+    fn extra_backends_merge_with_builtins_by_priority() {
+        let extras = parse_extra_backends(
+            r#"[
+                {"name": "OpenRouter", "endpoint": "https://openrouter.ai/api/v1/chat/completions",
+                 "model": "meta-llama/llama-3.1-70b-instruct", "api_key_env": "OPENROUTER_API_KEY",
+                 "priority": 5, "cost_per_1k_in": 0.0005, "cost_per_1k_out": 0.0007},
+                {"name": "together", "endpoint": "https://api.together.xyz/v1/chat/completions",
+                 "model": "mistralai/Mixtral-8x7B-Instruct-v0.1", "api_key_env": "TOGETHER_API_KEY"}
+            ]"#,
+            extra_backend_keys,
+        )
+        .expect("extra backends should parse");
+        assert_eq!(extras.len(), 2);
+        assert_eq!(extras[0].name, "openrouter");
+        assert_eq!(extras[0].label, "OpenRouter");
+        assert_eq!(extras[0].api_key.as_str(), "or-key");
+        assert_eq!(extras[1].priority, EXTRA_BACKEND_DEFAULT_PRIORITY);
+        assert_eq!(extras[1].pricing, None, "Missing prices should mean free");
+
+        let client = AiClient::new(
+            Some("google-key".to_string()),
+            Some("groq-key".to_string()),
+            Some("openai-key".to_string()),
+            extras,
+        )
+        .expect("client should construct");
+        assert_eq!(
+            backend_names(&client, None),
+            vec!["openrouter", "groq", "google", "openai", "together"],
+            "Backends should be tried in ascending priority order"
+        );
+        assert_eq!(
+            client.primary_model(),
+            Some("meta-llama/llama-3.1-70b-instruct")
+        );
+        assert_eq!(client.resolve_preferred("Together"), Some("together"));
+        assert_eq!(
+            backend_names(&client, Some("together"))[0],
+            "together",
+            "Custom backends should be selectable as the preferred model"
+        );
+    }
+
+    #[test]
+    fn extra_backends_reject_invalid_entries() {
+        let cases = [
+            ("not json", "JSON array"),
+            (
+                r#"[{"name": "groq", "endpoint": "https://x.test", "model": "m", "api_key_env": "OPENROUTER_API_KEY"}]"#,
+                "duplicates",
+            ),
+            (
+                r#"[{"name": "a", "endpoint": "https://x.test", "model": "m", "api_key_env": "OPENROUTER_API_KEY"},
+                    {"name": "A", "endpoint": "https://y.test", "model": "m", "api_key_env": "OPENROUTER_API_KEY"}]"#,
+                "duplicates",
+            ),
+            (
+                r#"[{"name": "a", "endpoint": "ftp://x.test", "model": "m", "api_key_env": "OPENROUTER_API_KEY"}]"#,
+                "http(s) endpoint",
+            ),
+            (
+                r#"[{"name": "a", "endpoint": "https://x.test", "model": " ", "api_key_env": "OPENROUTER_API_KEY"}]"#,
+                "missing a model",
+            ),
+            (
+                r#"[{"name": "a", "endpoint": "https://x.test", "model": "m", "api_key_env": "MISSING_KEY"}]"#,
+                "unset key variable",
+            ),
+            (
+                r#"[{"name": "a", "endpoint": "https://x.test", "model": "m", "api_key_env": "OPENROUTER_API_KEY", "cost_per_1k_in": -1.0}]"#,
+                "invalid pricing",
+            ),
+        ];
+        for (raw, expected) in cases {
+            let err = match parse_extra_backends(raw, extra_backend_keys) {
+                Ok(_) => panic!("`{raw}` should be rejected"),
+                Err(err) => format!("{err:#}"),
+            };
+            assert!(
+                err.contains(expected),
+                "Expected `{expected}` in error for `{raw}`, got: {err}"
+            );
+        }
+        assert!(parse_extra_backends("  ", extra_backend_keys)
+            .expect("blank config is allowed")
+            .is_empty());
+    }
+
+    #[test]
+    fn cost_estimate_follows_backend_pricing() {
+        let extras = parse_extra_backends(
+            r#"[{"name": "paid", "endpoint": "https://x.test", "model": "m",
+                 "api_key_env": "OPENROUTER_API_KEY", "priority": 1,
+                 "cost_per_1k_in": 1.0, "cost_per_1k_out": 2.0}]"#,
+            extra_backend_keys,
+        )
+        .expect("extra backend should parse");
+        let client = AiClient::new(None, Some("groq-key".to_string()), None, extras)
+            .expect("client should construct");
+        let expected = 1.0 * 0.5 + 2.0 * (MAX_COMPLETION_TOKENS as f64 / 1000.0);
+        assert!((client.estimate_cost(500, None) - expected).abs() < 1e-9);
+        assert_eq!(
+            client.estimate_cost(500, Some("groq")),
+            0.0,
+            "Preferring a free backend should not reserve budget"
+        );
+
+        let client =
+            AiClient::new(None, None, Some("openai-key".to_string()), Vec::new()).expect("OpenAI");
+        assert_eq!(
+            client.estimate_cost(500, None),
+            OPENAI_PRICING.cost(500, MAX_COMPLETION_TOKENS)
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_backend_answers_through_openai_compatible_api() {
+        use axum::http::header::AUTHORIZATION;
+
+        let mock = Router::new().route(
+            "/v1/chat/completions",
+            post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                let authorized = headers
+                    .get(AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    == Some("Bearer or-key");
+                if !authorized || body["model"] != "mock/model" {
+                    return (StatusCode::UNAUTHORIZED, Json(json!({})));
+                }
+                (
+                    StatusCode::OK,
+                    Json(json!({
+                        "choices": [{ "message": { "content": "  Hello from the mock  " } }]
+                    })),
+                )
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("mock listener should bind");
+        let addr = listener.local_addr().expect("mock address");
+        tokio::spawn(async move {
+            axum::serve(listener, mock).await.expect("mock server");
+        });
+
+        let raw = format!(
+            r#"[{{"name": "mock", "endpoint": "http://{addr}/v1/chat/completions",
+                  "model": "mock/model", "api_key_env": "OPENROUTER_API_KEY",
+                  "cost_per_1k_in": 1.0, "cost_per_1k_out": 1.0}}]"#
+        );
+        let extras = parse_extra_backends(&raw, extra_backend_keys).expect("mock backend parses");
+        let client = AiClient::new(None, None, None, extras).expect("client should construct");
+        let knowledge = KnowledgeBase {
+            system_prompt: "prompt".to_string(),
+            system_tokens: 4,
+        };
+        let answer = client
+            .ask(
+                &knowledge,
+                AiPrompt {
+                    question: "Hello?",
+                    context: None,
+                    language: AnswerLanguage::English,
+                    history: &[],
+                },
+                1000,
+                None,
+                &Deadline::after(REQUEST_DEADLINE),
+            )
+            .await
+            .expect("mock backend should answer");
+        assert_eq!(answer.text, "Hello from the mock");
+        assert_eq!(answer.model, "mock/model");
+        assert!((answer.cost_eur - (1.0 + MAX_COMPLETION_TOKENS as f64 / 1000.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backend_errors_classify_into_failure_kinds() {
+        let api = |status: StatusCode| BackendError::ApiFailure(status, "raw".to_string());
+        let cases = [
+            (api(StatusCode::UNAUTHORIZED), FailureKind::Auth),
+            (api(StatusCode::FORBIDDEN), FailureKind::Auth),
+            (api(StatusCode::TOO_MANY_REQUESTS), FailureKind::Quota),
+            (api(StatusCode::PAYMENT_REQUIRED), FailureKind::Quota),
+            (api(StatusCode::GATEWAY_TIMEOUT), FailureKind::Timeout),
+            (api(StatusCode::REQUEST_TIMEOUT), FailureKind::Timeout),
+            (api(StatusCode::INTERNAL_SERVER_ERROR), FailureKind::Server),
+            (api(StatusCode::BAD_REQUEST), FailureKind::Server),
+            (BackendError::EmptyAnswer, FailureKind::Empty),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "unexpected kind for {error}");
+        }
+        assert_eq!(api(StatusCode::TOO_MANY_REQUESTS).status(), Some(429));
+        assert_eq!(BackendError::EmptyAnswer.status(), None);
+    }
+
+    #[test]
+    fn provider_error_bodies_refine_failure_kind_and_detail() {
+        let invalid_key = BackendError::ApiFailure(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":{"code":400,"message":"API key not valid.","status":"INVALID_ARGUMENT"}}"#
+                .to_string(),
+        );
+        assert_eq!(invalid_key.kind(), FailureKind::Auth);
+        assert_eq!(
+            invalid_key.provider_reason(),
+            Some(ProviderReason::InvalidApiKey)
+        );
+
+        let quota = BackendFailure {
+            backend: "Groq".to_string(),
+            error: BackendError::ApiFailure(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":{"message":"quota","type":"insufficient_quota","code":"insufficient_quota"}}"#
+                    .to_string(),
+            ),
+        };
+        assert_eq!(
+            serde_json::to_value(quota.detail(false)).expect("serializes"),
+            json!({"backend": "Groq", "kind": "quota", "status": 400, "reason": "quota_exhausted"})
+        );
+    }
+
+    #[tokio::test]
+    async fn network_timeouts_classify_as_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let addr = listener.local_addr().expect("listener address");
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.expect("accept");
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+        let err = reqwest::Client::new()
+            .get(format!("http://{addr}/"))
+            .timeout(std::time::Duration::from_millis(50))
+            .send()
+            .await
+            .expect_err("request should time out");
+        assert_eq!(BackendError::Network(err).kind(), FailureKind::Timeout);
+    }
+
+    #[tokio::test]
+    async fn exhausted_backends_report_structured_failures() {
+        let mock = Router::new().route(
+            "/v1/chat/completions",
+            post(|| async {
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "quota exceeded for key sk-secret",
+                )
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("mock listener should bind");
+        let addr = listener.local_addr().expect("mock address");
+        tokio::spawn(async move {
+            axum::serve(listener, mock).await.expect("mock server");
+        });
+
+        let raw = format!(
+            r#"[{{"name": "Mock", "endpoint": "http://{addr}/v1/chat/completions",
+                  "model": "mock/model", "api_key_env": "OPENROUTER_API_KEY"}}]"#
+        );
+        let extras = parse_extra_backends(&raw, extra_backend_keys).expect("mock backend parses");
+        let client = AiClient::new(None, None, None, extras).expect("client should construct");
+        let knowledge = KnowledgeBase {
+            system_prompt: "prompt".to_string(),
+            system_tokens: 4,
+        };
+        let Err(err) = client
+            .ask(
+                &knowledge,
+                AiPrompt {
+                    question: "Hello?",
+                    context: None,
+                    language: AnswerLanguage::English,
+                    history: &[],
+                },
+                100,
+                None,
+                &Deadline::after(REQUEST_DEADLINE),
+            )
+            .await
+        else {
+            panic!("a 429 backend should fail");
+        };
+
+        let details = serde_json::to_value(err.failure_details(false)).expect("serialize");
+        assert_eq!(
+            details,
+            json!([{ "backend": "Mock", "kind": "quota", "status": 429 }]),
+            "Raw provider errors must stay out of the default response"
+        );
+        let debug = serde_json::to_value(err.failure_details(true)).expect("serialize");
+        assert!(
+            debug[0]["detail"]
+                .as_str()
+                .is_some_and(|detail| detail.contains("quota exceeded")),
+            "Debug mode should expose the raw provider error: {debug}"
+        );
+    }
+
+    #[test]
+    fn fixture_answers_echo_the_question_and_chunk_ids() {
+        let chunks = ["experience-1", "projects-2"]
+            .into_iter()
+            .map(|id| ContextChunk {
+                id: id.to_string(),
+                source: "fixture".to_string(),
+                topic: "test".to_string(),
+                body: "Body".to_string(),
+                score: 1.0,
+            })
+            .collect::<Vec<_>>();
+        let prompt = AiPrompt {
+            question: " Where does Alexandre work? ",
+            context: Some(&chunks),
+            language: AnswerLanguage::English,
+            history: &[],
+        };
+        let answer = fixture_answer(&prompt);
+        assert_eq!(
+            answer.text,
+            "**Fixture answer** to: Where does Alexandre work?\n\n- Context chunks: `experience-1`, `projects-2`\n- Language: en\n\n_Served by AI_FIXTURE_MODE; no model was called._"
+        );
+        assert_eq!(answer.model, FIXTURE_MODEL_NAME);
+        assert_eq!(answer.backend, FIXTURE_BACKEND_NAME);
+        assert_eq!(answer.cost_eur, 0.0);
+        assert!(!answer.truncated);
+
+        let bare = fixture_answer(&AiPrompt {
+            context: None,
+            ..prompt
+        });
+        assert!(
+            bare.text.contains("- Context chunks: none"),
+            "{}",
+            bare.text
+        );
+    }
+
+    #[tokio::test]
+    async fn warm_up_opens_breakers_for_unreachable_backends() {
+        let broken_hits = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let healthy_hits = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let broken = spawn_mock_backend(
+            StatusCode::UNAUTHORIZED,
+            std::sync::Arc::clone(&broken_hits),
+        )
+        .await;
+        let healthy =
+            spawn_mock_backend(StatusCode::OK, std::sync::Arc::clone(&healthy_hits)).await;
+        let client = mock_client(&[("broken", broken, 1), ("healthy", healthy, 2)]);
+
+        let reports = client.warm_up().await;
+        let reachability: Vec<(&str, bool)> = reports
+            .iter()
+            .map(|report| (report.backend.as_str(), report.reachable))
+            .collect();
+        assert_eq!(reachability, [("broken", false), ("healthy", true)]);
+        assert!(client.backends[0].breaker.is_open());
+        assert!(!client.backends[1].breaker.is_open());
+
+        let knowledge = KnowledgeBase {
+            system_prompt: "prompt".to_string(),
+            system_tokens: 4,
+        };
+        let Ok(answer) = client
+            .ask(
+                &knowledge,
+                AiPrompt {
+                    question: "Hello?",
+                    context: None,
+                    language: AnswerLanguage::English,
+                    history: &[],
+                },
+                100,
+                None,
+                &Deadline::after(REQUEST_DEADLINE),
+            )
+            .await
+        else {
+            panic!("the healthy backend should answer");
+        };
+        assert_eq!(answer.model, "healthy/model");
+        assert_eq!(
+            broken_hits.load(Ordering::SeqCst),
+            1,
+            "An open breaker should skip the broken backend after warm-up"
+        );
+        assert_eq!(healthy_hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn finish_reasons_flag_truncation_for_every_provider() {
+        let chat: ChatResponse =
+            serde_json::from_value(chat_completion("Rust and", "length")).expect("chat parses");
+        let reply = chat_reply(chat).expect("reply");
+        assert_eq!(reply.text, "Rust and");
+        assert!(reply.truncated);
+
+        let chat: ChatResponse =
+            serde_json::from_value(chat_completion("Rust.", "stop")).expect("chat parses");
+        assert!(!chat_reply(chat).expect("reply").truncated);
+
+        let legacy: ChatResponse =
+            serde_json::from_value(json!({ "choices": [{ "message": { "content": "Hi" } }] }))
+                .expect("chat without finish_reason parses");
+        assert!(!chat_reply(legacy).expect("reply").truncated);
+
+        let gemini = |reason: &str| -> GoogleGenerateResponse {
+            serde_json::from_value(json!({
+                "candidates": [{
+                    "content": { "parts": [{ "text": " Kubernetes and " }] },
+                    "finishReason": reason,
+                }]
+            }))
+            .expect("gemini parses")
+        };
+        let reply = google_reply(gemini("MAX_TOKENS")).expect("reply");
+        assert_eq!(reply.text, "Kubernetes and");
+        assert!(reply.truncated);
+        assert!(!google_reply(gemini("STOP")).expect("reply").truncated);
+    }
+
+    #[test]
+    fn chat_stream_joins_events_split_across_chunks() {
+        let mut stream = ChatStream::default();
+        assert!(!stream.push(b"data: {\"choices\":[{\"delta\":{\"content\":\"Hel"));
+        assert!(!stream.push(b"lo\"}}]}\n\n: keep-alive\n\n"));
+        assert!(!stream.push(
+            b"data: {\"choices\":[{\"delta\":{\"content\":\" world\"},\"finish_reason\":\"length\"}]}\n\n"
+        ));
+        assert!(stream.push(b"data: [DONE]\n\n"));
+        let reply = stream.finish().expect("complete reply");
+        assert_eq!(reply.text, "Hello world");
+        assert!(reply.truncated);
+        assert_eq!(reply.partial_tokens, None);
+        assert!(ChatStream::default().finish_partial().is_none());
+    }
+
+    #[test]
+    fn chat_request_uses_backend_model() {
+        let knowledge = KnowledgeBase {
+            system_prompt: "prompt".to_string(),
+            system_tokens: 4,
+        };
+        let question = "What is the latest project?";
+        let request = ChatRequest::new(GROQ_MODEL_NAME, &knowledge.system_prompt, question);
+        assert_eq!(request.model, GROQ_MODEL_NAME);
+        assert_eq!(request.messages[0].content, "prompt");
+        assert_eq!(request.messages[1].content, question);
+    }
+
+    #[test]
+    fn google_request_includes_prompt_and_question() {
+        let prompt = "system instructions";
+        let question = "Tell me about Alexandre.";
+        let request = GoogleGenerateRequest::new(prompt, &[], question);
+        assert_eq!(request.system_instruction.parts[0].text, prompt);
+        assert_eq!(request.contents[0].parts[0].text, question);
+        assert_eq!(request.contents[0].role, Some("user"));
+        assert_eq!(
+            request.generation_config.max_output_tokens,
+            MAX_COMPLETION_TOKENS as u32
+        );
+    }
+
+    #[test]
+    fn request_payloads_carry_the_generation_preset() {
+        let preset = GenerationPreset {
+            temperature: 0.9,
+            top_p: Some(0.8),
+            max_tokens: 200,
+        };
+        let chat = serde_json::to_value(
+            ChatRequest::new(GROQ_MODEL_NAME, "system", "question").with_preset(preset),
+        )
+        .expect("chat request serializes");
+        assert_eq!(chat["temperature"].as_f64(), Some(0.9f32 as f64));
+        assert_eq!(chat["top_p"].as_f64(), Some(0.8f32 as f64));
+        assert_eq!(chat["max_tokens"], 200);
+        let google = serde_json::to_value(
+            GoogleGenerateRequest::new("system", &[], "question").with_preset(preset),
+        )
+        .expect("google request serializes");
+        let config = &google["generationConfig"];
+        assert_eq!(config["temperature"].as_f64(), Some(0.9f32 as f64));
+        assert_eq!(config["topP"].as_f64(), Some(0.8f32 as f64));
+        assert_eq!(config["maxOutputTokens"], 200);
+
+        let defaults = serde_json::to_value(ChatRequest::new(GROQ_MODEL_NAME, "system", "q"))
+            .expect("chat request serializes");
+        assert!(defaults.get("top_p").is_none(), "Unset top_p is omitted");
+        let defaults = serde_json::to_value(GoogleGenerateRequest::new("system", &[], "q"))
+            .expect("google request serializes");
+        assert!(defaults["generationConfig"].get("topP").is_none());
+    }
+
+    #[test]
+    fn client_modes_pick_their_own_preset() {
+        let follow_up = GenerationPreset {
+            temperature: 0.1,
+            top_p: None,
+            max_tokens: 96,
+        };
+        let client = AiClient::new(None, None, Some("openai-key".to_string()), Vec::new())
+            .expect("client should construct")
+            .with_presets(GenerationPresets {
+                follow_up,
+                ..DEFAULT_GENERATION_PRESETS
+            });
+        assert_eq!(client.preset, DEFAULT_GENERATION_PRESETS.answer);
+        assert_eq!(
+            client.for_mode(GenerationMode::Summary).preset.max_tokens,
+            SUMMARY_MAX_COMPLETION_TOKENS
+        );
+        assert_eq!(client.for_mode(GenerationMode::FollowUp).preset, follow_up);
+    }
+
+    #[test]
+    fn google_candidate_extracts_trimmed_text() {
+        let candidate = GoogleCandidate {
+            content: Some(GoogleCandidateContent {
+                parts: Some(vec![GoogleCandidatePart {
+                    text: Some("  Answer with whitespace  ".to_string()),
+                }]),
+            }),
+            finish_reason: None,
+        };
+        assert_eq!(
+            GoogleCandidate::into_text(candidate),
+            Some("Answer with whitespace".to_string())
+        );
+    }
+
+    #[test]
+    fn summary_cost_estimate_uses_the_smaller_cap() {
+        let client = AiClient::new(None, None, Some("openai-key".to_string()), Vec::new())
+            .expect("client should construct");
+        let summary_client = client.for_mode(GenerationMode::Summary);
+        assert_eq!(
+            summary_client.estimate_cost(500, None),
+            OPENAI_PRICING.cost(500, SUMMARY_MAX_COMPLETION_TOKENS)
+        );
+        assert!(summary_client.estimate_cost(500, None) < client.estimate_cost(500, None));
+    }
+
+    #[test]
+    fn cost_calculation_scales_with_tokens() {
+        let low = OPENAI_PRICING.cost(500, 100);
+        let high = OPENAI_PRICING.cost(5000, 1000);
+        assert!(high > low);
+    }
+
+    #[test]
+    fn backend_order_rejects_unknown_and_duplicate_names() {
+        let err = all_builtin_client()
+            .with_backend_order("groq,grok,openai", false)
+            .err()
+            .expect("typos should stop startup")
+            .to_string();
+        assert!(err.contains("unknown backend `grok`"), "{err}");
+        assert!(err.contains("groq, google, openai"), "{err}");
+
+        for raw in ["groq,groq", "groq,llama"] {
+            let err = all_builtin_client()
+                .with_backend_order(raw, false)
+                .err()
+                .expect("duplicates should stop startup")
+                .to_string();
+            assert!(err.contains("`groq` more than once"), "{raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn paid_backends_only_lead_when_explicitly_allowed() {
+        for raw in ["openai,groq,google", "openai", "groq,openai,google"] {
+            let err = all_builtin_client()
+                .with_backend_order(raw, false)
+                .err()
+                .expect("paid before free should be rejected")
+                .to_string();
+            assert!(err.contains("paid backend `openai`"), "{raw}: {err}");
+            assert!(err.contains("AI_ALLOW_PAID_FIRST"), "{raw}: {err}");
+        }
+
+        let client = all_builtin_client()
+            .with_backend_order("openai,groq", true)
+            .expect("override allows paid first");
+        assert_eq!(
+            backend_names(&client, None),
+            vec!["openai", "groq", "google"]
+        );
+        assert_eq!(client.primary_model(), Some(OPENAI_MODEL_NAME));
+    }
+
+    #[test]
+    fn google_request_without_history_is_a_single_user_turn() {
+        assert_eq!(
+            serialized_contents(&[]),
+            json!([{ "role": "user", "parts": [{ "text": "Latest question?" }] }])
+        );
+    }
+
+    #[test]
+    fn google_request_alternates_user_and_model_turns() {
+        assert_eq!(
+            serialized_contents(&[turn("Who is he?", " A Rust developer. ")]),
+            json!([
+                { "role": "user", "parts": [{ "text": "Who is he?" }] },
+                { "role": "model", "parts": [{ "text": "A Rust developer." }] },
+                { "role": "user", "parts": [{ "text": "Latest question?" }] },
+            ])
+        );
+
+        let history = [turn("Q1", "A1"), turn("Q2", "A2"), turn("Q3", "A3")];
+        let contents = serialized_contents(&history);
+        let roles: Vec<&str> = contents
+            .as_array()
+            .expect("contents array")
+            .iter()
+            .map(|content| content["role"].as_str().expect("role"))
+            .collect();
+        assert_eq!(
+            roles,
+            ["user", "model", "user", "model", "user", "model", "user"]
+        );
+        assert_eq!(contents[0]["parts"][0]["text"], "Q1");
+        assert_eq!(contents[5]["parts"][0]["text"], "A3");
+    }
+}
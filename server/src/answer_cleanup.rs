@@ -0,0 +1,385 @@
+const DEFAULT_BOILERPLATE_PREFIXES: [&str; 6] = [
+    "As an AI language model",
+    "As an AI assistant",
+    "As a language model",
+    "As an AI",
+    "I am an AI language model",
+    "I'm an AI language model",
+];
+const MAX_CONSECUTIVE_BLANK_LINES: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct AnswerCleanup {
+    enabled: bool,
+    prefixes: Vec<String>,
+    forbidden: Vec<String>,
+}
+
+impl Default for AnswerCleanup {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            prefixes: DEFAULT_BOILERPLATE_PREFIXES
+                .iter()
+                .map(|prefix| prefix.to_string())
+                .collect(),
+            forbidden: Vec::new(),
+        }
+    }
+}
+
+impl AnswerCleanup {
+    pub fn from_env_values(enabled: Option<&str>, extra_prefixes: Option<&str>) -> Self {
+        let mut cleanup = Self {
+            enabled: !matches!(
+                enabled
+                    .map(|value| value.trim().to_ascii_lowercase())
+                    .as_deref(),
+                Some("0" | "false" | "no" | "off")
+            ),
+            ..Self::default()
+        };
+        if let Some(extra) = extra_prefixes {
+            cleanup.prefixes.extend(split_phrases(extra));
+        }
+        // Longest first so "As an AI language model" wins over "As an AI".
+        cleanup
+            .prefixes
+            .sort_by_key(|prefix| std::cmp::Reverse(prefix.len()));
+        cleanup
+    }
+
+    // Guardrail phrases are stripped even when the rest of the cleanup is off.
+    pub fn with_forbidden_phrases(mut self, phrases: Option<&str>) -> Self {
+        self.forbidden = split_phrases(phrases.unwrap_or_default());
+        self.forbidden
+            .sort_by_key(|phrase| std::cmp::Reverse(phrase.len()));
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn apply(&self, answer: &str) -> String {
+        let answer = strip_forbidden_phrases(answer, &self.forbidden);
+        if !self.enabled {
+            return answer;
+        }
+        let stripped = strip_boilerplate(&answer, &self.prefixes);
+        let cited = normalize_citations(&stripped);
+        collapse_blank_lines(&cited, MAX_CONSECUTIVE_BLANK_LINES)
+    }
+}
+
+fn split_phrases(raw: &str) -> Vec<String> {
+    raw.split('|')
+        .map(str::trim)
+        .filter(|phrase| !phrase.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn strip_forbidden_phrases(answer: &str, phrases: &[String]) -> String {
+    if phrases.is_empty() {
+        return answer.to_string();
+    }
+    // ASCII lowercasing keeps byte offsets aligned with the original text.
+    let lowered = answer.to_ascii_lowercase();
+    let mut output = String::with_capacity(answer.len());
+    let mut cursor = 0;
+    while cursor < answer.len() {
+        let matched = phrases
+            .iter()
+            .find(|phrase| lowered[cursor..].starts_with(&phrase.to_ascii_lowercase()));
+        match matched {
+            Some(phrase) => cursor += phrase.len(),
+            None => {
+                let ch = answer[cursor..].chars().next().unwrap_or_default();
+                output.push(ch);
+                cursor += ch.len_utf8();
+            }
+        }
+    }
+    if output == answer {
+        return output;
+    }
+    output
+        .lines()
+        .map(tidy_spacing)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn tidy_spacing(line: &str) -> String {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let mut tidy = indent.to_string();
+    for word in line.split_whitespace() {
+        let glued = word.starts_with(['.', ',', ';', ':', '!', '?', ')']);
+        if !glued && tidy.len() > indent.len() {
+            tidy.push(' ');
+        }
+        tidy.push_str(word);
+    }
+    tidy
+}
+
+pub fn strip_boilerplate(answer: &str, prefixes: &[String]) -> String {
+    let mut rest = answer.trim_start();
+    let mut stripped_any = false;
+    loop {
+        let matched = prefixes.iter().find_map(|prefix| {
+            let head = rest.get(..prefix.len())?;
+            let after = &rest[prefix.len()..];
+            let splits_word =
+                prefix.ends_with(char::is_alphanumeric) && after.starts_with(char::is_alphanumeric);
+            (head.eq_ignore_ascii_case(prefix) && !splits_word).then_some(after)
+        });
+        let Some(after) = matched else {
+            break;
+        };
+        rest = skip_clause_separator(after);
+        stripped_any = true;
+    }
+    if !stripped_any {
+        return answer.to_string();
+    }
+    capitalize_first(rest)
+}
+
+fn skip_clause_separator(text: &str) -> &str {
+    text.trim_start_matches(|ch: char| ch == ',' || ch == ':' || ch == ';' || ch.is_whitespace())
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub fn normalize_citations(answer: &str) -> String {
+    let mut output = String::with_capacity(answer.len());
+    let mut rest = answer;
+    while let Some(offset) = rest.find(['[', '(']) {
+        output.push_str(&rest[..offset]);
+        let candidate = &rest[offset..];
+        match parse_citation_group(candidate) {
+            Some((ids, consumed)) => {
+                let canonical = ids
+                    .iter()
+                    .map(|id| format!("[chunk-{id}]"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                output.push_str(&canonical);
+                rest = &candidate[consumed..];
+            }
+            None => {
+                let opener = candidate.chars().next().map_or(1, char::len_utf8);
+                output.push_str(&candidate[..opener]);
+                rest = &candidate[opener..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn parse_citation_group(text: &str) -> Option<(Vec<u32>, usize)> {
+    let closer = match text.as_bytes().first()? {
+        b'[' => ']',
+        b'(' => ')',
+        _ => return None,
+    };
+    let end = text.find(closer)?;
+    let inner = &text[1..end];
+    let mut ids = Vec::new();
+    for part in inner.split([',', ';']).flat_map(|part| part.split(" and ")) {
+        let id = match parse_chunk_reference(part) {
+            Some(id) => id,
+            // "(chunks 6, 7)" lists the remaining ids without repeating the keyword.
+            None if !ids.is_empty() => part.trim().parse().ok()?,
+            None => return None,
+        };
+        ids.push(id);
+    }
+    (!ids.is_empty()).then_some((ids, end + closer.len_utf8()))
+}
+
+fn parse_chunk_reference(reference: &str) -> Option<u32> {
+    let trimmed = reference.trim();
+    let head = trimmed.get(..5)?;
+    if !head.eq_ignore_ascii_case("chunk") {
+        return None;
+    }
+    let mut tail = &trimmed[5..];
+    if tail.starts_with(['s', 'S']) {
+        tail = &tail[1..];
+    }
+    let digits = tail.trim_start_matches(['-', '_', '#', ' ']);
+    if digits.is_empty() || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+pub fn collapse_blank_lines(answer: &str, max_blank: usize) -> String {
+    let mut lines = Vec::new();
+    let mut blank_run = 0;
+    for line in answer.lines() {
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > max_blank {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_prefixes() -> Vec<String> {
+        AnswerCleanup::from_env_values(None, None).prefixes
+    }
+
+    #[test]
+    fn strips_refusal_boilerplate_prefixes() {
+        let prefixes = default_prefixes();
+        assert_eq!(
+            strip_boilerplate(
+                "As an AI language model, I can tell you that Alexandre works at PlayStation.",
+                &prefixes
+            ),
+            "I can tell you that Alexandre works at PlayStation."
+        );
+        assert_eq!(
+            strip_boilerplate("  as an ai: he ships Rust services.", &prefixes),
+            "He ships Rust services."
+        );
+        assert_eq!(
+            strip_boilerplate(
+                "As an AI assistant, as an AI language model, here is the answer.",
+                &prefixes
+            ),
+            "Here is the answer.",
+            "Stacked boilerplate should be removed entirely"
+        );
+        assert_eq!(
+            strip_boilerplate("Alexandre mentions AI as an AI enthusiast.", &prefixes),
+            "Alexandre mentions AI as an AI enthusiast.",
+            "Only leading boilerplate is removed"
+        );
+        assert_eq!(
+            strip_boilerplate("As an AIrline fan, he flies often.", &prefixes),
+            "As an AIrline fan, he flies often.",
+            "Prefixes must end on a word boundary"
+        );
+    }
+
+    #[test]
+    fn custom_prefixes_extend_the_defaults() {
+        let cleanup = AnswerCleanup::from_env_values(None, Some("Certainly! | Great question!"));
+        assert_eq!(
+            cleanup.apply("Great question! Certainly! He led the migration."),
+            "He led the migration."
+        );
+        assert_eq!(
+            cleanup.apply("As an AI, he led it."),
+            "He led it.",
+            "Defaults remain active alongside custom prefixes"
+        );
+    }
+
+    #[test]
+    fn normalizes_citation_variants() {
+        let messy = "He led the team (chunk 2) and shipped [Chunk-3]. See [chunk_4], [CHUNK #5] and (chunks 6, 7).";
+        assert_eq!(
+            normalize_citations(messy),
+            "He led the team [chunk-2] and shipped [chunk-3]. See [chunk-4], [chunk-5] and [chunk-6] [chunk-7]."
+        );
+        assert_eq!(
+            normalize_citations("Sources: [chunk-1 and chunk-2]"),
+            "Sources: [chunk-1] [chunk-2]"
+        );
+        assert_eq!(
+            normalize_citations("Canonical [chunk-1] stays put."),
+            "Canonical [chunk-1] stays put."
+        );
+    }
+
+    #[test]
+    fn leaves_non_citation_brackets_alone() {
+        let text =
+            "Use `vec[0]` (see docs) or [link](https://zqsdev.com) [chunk] (chunky 3) [chunk-x] (";
+        assert_eq!(normalize_citations(text), text);
+        assert_eq!(normalize_citations("Café (chunk 1) ✓"), "Café [chunk-1] ✓");
+    }
+
+    #[test]
+    fn collapses_runs_of_blank_lines() {
+        assert_eq!(
+            collapse_blank_lines("One\n\n\n\n\nTwo\n\nThree", 2),
+            "One\n\n\nTwo\n\nThree"
+        );
+        assert_eq!(collapse_blank_lines("A\n  \n\t\n \nB", 2), "A\n  \n\t\nB");
+        assert_eq!(collapse_blank_lines("Single line", 2), "Single line");
+    }
+
+    #[test]
+    fn disabled_cleanup_returns_answer_untouched() {
+        let cleanup = AnswerCleanup::from_env_values(Some("off"), None);
+        assert!(!cleanup.is_enabled());
+        let raw = "As an AI language model, see (chunk 1).\n\n\n\nDone";
+        assert_eq!(cleanup.apply(raw), raw);
+    }
+
+    #[test]
+    fn full_pass_cleans_messy_model_output() {
+        let cleanup = AnswerCleanup::from_env_values(Some("1"), None);
+        let raw = "As an AI language model, I don't have personal opinions, but:\n\n\n\n\n- Led platform work (Chunk 1)\n- Built tooling [chunk 2 , chunk 3]";
+        assert_eq!(
+            cleanup.apply(raw),
+            "I don't have personal opinions, but:\n\n\n- Led platform work [chunk-1]\n- Built tooling [chunk-2] [chunk-3]"
+        );
+    }
+
+    #[test]
+    fn forbidden_phrases_are_stripped_anywhere() {
+        let cleanup = AnswerCleanup::from_env_values(None, None)
+            .with_forbidden_phrases(Some("guaranteed | 100% available |"));
+        assert_eq!(
+            cleanup.apply("He is GUARANTEED to deliver and 100% available , starting May."),
+            "He is to deliver and, starting May."
+        );
+        assert_eq!(
+            cleanup.apply("- Rust\n- Guaranteed uptime"),
+            "- Rust\n- uptime",
+            "Line structure and list markers survive"
+        );
+        assert_eq!(
+            cleanup.apply("Nothing to strip here."),
+            "Nothing to strip here."
+        );
+    }
+
+    #[test]
+    fn forbidden_phrases_apply_even_when_cleanup_is_disabled() {
+        let cleanup = AnswerCleanup::from_env_values(Some("off"), None)
+            .with_forbidden_phrases(Some("booking link"));
+        assert_eq!(
+            cleanup.apply("As an AI, see the booking link (chunk 1)."),
+            "As an AI, see the (chunk 1)."
+        );
+        let none = AnswerCleanup::from_env_values(None, None).with_forbidden_phrases(None);
+        assert_eq!(none.apply("Café ✓"), "Café ✓");
+    }
+}
@@ -0,0 +1,219 @@
+// A detection is trusted once one language leads by this many marker words;
+// below that the Accept-Language header breaks the tie.
+const CONFIDENT_MARGIN: usize = 2;
+
+const FRENCH_MARKERS: &[&str] = &[
+    "le",
+    "la",
+    "les",
+    "un",
+    "une",
+    "des",
+    "du",
+    "et",
+    "est",
+    "il",
+    "elle",
+    "tu",
+    "vous",
+    "quel",
+    "quelle",
+    "quels",
+    "quelles",
+    "qui",
+    "que",
+    "quoi",
+    "comment",
+    "pourquoi",
+    "où",
+    "sur",
+    "avec",
+    "dans",
+    "pour",
+    "ses",
+    "son",
+    "sa",
+    "a-t-il",
+    "est-ce",
+    "travaille",
+    "compétences",
+    "expérience",
+];
+
+const ENGLISH_MARKERS: &[&str] = &[
+    "the",
+    "a",
+    "an",
+    "and",
+    "is",
+    "are",
+    "does",
+    "do",
+    "did",
+    "he",
+    "his",
+    "what",
+    "which",
+    "who",
+    "how",
+    "why",
+    "where",
+    "with",
+    "on",
+    "for",
+    "in",
+    "of",
+    "has",
+    "have",
+    "work",
+    "skills",
+    "experience",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerLanguage {
+    English,
+    French,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Detection {
+    pub language: Option<AnswerLanguage>,
+    pub confident: bool,
+}
+
+impl AnswerLanguage {
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next()?.trim();
+        if primary.eq_ignore_ascii_case("fr") {
+            Some(Self::French)
+        } else if primary.eq_ignore_ascii_case("en") {
+            Some(Self::English)
+        } else {
+            None
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::French => "fr",
+        }
+    }
+
+    pub fn directive(self) -> &'static str {
+        match self {
+            Self::English => "Answer in English.",
+            Self::French => "Réponds en français.",
+        }
+    }
+}
+
+pub fn detect(question: &str) -> Detection {
+    let lowered = question.to_lowercase();
+    let words = lowered
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '-'))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let count = |markers: &[&str]| words.iter().filter(|word| markers.contains(word)).count();
+    let french = count(FRENCH_MARKERS);
+    let english = count(ENGLISH_MARKERS);
+    let language = match french.cmp(&english) {
+        std::cmp::Ordering::Greater => Some(AnswerLanguage::French),
+        std::cmp::Ordering::Less => Some(AnswerLanguage::English),
+        std::cmp::Ordering::Equal => None,
+    };
+    Detection {
+        language,
+        confident: french.abs_diff(english) >= CONFIDENT_MARGIN,
+    }
+}
+
+// Best supported locale from a header such as `fr-FR,fr;q=0.9,en;q=0.8`.
+// Entries keep their order when weights tie; `q=0` means "not acceptable".
+pub fn parse_accept_language(header: &str) -> Option<AnswerLanguage> {
+    let mut ranked = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map(|raw| raw.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (quality > 0.0).then_some((tag, quality))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+        .into_iter()
+        .find_map(|(tag, _)| AnswerLanguage::from_tag(tag))
+}
+
+pub fn negotiate(question: &str, accept_language: Option<&str>) -> AnswerLanguage {
+    let detection = detect(question);
+    if detection.confident {
+        if let Some(language) = detection.language {
+            return language;
+        }
+    }
+    accept_language
+        .and_then(parse_accept_language)
+        .or(detection.language)
+        .unwrap_or(AnswerLanguage::English)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_language_picks_the_best_supported_locale() {
+        assert_eq!(
+            parse_accept_language("fr-FR,fr;q=0.9,en;q=0.8"),
+            Some(AnswerLanguage::French)
+        );
+        assert_eq!(
+            parse_accept_language("de-DE;q=1, en-GB;q=0.5, fr;q=0.7"),
+            Some(AnswerLanguage::French),
+            "Weights matter more than order"
+        );
+        assert_eq!(
+            parse_accept_language("fr;q=0, en-US"),
+            Some(AnswerLanguage::English)
+        );
+        assert_eq!(parse_accept_language("de-DE,es;q=0.8"), None);
+        assert_eq!(parse_accept_language(""), None);
+    }
+
+    #[test]
+    fn confident_detection_wins_over_the_header() {
+        assert_eq!(
+            negotiate(
+                "Quelles sont les compétences de Alexandre ?",
+                Some("en-US,en;q=0.9")
+            ),
+            AnswerLanguage::French
+        );
+        assert_eq!(
+            negotiate("What does he work on these days?", Some("fr-FR,fr;q=0.9")),
+            AnswerLanguage::English
+        );
+    }
+
+    #[test]
+    fn low_confidence_questions_follow_the_header() {
+        let question = "Kubernetes, Rust?";
+        assert!(!detect(question).confident);
+        assert_eq!(
+            negotiate(question, Some("fr-FR,fr;q=0.9,en;q=0.8")),
+            AnswerLanguage::French
+        );
+        assert_eq!(
+            negotiate(question, Some("de-DE,es;q=0.8")),
+            AnswerLanguage::English,
+            "Unsupported lists default to English"
+        );
+        assert_eq!(negotiate(question, None), AnswerLanguage::English);
+    }
+}
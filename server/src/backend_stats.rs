@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::provider_error::{self, ProviderReason};
+
+// Published free-tier request caps per day for the built-in models. They
+// change without notice, so remaining counts are estimates, not guarantees.
+const GROQ_FREE_DAILY_REQUESTS: u64 = 14_400;
+const GOOGLE_FREE_DAILY_REQUESTS: u64 = 1_000;
+
+fn free_tier_daily_requests(backend: &str) -> Option<u64> {
+    match backend {
+        "groq" => Some(GROQ_FREE_DAILY_REQUESTS),
+        "google" => Some(GOOGLE_FREE_DAILY_REQUESTS),
+        _ => None,
+    }
+}
+
+// A 429 only counts as a quota error when the body says so; plain per-second
+// throttling without a recognizable code is left to the circuit breaker.
+pub fn quota_reason(status: StatusCode, body: &str) -> Option<ProviderReason> {
+    if status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    match provider_error::parse(body) {
+        Some(reason) if reason.is_quota() => Some(reason),
+        Some(_) => None,
+        None => body
+            .to_ascii_lowercase()
+            .contains("quota")
+            .then_some(ProviderReason::QuotaExhausted),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BackendStats {
+    backends: BTreeMap<String, BackendCounters>,
+}
+
+#[derive(Debug, Default)]
+struct BackendCounters {
+    requests_total: u64,
+    day: Option<NaiveDate>,
+    requests_today: u64,
+    quota_errors: u64,
+    last_quota_error: Option<QuotaError>,
+    warned_hour: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QuotaError {
+    at: DateTime<Utc>,
+    reason: ProviderReason,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BackendUsage {
+    pub backend: String,
+    pub requests_total: u64,
+    pub requests_today: u64,
+    pub quota_errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_quota_error_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_quota_reason: Option<ProviderReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_tier_daily_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_remaining_today: Option<u64>,
+}
+
+impl BackendStats {
+    pub fn record_request(&mut self, backend: &str, now: DateTime<Utc>) {
+        let counters = self.counters(backend, now);
+        counters.requests_total += 1;
+        counters.requests_today += 1;
+    }
+
+    // Returns true for the first quota error of a backend in a given clock
+    // hour, so callers warn once instead of on every fallback.
+    pub fn record_quota_error(
+        &mut self,
+        backend: &str,
+        reason: ProviderReason,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let counters = self.counters(backend, now);
+        counters.quota_errors += 1;
+        counters.last_quota_error = Some(QuotaError { at: now, reason });
+        let hour = now.timestamp().div_euclid(3600);
+        let first_this_hour = counters.warned_hour != Some(hour);
+        counters.warned_hour = Some(hour);
+        first_this_hour
+    }
+
+    pub fn snapshot(&self, now: DateTime<Utc>) -> Vec<BackendUsage> {
+        let today = now.date_naive();
+        self.backends
+            .iter()
+            .map(|(backend, counters)| {
+                let requests_today = if counters.day == Some(today) {
+                    counters.requests_today
+                } else {
+                    0
+                };
+                let limit = free_tier_daily_requests(backend);
+                let exhausted_today = counters
+                    .last_quota_error
+                    .is_some_and(|error| error.at.date_naive() == today);
+                BackendUsage {
+                    backend: backend.clone(),
+                    requests_total: counters.requests_total,
+                    requests_today,
+                    quota_errors: counters.quota_errors,
+                    last_quota_error_at: counters
+                        .last_quota_error
+                        .map(|error| error.at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                    last_quota_reason: counters.last_quota_error.map(|error| error.reason),
+                    free_tier_daily_limit: limit,
+                    estimated_remaining_today: limit.map(|limit| {
+                        if exhausted_today {
+                            0
+                        } else {
+                            limit.saturating_sub(requests_today)
+                        }
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    fn counters(&mut self, backend: &str, now: DateTime<Utc>) -> &mut BackendCounters {
+        let counters = self.backends.entry(backend.to_string()).or_default();
+        let today = now.date_naive();
+        if counters.day != Some(today) {
+            counters.day = Some(today);
+            counters.requests_today = 0;
+        }
+        counters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn quota_errors_need_a_429_and_a_quota_body() {
+        let gemini = r#"{"error":{"code":429,"message":"Resource has been exhausted","status":"RESOURCE_EXHAUSTED"}}"#;
+        assert_eq!(
+            quota_reason(StatusCode::TOO_MANY_REQUESTS, gemini),
+            Some(ProviderReason::RateLimited)
+        );
+        let groq = r#"{"error":{"message":"Rate limit reached on requests per day (RPD)","type":"requests","code":"rate_limit_exceeded"}}"#;
+        assert_eq!(
+            quota_reason(StatusCode::TOO_MANY_REQUESTS, groq),
+            Some(ProviderReason::RateLimited)
+        );
+        assert_eq!(
+            quota_reason(StatusCode::TOO_MANY_REQUESTS, "Daily quota exceeded"),
+            Some(ProviderReason::QuotaExhausted)
+        );
+        assert_eq!(
+            quota_reason(StatusCode::TOO_MANY_REQUESTS, "slow down"),
+            None
+        );
+        assert_eq!(quota_reason(StatusCode::BAD_REQUEST, gemini), None);
+        let bad_key = r#"{"error":{"code":"invalid_api_key","message":"Incorrect API key"}}"#;
+        assert_eq!(quota_reason(StatusCode::TOO_MANY_REQUESTS, bad_key), None);
+    }
+
+    #[test]
+    fn stats_count_requests_per_day_and_estimate_remaining_quota() {
+        let mut stats = BackendStats::default();
+        for _ in 0..3 {
+            stats.record_request("google", at("2026-10-15T23:00:00Z"));
+        }
+        stats.record_request("google", at("2026-10-16T08:00:00Z"));
+        stats.record_request("openai", at("2026-10-16T08:00:00Z"));
+
+        let usage = stats.snapshot(at("2026-10-16T09:00:00Z"));
+        let google = &usage[0];
+        assert_eq!(google.backend, "google");
+        assert_eq!(google.requests_total, 4);
+        assert_eq!(
+            google.requests_today, 1,
+            "Daily counts reset at midnight UTC"
+        );
+        assert_eq!(
+            google.free_tier_daily_limit,
+            Some(GOOGLE_FREE_DAILY_REQUESTS)
+        );
+        assert_eq!(
+            google.estimated_remaining_today,
+            Some(GOOGLE_FREE_DAILY_REQUESTS - 1)
+        );
+        let openai = &usage[1];
+        assert_eq!(openai.free_tier_daily_limit, None);
+        assert_eq!(openai.estimated_remaining_today, None);
+
+        stats.record_quota_error(
+            "google",
+            ProviderReason::QuotaExhausted,
+            at("2026-10-16T09:30:00Z"),
+        );
+        let google = &stats.snapshot(at("2026-10-16T10:00:00Z"))[0];
+        assert_eq!(google.quota_errors, 1);
+        assert_eq!(google.estimated_remaining_today, Some(0));
+        assert_eq!(
+            google.last_quota_error_at.as_deref(),
+            Some("2026-10-16T09:30:00Z")
+        );
+        assert_eq!(
+            google.last_quota_reason,
+            Some(ProviderReason::QuotaExhausted)
+        );
+        let tomorrow = &stats.snapshot(at("2026-10-17T00:30:00Z"))[0];
+        assert_eq!(tomorrow.requests_today, 0);
+        assert_eq!(
+            tomorrow.estimated_remaining_today,
+            Some(GOOGLE_FREE_DAILY_REQUESTS)
+        );
+    }
+
+    #[test]
+    fn quota_warnings_fire_once_per_backend_and_hour() {
+        let mut stats = BackendStats::default();
+        let reason = ProviderReason::RateLimited;
+        assert!(stats.record_quota_error("groq", reason, at("2026-10-16T10:05:00Z")));
+        assert!(!stats.record_quota_error("groq", reason, at("2026-10-16T10:55:00Z")));
+        assert!(
+            stats.record_quota_error("google", reason, at("2026-10-16T10:56:00Z")),
+            "Each backend has its own gate"
+        );
+        assert!(stats.record_quota_error("groq", reason, at("2026-10-16T11:00:00Z")));
+        assert_eq!(
+            stats.snapshot(at("2026-10-16T11:00:00Z"))[1].quota_errors,
+            3
+        );
+    }
+}
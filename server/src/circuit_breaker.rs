@@ -0,0 +1,99 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const FAILURE_THRESHOLD: u32 = 3;
+const OPEN_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn is_open(&self) -> bool {
+        self.is_open_at(Instant::now())
+    }
+
+    pub fn record_success(&self) {
+        *self.lock() = BreakerState::default();
+    }
+
+    pub fn record_failure(&self) {
+        self.record_failure_at(Instant::now());
+    }
+
+    pub fn trip(&self) {
+        self.trip_at(Instant::now());
+    }
+
+    fn is_open_at(&self, now: Instant) -> bool {
+        self.lock()
+            .open_until
+            .map(|until| now < until)
+            .unwrap_or(false)
+    }
+
+    fn record_failure_at(&self, now: Instant) {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= FAILURE_THRESHOLD {
+            state.open_until = Some(now + OPEN_COOLDOWN);
+        }
+    }
+
+    fn trip_at(&self, now: Instant) {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.max(FAILURE_THRESHOLD);
+        state.open_until = Some(now + OPEN_COOLDOWN);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_repeated_failures() {
+        let breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        for _ in 1..FAILURE_THRESHOLD {
+            breaker.record_failure_at(now);
+        }
+        assert!(!breaker.is_open_at(now), "Below the threshold stays closed");
+        breaker.record_failure_at(now);
+        assert!(breaker.is_open_at(now));
+        assert!(
+            !breaker.is_open_at(now + OPEN_COOLDOWN),
+            "Cooldown elapsed lets a probe through"
+        );
+    }
+
+    #[test]
+    fn trip_opens_immediately_and_success_closes() {
+        let breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        breaker.trip_at(now);
+        assert!(breaker.is_open_at(now));
+        breaker.record_success();
+        assert!(!breaker.is_open_at(now));
+
+        breaker.trip_at(now);
+        breaker.record_failure_at(now + OPEN_COOLDOWN);
+        assert!(
+            breaker.is_open_at(now + OPEN_COOLDOWN),
+            "A failed probe after a trip reopens the breaker"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use crate::qa_log::QaLogger;
+use crate::rag::RagRetriever;
+use anyhow::{anyhow, Context};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, Method};
+use dotenvy::Error as DotenvError;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tower_http::cors::{self, AllowOrigin, CorsLayer};
+use tracing::{info, warn};
+
+const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+pub const PER_MINUTE_BUDGET_EUR: f64 = 0.50;
+pub const PER_HOUR_BUDGET_EUR: f64 = 2.00;
+pub const PER_DAY_BUDGET_EUR: f64 = 2.00; // Align daily to €2 hard cap
+pub const PER_MONTH_BUDGET_EUR: f64 = 10.00;
+pub const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+const CORS_MAX_AGE_SECS: u64 = 600;
+pub const DEFAULT_SITE_URL: &str = "https://zqsdev.com";
+pub const WIDGET_TOKEN_DEFAULT_DAYS: i64 = 90;
+const DEFAULT_QA_LOG_PATH: &str = "qa_log.db";
+const DEFAULT_QA_LOG_RETENTION_DAYS: u32 = 30;
+
+pub fn server_commit_hash() -> &'static str {
+    option_env!("GIT_COMMIT_HASH").unwrap_or("unknown")
+}
+
+pub fn server_built_at() -> Option<&'static str> {
+    option_env!("BUILD_TIMESTAMP")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+pub async fn build_retriever(
+    static_dir: &Path,
+    openai_key: &str,
+) -> anyhow::Result<Option<RagRetriever>> {
+    let pinecone_key = match std::env::var("PINECONE_API_KEY") {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+    let pinecone_host = match std::env::var("PINECONE_HOST") {
+        Ok(value) => value,
+        Err(_) => {
+            warn!(target: "rag", "PINECONE_HOST not set; skipping retriever initialization");
+            return Ok(None);
+        }
+    };
+    let pinecone_namespace = std::env::var("PINECONE_NAMESPACE").ok();
+    let rag_path = rag_db_path(static_dir);
+    if !rag_path.exists() {
+        warn!(
+            target: "rag",
+            path = %rag_path.display(),
+            "RAG SQLite bundle missing; skipping retriever"
+        );
+        return Ok(None);
+    }
+    let embedding_model = std::env::var("OPENAI_EMBEDDING_MODEL")
+        .unwrap_or_else(|_| OPENAI_EMBEDDING_MODEL.to_string());
+    let top_k = std::env::var("RAG_TOP_K")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(4);
+    let min_score = std::env::var("RAG_MIN_SCORE")
+        .ok()
+        .and_then(|value| value.parse::<f32>().ok())
+        .unwrap_or(0.45);
+    let retriever = RagRetriever::new(
+        rag_path,
+        pinecone_host,
+        pinecone_key,
+        pinecone_namespace,
+        openai_key.to_string(),
+        embedding_model,
+        top_k,
+        min_score,
+    )
+    .await?;
+    info!(
+        target: "rag",
+        top_k,
+        min_score = min_score,
+        "Pinecone-backed retriever ready"
+    );
+    Ok(Some(retriever))
+}
+
+pub fn configure_tracing() {
+    let default_filter = "info";
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| default_filter.into()),
+        )
+        .init();
+}
+
+pub fn parse_allowed_origins(raw: &str) -> anyhow::Result<Option<AllowedOrigins>> {
+    let entries: Vec<&str> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    if entries.contains(&"*") {
+        if entries.len() > 1 {
+            return Err(anyhow!(
+                "ALLOWED_ORIGINS cannot mix `*` with explicit origins"
+            ));
+        }
+        return Ok(Some(AllowedOrigins::Any));
+    }
+
+    let mut origins = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let origin = entry.trim_end_matches('/');
+        if !(origin.starts_with("https://") || origin.starts_with("http://")) {
+            return Err(anyhow!(
+                "ALLOWED_ORIGINS entry {entry:?} must start with http:// or https://"
+            ));
+        }
+        let value = HeaderValue::from_str(origin)
+            .with_context(|| format!("ALLOWED_ORIGINS entry {entry:?} is not a valid origin"))?;
+        if !origins.contains(&value) {
+            origins.push(value);
+        }
+    }
+    Ok(Some(AllowedOrigins::List(origins)))
+}
+
+// Pages allowed to request embed tokens. Same syntax as ALLOWED_ORIGINS, but
+// a wildcard would defeat the point of binding tokens to an origin.
+pub fn parse_embed_origins(raw: &str) -> anyhow::Result<Vec<String>> {
+    match parse_allowed_origins(raw).context("EMBED_ALLOWED_ORIGINS is invalid")? {
+        None => Ok(Vec::new()),
+        Some(AllowedOrigins::Any) => Err(anyhow!(
+            "EMBED_ALLOWED_ORIGINS must list explicit origins, not `*`"
+        )),
+        Some(AllowedOrigins::List(origins)) => Ok(origins
+            .iter()
+            .filter_map(|origin| origin.to_str().ok())
+            .map(str::to_string)
+            .collect()),
+    }
+}
+
+pub fn cors_layer(origins: &AllowedOrigins) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([CONTENT_TYPE])
+        .max_age(Duration::from_secs(CORS_MAX_AGE_SECS));
+    match origins {
+        AllowedOrigins::Any => layer.allow_origin(cors::Any),
+        AllowedOrigins::List(origins) => layer.allow_origin(AllowOrigin::list(origins.clone())),
+    }
+}
+
+pub fn load_env_files() {
+    fn load(file: &str) {
+        match dotenvy::from_filename(file) {
+            Ok(_) => {}
+            Err(DotenvError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => eprintln!("Warning: unable to load {file}: {err}"),
+        }
+    }
+
+    load(".env.local");
+    load(".env");
+}
+
+pub fn warmup_enabled(value: Option<&str>) -> bool {
+    !matches!(
+        value
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref(),
+        Some("0" | "false" | "no" | "off")
+    )
+}
+
+pub fn env_flag_enabled(key: &str) -> bool {
+    flag_enabled(std::env::var(key).ok().as_deref())
+}
+
+fn flag_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on"))
+}
+
+pub fn resolve_log_path(env_key: &str, default: &str) -> PathBuf {
+    std::env::var(env_key)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(default))
+}
+
+pub fn rag_db_path(static_dir: &Path) -> PathBuf {
+    std::env::var("RAG_DB_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| static_dir.join("data/rag_chunks.db"))
+}
+
+pub fn qa_log_path() -> PathBuf {
+    resolve_log_path("AI_QA_LOG_PATH", DEFAULT_QA_LOG_PATH)
+}
+
+pub async fn start_qa_log() -> Option<QaLogger> {
+    let backend = std::env::var("AI_QA_LOG").ok()?;
+    if !backend.trim().eq_ignore_ascii_case("sqlite") {
+        warn!(target: "log", backend = backend.as_str(), "Unsupported AI_QA_LOG backend; Q&A log disabled");
+        return None;
+    }
+    let path = qa_log_path();
+    let retention_days = std::env::var("AI_QA_LOG_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_QA_LOG_RETENTION_DAYS);
+    let log_prompts = env_flag_enabled("AI_LOG_PROMPTS");
+    match QaLogger::start(&path, log_prompts, retention_days).await {
+        Ok(logger) => {
+            info!(
+                target: "log",
+                path = %path.display(),
+                retention_days,
+                log_prompts,
+                "Q&A log enabled"
+            );
+            Some(logger)
+        }
+        Err(err) => {
+            warn!(target: "log", error = %err, "Failed to start the Q&A log; continuing without it");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_client::{AiClient, FIXTURE_MODEL_NAME, GROQ_MODEL_NAME};
+    use crate::handlers::build_router;
+    use crate::test_support::{all_builtin_client, test_app_state};
+    use axum::http::{Request, StatusCode};
+    use axum::{body::Body, Router};
+    use tower::ServiceExt;
+
+    fn cors_app(allowed: &str) -> Router {
+        let client = AiClient::new(None, None, Some("openai-key".to_string()), Vec::new())
+            .expect("client should construct");
+        let origins = parse_allowed_origins(allowed)
+            .expect("origins should parse")
+            .expect("origins should be configured");
+        build_router(test_app_state(client)).layer(cors_layer(&origins))
+    }
+
+    fn request_from(method: Method, uri: &str, origin: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(axum::http::header::ORIGIN, origin)
+            .header(axum::http::header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .expect("request should build")
+    }
+
+    #[test]
+    fn fixture_mode_is_off_unless_explicitly_enabled() {
+        assert!(!flag_enabled(None));
+        assert!(!flag_enabled(Some("")));
+        assert!(!flag_enabled(Some("0")));
+        assert!(!flag_enabled(Some("off")));
+        assert!(flag_enabled(Some("1")));
+        assert!(flag_enabled(Some(" true ")));
+
+        let fixture = AiClient::fixture().expect("fixture client");
+        assert_eq!(fixture.primary_model(), Some(FIXTURE_MODEL_NAME));
+        assert!(fixture.backend_order(None).is_empty());
+        assert_eq!(fixture.estimate_cost(1_000, None), 0.0);
+        let real = all_builtin_client();
+        assert!(!real.fixture);
+        assert_eq!(real.primary_model(), Some(GROQ_MODEL_NAME));
+    }
+
+    #[test]
+    fn allowed_origins_parse_lists_and_wildcard() {
+        assert_eq!(parse_allowed_origins("").expect("empty is valid"), None);
+        assert_eq!(parse_allowed_origins(" , ").expect("blank is valid"), None);
+        assert_eq!(
+            parse_allowed_origins("*").expect("wildcard is valid"),
+            Some(AllowedOrigins::Any)
+        );
+        assert_eq!(
+            parse_allowed_origins("https://zqsdev.com/, http://localhost:8080,https://zqsdev.com")
+                .expect("list is valid"),
+            Some(AllowedOrigins::List(vec![
+                HeaderValue::from_static("https://zqsdev.com"),
+                HeaderValue::from_static("http://localhost:8080"),
+            ]))
+        );
+        assert!(parse_allowed_origins("*,https://zqsdev.com").is_err());
+        assert!(parse_allowed_origins("zqsdev.com").is_err());
+    }
+
+    #[test]
+    fn embed_origins_reject_wildcards() {
+        assert!(parse_embed_origins("").expect("empty is valid").is_empty());
+        assert_eq!(
+            parse_embed_origins("https://partner.example/, https://partner.example")
+                .expect("list is valid"),
+            vec!["https://partner.example".to_string()]
+        );
+        assert!(parse_embed_origins("*").is_err());
+        assert!(parse_embed_origins("partner.example").is_err());
+    }
+
+    #[tokio::test]
+    async fn cors_allows_listed_origins_only() {
+        let app = cors_app("https://embed.example.com");
+
+        let allowed = app
+            .clone()
+            .oneshot(request_from(
+                Method::GET,
+                "/api/version",
+                "https://embed.example.com",
+            ))
+            .await
+            .expect("request should succeed");
+        assert_eq!(allowed.status(), StatusCode::OK);
+        assert_eq!(
+            allowed
+                .headers()
+                .get(axum::http::header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&HeaderValue::from_static("https://embed.example.com"))
+        );
+
+        let denied = app
+            .oneshot(request_from(
+                Method::GET,
+                "/api/version",
+                "https://evil.example.com",
+            ))
+            .await
+            .expect("request should succeed");
+        assert!(denied
+            .headers()
+            .get(axum::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn cors_answers_preflight_for_api_routes() {
+        let app = cors_app("https://embed.example.com");
+        let response = app
+            .oneshot(request_from(
+                Method::OPTIONS,
+                "/api/ai",
+                "https://embed.example.com",
+            ))
+            .await
+            .expect("preflight should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+        let methods = response
+            .headers()
+            .get(axum::http::header::ACCESS_CONTROL_ALLOW_METHODS)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        assert!(
+            methods.contains("POST"),
+            "Preflight should allow POST: {methods}"
+        );
+
+        let wildcard = cors_app("*")
+            .oneshot(request_from(
+                Method::OPTIONS,
+                "/api/data",
+                "https://anywhere.dev",
+            ))
+            .await
+            .expect("preflight should succeed");
+        assert_eq!(
+            wildcard
+                .headers()
+                .get(axum::http::header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&HeaderValue::from_static("*"))
+        );
+        assert!(wildcard
+            .headers()
+            .get(axum::http::header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .is_none());
+    }
+
+    #[test]
+    fn warmup_is_enabled_unless_explicitly_disabled() {
+        assert!(warmup_enabled(None));
+        assert!(warmup_enabled(Some("1")));
+        assert!(warmup_enabled(Some("")));
+        assert!(!warmup_enabled(Some("0")));
+        assert!(!warmup_enabled(Some(" OFF ")));
+        assert!(!warmup_enabled(Some("false")));
+    }
+}
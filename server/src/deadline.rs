@@ -0,0 +1,123 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+// Upper bound for one `/api/ai` request across retrieval, every backend
+// attempt and the continuation call.
+pub const REQUEST_DEADLINE: Duration = Duration::from_secs(20);
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Deadline<C: Clock = TokioClock> {
+    clock: C,
+    expires_at: Instant,
+}
+
+impl Deadline {
+    pub fn after(budget: Duration) -> Self {
+        Self::with_clock(TokioClock, budget)
+    }
+}
+
+impl<C: Clock> Deadline<C> {
+    pub fn with_clock(clock: C, budget: Duration) -> Self {
+        let expires_at = clock.now() + budget;
+        Self { clock, expires_at }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.expires_at.saturating_duration_since(self.clock.now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    // A stage's own timeout, shortened when less than that is left overall.
+    pub fn cap(&self, timeout: Duration) -> Duration {
+        timeout.min(self.remaining())
+    }
+
+    pub fn allows(&self, needed: Duration) -> bool {
+        !self.is_expired() && self.remaining() >= needed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct ManualClock {
+        now: Cell<Instant>,
+    }
+
+    impl ManualClock {
+        fn new() -> Self {
+            Self {
+                now: Cell::new(Instant::now()),
+            }
+        }
+
+        fn advance(&self, by: Duration) {
+            self.now.set(self.now.get() + by);
+        }
+    }
+
+    impl Clock for &ManualClock {
+        fn now(&self) -> Instant {
+            self.now.get()
+        }
+    }
+
+    #[test]
+    fn remaining_time_shrinks_with_the_clock_and_saturates() {
+        let clock = ManualClock::new();
+        let deadline = Deadline::with_clock(&clock, Duration::from_secs(20));
+        assert_eq!(deadline.remaining(), Duration::from_secs(20));
+        clock.advance(Duration::from_secs(12));
+        assert_eq!(deadline.remaining(), Duration::from_secs(8));
+        assert!(!deadline.is_expired());
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(deadline.remaining(), Duration::ZERO);
+        assert!(deadline.is_expired());
+    }
+
+    #[test]
+    fn stage_timeouts_are_capped_by_what_is_left() {
+        let clock = ManualClock::new();
+        let deadline = Deadline::with_clock(&clock, Duration::from_secs(20));
+        assert_eq!(deadline.cap(Duration::from_secs(8)), Duration::from_secs(8));
+        clock.advance(Duration::from_secs(15));
+        assert_eq!(deadline.cap(Duration::from_secs(8)), Duration::from_secs(5));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(deadline.cap(Duration::from_secs(8)), Duration::ZERO);
+    }
+
+    #[test]
+    fn attempts_need_their_minimum_time_left() {
+        let clock = ManualClock::new();
+        let deadline = Deadline::with_clock(&clock, Duration::from_secs(10));
+        assert!(deadline.allows(Duration::from_secs(3)));
+        clock.advance(Duration::from_secs(7));
+        assert!(deadline.allows(Duration::from_secs(3)));
+        clock.advance(Duration::from_millis(1));
+        assert!(!deadline.allows(Duration::from_secs(3)));
+        clock.advance(Duration::from_secs(5));
+        assert!(
+            !deadline.allows(Duration::ZERO),
+            "an expired deadline allows nothing"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use serde_json::Value;
+
+pub const FAQ_DIRECT_MODEL: &str = "faq-direct";
+
+// Below this similarity a question is only close to an FAQ entry and still
+// goes to a model, which can answer the part that differs.
+const MATCH_THRESHOLD: f64 = 0.85;
+// A single swapped word ("onsite" for "remote") barely moves the overall
+// ratio, so every word also needs a close counterpart on the other side.
+const WORD_THRESHOLD: f64 = 0.7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaqMatch<'a> {
+    pub question: &'a str,
+    pub answer: &'a str,
+    pub similarity: f64,
+}
+
+// Emoji and punctuation are dropped, so "🌍 Are you open to remote roles?"
+// and "are you open to remote roles" compare equal.
+pub fn normalize_question(text: &str) -> String {
+    text.chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() {
+                ch.to_lowercase().next().unwrap_or(ch)
+            } else if ch.is_whitespace() {
+                ' '
+            } else {
+                '\0'
+            }
+        })
+        .filter(|ch| *ch != '\0')
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Levenshtein distance scaled to 0..=1 by the longer string's length.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, left) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, right) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left != right);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+fn words_align(left: &str, right: &str) -> bool {
+    let covered = |from: &str, to: &str| {
+        from.split(' ').all(|word| {
+            to.split(' ')
+                .any(|other| similarity(word, other) >= WORD_THRESHOLD)
+        })
+    };
+    covered(left, right) && covered(right, left)
+}
+
+pub fn find_match<'a>(question: &str, faqs: &'a Value) -> Option<FaqMatch<'a>> {
+    let normalized = normalize_question(question);
+    if normalized.is_empty() {
+        return None;
+    }
+    faqs.as_array()?
+        .iter()
+        .filter_map(|entry| {
+            let faq_question = entry.get("question")?.as_str()?;
+            let answer = entry.get("answer")?.as_str()?.trim();
+            if answer.is_empty() {
+                return None;
+            }
+            let candidate = normalize_question(faq_question);
+            let similarity = similarity(&normalized, &candidate);
+            if similarity < MATCH_THRESHOLD || !words_align(&normalized, &candidate) {
+                return None;
+            }
+            Some(FaqMatch {
+                question: faq_question,
+                answer,
+                similarity,
+            })
+        })
+        .max_by(|a, b| a.similarity.total_cmp(&b.similarity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn bundled_faqs() -> Value {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/data/faq.json");
+        let raw = std::fs::read_to_string(path).expect("bundled faq.json");
+        serde_json::from_str(&raw).expect("faq.json parses")
+    }
+
+    #[test]
+    fn normalization_drops_emoji_punctuation_and_case() {
+        assert_eq!(
+            normalize_question("🌍 Are you open to   REMOTE roles?"),
+            "are you open to remote roles"
+        );
+        assert_eq!(
+            normalize_question("⏱️ How soon can you start?"),
+            "how soon can you start"
+        );
+        assert_eq!(normalize_question("🤖 ❓ !!"), "");
+    }
+
+    #[test]
+    fn similarity_is_a_normalized_edit_distance() {
+        assert_eq!(similarity("remote", "remote"), 1.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("abc", ""), 0.0);
+        assert!((similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn every_bundled_faq_matches_itself_verbatim_and_without_emoji() {
+        let faqs = bundled_faqs();
+        let entries = faqs.as_array().expect("faq array");
+        assert!(!entries.is_empty());
+        for entry in entries {
+            let question = entry["question"].as_str().expect("question");
+            let answer = entry["answer"].as_str().expect("answer");
+            for asked in [question.to_string(), normalize_question(question)] {
+                let found = find_match(&asked, &faqs).expect("verbatim FAQ should match");
+                assert_eq!(found.question, question);
+                assert_eq!(found.answer, answer.trim());
+            }
+        }
+    }
+
+    #[test]
+    fn small_typos_still_match() {
+        let faqs = bundled_faqs();
+        let found = find_match("are you open to remote role", &faqs).expect("typo should match");
+        assert!(found.question.contains("remote roles"));
+        assert!(found.similarity >= MATCH_THRESHOLD && found.similarity < 1.0);
+    }
+
+    #[test]
+    fn near_miss_questions_do_not_short_circuit() {
+        let faqs = bundled_faqs();
+        for question in [
+            "Are you open to remote roles in Canada?",
+            "Are you open to onsite roles?",
+            "How soon can you start a Rust project with us?",
+            "What industries does PlayStation focus on?",
+            "Can you lead teams?",
+            "remote",
+            "",
+        ] {
+            assert_eq!(
+                find_match(question, &faqs),
+                None,
+                "{question:?} should reach a model"
+            );
+        }
+    }
+
+    #[test]
+    fn word_alignment_rejects_swapped_words_but_allows_typos() {
+        assert!(words_align(
+            "are you open to remote roles",
+            "are you open to remote role"
+        ));
+        assert!(!words_align(
+            "are you open to onsite roles",
+            "are you open to remote roles"
+        ));
+    }
+
+    #[test]
+    fn malformed_faq_payloads_never_match() {
+        assert_eq!(find_match("anything", &Value::Null), None);
+        let faqs = serde_json::json!([
+            { "question": "Where are you based?" },
+            { "question": "Where are you based?", "answer": "   " },
+        ]);
+        assert_eq!(find_match("Where are you based?", &faqs), None);
+    }
+}
@@ -0,0 +1,267 @@
+use thiserror::Error;
+use tracing::warn;
+
+pub const DEFAULT_TEMPERATURE: f32 = 0.3;
+const MAX_TEMPERATURE: f32 = 2.0;
+const MIN_TOP_P: f32 = 0.01;
+
+// Sampling settings sent with one kind of request. `top_p` is omitted from
+// payloads unless configured, leaving the provider default in place.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationPreset {
+    pub temperature: f32,
+    pub top_p: Option<f32>,
+    pub max_tokens: usize,
+}
+
+impl GenerationPreset {
+    pub const fn new(max_tokens: usize) -> Self {
+        Self {
+            temperature: DEFAULT_TEMPERATURE,
+            top_p: None,
+            max_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerationMode {
+    Answer,
+    Summary,
+    FollowUp,
+}
+
+impl GenerationMode {
+    pub const ALL: [GenerationMode; 3] = [
+        GenerationMode::Answer,
+        GenerationMode::Summary,
+        GenerationMode::FollowUp,
+    ];
+
+    pub fn env_var(self) -> &'static str {
+        match self {
+            GenerationMode::Answer => "AI_PRESET_ANSWER",
+            GenerationMode::Summary => "AI_PRESET_SUMMARY",
+            GenerationMode::FollowUp => "AI_PRESET_FOLLOW_UP",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationPresets {
+    pub answer: GenerationPreset,
+    pub summary: GenerationPreset,
+    pub follow_up: GenerationPreset,
+}
+
+impl GenerationPresets {
+    pub fn get(&self, mode: GenerationMode) -> GenerationPreset {
+        match mode {
+            GenerationMode::Answer => self.answer,
+            GenerationMode::Summary => self.summary,
+            GenerationMode::FollowUp => self.follow_up,
+        }
+    }
+
+    fn get_mut(&mut self, mode: GenerationMode) -> &mut GenerationPreset {
+        match mode {
+            GenerationMode::Answer => &mut self.answer,
+            GenerationMode::Summary => &mut self.summary,
+            GenerationMode::FollowUp => &mut self.follow_up,
+        }
+    }
+
+    // Each mode reads `AI_PRESET_<MODE>`; unset variables keep the defaults.
+    pub fn with_env_overrides(
+        mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, PresetError> {
+        for mode in GenerationMode::ALL {
+            let var = mode.env_var();
+            if let Some(raw) = lookup(var) {
+                let preset = self.get_mut(mode);
+                *preset = parse_preset(var, &raw, *preset)?;
+            }
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PresetError {
+    #[error("{var} entries must look like key=value, got {entry:?}")]
+    Malformed { var: String, entry: String },
+    #[error("{var} has an unknown key {key:?} (expected temperature, top_p or max_tokens)")]
+    UnknownKey { var: String, key: String },
+    #[error("{var}: {key} must be a number, got {value:?}")]
+    BadNumber {
+        var: String,
+        key: String,
+        value: String,
+    },
+}
+
+// Parses `temperature=0.2,top_p=0.9,max_tokens=256` over `base`. Values that
+// parse but fall outside what providers accept are clamped with a warning.
+pub fn parse_preset(
+    var: &str,
+    raw: &str,
+    base: GenerationPreset,
+) -> Result<GenerationPreset, PresetError> {
+    let mut preset = base;
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (key, value) = entry
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| PresetError::Malformed {
+                var: var.to_string(),
+                entry: entry.to_string(),
+            })?;
+        let bad_number = || PresetError::BadNumber {
+            var: var.to_string(),
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "temperature" => {
+                let temperature = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|value| value.is_finite())
+                    .ok_or_else(bad_number)?;
+                preset.temperature = clamped(var, key, temperature, 0.0, MAX_TEMPERATURE);
+            }
+            "top_p" => {
+                let top_p = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|value| value.is_finite())
+                    .ok_or_else(bad_number)?;
+                preset.top_p = Some(clamped(var, key, top_p, MIN_TOP_P, 1.0));
+            }
+            "max_tokens" => {
+                let max_tokens = value.parse::<usize>().map_err(|_| bad_number())?;
+                if max_tokens == 0 {
+                    warn!(target: "ai", var, "max_tokens must be at least 1; using 1");
+                }
+                preset.max_tokens = max_tokens.max(1);
+            }
+            _ => {
+                return Err(PresetError::UnknownKey {
+                    var: var.to_string(),
+                    key: key.to_string(),
+                })
+            }
+        }
+    }
+    Ok(preset)
+}
+
+fn clamped(var: &str, key: &str, value: f32, min: f32, max: f32) -> f32 {
+    let clamped = value.clamp(min, max);
+    if clamped != value {
+        warn!(
+            target: "ai",
+            var,
+            key,
+            value,
+            clamped,
+            "Generation preset value out of range; clamping"
+        );
+    }
+    clamped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: GenerationPreset = GenerationPreset::new(384);
+
+    #[test]
+    fn presets_parse_known_keys_over_the_base() {
+        let preset = parse_preset(
+            "AI_PRESET_ANSWER",
+            " temperature=0.7, top_p = 0.9 ,max_tokens=256,",
+            BASE,
+        )
+        .expect("valid preset");
+        assert_eq!(
+            preset,
+            GenerationPreset {
+                temperature: 0.7,
+                top_p: Some(0.9),
+                max_tokens: 256,
+            }
+        );
+        let partial = parse_preset("AI_PRESET_ANSWER", "max_tokens=120", BASE).expect("valid");
+        assert_eq!(partial.temperature, DEFAULT_TEMPERATURE);
+        assert_eq!(partial.top_p, None);
+        assert_eq!(partial.max_tokens, 120);
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let preset = parse_preset(
+            "AI_PRESET_SUMMARY",
+            "temperature=3.5,top_p=0,max_tokens=0",
+            BASE,
+        )
+        .expect("clamped preset");
+        assert_eq!(preset.temperature, 2.0);
+        assert_eq!(preset.top_p, Some(MIN_TOP_P));
+        assert_eq!(preset.max_tokens, 1);
+        let negative = parse_preset("AI_PRESET_SUMMARY", "temperature=-1,top_p=4", BASE)
+            .expect("clamped preset");
+        assert_eq!(negative.temperature, 0.0);
+        assert_eq!(negative.top_p, Some(1.0));
+    }
+
+    #[test]
+    fn malformed_presets_are_rejected() {
+        assert!(matches!(
+            parse_preset("AI_PRESET_ANSWER", "temperature", BASE),
+            Err(PresetError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse_preset("AI_PRESET_ANSWER", "temp=0.5", BASE),
+            Err(PresetError::UnknownKey { .. })
+        ));
+        assert!(matches!(
+            parse_preset("AI_PRESET_ANSWER", "temperature=warm", BASE),
+            Err(PresetError::BadNumber { .. })
+        ));
+        assert!(matches!(
+            parse_preset("AI_PRESET_ANSWER", "temperature=NaN", BASE),
+            Err(PresetError::BadNumber { .. })
+        ));
+    }
+
+    #[test]
+    fn env_overrides_apply_per_mode() {
+        let defaults = GenerationPresets {
+            answer: BASE,
+            summary: GenerationPreset::new(160),
+            follow_up: BASE,
+        };
+        let presets = defaults
+            .with_env_overrides(|var| match var {
+                "AI_PRESET_SUMMARY" => Some("temperature=0.1".to_string()),
+                "AI_PRESET_FOLLOW_UP" => Some("top_p=0.5,max_tokens=200".to_string()),
+                _ => None,
+            })
+            .expect("valid env");
+        assert_eq!(presets.get(GenerationMode::Answer), BASE);
+        assert_eq!(presets.summary.temperature, 0.1);
+        assert_eq!(presets.summary.max_tokens, 160);
+        assert_eq!(presets.follow_up.top_p, Some(0.5));
+        assert_eq!(presets.follow_up.max_tokens, 200);
+        assert!(defaults
+            .with_env_overrides(|_| Some("bogus".to_string()))
+            .is_err());
+    }
+}
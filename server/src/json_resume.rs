@@ -0,0 +1,513 @@
+use crate::static_data::TerminalDataPayload;
+use serde::Serialize;
+use serde_json::Value;
+
+// Maps the terminal data onto the JSON Resume schema (https://jsonresume.org/schema).
+// Fields the schema has no slot for are dropped.
+pub fn json_resume(payload: &TerminalDataPayload) -> Value {
+    let resume = Resume {
+        basics: basics(&payload.profile),
+        work: items(&payload.experiences).iter().map(work).collect(),
+        education: items(&payload.education).iter().map(education).collect(),
+        skills: skills(&payload.skills),
+        projects: items(payload.projects.get("projects").unwrap_or(&Value::Null))
+            .iter()
+            .map(project)
+            .collect(),
+    };
+    serde_json::to_value(resume).unwrap_or(Value::Null)
+}
+
+#[derive(Serialize)]
+struct Resume {
+    basics: Basics,
+    work: Vec<Work>,
+    education: Vec<Education>,
+    skills: Vec<Skill>,
+    projects: Vec<Project>,
+}
+
+#[derive(Serialize)]
+struct Basics {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<Location>,
+    profiles: Vec<SocialProfile>,
+}
+
+#[derive(Serialize)]
+struct Location {
+    city: String,
+}
+
+#[derive(Serialize)]
+struct SocialProfile {
+    network: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    url: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Work {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    highlights: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Education {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    institution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    study_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Skill {
+    name: String,
+    keywords: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Project {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+// Known link keys and the network name the schema expects.
+const PROFILE_NETWORKS: [(&str, &str); 2] = [("github", "GitHub"), ("linkedin", "LinkedIn")];
+
+fn basics(profile: &Value) -> Basics {
+    let links = profile.get("links");
+    let profiles = PROFILE_NETWORKS
+        .iter()
+        .filter_map(|(key, network)| {
+            let url = links.and_then(|links| text(links, key))?;
+            Some(SocialProfile {
+                network,
+                username: username_from_url(&url),
+                url,
+            })
+        })
+        .collect();
+    Basics {
+        name: text(profile, "name"),
+        label: label(profile, "headline"),
+        email: text(profile, "email"),
+        url: links.and_then(|links| text(links, "website")),
+        summary: text(profile, "summary_en"),
+        location: text(profile, "location").and_then(|location| {
+            let city = location.split(',').next()?.trim();
+            (!city.is_empty()).then(|| Location {
+                city: city.to_string(),
+            })
+        }),
+        profiles,
+    }
+}
+
+fn work(entry: &Value) -> Work {
+    Work {
+        name: label(entry, "company"),
+        position: label(entry, "title"),
+        location: text(entry, "location"),
+        start_date: text(entry, "start").and_then(|start| iso_date(&start)),
+        end_date: text(entry, "end").and_then(|end| iso_date(&end)),
+        highlights: strings(entry.get("highlights")),
+    }
+}
+
+fn education(entry: &Value) -> Education {
+    let degree = label(entry, "degree");
+    let (study_type, area) = match degree.as_deref().and_then(|d| d.split_once(" in ")) {
+        Some((study_type, area)) => (Some(study_type.to_string()), Some(area.to_string())),
+        None => (degree, None),
+    };
+    let (start_date, end_date) = text(entry, "years")
+        .map(|years| date_range(&years))
+        .unwrap_or_default();
+    Education {
+        institution: text(entry, "school"),
+        study_type,
+        area,
+        start_date,
+        end_date,
+    }
+}
+
+fn skills(skills: &Value) -> Vec<Skill> {
+    let Some(categories) = skills.as_object() else {
+        return Vec::new();
+    };
+    categories
+        .iter()
+        .map(|(name, keywords)| Skill {
+            name: strip_decoration(name),
+            keywords: strings(Some(keywords)),
+        })
+        .collect()
+}
+
+fn project(entry: &Value) -> Project {
+    let (start_date, end_date) = text(entry, "date")
+        .map(|date| date_range(&date))
+        .unwrap_or_default();
+    Project {
+        name: label(entry, "title"),
+        description: text(entry, "description"),
+        start_date,
+        end_date,
+        keywords: strings(entry.get("tech")),
+        url: text(entry, "link"),
+    }
+}
+
+fn items(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    let text = value.get(key)?.as_str()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+// Titles in the data files lead with an emoji; the schema wants plain text.
+fn label(value: &Value, key: &str) -> Option<String> {
+    text(value, key)
+        .map(|text| strip_decoration(&text))
+        .filter(|text| !text.is_empty())
+}
+
+fn strip_decoration(text: &str) -> String {
+    text.trim_start_matches(|ch: char| !ch.is_alphanumeric())
+        .trim_end()
+        .to_string()
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn username_from_url(url: &str) -> Option<String> {
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (_, path) = path.split_once('/')?;
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+}
+
+// "2016–2019", "2023 - Present" or a lone "2010". An open end stays unset;
+// a single date covers both ends.
+fn date_range(text: &str) -> (Option<String>, Option<String>) {
+    if let Some(date) = iso_date(text) {
+        return (Some(date.clone()), Some(date));
+    }
+    let separators = [" - ", "–", "—", "-"];
+    let Some((start, end)) = separators
+        .iter()
+        .find_map(|separator| text.split_once(separator))
+    else {
+        return (None, None);
+    };
+    (iso_date(start), iso_date(end))
+}
+
+// Accepts YYYY, YYYY-MM and YYYY-MM-DD; anything else ("Present") is unparseable.
+fn iso_date(text: &str) -> Option<String> {
+    let text = text.trim();
+    let mut parts = text.split('-');
+    let year = parts.next()?;
+    if year.len() != 4 || !year.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let mut bounds = [1..=12, 1..=31].into_iter();
+    for part in parts {
+        let range = bounds.next()?;
+        let value: u32 = part.parse().ok().filter(|_| part.len() == 2)?;
+        if !range.contains(&value) {
+            return None;
+        }
+    }
+    Some(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::load_terminal_payload;
+    use serde_json::{json, Map};
+    use std::path::Path;
+
+    fn fixture_payload() -> TerminalDataPayload {
+        TerminalDataPayload {
+            profile: json!({
+                "name": "Ada Example",
+                "headline": "💡 Platform Engineer • Tooling",
+                "summary_en": "Builds delivery pipelines.",
+                "location": "Lyon, France (Remote)",
+                "email": "ada@example.com",
+                "links": {
+                    "github": "https://github.com/ada",
+                    "linkedin": "https://www.linkedin.com/in/ada-example/",
+                    "website": "https://ada.example.com/",
+                    "resume_url": "https://cv.example.com/"
+                },
+                "languages": ["French", "English"]
+            }),
+            skills: json!({
+                "💻 Languages": ["Rust", "Python"],
+                "☁️ Cloud": ["AWS"]
+            }),
+            experiences: json!([
+                {
+                    "title": "🚀 Lead Engineer",
+                    "company": "🎮 Example Games",
+                    "location": "Remote",
+                    "start": "2021-03",
+                    "end": "Present",
+                    "highlights": ["Shipped CI."]
+                }
+            ]),
+            education: json!([
+                {
+                    "degree": "🎓 Master’s Degree in Computer Science",
+                    "school": "Example University",
+                    "years": "2016–2018",
+                    "location": "Lyon, France"
+                },
+                {
+                    "degree": "⚛️ High School Diploma",
+                    "school": "Example High School",
+                    "years": "2013"
+                }
+            ]),
+            projects: json!({
+                "projects": [
+                    {
+                        "title": "🧠 Terminal",
+                        "date": "2023 - Present",
+                        "description": "A résumé terminal.",
+                        "tech": ["Rust", "WASM"],
+                        "link": "https://example.com"
+                    },
+                    {
+                        "title": "🕹️ Early Game",
+                        "date": "Summer 2010",
+                        "description": "First game.",
+                        "tech": []
+                    }
+                ],
+                "awards": [{"title": "Dropped"}]
+            }),
+            testimonials: json!([]),
+            faqs: json!([]),
+            generated_at: "2025-01-01T00:00:00Z".to_string(),
+            source_mtime: None,
+            file_mtimes: Default::default(),
+            counts: Default::default(),
+            prompt_extras: None,
+        }
+    }
+
+    #[test]
+    fn fixture_maps_onto_the_json_resume_schema() {
+        let expected = json!({
+            "basics": {
+                "name": "Ada Example",
+                "label": "Platform Engineer • Tooling",
+                "email": "ada@example.com",
+                "url": "https://ada.example.com/",
+                "summary": "Builds delivery pipelines.",
+                "location": {"city": "Lyon"},
+                "profiles": [
+                    {"network": "GitHub", "username": "ada", "url": "https://github.com/ada"},
+                    {
+                        "network": "LinkedIn",
+                        "username": "ada-example",
+                        "url": "https://www.linkedin.com/in/ada-example/"
+                    }
+                ]
+            },
+            "work": [
+                {
+                    "name": "Example Games",
+                    "position": "Lead Engineer",
+                    "location": "Remote",
+                    "startDate": "2021-03",
+                    "highlights": ["Shipped CI."]
+                }
+            ],
+            "education": [
+                {
+                    "institution": "Example University",
+                    "studyType": "Master’s Degree",
+                    "area": "Computer Science",
+                    "startDate": "2016",
+                    "endDate": "2018"
+                },
+                {
+                    "institution": "Example High School",
+                    "studyType": "High School Diploma",
+                    "startDate": "2013",
+                    "endDate": "2013"
+                }
+            ],
+            "skills": [
+                {"name": "Cloud", "keywords": ["AWS"]},
+                {"name": "Languages", "keywords": ["Rust", "Python"]}
+            ],
+            "projects": [
+                {
+                    "name": "Terminal",
+                    "description": "A résumé terminal.",
+                    "startDate": "2023",
+                    "keywords": ["Rust", "WASM"],
+                    "url": "https://example.com"
+                },
+                {
+                    "name": "Early Game",
+                    "description": "First game.",
+                    "keywords": []
+                }
+            ]
+        });
+        assert_eq!(json_resume(&fixture_payload()), expected);
+    }
+
+    #[test]
+    fn empty_payload_still_has_every_section() {
+        let mut payload = fixture_payload();
+        payload.profile = json!({});
+        payload.skills = json!({});
+        payload.experiences = json!([]);
+        payload.education = json!([]);
+        payload.projects = json!({});
+        assert_eq!(
+            json_resume(&payload),
+            json!({
+                "basics": {"profiles": []},
+                "work": [],
+                "education": [],
+                "skills": [],
+                "projects": []
+            })
+        );
+    }
+
+    #[test]
+    fn only_iso_shaped_dates_are_kept() {
+        assert_eq!(iso_date("2019").as_deref(), Some("2019"));
+        assert_eq!(iso_date(" 2019-04 ").as_deref(), Some("2019-04"));
+        assert_eq!(iso_date("2019-04-30").as_deref(), Some("2019-04-30"));
+        assert_eq!(iso_date("2019-13"), None);
+        assert_eq!(iso_date("2019-4"), None);
+        assert_eq!(iso_date("Present"), None);
+        assert_eq!(iso_date("19"), None);
+        assert_eq!(
+            date_range("2019-04 - 2020-01"),
+            (Some("2019-04".to_string()), Some("2020-01".to_string()))
+        );
+    }
+
+    fn assert_iso_dates(entry: &Map<String, Value>) {
+        for key in ["startDate", "endDate"] {
+            if let Some(date) = entry.get(key) {
+                let date = date.as_str().expect("dates are strings");
+                assert!(iso_date(date).is_some(), "{key} {date:?} is not ISO 8601");
+            }
+        }
+    }
+
+    #[test]
+    fn bundled_data_produces_a_well_formed_resume() {
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/data");
+        let resume = json_resume(&load_terminal_payload(&data_dir));
+        let root = resume.as_object().expect("resume is an object");
+        let mut keys: Vec<_> = root.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["basics", "education", "projects", "skills", "work"]);
+
+        let basics = &root["basics"];
+        for key in ["name", "label", "email", "url", "summary"] {
+            assert!(basics[key].is_string(), "basics.{key} should be a string");
+        }
+        assert!(basics["location"]["city"].is_string());
+        assert!(basics["profiles"].as_array().is_some_and(|p| !p.is_empty()));
+
+        let required = [
+            ("work", &["name", "position"][..]),
+            ("education", &["institution", "studyType"][..]),
+            ("skills", &["name", "keywords"][..]),
+            ("projects", &["name", "description", "keywords"][..]),
+        ];
+        for (section, fields) in required {
+            let entries = root[section].as_array().expect("sections are arrays");
+            assert!(!entries.is_empty(), "{section} should not be empty");
+            for entry in entries {
+                let entry = entry.as_object().expect("entries are objects");
+                for field in fields {
+                    assert!(entry.contains_key(*field), "{section} entry misses {field}");
+                }
+                if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                    assert!(
+                        name.starts_with(char::is_alphanumeric),
+                        "{section} name {name:?} keeps its decoration"
+                    );
+                }
+                assert_iso_dates(entry);
+            }
+        }
+        assert!(root["work"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|job| job["startDate"].is_string()));
+    }
+}
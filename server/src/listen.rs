@@ -0,0 +1,359 @@
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+
+use axum::Router;
+use socket2::{Domain, Protocol, Socket, Type};
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+#[cfg(unix)]
+pub use self::unix::{bind_unix, parse_socket_mode, serve_unix, DEFAULT_SOCKET_MODE};
+
+const LISTEN_BACKLOG: i32 = 1024;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ListenError {
+    #[error("HOST does not list any address")]
+    Empty,
+    #[error("HOST entry {0:?} is not an IP address or IP:port pair")]
+    InvalidAddress(String),
+}
+
+// `HOST` takes a comma-separated list such as `0.0.0.0,::`. Entries may carry
+// their own port (`127.0.0.1:8080`, `[::1]:8080`); bare ones use `PORT`.
+pub fn parse_addresses(raw: &str, port: u16) -> Result<Vec<SocketAddr>, ListenError> {
+    let addrs = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let bare = entry
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .unwrap_or(entry);
+            bare.parse::<IpAddr>()
+                .map(|ip| SocketAddr::new(ip, port))
+                .or_else(|_| entry.parse::<SocketAddr>())
+                .map_err(|_| ListenError::InvalidAddress(entry.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if addrs.is_empty() {
+        return Err(ListenError::Empty);
+    }
+    Ok(addrs)
+}
+
+// Linux binds `::` dual-stack by default, which collides with an explicit
+// IPv4 listener on the same port; IPv6 sockets go v6-only in that case.
+pub fn bind_tcp(addrs: &[SocketAddr]) -> io::Result<Vec<TcpListener>> {
+    addrs
+        .iter()
+        .map(|addr| {
+            let only_v6 = addr.is_ipv6()
+                && addrs
+                    .iter()
+                    .any(|other| other.is_ipv4() && other.port() == addr.port());
+            bind_one(*addr, only_v6)
+                .map_err(|err| io::Error::new(err.kind(), format!("Failed to bind {addr}: {err}")))
+        })
+        .collect()
+}
+
+fn bind_one(addr: SocketAddr, only_v6: bool) -> io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if only_v6 {
+        socket.set_only_v6(true)?;
+    }
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    socket.set_nonblocking(true)?;
+    TcpListener::from_std(socket.into())
+}
+
+pub async fn serve_tcp(
+    listeners: Vec<TcpListener>,
+    router: Router,
+    shutdown: watch::Receiver<bool>,
+) -> io::Result<()> {
+    let mut servers = JoinSet::new();
+    for listener in listeners {
+        let app = router
+            .clone()
+            .into_make_service_with_connect_info::<SocketAddr>();
+        let shutdown = shutdown.clone();
+        servers.spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(stopped(shutdown))
+                .await
+        });
+    }
+    while let Some(result) = servers.join_next().await {
+        result.map_err(io::Error::other)??;
+    }
+    Ok(())
+}
+
+async fn stopped(mut shutdown: watch::Receiver<bool>) {
+    // A dropped sender also means the server is going away.
+    let _ = shutdown.wait_for(|stop| *stop).await;
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::io;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::path::Path;
+
+    use axum::extract::ConnectInfo;
+    use axum::{Extension, Router};
+    use hyper_util::rt::TokioIo;
+    use hyper_util::server::graceful::GracefulShutdown;
+    use hyper_util::service::TowerToHyperService;
+    use tokio::net::UnixListener;
+    use tokio::sync::watch;
+    use tracing::warn;
+
+    pub const DEFAULT_SOCKET_MODE: u32 = 0o660;
+
+    pub fn parse_socket_mode(raw: &str) -> Option<u32> {
+        u32::from_str_radix(raw.trim().trim_start_matches("0o"), 8)
+            .ok()
+            .filter(|mode| *mode <= 0o777)
+    }
+
+    // A socket file left by a crashed run blocks `bind`. Only sockets nobody
+    // accepts on are removed; live sockets and regular files are reported.
+    pub fn remove_stale_socket(path: &Path) -> io::Result<()> {
+        let metadata = match std::fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if !metadata.file_type().is_socket() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("{} is still served by another process", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)
+    }
+
+    pub fn bind_unix(path: &Path, mode: u32) -> io::Result<UnixListener> {
+        remove_stale_socket(path)?;
+        let listener = UnixListener::bind(path)?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+        Ok(listener)
+    }
+
+    pub async fn serve_unix(
+        listener: Option<(UnixListener, &Path)>,
+        router: Router,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> io::Result<()> {
+        let Some((listener, path)) = listener else {
+            return Ok(());
+        };
+        // Peers on the socket are the local reverse proxy; presenting them as
+        // loopback lets `client_ip` trust its X-Forwarded-For header.
+        let app = router.layer(Extension(ConnectInfo(SocketAddr::from((
+            Ipv4Addr::LOCALHOST,
+            0,
+        )))));
+        let graceful = GracefulShutdown::new();
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        warn!(error = %err, "Failed to accept unix socket connection");
+                        continue;
+                    }
+                },
+                _ = shutdown.wait_for(|stop| *stop) => break,
+            };
+            let connection = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), TowerToHyperService::new(app.clone()));
+            let connection = graceful.watch(connection);
+            tokio::spawn(async move {
+                // Clients hanging up mid-request are not worth logging.
+                let _ = connection.await;
+            });
+        }
+        graceful.shutdown().await;
+        let _ = std::fs::remove_file(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    #[test]
+    fn host_lists_mix_bare_addresses_and_explicit_ports() {
+        assert_eq!(
+            parse_addresses("0.0.0.0, ::", 3000),
+            Ok(vec![
+                "0.0.0.0:3000".parse().expect("addr"),
+                "[::]:3000".parse().expect("addr"),
+            ])
+        );
+        assert_eq!(
+            parse_addresses("[::1],127.0.0.1:8080,[::1]:8081,", 3000),
+            Ok(vec![
+                "[::1]:3000".parse().expect("addr"),
+                "127.0.0.1:8080".parse().expect("addr"),
+                "[::1]:8081".parse().expect("addr"),
+            ])
+        );
+        assert_eq!(parse_addresses(" , ", 3000), Err(ListenError::Empty));
+        assert_eq!(
+            parse_addresses("0.0.0.0,localhost", 3000),
+            Err(ListenError::InvalidAddress("localhost".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn every_listed_address_serves_the_router() {
+        let addrs = parse_addresses("127.0.0.1,127.0.0.1", 0).expect("addresses");
+        let listeners = bind_tcp(&addrs).expect("bind listeners");
+        let bound: Vec<SocketAddr> = listeners
+            .iter()
+            .map(|listener| listener.local_addr().expect("local addr"))
+            .collect();
+        assert_ne!(bound[0].port(), bound[1].port());
+
+        let router = Router::new().route("/ping", get(|| async { "pong" }));
+        let (stop, shutdown) = watch::channel(false);
+        let server = tokio::spawn(serve_tcp(listeners, router, shutdown));
+        let http = reqwest::Client::new();
+        for addr in &bound {
+            let body = http
+                .get(format!("http://{addr}/ping"))
+                .send()
+                .await
+                .expect("request")
+                .text()
+                .await
+                .expect("body");
+            assert_eq!(body, "pong", "served on {addr}");
+        }
+        stop.send(true).expect("signal shutdown");
+        server
+            .await
+            .expect("server task")
+            .expect("servers stop cleanly");
+    }
+
+    #[cfg(unix)]
+    mod unix_socket {
+        use super::*;
+        use crate::listen::unix::remove_stale_socket;
+        use axum::extract::ConnectInfo;
+        use std::path::PathBuf;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        struct TempSocket(PathBuf);
+
+        impl TempSocket {
+            fn new() -> Self {
+                Self(std::env::temp_dir().join(format!("zqs-{}.sock", uuid::Uuid::new_v4())))
+            }
+        }
+
+        impl Drop for TempSocket {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
+
+        #[test]
+        fn only_stale_sockets_are_removed() {
+            let socket = TempSocket::new();
+            remove_stale_socket(&socket.0).expect("missing files are fine");
+
+            let live = std::os::unix::net::UnixListener::bind(&socket.0).expect("bind");
+            let err = remove_stale_socket(&socket.0).expect_err("socket in use");
+            assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+            drop(live);
+            assert!(socket.0.exists(), "Dropping a listener leaves the file");
+            remove_stale_socket(&socket.0).expect("stale socket removed");
+            assert!(!socket.0.exists());
+
+            std::fs::write(&socket.0, "not a socket").expect("write file");
+            let err = remove_stale_socket(&socket.0).expect_err("regular file kept");
+            assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+            assert!(socket.0.exists());
+        }
+
+        #[test]
+        fn socket_modes_are_octal() {
+            assert_eq!(parse_socket_mode("660"), Some(0o660));
+            assert_eq!(parse_socket_mode("0o600"), Some(0o600));
+            assert_eq!(parse_socket_mode("0777"), Some(0o777));
+            assert_eq!(parse_socket_mode("888"), None);
+            assert_eq!(parse_socket_mode("1777"), None);
+        }
+
+        #[tokio::test]
+        async fn unix_socket_peers_look_like_loopback() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let socket = TempSocket::new();
+            let stale = std::os::unix::net::UnixListener::bind(&socket.0).expect("bind");
+            drop(stale);
+            let listener = bind_unix(&socket.0, 0o600).expect("bind over stale socket");
+            let mode = std::fs::metadata(&socket.0)
+                .expect("socket metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            let router = Router::new().route(
+                "/ip",
+                get(|ConnectInfo(remote): ConnectInfo<SocketAddr>| async move {
+                    remote.ip().to_string()
+                }),
+            );
+            let (stop, shutdown) = watch::channel(false);
+            let path = socket.0.clone();
+            let server = tokio::spawn(async move {
+                serve_unix(Some((listener, path.as_path())), router, shutdown).await
+            });
+
+            let mut stream = tokio::net::UnixStream::connect(&socket.0)
+                .await
+                .expect("connect");
+            stream
+                .write_all(b"GET /ip HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .await
+                .expect("write request");
+            let mut response = String::new();
+            stream
+                .read_to_string(&mut response)
+                .await
+                .expect("read response");
+            assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            assert!(response.ends_with("127.0.0.1"), "{response}");
+
+            stop.send(true).expect("signal shutdown");
+            server
+                .await
+                .expect("server task")
+                .expect("socket server stops");
+            assert!(!socket.0.exists(), "The socket file is removed on shutdown");
+        }
+    }
+}
@@ -0,0 +1,405 @@
+use crate::handlers::{build_router, AppState};
+use anyhow::anyhow;
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Method, Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use tracing::warn;
+use zqs_model::ReasonCode;
+
+const DEFAULT_REQUESTS: usize = 200;
+const DEFAULT_CONCURRENCY: usize = 10;
+// Fake clients the requests rotate through, taken from the 198.18.0.0/15
+// benchmarking range.
+const SYNTHETIC_IPS: usize = 50;
+const SYNTHETIC_QUESTIONS: [&str; 6] = [
+    "What did Alexandre build at PlayStation?",
+    "Which programming languages does Alexandre use most?",
+    "Are you open to remote roles?",
+    "Tell me about the Micro Mages port.",
+    "What did Alexandre study?",
+    "Has Alexandre led a team before?",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadTestOptions {
+    pub requests: usize,
+    pub concurrency: usize,
+}
+
+impl Default for LoadTestOptions {
+    fn default() -> Self {
+        Self {
+            requests: DEFAULT_REQUESTS,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+impl LoadTestOptions {
+    pub fn parse(args: &[String]) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let target = match flag.as_str() {
+                "--requests" => &mut options.requests,
+                "--concurrency" => &mut options.concurrency,
+                other => {
+                    return Err(anyhow!(
+                        "Unknown load test option {other:?}; expected --requests or --concurrency"
+                    ))
+                }
+            };
+            let raw = args.next().ok_or_else(|| anyhow!("{flag} needs a value"))?;
+            *target = raw
+                .parse()
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| anyhow!("{flag} must be a positive integer, got {raw:?}"))?;
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub latency: Duration,
+    pub status: StatusCode,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LoadTestReport {
+    pub requests: usize,
+    pub answered: usize,
+    pub faq_hits: usize,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub rejections: BTreeMap<String, usize>,
+    pub budget_spent_eur: f64,
+    pub elapsed: Duration,
+}
+
+impl LoadTestReport {
+    pub fn from_samples(samples: &[Sample], budget_spent_eur: f64, elapsed: Duration) -> Self {
+        let mut latencies = samples
+            .iter()
+            .map(|sample| sample.latency)
+            .collect::<Vec<_>>();
+        latencies.sort_unstable();
+        let mut answered = 0;
+        let mut faq_hits = 0;
+        let mut rejections = BTreeMap::new();
+        for sample in samples {
+            if sample.status.is_success() {
+                answered += 1;
+                if sample.reason.as_deref() == Some(ReasonCode::FaqMatch.as_str()) {
+                    faq_hits += 1;
+                }
+            } else {
+                let reason = sample
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| format!("http_{}", sample.status.as_u16()));
+                *rejections.entry(reason).or_insert(0) += 1;
+            }
+        }
+        Self {
+            requests: samples.len(),
+            answered,
+            faq_hits,
+            p50: percentile(&latencies, 50),
+            p90: percentile(&latencies, 90),
+            p99: percentile(&latencies, 99),
+            max: latencies.last().copied().unwrap_or_default(),
+            rejections,
+            budget_spent_eur,
+            elapsed,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let rate = |count: usize| {
+            if self.requests == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / self.requests as f64
+            }
+        };
+        let throughput = if self.elapsed.is_zero() {
+            0.0
+        } else {
+            self.requests as f64 / self.elapsed.as_secs_f64()
+        };
+        let rejections = if self.rejections.is_empty() {
+            "none".to_string()
+        } else {
+            self.rejections
+                .iter()
+                .map(|(reason, count)| format!("{reason} {count}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            concat!(
+                "Load test: {requests} requests in {elapsed:.2}s ({throughput:.1} req/s)\n",
+                "Answered: {answered} ({answered_rate:.1}%), {faq} straight from the FAQ ({faq_rate:.1}%)\n",
+                "Latency: p50 {p50} · p90 {p90} · p99 {p99} · max {max}\n",
+                "Rejections: {rejections}\n",
+                "Budget consumed: €{budget:.4}\n"
+            ),
+            requests = self.requests,
+            elapsed = self.elapsed.as_secs_f64(),
+            throughput = throughput,
+            answered = self.answered,
+            answered_rate = rate(self.answered),
+            faq = self.faq_hits,
+            faq_rate = rate(self.faq_hits),
+            p50 = format_ms(self.p50),
+            p90 = format_ms(self.p90),
+            p99 = format_ms(self.p99),
+            max = format_ms(self.max),
+            rejections = rejections,
+            budget = self.budget_spent_eur,
+        )
+    }
+}
+
+// Nearest-rank percentile over sorted latencies.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn synthetic_ip(index: usize) -> Ipv4Addr {
+    let host = index % SYNTHETIC_IPS + 1;
+    Ipv4Addr::new(198, 18, (host / 256) as u8, (host % 256) as u8)
+}
+
+pub async fn run(state: Arc<AppState>, options: LoadTestOptions) -> LoadTestReport {
+    let spent_before = month_spend(&state).await;
+    let router = build_router(Arc::clone(&state));
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let workers = (0..options.concurrency.min(options.requests))
+        .map(|_| {
+            let router = router.clone();
+            let next = Arc::clone(&next);
+            tokio::spawn(async move {
+                let mut samples = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= options.requests {
+                        break samples;
+                    }
+                    samples.push(send_synthetic(router.clone(), index).await);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut samples = Vec::with_capacity(options.requests);
+    for worker in workers {
+        match worker.await {
+            Ok(batch) => samples.extend(batch),
+            Err(err) => warn!(target: "loadtest", error = %err, "Load test worker failed"),
+        }
+    }
+    let elapsed = started.elapsed();
+    let spent = (month_spend(&state).await - spent_before).max(0.0);
+    LoadTestReport::from_samples(&samples, spent, elapsed)
+}
+
+async fn month_spend(state: &AppState) -> f64 {
+    state.limiter.lock().await.usage_snapshot("").month_spend
+}
+
+async fn send_synthetic(router: Router, index: usize) -> Sample {
+    let question = SYNTHETIC_QUESTIONS[index % SYNTHETIC_QUESTIONS.len()];
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/ai")
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "question": question }).to_string()))
+        .unwrap_or_default();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((synthetic_ip(index), 40_000))));
+    let started = Instant::now();
+    let response = router
+        .oneshot(request)
+        .await
+        .unwrap_or_else(|never| match never {});
+    let status = response.status();
+    let reason = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .ok()
+        .and_then(|body| serde_json::from_slice::<Value>(&body).ok())
+        .and_then(|body| body.get("reason")?.as_str().map(str::to_string));
+    Sample {
+        latency: started.elapsed(),
+        status,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_client::AiClient;
+    use crate::test_support::test_app_state;
+
+    fn args(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn sample(ms: u64, status: StatusCode, reason: Option<&str>) -> Sample {
+        Sample {
+            latency: Duration::from_millis(ms),
+            status,
+            reason: reason.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn options_default_and_parse_flags() {
+        assert_eq!(
+            LoadTestOptions::parse(&[]).expect("defaults"),
+            LoadTestOptions {
+                requests: 200,
+                concurrency: 10
+            }
+        );
+        assert_eq!(
+            LoadTestOptions::parse(&args(&["--requests", "40", "--concurrency", "4"]))
+                .expect("flags"),
+            LoadTestOptions {
+                requests: 40,
+                concurrency: 4
+            }
+        );
+        for bad in [
+            &["--requests"][..],
+            &["--requests", "0"][..],
+            &["--concurrency", "many"][..],
+            &["--ips", "3"][..],
+        ] {
+            assert!(LoadTestOptions::parse(&args(bad)).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn report_aggregates_latencies_outcomes_and_rejections() {
+        let samples = [
+            sample(4, StatusCode::OK, None),
+            sample(1, StatusCode::OK, Some("faq_match")),
+            sample(9, StatusCode::OK, None),
+            sample(2, StatusCode::TOO_MANY_REQUESTS, Some("per_ip_burst")),
+            sample(3, StatusCode::TOO_MANY_REQUESTS, Some("per_ip_burst")),
+            sample(7, StatusCode::TOO_MANY_REQUESTS, Some("minute_budget")),
+            sample(5, StatusCode::BAD_GATEWAY, None),
+            sample(6, StatusCode::OK, None),
+            sample(8, StatusCode::OK, None),
+            sample(10, StatusCode::OK, None),
+        ];
+        let report = LoadTestReport::from_samples(&samples, 0.0125, Duration::from_secs(2));
+        assert_eq!(report.requests, 10);
+        assert_eq!(report.answered, 6);
+        assert_eq!(report.faq_hits, 1);
+        assert_eq!(report.p50, Duration::from_millis(5));
+        assert_eq!(report.p90, Duration::from_millis(9));
+        assert_eq!(report.p99, Duration::from_millis(10));
+        assert_eq!(report.max, Duration::from_millis(10));
+        assert_eq!(
+            report.rejections,
+            BTreeMap::from([
+                ("http_502".to_string(), 1),
+                ("minute_budget".to_string(), 1),
+                ("per_ip_burst".to_string(), 2),
+            ])
+        );
+        assert_eq!(
+            report.render(),
+            concat!(
+                "Load test: 10 requests in 2.00s (5.0 req/s)\n",
+                "Answered: 6 (60.0%), 1 straight from the FAQ (10.0%)\n",
+                "Latency: p50 5.0 ms · p90 9.0 ms · p99 10.0 ms · max 10.0 ms\n",
+                "Rejections: http_502 1, minute_budget 1, per_ip_burst 2\n",
+                "Budget consumed: €0.0125\n"
+            )
+        );
+    }
+
+    #[test]
+    fn empty_runs_report_zeroes() {
+        let report = LoadTestReport::from_samples(&[], 0.0, Duration::ZERO);
+        assert_eq!(report.p99, Duration::ZERO);
+        assert!(report.render().contains("Rejections: none"));
+    }
+
+    #[test]
+    fn synthetic_clients_rotate_through_the_benchmark_range() {
+        assert_eq!(synthetic_ip(0), Ipv4Addr::new(198, 18, 0, 1));
+        assert_eq!(synthetic_ip(49), Ipv4Addr::new(198, 18, 0, 50));
+        assert_eq!(synthetic_ip(SYNTHETIC_IPS), synthetic_ip(0));
+    }
+
+    #[tokio::test]
+    async fn small_runs_drive_the_router_and_spend_priced_budget() {
+        let client = AiClient::fixture_priced_like_openai().expect("fixture client");
+        let report = run(
+            test_app_state(client),
+            LoadTestOptions {
+                requests: 12,
+                concurrency: 3,
+            },
+        )
+        .await;
+        assert_eq!(report.requests, 12);
+        assert_eq!(report.answered, 12, "{:?}", report.rejections);
+        assert!(report.rejections.is_empty());
+        assert!(report.budget_spent_eur > 0.0);
+        assert!(report.max >= report.p50);
+    }
+
+    #[tokio::test]
+    async fn repeated_clients_hit_the_per_ip_limits() {
+        let client = AiClient::fixture_priced_like_openai().expect("fixture client");
+        let requests = SYNTHETIC_IPS * 10;
+        let report = run(
+            test_app_state(client),
+            LoadTestOptions {
+                requests,
+                concurrency: 8,
+            },
+        )
+        .await;
+        assert_eq!(report.requests, requests);
+        let rejected: usize = report.rejections.values().sum();
+        assert_eq!(report.answered + rejected, requests);
+        assert!(
+            report
+                .rejections
+                .keys()
+                .any(|reason| reason.starts_with("per_ip")),
+            "{:?}",
+            report.rejections
+        );
+    }
+}
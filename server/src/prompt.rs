@@ -0,0 +1,1030 @@
+use crate::answer_language::AnswerLanguage;
+use crate::rag::ContextChunk;
+use crate::static_data::TerminalDataPayload;
+use crate::summary::SummarySection;
+use crate::topic_classifier::Topic;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write;
+use tracing::{info, warn};
+
+pub const HISTORY_MAX_TOKENS: usize = 600;
+pub const USER_OVERHEAD_TOKENS: usize = 32;
+const PROMPT_EXTRAS_MAX_TOKENS: usize = 300;
+const PROMPT_EXTRAS_HEADING: &str = "Additional instructions:\n";
+
+#[derive(Debug, Clone)]
+pub struct KnowledgeBase {
+    pub system_prompt: String,
+    pub system_tokens: usize,
+}
+
+pub struct AiPrompt<'a> {
+    pub question: &'a str,
+    pub context: Option<&'a [ContextChunk]>,
+    pub language: AnswerLanguage,
+    pub history: &'a [ConversationTurn],
+}
+
+impl AiPrompt<'_> {
+    pub fn user_prompt(&self) -> String {
+        with_language_directive(
+            build_user_prompt(self.question, self.context),
+            self.language,
+        )
+    }
+}
+
+// Everything `handle_ai` works out before spending anything: context chunks,
+// topic, answer language and the estimates charged to the limiter. The admin
+// prompt export runs the same preparation so the two cannot drift.
+pub struct PreparedRequest {
+    pub chunks: Vec<ContextChunk>,
+    pub topic: Topic,
+    pub language: AnswerLanguage,
+    pub tokens: TokenBreakdown,
+    pub cost_estimate: f64,
+}
+
+impl PreparedRequest {
+    pub fn context(&self) -> Option<&[ContextChunk]> {
+        (!self.chunks.is_empty()).then_some(self.chunks.as_slice())
+    }
+
+    pub fn context_meta(&self) -> Option<Vec<ContextChunkMeta>> {
+        self.context()
+            .map(|chunks| chunks.iter().map(ContextChunkMeta::from_chunk).collect())
+    }
+
+    pub fn prompt<'a>(
+        &'a self,
+        question: &'a str,
+        history: &'a [ConversationTurn],
+    ) -> AiPrompt<'a> {
+        AiPrompt {
+            question,
+            context: self.context(),
+            language: self.language,
+            history,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TokenBreakdown {
+    pub system: usize,
+    pub question: usize,
+    pub context: usize,
+    pub overhead: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ContextChunkMeta {
+    pub id: String,
+    pub source: String,
+    pub topic: String,
+    pub score: f32,
+}
+
+impl ContextChunkMeta {
+    pub fn from_chunk(chunk: &ContextChunk) -> Self {
+        Self {
+            id: chunk.id.clone(),
+            source: chunk.source.clone(),
+            topic: chunk.topic.clone(),
+            score: chunk.score,
+        }
+    }
+}
+
+// One earlier question/answer pair the client wants the model to remember.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConversationTurn {
+    pub question: String,
+    pub answer: String,
+}
+
+// Empty sections are not worth a model call.
+pub fn summary_context(
+    payload: &TerminalDataPayload,
+    section: SummarySection,
+) -> Option<ContextChunk> {
+    let value = match section {
+        SummarySection::Experience => &payload.experiences,
+        SummarySection::Projects => &payload.projects,
+        SummarySection::Skills => &payload.skills,
+        SummarySection::Education => &payload.education,
+    };
+    let empty = match value {
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        other => other.is_null(),
+    };
+    if empty {
+        return None;
+    }
+    match section {
+        SummarySection::Experience => build_experience_chunk(payload),
+        SummarySection::Projects => build_projects_chunk(payload),
+        SummarySection::Skills => build_skills_chunk(payload),
+        SummarySection::Education => build_education_chunk(payload),
+    }
+}
+
+impl KnowledgeBase {
+    pub fn from_payload(
+        payload: &TerminalDataPayload,
+        max_tokens: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        let profile_name = payload
+            .profile
+            .get("name")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Alexandre DO-O ALMEIDA");
+        let headline = payload
+            .profile
+            .get("headline")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Senior DevOps & product engineer");
+        let location = payload
+            .profile
+            .get("location")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Remote");
+        let summary = payload
+            .profile
+            .get("summary_en")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Use the supplied résumé context to answer questions about Alexandre.");
+        let render = |summary: &str| {
+            format!(
+                concat!(
+                    "You are the AI concierge for {name} ({headline}) based in {location}. ",
+                    "Answer using only the provided context chunks (tagged as [chunk-n]) that accompany each user question. ",
+                    "Cite the chunk ids you reference, keep responses structured, and never invent employers, dates, metrics, or locations that are not in context. ",
+                    "If context is missing, clearly say so and outline what can be shared from the résumé at a high level.\n",
+                    "Profile summary: {summary}\n"
+                ),
+                name = profile_name,
+                headline = headline,
+                location = location,
+                summary = summary
+            )
+        };
+        let mut system_prompt = render(summary);
+        if let Some(max_tokens) = max_tokens {
+            if estimate_tokens(&system_prompt) > max_tokens {
+                // Only the summary is trimmed; name, headline, location and the
+                // grounding instructions always stay in the prompt.
+                let fixed_tokens = estimate_tokens(&render(""));
+                let summary_budget = max_tokens.saturating_sub(fixed_tokens);
+                let trimmed = truncate_to_token_budget(summary, summary_budget);
+                system_prompt = render(&trimmed);
+                info!(
+                    target: "ai",
+                    max_tokens,
+                    summary_chars = summary.chars().count(),
+                    trimmed_chars = trimmed.chars().count(),
+                    "Profile summary trimmed to fit SYSTEM_PROMPT_MAX_TOKENS"
+                );
+            }
+        }
+        if let Some(extras) = payload.prompt_extras.as_deref() {
+            system_prompt.push_str(&render_prompt_extras(
+                extras,
+                estimate_tokens(&system_prompt),
+                max_tokens,
+            ));
+        }
+        let system_tokens = estimate_tokens(&system_prompt);
+
+        Ok(Self {
+            system_prompt,
+            system_tokens,
+        })
+    }
+}
+
+// Extras get their own budget and never push the prompt past
+// SYSTEM_PROMPT_MAX_TOKENS; whatever does not fit is cut with a warning.
+fn render_prompt_extras(extras: &str, prompt_tokens: usize, max_tokens: Option<usize>) -> String {
+    // One extra token covers the trailing newline.
+    let heading_tokens = estimate_tokens(PROMPT_EXTRAS_HEADING) + 1;
+    let budget = max_tokens
+        .map(|max| max.saturating_sub(prompt_tokens + heading_tokens))
+        .unwrap_or(usize::MAX)
+        .min(PROMPT_EXTRAS_MAX_TOKENS);
+    let extras_tokens = estimate_tokens(extras);
+    let kept = truncate_to_token_budget(extras, budget);
+    if extras_tokens > budget {
+        warn!(
+            target: "ai",
+            extras_tokens,
+            budget,
+            kept_chars = kept.chars().count(),
+            "prompt_extras.md exceeds its token budget; truncating"
+        );
+    } else {
+        info!(
+            target: "ai",
+            extras_chars = extras.chars().count(),
+            extras_tokens,
+            "Prompt extras appended to the system prompt"
+        );
+    }
+    if kept.is_empty() {
+        return String::new();
+    }
+    format!("{PROMPT_EXTRAS_HEADING}{kept}\n")
+}
+
+fn truncate_to_token_budget(text: &str, max_tokens: usize) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+    // Leave room for the ellipsis so the result still fits the budget.
+    let max_chars = (max_tokens * 4).saturating_sub(1);
+    if max_chars == 0 {
+        return String::new();
+    }
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map(|(idx, _)| idx)
+        .unwrap_or(text.len());
+    let head = &text[..cut];
+    // Prefer ending on a full sentence, then on a word boundary.
+    let sentence_end = head
+        .rfind(". ")
+        .map(|idx| idx + 1)
+        .filter(|idx| *idx >= head.len() / 2);
+    match sentence_end {
+        Some(end) => head[..end].to_string(),
+        None => {
+            let word_end = head.rfind(char::is_whitespace).unwrap_or(head.len());
+            format!(
+                "{}…",
+                head[..word_end].trim_end_matches([',', ';', ':', ' '])
+            )
+        }
+    }
+}
+
+// Keeps the most recent complete turns that fit in `max_tokens`; blank turns
+// are dropped so the user/model alternation is never broken.
+pub fn trim_history(history: &[ConversationTurn], max_tokens: usize) -> Vec<&ConversationTurn> {
+    let mut budget = max_tokens;
+    let mut kept: Vec<&ConversationTurn> = history
+        .iter()
+        .rev()
+        .filter(|turn| !turn.question.trim().is_empty() && !turn.answer.trim().is_empty())
+        .take_while(|turn| {
+            let cost = estimate_tokens(&turn.question) + estimate_tokens(&turn.answer);
+            match budget.checked_sub(cost) {
+                Some(remaining) => {
+                    budget = remaining;
+                    true
+                }
+                None => false,
+            }
+        })
+        .collect();
+    kept.reverse();
+    kept
+}
+
+pub fn continuation_prompt(user_prompt: &str, partial: &str) -> String {
+    format!(
+        "{user_prompt}\n\nYour previous answer was cut off at the length limit:\n{partial}\n\nContinue exactly where it stopped, without repeating it.\ncontinue"
+    )
+}
+
+pub fn join_continuation(partial: &str, continuation: &str) -> String {
+    let continuation = continuation.trim_start();
+    let glued = partial.ends_with(char::is_whitespace)
+        || continuation.starts_with(['.', ',', ';', ':', '!', '?', ')']);
+    if glued {
+        format!("{partial}{continuation}")
+    } else {
+        format!("{partial} {continuation}")
+    }
+}
+
+pub fn estimate_tokens(text: &str) -> usize {
+    let chars = text.chars().count() as f64;
+    (chars / 4.0).ceil() as usize
+}
+
+fn with_language_directive(mut prompt: String, language: AnswerLanguage) -> String {
+    prompt.push_str("\n\n");
+    prompt.push_str(language.directive());
+    prompt
+}
+
+fn build_user_prompt(question: &str, context: Option<&[ContextChunk]>) -> String {
+    if let Some(chunks) = context {
+        let mut buffer = String::new();
+        buffer.push_str(
+            "Use the referenced context snippets to answer the question. When citing a snippet, mention it naturally like \"(source: Core skills section)\" and never reference file names.\n",
+        );
+        buffer.push_str(
+            "If the context includes a project tech stack or named technologies, repeat the exact technology names from context in your answer. Do not collapse or generalize them into broader categories.\n",
+        );
+        for chunk in chunks {
+            let label = format!("{} section", chunk.topic);
+            let _ = writeln!(buffer, "[context] {label}\n{}\n", chunk.body.trim());
+        }
+        let explicit_technologies = extract_explicit_technologies(question, chunks);
+        if !explicit_technologies.is_empty() {
+            let _ = writeln!(
+                buffer,
+                "Explicit technologies found in context: {}.",
+                explicit_technologies.join(", ")
+            );
+        }
+        buffer.push_str("Question:\n");
+        buffer.push_str(question);
+        buffer
+    } else {
+        question.to_string()
+    }
+}
+
+fn extract_explicit_technologies(question: &str, chunks: &[ContextChunk]) -> Vec<String> {
+    let keywords = question_keywords(question);
+    let mut collected = Vec::new();
+
+    for chunk in chunks {
+        let Some(value) = parse_chunk_json_body(&chunk.body) else {
+            continue;
+        };
+        let mut matched = Vec::new();
+        collect_matching_tech_terms(&value, &keywords, &mut matched, true);
+        if matched.is_empty() {
+            collect_matching_tech_terms(&value, &keywords, &mut matched, false);
+        }
+        for tech in matched {
+            if !collected.iter().any(|existing| existing == &tech) {
+                collected.push(tech);
+            }
+        }
+    }
+
+    collected
+}
+
+fn parse_chunk_json_body(body: &str) -> Option<Value> {
+    let trimmed = body.trim();
+    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+        return Some(value);
+    }
+
+    let json_start = trimmed
+        .char_indices()
+        .find_map(|(idx, ch)| (ch == '{' || ch == '[').then_some(idx))?;
+    serde_json::from_str::<Value>(&trimmed[json_start..]).ok()
+}
+
+fn question_keywords(question: &str) -> Vec<String> {
+    const STOP_WORDS: &[&str] = &[
+        "the", "a", "an", "and", "or", "to", "for", "of", "in", "on", "with", "which", "what",
+        "who", "how", "is", "are", "do", "does", "power", "powers", "project", "technologies",
+    ];
+
+    question
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter_map(|token| {
+            let lowered = token.trim().to_ascii_lowercase();
+            if lowered.len() < 3 || STOP_WORDS.contains(&lowered.as_str()) {
+                None
+            } else {
+                Some(lowered)
+            }
+        })
+        .collect()
+}
+
+fn collect_matching_tech_terms(
+    value: &Value,
+    keywords: &[String],
+    out: &mut Vec<String>,
+    require_keyword_match: bool,
+) {
+    match value {
+        Value::Object(map) => {
+            let title = map
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let description = map
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let keyword_match = keywords.is_empty()
+                || keywords
+                    .iter()
+                    .any(|keyword| title.contains(keyword) || description.contains(keyword));
+
+            if let Some(tech) = map.get("tech").and_then(Value::as_array) {
+                if keyword_match || !require_keyword_match {
+                    for item in tech.iter().filter_map(Value::as_str) {
+                        let trimmed = item.trim();
+                        if !trimmed.is_empty() && !out.iter().any(|existing| existing == trimmed) {
+                            out.push(trimmed.to_string());
+                        }
+                    }
+                }
+            }
+
+            for child in map.values() {
+                collect_matching_tech_terms(child, keywords, out, require_keyword_match);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_matching_tech_terms(item, keywords, out, require_keyword_match);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn fallback_context_chunks(payload: &TerminalDataPayload) -> Vec<ContextChunk> {
+    let mut chunks = Vec::new();
+    if let Some(profile_chunk) = build_profile_chunk(payload) {
+        chunks.push(profile_chunk);
+    }
+    if let Some(experience_chunk) = build_experience_chunk(payload) {
+        chunks.push(experience_chunk);
+    }
+    if let Some(project_chunk) = build_projects_chunk(payload) {
+        chunks.push(project_chunk);
+    }
+    if let Some(skills_chunk) = build_skills_chunk(payload) {
+        chunks.push(skills_chunk);
+    }
+    if let Some(education_chunk) = build_education_chunk(payload) {
+        chunks.push(education_chunk);
+    }
+    if let Some(testimonials_chunk) = build_testimonials_chunk(payload) {
+        chunks.push(testimonials_chunk);
+    }
+    if let Some(faq_chunk) = build_faq_chunk(payload) {
+        chunks.push(faq_chunk);
+    }
+    if chunks.is_empty() {
+        if let Ok(snapshot) = serde_json::to_string(&payload.knowledge_json()) {
+            chunks.push(ContextChunk {
+                id: "static-terminal-snapshot".to_string(),
+                source: "static/data".to_string(),
+                topic: "Résumé snapshot".to_string(),
+                body: snapshot,
+                score: 0.0,
+            });
+        }
+    }
+    chunks
+}
+
+fn build_profile_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.profile,
+        "static-profile",
+        "profile.json",
+        "Profile data",
+    )
+}
+
+fn build_experience_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.experiences,
+        "static-experience",
+        "experience.json",
+        "Experience data",
+    )
+}
+
+fn build_projects_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.projects,
+        "static-projects",
+        "projects.json",
+        "Projects data",
+    )
+}
+
+fn build_skills_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.skills,
+        "static-skills",
+        "skills.json",
+        "Skills data",
+    )
+}
+
+fn build_education_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.education,
+        "static-education",
+        "education.json",
+        "Education data",
+    )
+}
+
+fn build_testimonials_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(
+        &payload.testimonials,
+        "static-testimonials",
+        "testimonials.json",
+        "Testimonials data",
+    )
+}
+
+fn build_faq_chunk(payload: &TerminalDataPayload) -> Option<ContextChunk> {
+    chunk_from_value(&payload.faqs, "static-faq", "faq.json", "FAQ data")
+}
+
+fn chunk_from_value(value: &Value, id: &str, source: &str, topic: &str) -> Option<ContextChunk> {
+    if value.is_null() {
+        return None;
+    }
+    let body = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    if body.trim().is_empty() {
+        return None;
+    }
+    Some(ContextChunk {
+        id: id.to_string(),
+        source: source.to_string(),
+        topic: topic.to_string(),
+        body,
+        score: 0.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        empty_terminal_data, load_terminal_payload, serialized_contents, turn,
+    };
+    use serde_json::json;
+    use std::path::Path;
+
+    fn load_embedded_knowledge() -> serde_json::Value {
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/data");
+        load_terminal_payload(&data_dir).knowledge_json()
+    }
+
+    fn payload_with_summary(summary: &str) -> TerminalDataPayload {
+        let mut payload = std::sync::Arc::unwrap_or_clone(empty_terminal_data());
+        payload.profile = json!({
+            "name": "Alexandre DO-O ALMEIDA",
+            "headline": "DevOps engineer",
+            "location": "Paris",
+            "summary_en": summary,
+        });
+        payload
+    }
+
+    #[test]
+    fn system_prompt_summary_is_trimmed_to_budget() {
+        let sentence = "Built CI/CD and autotest infrastructure for console emulators. ";
+        let long_summary = sentence.repeat(60);
+        let payload = payload_with_summary(&long_summary);
+
+        let untrimmed = KnowledgeBase::from_payload(&payload, None).expect("knowledge base");
+        let budget = 200;
+        assert!(untrimmed.system_tokens > budget);
+
+        let trimmed = KnowledgeBase::from_payload(&payload, Some(budget)).expect("knowledge base");
+        assert!(
+            trimmed.system_tokens <= budget,
+            "system_tokens {} should fit the {budget} budget",
+            trimmed.system_tokens
+        );
+        assert_eq!(
+            trimmed.system_tokens,
+            estimate_tokens(&trimmed.system_prompt),
+            "system_tokens must be recomputed from the trimmed prompt"
+        );
+        assert!(trimmed.system_tokens < untrimmed.system_tokens);
+        for field in [
+            "Alexandre DO-O ALMEIDA",
+            "DevOps engineer",
+            "Paris",
+            "[chunk-n]",
+        ] {
+            assert!(
+                trimmed.system_prompt.contains(field),
+                "{field} must survive trimming"
+            );
+        }
+        assert!(
+            trimmed
+                .system_prompt
+                .contains("Profile summary: Built CI/CD and autotest infrastructure"),
+            "The start of the summary is kept: {}",
+            trimmed.system_prompt
+        );
+        assert!(
+            trimmed.system_prompt.ends_with("emulators.\n"),
+            "Trimming should stop on a sentence boundary: {}",
+            trimmed.system_prompt
+        );
+    }
+
+    #[test]
+    fn system_prompt_within_budget_is_unchanged() {
+        let payload = payload_with_summary("Short summary.");
+        let unbounded = KnowledgeBase::from_payload(&payload, None).expect("knowledge base");
+        let bounded = KnowledgeBase::from_payload(&payload, Some(10_000)).expect("knowledge base");
+        assert_eq!(bounded.system_prompt, unbounded.system_prompt);
+        assert_eq!(bounded.system_tokens, unbounded.system_tokens);
+
+        let starved = KnowledgeBase::from_payload(&payload, Some(1)).expect("knowledge base");
+        assert!(starved.system_prompt.contains("Profile summary: \n"));
+        assert!(starved.system_prompt.contains("Alexandre DO-O ALMEIDA"));
+    }
+
+    #[test]
+    fn prompt_extras_are_appended_within_their_budget() {
+        let mut payload = payload_with_summary("Short summary.");
+        let base = KnowledgeBase::from_payload(&payload, None).expect("knowledge base");
+        assert!(!base.system_prompt.contains(PROMPT_EXTRAS_HEADING));
+
+        payload.prompt_extras = Some("Be concise. Mention the booking link when relevant.".into());
+        let extended = KnowledgeBase::from_payload(&payload, None).expect("knowledge base");
+        assert!(extended.system_prompt.starts_with(&base.system_prompt));
+        assert!(extended.system_prompt.ends_with(
+            "Additional instructions:\nBe concise. Mention the booking link when relevant.\n"
+        ));
+        assert_eq!(
+            extended.system_tokens,
+            estimate_tokens(&extended.system_prompt)
+        );
+
+        payload.prompt_extras = Some("Always answer in bullet points. ".repeat(100));
+        let oversized = KnowledgeBase::from_payload(&payload, None).expect("knowledge base");
+        let appended = &oversized.system_prompt[base.system_prompt.len()..];
+        assert!(
+            estimate_tokens(appended) <= PROMPT_EXTRAS_MAX_TOKENS + 10,
+            "Oversized extras are truncated: {} tokens",
+            estimate_tokens(appended)
+        );
+        assert!(appended.starts_with("Additional instructions:\nAlways answer in bullet points."));
+    }
+
+    #[test]
+    fn prompt_extras_respect_the_system_prompt_budget() {
+        let mut payload = payload_with_summary("Short summary.");
+        payload.prompt_extras = Some("Mention the booking link when relevant. ".repeat(40));
+        let base_tokens =
+            KnowledgeBase::from_payload(&payload_with_summary("Short summary."), None)
+                .expect("knowledge base")
+                .system_tokens;
+
+        let budget = base_tokens + 40;
+        let bounded = KnowledgeBase::from_payload(&payload, Some(budget)).expect("knowledge base");
+        assert!(
+            bounded.system_tokens <= budget,
+            "{} tokens should fit {budget}",
+            bounded.system_tokens
+        );
+        assert!(bounded.system_prompt.contains("Mention the booking link"));
+
+        let starved =
+            KnowledgeBase::from_payload(&payload, Some(base_tokens)).expect("knowledge base");
+        assert!(
+            !starved.system_prompt.contains(PROMPT_EXTRAS_HEADING),
+            "No room left means no extras"
+        );
+    }
+
+    #[test]
+    fn truncation_falls_back_to_word_boundaries() {
+        assert_eq!(
+            truncate_to_token_budget("alpha beta gamma delta epsilon", 4),
+            "alpha beta…"
+        );
+        assert_eq!(truncate_to_token_budget("short", 4), "short");
+        assert_eq!(truncate_to_token_budget("anything", 0), "");
+    }
+
+    #[test]
+    fn profile_links_target_primary_domains() {
+        let data = load_embedded_knowledge();
+        let links = data
+            .get("profile")
+            .and_then(|profile| profile.get("links"))
+            .expect("profile.links should be present");
+
+        let resume = links
+            .get("resume_url")
+            .and_then(|value| value.as_str())
+            .expect("profile.links.resume_url should be populated");
+        assert!(
+            resume.starts_with("https://founding.zqsdev.com"),
+            "Résumé link should point to the default founding résumé domain: {resume}"
+        );
+
+        let website = links
+            .get("website")
+            .and_then(|value| value.as_str())
+            .expect("profile.links.website should be populated");
+        assert!(
+            website.starts_with("https://www.zqsdev.com")
+                || website.starts_with("https://zqsdev.com"),
+            "Website link should target the primary domain: {website}"
+        );
+    }
+
+    #[test]
+    fn token_estimate_is_positive() {
+        let sample = "Hello world";
+        assert!(estimate_tokens(sample) > 0);
+    }
+
+    #[test]
+    fn continuations_are_joined_without_doubling_spaces() {
+        assert_eq!(
+            join_continuation("He works on", "Rust."),
+            "He works on Rust."
+        );
+        assert_eq!(
+            join_continuation("He works on ", "Rust."),
+            "He works on Rust."
+        );
+        assert_eq!(
+            join_continuation("He works on Rust", ". Mostly"),
+            "He works on Rust. Mostly"
+        );
+    }
+
+    #[test]
+    fn history_is_trimmed_to_the_most_recent_turns_within_budget() {
+        let long_answer = "word ".repeat(HISTORY_MAX_TOKENS * 2);
+        let history = [
+            turn("Oldest", &long_answer),
+            turn("Blank answer", "   "),
+            turn("Q2", "A2"),
+            turn("Q3", "A3"),
+        ];
+        let kept = trim_history(&history, HISTORY_MAX_TOKENS);
+        let questions: Vec<&str> = kept.iter().map(|turn| turn.question.as_str()).collect();
+        assert_eq!(questions, ["Q2", "Q3"]);
+        assert!(trim_history(&history, 0).is_empty());
+
+        let contents = serialized_contents(&history);
+        assert_eq!(contents.as_array().expect("contents array").len(), 5);
+        assert!(!contents.to_string().contains("Oldest"));
+    }
+
+    #[test]
+    fn user_prompt_includes_context_chunks() {
+        let chunks = vec![
+            ContextChunk {
+                id: "chunk-1".to_string(),
+                source: "profile.json".to_string(),
+                topic: "Profile".to_string(),
+                body: "Name: Alexandre".to_string(),
+                score: 0.92,
+            },
+            ContextChunk {
+                id: "chunk-2".to_string(),
+                source: "experience.json".to_string(),
+                topic: "PlayStation".to_string(),
+                body: "Highlights about CI/CD".to_string(),
+                score: 0.88,
+            },
+        ];
+        let prompt = build_user_prompt("What is Alexandre working on?", Some(&chunks));
+        assert!(
+            prompt.contains("[context] Profile section"),
+            "prompt should list chunk sources using readable section labels: {prompt}"
+        );
+        assert!(
+            prompt.contains("Highlights about CI/CD"),
+            "prompt should inline chunk bodies: {prompt}"
+        );
+        assert!(
+            prompt.contains("repeat the exact technology names from context"),
+            "prompt should explicitly preserve named technologies: {prompt}"
+        );
+        assert!(
+            prompt.ends_with("What is Alexandre working on?"),
+            "prompt should include the user question at the end: {prompt}"
+        );
+    }
+
+    #[test]
+    fn user_prompt_surfaces_matching_project_technologies() {
+        let chunks = vec![ContextChunk {
+            id: "projects-projects:1".to_string(),
+            source: "projects.json".to_string(),
+            topic: "projects: ZQSDev Terminal – AI Résumé Concierge".to_string(),
+            body: concat!(
+                "Source: projects\n",
+                "Topic: projects\n",
+                "Label: ZQSDev Terminal – AI Résumé Concierge\n\n",
+                "{\n",
+                "  \"title\": \"ZQSDev Terminal – AI Résumé Concierge\",\n",
+                "  \"description\": \"Built this interactive portfolio terminal.\",\n",
+                "  \"tech\": [\"Rust\", \"RAG\", \"LLM APIs\", \"WebAssembly\", \"Netlify\"]\n",
+                "}"
+            )
+            .to_string(),
+            score: 0.91,
+        }];
+
+        let prompt = build_user_prompt(
+            "Which technologies power the ZQSDev Terminal project?",
+            Some(&chunks),
+        );
+
+        assert!(
+            prompt.contains("Explicit technologies found in context: Rust, RAG, LLM APIs, WebAssembly, Netlify."),
+            "prompt should surface the matching project tech stack verbatim: {prompt}"
+        );
+    }
+
+    #[test]
+    fn parse_chunk_json_body_skips_rag_headers() {
+        let value = parse_chunk_json_body(
+            "Source: projects\nTopic: projects\nLabel: Demo\n\n{\"tech\":[\"Rust\",\"WebAssembly\"]}",
+        )
+        .expect("json payload should be parsed after headers");
+
+        let tech = value["tech"]
+            .as_array()
+            .expect("tech array should be present")
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tech, vec!["Rust", "WebAssembly"]);
+    }
+
+    #[test]
+    fn faq_knowledge_reflects_latest_details() {
+        let data = load_embedded_knowledge();
+        let faqs = data
+            .get("faq")
+            .and_then(|value| value.as_array())
+            .expect("faq data should be an array");
+
+        let remote = faqs
+            .iter()
+            .find(|entry| {
+                entry.get("question").and_then(|value| value.as_str())
+                    == Some("🌍 Are you open to remote roles?")
+            })
+            .and_then(|entry| entry.get("answer"))
+            .and_then(|value| value.as_str())
+            .expect("remote roles FAQ should be present");
+        assert!(
+            remote.contains("remote-first"),
+            "Remote FAQ answer should mention remote-first culture: {remote}"
+        );
+        assert!(
+            remote.contains("Montpellier"),
+            "Remote FAQ answer should include current location: {remote}"
+        );
+        assert!(
+            remote.contains("2026"),
+            "Remote FAQ answer should reference relocation timeline: {remote}"
+        );
+
+        let industries = faqs
+            .iter()
+            .find(|entry| {
+                entry.get("question").and_then(|value| value.as_str())
+                    == Some("🏢 What industries do you focus on?")
+            })
+            .and_then(|entry| entry.get("answer"))
+            .and_then(|value| value.as_str())
+            .expect("industry focus FAQ should be present");
+        assert!(
+            industries.contains("Gaming"),
+            "Industry answer should include gaming focus: {industries}"
+        );
+        assert!(
+            industries.contains("biotech"),
+            "Industry answer should include biotech focus: {industries}"
+        );
+        assert!(
+            industries.contains("automation"),
+            "Industry answer should mention automation projects: {industries}"
+        );
+
+        let leadership = faqs
+            .iter()
+            .find(|entry| {
+                entry.get("question").and_then(|value| value.as_str())
+                    == Some("👥 Can you lead cross-functional teams?")
+            })
+            .and_then(|entry| entry.get("answer"))
+            .and_then(|value| value.as_str())
+            .expect("leadership FAQ should be present");
+        assert!(
+            leadership.contains("PlayStation") && leadership.contains("Atos"),
+            "Leadership answer should reference enterprise teams: {leadership}"
+        );
+        assert!(
+            leadership.contains("Jam.gg"),
+            "Leadership answer should mention Jam.gg founding role: {leadership}"
+        );
+        assert!(
+            leadership.contains("artists")
+                && leadership.contains("QA")
+                && leadership.contains("marketing"),
+            "Leadership answer should cover cross-discipline personal projects: {leadership}"
+        );
+
+        let ai_usage = faqs
+            .iter()
+            .find(|entry| {
+                entry.get("question").and_then(|value| value.as_str())
+                    == Some("🤖 How do you use AI in your workflow?")
+            })
+            .and_then(|entry| entry.get("answer"))
+            .and_then(|value| value.as_str())
+            .expect("AI workflow FAQ should be present");
+        assert!(
+            ai_usage.contains("Codex CLI") && ai_usage.contains("Gemini CLI"),
+            "AI usage answer should cite key copilots: {ai_usage}"
+        );
+        assert!(
+            ai_usage.contains("multiple projects") && ai_usage.contains("parallel"),
+            "AI usage answer should highlight concurrent workflows: {ai_usage}"
+        );
+
+        let availability = faqs
+            .iter()
+            .find(|entry| {
+                entry.get("question").and_then(|value| value.as_str())
+                    == Some("⏱️ How soon can you start?")
+            })
+            .and_then(|entry| entry.get("answer"))
+            .and_then(|value| value.as_str())
+            .expect("availability FAQ should be present");
+        assert!(
+            availability.contains("start this month"),
+            "Availability answer should confirm immediate start: {availability}"
+        );
+    }
+
+    #[test]
+    fn missing_prompt_extras_file_is_fine() {
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/data");
+        assert_eq!(load_terminal_payload(&data_dir).prompt_extras, None);
+    }
+
+    #[test]
+    fn fallback_context_includes_profile_and_experience() {
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/data");
+        let payload = load_terminal_payload(&data_dir);
+        let chunks = fallback_context_chunks(&payload);
+        assert!(
+            !chunks.is_empty(),
+            "fallback context should include at least one chunk"
+        );
+        assert!(
+            chunks.iter().any(|chunk| chunk.source == "profile.json"),
+            "profile chunk missing from fallback context: {chunks:?}"
+        );
+        assert!(
+            chunks.iter().any(|chunk| chunk.source == "experience.json"),
+            "experience chunk missing from fallback context: {chunks:?}"
+        );
+        assert!(
+            chunks.iter().any(|chunk| chunk.source == "skills.json"),
+            "skills chunk missing from fallback context: {chunks:?}"
+        );
+        assert!(
+            chunks.iter().any(|chunk| chunk.source == "faq.json"),
+            "faq chunk missing from fallback context: {chunks:?}"
+        );
+        assert!(
+            chunks.iter().any(|chunk| chunk.source == "education.json"),
+            "education chunk missing from fallback context: {chunks:?}"
+        );
+        assert!(
+            chunks
+                .iter()
+                .any(|chunk| chunk.source == "testimonials.json"),
+            "testimonials chunk missing from fallback context: {chunks:?}"
+        );
+        let projects_chunk = chunks
+            .iter()
+            .find(|chunk| chunk.source == "projects.json")
+            .expect("projects chunk missing from fallback context");
+        assert!(
+            projects_chunk.body.contains("WebAssembly"),
+            "projects chunk should include full tech stack details: {}",
+            projects_chunk.body
+        );
+    }
+}
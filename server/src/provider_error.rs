@@ -0,0 +1,227 @@
+use serde::Serialize;
+use serde_json::Value;
+
+// Normalized cause behind a provider's error body. OpenAI and Groq answer with
+// `{"error": {"code", "type", "message"}}`, Gemini with
+// `{"error": {"code": 400, "status", "message", "details": [{"reason"}]}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderReason {
+    RateLimited,
+    QuotaExhausted,
+    InvalidApiKey,
+    PermissionDenied,
+    ModelNotFound,
+    ContextTooLong,
+    ContentFiltered,
+    Overloaded,
+    InvalidRequest,
+}
+
+impl ProviderReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProviderReason::RateLimited => "rate_limited",
+            ProviderReason::QuotaExhausted => "quota_exhausted",
+            ProviderReason::InvalidApiKey => "invalid_api_key",
+            ProviderReason::PermissionDenied => "permission_denied",
+            ProviderReason::ModelNotFound => "model_not_found",
+            ProviderReason::ContextTooLong => "context_too_long",
+            ProviderReason::ContentFiltered => "content_filtered",
+            ProviderReason::Overloaded => "overloaded",
+            ProviderReason::InvalidRequest => "invalid_request",
+        }
+    }
+
+    pub fn is_auth(self) -> bool {
+        matches!(
+            self,
+            ProviderReason::InvalidApiKey | ProviderReason::PermissionDenied
+        )
+    }
+
+    pub fn is_quota(self) -> bool {
+        matches!(
+            self,
+            ProviderReason::RateLimited | ProviderReason::QuotaExhausted
+        )
+    }
+}
+
+// Returns None for bodies that are not JSON or carry no recognizable code, so
+// callers fall back to the HTTP status.
+pub fn parse(body: &str) -> Option<ProviderReason> {
+    let value: Value = serde_json::from_str(body.trim()).ok()?;
+    // Gemini occasionally wraps the error object in a one-element array.
+    let root = match &value {
+        Value::Array(items) => items.first()?,
+        other => other,
+    };
+    let error = root.get("error").unwrap_or(root);
+
+    let mut codes: Vec<&str> = ["code", "status", "type"]
+        .iter()
+        .filter_map(|key| error.get(key).and_then(Value::as_str))
+        .collect();
+    if let Some(details) = error.get("details").and_then(Value::as_array) {
+        codes.extend(
+            details
+                .iter()
+                .filter_map(|detail| detail.get("reason").and_then(Value::as_str)),
+        );
+    }
+
+    codes
+        .iter()
+        .find_map(|code| specific_code(code))
+        .or_else(|| {
+            error
+                .get("message")
+                .and_then(Value::as_str)
+                .and_then(message_reason)
+        })
+        .or_else(|| {
+            codes
+                .iter()
+                .any(|code| is_generic_request_code(code))
+                .then_some(ProviderReason::InvalidRequest)
+        })
+}
+
+fn specific_code(code: &str) -> Option<ProviderReason> {
+    let reason = match code.trim().to_ascii_lowercase().as_str() {
+        "rate_limit_exceeded" | "rate_limit_error" | "resource_exhausted" => {
+            ProviderReason::RateLimited
+        }
+        "insufficient_quota" | "quota_exceeded" | "billing_hard_limit_reached" => {
+            ProviderReason::QuotaExhausted
+        }
+        "invalid_api_key"
+        | "api_key_invalid"
+        | "authentication_error"
+        | "unauthenticated"
+        | "invalid_authentication" => ProviderReason::InvalidApiKey,
+        "permission_denied" | "permission_error" | "organization_restricted" => {
+            ProviderReason::PermissionDenied
+        }
+        "model_not_found" | "model_decommissioned" | "not_found" | "not_found_error" => {
+            ProviderReason::ModelNotFound
+        }
+        "context_length_exceeded" | "request_too_large" | "string_above_max_length" => {
+            ProviderReason::ContextTooLong
+        }
+        "content_filter" | "content_policy_violation" => ProviderReason::ContentFiltered,
+        "overloaded_error" | "server_overloaded" | "unavailable" | "service_unavailable" => {
+            ProviderReason::Overloaded
+        }
+        _ => return None,
+    };
+    Some(reason)
+}
+
+fn message_reason(message: &str) -> Option<ProviderReason> {
+    let message = message.to_ascii_lowercase();
+    if message.contains("api key")
+        && ["invalid", "not valid", "incorrect"]
+            .iter()
+            .any(|hint| message.contains(hint))
+    {
+        Some(ProviderReason::InvalidApiKey)
+    } else if message.contains("model")
+        && (message.contains("does not exist") || message.contains("not found"))
+    {
+        Some(ProviderReason::ModelNotFound)
+    } else if message.contains("context length") || message.contains("maximum context") {
+        Some(ProviderReason::ContextTooLong)
+    } else if message.contains("quota") {
+        Some(ProviderReason::QuotaExhausted)
+    } else if message.contains("rate limit") {
+        Some(ProviderReason::RateLimited)
+    } else {
+        None
+    }
+}
+
+fn is_generic_request_code(code: &str) -> bool {
+    matches!(
+        code.trim().to_ascii_lowercase().as_str(),
+        "invalid_request_error" | "invalid_argument" | "bad_request"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openai_and_groq_error_codes_are_normalized() {
+        let cases = [
+            (
+                r#"{"error":{"message":"Rate limit reached for gpt-4o-mini","type":"requests","param":null,"code":"rate_limit_exceeded"}}"#,
+                ProviderReason::RateLimited,
+            ),
+            (
+                r#"{"error":{"message":"You exceeded your current quota","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#,
+                ProviderReason::QuotaExhausted,
+            ),
+            (
+                r#"{"error":{"message":"Incorrect API key provided: sk-***","type":"invalid_request_error","param":null,"code":"invalid_api_key"}}"#,
+                ProviderReason::InvalidApiKey,
+            ),
+            (
+                r#"{"error":{"message":"The model `llama-9` does not exist or you do not have access to it.","type":"invalid_request_error","code":"model_not_found"}}"#,
+                ProviderReason::ModelNotFound,
+            ),
+            (
+                r#"{"error":{"message":"The model `mixtral-8x7b-32768` has been decommissioned","type":"invalid_request_error","code":"model_decommissioned"}}"#,
+                ProviderReason::ModelNotFound,
+            ),
+            (
+                r#"{"error":{"message":"This model's maximum context length is 8192 tokens","type":"invalid_request_error","code":"context_length_exceeded"}}"#,
+                ProviderReason::ContextTooLong,
+            ),
+        ];
+        for (body, expected) in cases {
+            assert_eq!(parse(body), Some(expected), "for {body}");
+        }
+    }
+
+    #[test]
+    fn gemini_errors_use_status_details_and_message() {
+        assert_eq!(
+            parse(
+                r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT","details":[{"@type":"type.googleapis.com/google.rpc.ErrorInfo","reason":"API_KEY_INVALID"}]}}"#
+            ),
+            Some(ProviderReason::InvalidApiKey)
+        );
+        assert_eq!(
+            parse(
+                r#"[{"error":{"code":429,"message":"Resource has been exhausted","status":"RESOURCE_EXHAUSTED"}}]"#
+            ),
+            Some(ProviderReason::RateLimited)
+        );
+        assert_eq!(
+            parse(
+                r#"{"error":{"code":400,"message":"Invalid JSON payload","status":"INVALID_ARGUMENT"}}"#
+            ),
+            Some(ProviderReason::InvalidRequest)
+        );
+    }
+
+    #[test]
+    fn unrecognized_or_non_json_bodies_yield_nothing() {
+        assert_eq!(parse("quota exceeded for key sk-secret"), None);
+        assert_eq!(parse("<html>502 Bad Gateway</html>"), None);
+        assert_eq!(parse(r#"{"error":{"code":"something_new"}}"#), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn reasons_group_into_auth_and_quota() {
+        assert!(ProviderReason::InvalidApiKey.is_auth());
+        assert!(ProviderReason::QuotaExhausted.is_quota());
+        assert!(!ProviderReason::ModelNotFound.is_auth());
+        assert!(!ProviderReason::ModelNotFound.is_quota());
+        assert_eq!(ProviderReason::ContextTooLong.as_str(), "context_too_long");
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_rusqlite::{Connection, Error as TokioSqlError};
+use tracing::{info, warn};
+
+const CHANNEL_CAPACITY: usize = 256;
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+const SECONDS_PER_DAY: i64 = 60 * 60 * 24;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS qa_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at INTEGER NOT NULL,
+    question_hash TEXT NOT NULL,
+    topic TEXT,
+    model TEXT,
+    cost_eur REAL NOT NULL,
+    chunk_ids TEXT NOT NULL,
+    latency_ms INTEGER NOT NULL,
+    question TEXT,
+    answer TEXT
+);
+CREATE INDEX IF NOT EXISTS qa_log_created_at ON qa_log (created_at);
+";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaLogEntry {
+    pub created_at: i64,
+    pub question_hash: String,
+    pub topic: Option<&'static str>,
+    pub model: Option<String>,
+    pub cost_eur: f64,
+    pub chunk_ids: Vec<String>,
+    pub latency_ms: u64,
+    pub question: Option<String>,
+    pub answer: Option<String>,
+}
+
+// Cheap to clone into handlers; `record` never waits, a full channel drops the
+// row rather than slowing an answer down.
+#[derive(Clone)]
+pub struct QaLogger {
+    sender: mpsc::Sender<QaLogEntry>,
+    log_prompts: bool,
+}
+
+impl QaLogger {
+    pub async fn start(path: &Path, log_prompts: bool, retention_days: u32) -> Result<Self> {
+        let connection = open(path).await?;
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        spawn_writer(connection.clone(), receiver);
+        spawn_retention_sweep(connection, retention_days);
+        Ok(Self {
+            sender,
+            log_prompts,
+        })
+    }
+
+    pub fn record(&self, mut entry: QaLogEntry) {
+        if !self.log_prompts {
+            entry.question = None;
+            entry.answer = None;
+        }
+        if let Err(err) = self.sender.try_send(entry) {
+            warn!(target: "log", error = %err, "Dropping Q&A log row");
+        }
+    }
+}
+
+pub fn question_hash(question: &str) -> String {
+    let normalized = question
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+pub async fn open(path: &Path) -> Result<Connection> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let connection = Connection::open(path)
+        .await
+        .with_context(|| format!("Failed to open Q&A log database at {path:?}"))?;
+    connection
+        .call(
+            |conn: &mut rusqlite::Connection| -> Result<(), TokioSqlError> {
+                conn.execute_batch(SCHEMA)?;
+                Ok(())
+            },
+        )
+        .await
+        .context("Failed to create the qa_log schema")?;
+    Ok(connection)
+}
+
+fn spawn_writer(
+    connection: Connection,
+    mut receiver: mpsc::Receiver<QaLogEntry>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(entry) = receiver.recv().await {
+            if let Err(err) = insert(&connection, entry).await {
+                warn!(target: "log", error = %err, "Failed to persist Q&A log row");
+            }
+        }
+    })
+}
+
+fn spawn_retention_sweep(connection: Connection, retention_days: u32) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = chrono::Utc::now().timestamp();
+            match sweep(&connection, now, retention_days).await {
+                Ok(0) => {}
+                Ok(removed) => {
+                    info!(target: "log", removed, retention_days, "Pruned expired Q&A log rows");
+                }
+                Err(err) => {
+                    warn!(target: "log", error = %err, "Q&A log retention sweep failed");
+                }
+            }
+        }
+    })
+}
+
+async fn insert(connection: &Connection, entry: QaLogEntry) -> Result<()> {
+    let chunk_ids = serde_json::to_string(&entry.chunk_ids)?;
+    connection
+        .call(move |conn: &mut rusqlite::Connection| -> Result<(), TokioSqlError> {
+            conn.execute(
+                "INSERT INTO qa_log (created_at, question_hash, topic, model, cost_eur, chunk_ids, latency_ms, question, answer)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    entry.created_at,
+                    entry.question_hash,
+                    entry.topic,
+                    entry.model,
+                    entry.cost_eur,
+                    chunk_ids,
+                    i64::try_from(entry.latency_ms).unwrap_or(i64::MAX),
+                    entry.question,
+                    entry.answer,
+                ],
+            )?;
+            Ok(())
+        })
+        .await?;
+    Ok(())
+}
+
+pub async fn sweep(connection: &Connection, now: i64, retention_days: u32) -> Result<usize> {
+    let cutoff = now - i64::from(retention_days) * SECONDS_PER_DAY;
+    let removed = connection
+        .call(
+            move |conn: &mut rusqlite::Connection| -> Result<usize, TokioSqlError> {
+                Ok(conn.execute("DELETE FROM qa_log WHERE created_at < ?1", [cutoff])?)
+            },
+        )
+        .await?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    struct TempDb(PathBuf);
+
+    impl TempDb {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("qa-log-{}.db", uuid::Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn entry(created_at: i64, question: &str) -> QaLogEntry {
+        QaLogEntry {
+            created_at,
+            question_hash: question_hash(question),
+            topic: Some("skills"),
+            model: Some("groq".to_string()),
+            cost_eur: 0.0012,
+            chunk_ids: vec!["chunk-1".to_string(), "chunk-7".to_string()],
+            latency_ms: 840,
+            question: Some(question.to_string()),
+            answer: Some("Rust and Go.".to_string()),
+        }
+    }
+
+    async fn rows(connection: &Connection) -> Vec<(i64, String, Option<String>, String)> {
+        connection
+            .call(
+                |conn: &mut rusqlite::Connection| -> Result<_, TokioSqlError> {
+                    let mut stmt = conn.prepare(
+                    "SELECT created_at, question_hash, question, chunk_ids FROM qa_log ORDER BY id",
+                )?;
+                    let rows = stmt
+                        .query_map([], |row| {
+                            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                },
+            )
+            .await
+            .expect("query rows")
+    }
+
+    #[tokio::test]
+    async fn schema_is_created_idempotently() {
+        let db = TempDb::new();
+        open(&db.0).await.expect("first open");
+        let connection = open(&db.0).await.expect("reopening keeps the schema");
+        let columns = connection
+            .call(
+                |conn: &mut rusqlite::Connection| -> Result<Vec<String>, TokioSqlError> {
+                    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('qa_log')")?;
+                    let names = stmt
+                        .query_map([], |row| row.get(0))?
+                        .collect::<Result<Vec<String>, _>>()?;
+                    Ok(names)
+                },
+            )
+            .await
+            .expect("table info");
+        assert_eq!(
+            columns,
+            [
+                "id",
+                "created_at",
+                "question_hash",
+                "topic",
+                "model",
+                "cost_eur",
+                "chunk_ids",
+                "latency_ms",
+                "question",
+                "answer"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn writer_persists_rows_sent_over_the_channel() {
+        let db = TempDb::new();
+        let connection = open(&db.0).await.expect("open");
+        let (sender, receiver) = mpsc::channel(4);
+        let writer = spawn_writer(connection.clone(), receiver);
+        let logger = QaLogger {
+            sender,
+            log_prompts: false,
+        };
+
+        logger.record(entry(1_000, "What  does he KNOW?"));
+        logger.record(entry(2_000, "Where is he based?"));
+        drop(logger);
+        writer.await.expect("writer drains the channel");
+
+        let stored = rows(&connection).await;
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].0, 1_000);
+        assert_eq!(stored[0].1, question_hash("what does he know?"));
+        assert_eq!(stored[0].2, None, "Raw text needs AI_LOG_PROMPTS=1");
+        assert_eq!(stored[0].3, r#"["chunk-1","chunk-7"]"#);
+        assert_eq!(stored[1].0, 2_000);
+    }
+
+    #[tokio::test]
+    async fn raw_text_is_kept_only_when_prompt_logging_is_enabled() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let logger = QaLogger {
+            sender,
+            log_prompts: true,
+        };
+        logger.record(entry(1_000, "Is he available?"));
+        let received = receiver.recv().await.expect("entry");
+        assert_eq!(received.question.as_deref(), Some("Is he available?"));
+        assert_eq!(received.answer.as_deref(), Some("Rust and Go."));
+    }
+
+    #[tokio::test]
+    async fn retention_sweep_deletes_only_expired_rows() {
+        let db = TempDb::new();
+        let connection = open(&db.0).await.expect("open");
+        let now = 1_750_000_000;
+        for age_days in [0, 29, 31, 400] {
+            insert(&connection, entry(now - age_days * SECONDS_PER_DAY, "q"))
+                .await
+                .expect("insert");
+        }
+
+        assert_eq!(sweep(&connection, now, 30).await.expect("sweep"), 2);
+        let kept = rows(&connection)
+            .await
+            .into_iter()
+            .map(|row| (now - row.0) / SECONDS_PER_DAY)
+            .collect::<Vec<_>>();
+        assert_eq!(kept, [0, 29]);
+        assert_eq!(sweep(&connection, now, 30).await.expect("sweep"), 0);
+    }
+}
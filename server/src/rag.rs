@@ -4,10 +4,46 @@ use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use thiserror::Error;
 use tokio_rusqlite::{Connection, Error as TokioSqlError};
 
 const OPENAI_EMBEDDING_ENDPOINT: &str = "https://api.openai.com/v1/embeddings";
+// Embedded once at startup to check the index dimension; the vector is kept so
+// the same question asked later skips the embedding call.
+const PROBE_TEXT: &str = "What projects have you worked on?";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error(
+    "Pinecone index dimension {index} does not match embedding model {model:?} ({embedding} dimensions); \
+     re-create the index or set OPENAI_EMBEDDING_MODEL to a matching model"
+)]
+pub struct DimensionMismatch {
+    pub index: usize,
+    pub embedding: usize,
+    pub model: String,
+}
+
+fn check_dimensions(index: usize, embedding: usize, model: &str) -> Result<(), DimensionMismatch> {
+    if index == embedding {
+        Ok(())
+    } else {
+        Err(DimensionMismatch {
+            index,
+            embedding,
+            model: model.to_string(),
+        })
+    }
+}
+
+async fn verify_dimensions(pinecone: &PineconeClient, embedder: &EmbeddingClient) -> Result<()> {
+    let probe = embedder.embed(PROBE_TEXT).await?;
+    let index = pinecone.dimension().await?;
+    check_dimensions(index, probe.len(), &embedder.model)?;
+    embedder.remember(PROBE_TEXT, probe);
+    Ok(())
+}
 
 #[derive(Clone)]
 pub struct RagRetriever {
@@ -27,6 +63,9 @@ pub struct ContextChunk {
     pub score: f32,
 }
 
+// Embedding plus the Pinecone query; the request deadline may shorten it.
+pub const RETRIEVAL_TIMEOUT: Duration = Duration::from_secs(8);
+
 impl RagRetriever {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
@@ -48,6 +87,7 @@ impl RagRetriever {
             pinecone_namespace,
         );
         let embedder = EmbeddingClient::new(client, embedding_key, embedding_model)?;
+        verify_dimensions(&pinecone, &embedder).await?;
         Ok(Self {
             store,
             pinecone,
@@ -181,6 +221,32 @@ impl PineconeClient {
         let body: PineconeQueryResponse = response.json().await?;
         Ok(body.matches.unwrap_or_default())
     }
+
+    async fn dimension(&self) -> Result<usize> {
+        let response = self
+            .client
+            .post(format!("{}/describe_index_stats", self.host))
+            .header("Api-Key", &self.api_key)
+            .json(&json!({}))
+            .send()
+            .await
+            .context("Failed to describe Pinecone index")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Pinecone describe_index_stats failed ({status}): {body}");
+        }
+
+        let body: PineconeIndexStats = response.json().await?;
+        body.dimension
+            .ok_or_else(|| anyhow!("Pinecone index stats did not report a dimension"))
+    }
+}
+
+#[derive(Deserialize)]
+struct PineconeIndexStats {
+    dimension: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -197,27 +263,40 @@ struct PineconeMatch {
 #[derive(Clone)]
 struct EmbeddingClient {
     client: Client,
+    endpoint: String,
     api_key: Arc<String>,
     model: String,
+    probe: Arc<OnceLock<(String, Vec<f32>)>>,
 }
 
 impl EmbeddingClient {
     fn new(client: Client, api_key: String, model: String) -> Result<Self> {
         Ok(Self {
             client,
+            endpoint: OPENAI_EMBEDDING_ENDPOINT.to_string(),
             api_key: Arc::new(api_key),
             model,
+            probe: Arc::new(OnceLock::new()),
         })
     }
 
+    fn remember(&self, text: &str, embedding: Vec<f32>) {
+        let _ = self.probe.set((text.to_string(), embedding));
+    }
+
     async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        if let Some((probe, embedding)) = self.probe.get() {
+            if probe == text {
+                return Ok(embedding.clone());
+            }
+        }
         let payload = serde_json::json!({
             "model": self.model,
             "input": text,
         });
         let response = self
             .client
-            .post(OPENAI_EMBEDDING_ENDPOINT)
+            .post(&self.endpoint)
             .bearer_auth(self.api_key.as_str())
             .json(&payload)
             .send()
@@ -250,3 +329,118 @@ struct EmbeddingResponse {
 struct EmbeddingData {
     embedding: Vec<f32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use serde_json::Value;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn matching_dimensions_pass_the_check() {
+        assert_eq!(
+            check_dimensions(1536, 1536, "text-embedding-3-small"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn mismatch_error_names_both_dimensions_and_the_model() {
+        let err = check_dimensions(1536, 3072, "text-embedding-3-large").unwrap_err();
+        assert_eq!(
+            err,
+            DimensionMismatch {
+                index: 1536,
+                embedding: 3072,
+                model: "text-embedding-3-large".to_string(),
+            }
+        );
+        let message = err.to_string();
+        assert!(message.contains("index dimension 1536"), "{message}");
+        assert!(
+            message.contains("\"text-embedding-3-large\" (3072 dimensions)"),
+            "{message}"
+        );
+    }
+
+    async fn mock_services(
+        index_dimension: usize,
+        embedding_len: usize,
+    ) -> (String, Arc<AtomicUsize>) {
+        let embed_calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&embed_calls);
+        let mock = Router::new()
+            .route(
+                "/describe_index_stats",
+                post(move || async move { Json(json!({ "dimension": index_dimension })) }),
+            )
+            .route(
+                "/v1/embeddings",
+                post(move |Json(_): Json<Value>| {
+                    let counter = Arc::clone(&counter);
+                    async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        Json(json!({ "data": [{ "embedding": vec![0.5_f32; embedding_len] }] }))
+                    }
+                }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("mock listener should bind");
+        let addr = listener.local_addr().expect("mock address");
+        tokio::spawn(async move {
+            axum::serve(listener, mock).await.expect("mock server");
+        });
+        (format!("http://{addr}"), embed_calls)
+    }
+
+    fn clients(base: &str, model: &str) -> (PineconeClient, EmbeddingClient) {
+        let client = Client::new();
+        let pinecone =
+            PineconeClient::new(client.clone(), base.to_string(), "pc-key".to_string(), None);
+        let mut embedder = EmbeddingClient::new(client, "sk-test".to_string(), model.to_string())
+            .expect("embedder");
+        embedder.endpoint = format!("{base}/v1/embeddings");
+        (pinecone, embedder)
+    }
+
+    #[tokio::test]
+    async fn startup_probe_rejects_a_mismatched_index() {
+        let (base, _) = mock_services(1536, 3072).await;
+        let (pinecone, embedder) = clients(&base, "text-embedding-3-large");
+        let err = verify_dimensions(&pinecone, &embedder)
+            .await
+            .expect_err("dimensions differ");
+        let mismatch = err
+            .downcast_ref::<DimensionMismatch>()
+            .expect("typed mismatch error");
+        assert_eq!((mismatch.index, mismatch.embedding), (1536, 3072));
+    }
+
+    #[tokio::test]
+    async fn startup_probe_embedding_is_reused() {
+        let (base, embed_calls) = mock_services(8, 8).await;
+        let (pinecone, embedder) = clients(&base, "text-embedding-3-small");
+        verify_dimensions(&pinecone, &embedder)
+            .await
+            .expect("dimensions match");
+        assert_eq!(embed_calls.load(Ordering::SeqCst), 1);
+
+        let cached = embedder.embed(PROBE_TEXT).await.expect("cached embedding");
+        assert_eq!(cached.len(), 8);
+        assert_eq!(
+            embed_calls.load(Ordering::SeqCst),
+            1,
+            "Probe is served from cache"
+        );
+
+        embedder
+            .embed("Something else")
+            .await
+            .expect("fresh embedding");
+        assert_eq!(embed_calls.load(Ordering::SeqCst), 2);
+    }
+}
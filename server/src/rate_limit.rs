@@ -1,11 +1,14 @@
-use axum::http::StatusCode;
 use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
+use zqs_model::ReasonCode;
 
 const PER_IP_BURST_MAX: usize = 4;
 const PER_IP_MINUTE_MAX: usize = 8;
 const PER_IP_HOUR_MAX: usize = 60;
 const PER_IP_DAY_MAX: usize = 120;
+// Owner widgets often share cloud egress IPs, so their per-IP windows are
+// wider but still finite: a leaked token cannot drain the budget from one IP.
+pub const WIDGET_IP_LIMIT_MULTIPLIER: usize = 5;
 
 const BURST: Duration = Duration::from_secs(1);
 const MINUTE: Duration = Duration::from_secs(60);
@@ -53,6 +56,28 @@ struct CountWindow {
     entries: VecDeque<Instant>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Caller {
+    Visitor,
+    Widget,
+}
+
+impl Caller {
+    fn ip_key(self, ip: &str) -> String {
+        match self {
+            Caller::Visitor => ip.to_string(),
+            Caller::Widget => format!("widget|{ip}"),
+        }
+    }
+
+    fn limit_multiplier(self) -> usize {
+        match self {
+            Caller::Visitor => 1,
+            Caller::Widget => WIDGET_IP_LIMIT_MULTIPLIER,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum RateLimitError {
     PerIpBurst,
@@ -83,9 +108,60 @@ impl RateLimiter {
         });
     }
 
+    pub fn check(&mut self, ip: &str, cost: f64) -> Result<(), RateLimitError> {
+        self.check_at(ip, cost, Instant::now())
+    }
+
     pub fn check_and_record(&mut self, ip: &str, cost: f64) -> Result<(), RateLimitError> {
+        self.check_and_record_as(Caller::Visitor, ip, cost)
+    }
+
+    pub fn check_and_record_as(
+        &mut self,
+        caller: Caller,
+        ip: &str,
+        cost: f64,
+    ) -> Result<(), RateLimitError> {
         let now = Instant::now();
+        let key = caller.ip_key(ip);
+        self.check_at(&key, cost, now)?;
+
+        self.minute_cost.record(now, cost);
+        self.hour_cost.record(now, cost);
+        self.day_cost.record(now, cost);
+        self.month_cost.record(now, cost);
+        self.record_ip(&key, caller.limit_multiplier(), now);
+
+        Ok(())
+    }
 
+    // Free answers (FAQ hits) count against the per-IP windows only, so an
+    // exhausted spend budget never blocks them.
+    pub fn check_and_record_request_as(
+        &mut self,
+        caller: Caller,
+        ip: &str,
+    ) -> Result<(), RateLimitError> {
+        let now = Instant::now();
+        let key = caller.ip_key(ip);
+        self.prune_stale_ips(now);
+        self.check_ip_at(&key, now)?;
+        self.record_ip(&key, caller.limit_multiplier(), now);
+        Ok(())
+    }
+
+    fn record_ip(&mut self, key: &str, multiplier: usize, now: Instant) {
+        let ip_windows = self
+            .per_ip
+            .entry(key.to_string())
+            .or_insert_with(|| IpWindows::new(multiplier));
+        ip_windows.burst.record(now);
+        ip_windows.minute.record(now);
+        ip_windows.hour.record(now);
+        ip_windows.day.record(now);
+    }
+
+    fn check_at(&mut self, ip: &str, cost: f64, now: Instant) -> Result<(), RateLimitError> {
         if cost > self.minute_cost.budget_eur {
             return Err(RateLimitError::MinuteBudget);
         }
@@ -104,23 +180,7 @@ impl RateLimiter {
         self.day_cost.prune(now);
         self.month_cost.prune(now);
         self.prune_stale_ips(now);
-
-        let ip_windows = self
-            .per_ip
-            .entry(ip.to_string())
-            .or_insert_with(IpWindows::new);
-        if ip_windows.burst.would_exceed(now) {
-            return Err(RateLimitError::PerIpBurst);
-        }
-        if ip_windows.minute.would_exceed(now) {
-            return Err(RateLimitError::PerIpMinute);
-        }
-        if ip_windows.hour.would_exceed(now) {
-            return Err(RateLimitError::PerIpHour);
-        }
-        if ip_windows.day.would_exceed(now) {
-            return Err(RateLimitError::PerIpDay);
-        }
+        self.check_ip_at(ip, now)?;
 
         if self.minute_cost.would_exceed(cost) {
             return Err(RateLimitError::MinuteBudget);
@@ -135,15 +195,24 @@ impl RateLimiter {
             return Err(RateLimitError::MonthBudget);
         }
 
-        self.minute_cost.record(now, cost);
-        self.hour_cost.record(now, cost);
-        self.day_cost.record(now, cost);
-        self.month_cost.record(now, cost);
-        ip_windows.burst.record(now);
-        ip_windows.minute.record(now);
-        ip_windows.hour.record(now);
-        ip_windows.day.record(now);
+        Ok(())
+    }
 
+    fn check_ip_at(&mut self, ip: &str, now: Instant) -> Result<(), RateLimitError> {
+        if let Some(ip_windows) = self.per_ip.get_mut(ip) {
+            if ip_windows.burst.would_exceed(now) {
+                return Err(RateLimitError::PerIpBurst);
+            }
+            if ip_windows.minute.would_exceed(now) {
+                return Err(RateLimitError::PerIpMinute);
+            }
+            if ip_windows.hour.would_exceed(now) {
+                return Err(RateLimitError::PerIpHour);
+            }
+            if ip_windows.day.would_exceed(now) {
+                return Err(RateLimitError::PerIpDay);
+            }
+        }
         Ok(())
     }
 
@@ -194,48 +263,29 @@ impl RateLimiter {
 }
 
 impl RateLimitError {
-    pub fn describe(&self) -> (StatusCode, &'static str, &'static str) {
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            RateLimitError::PerIpBurst => ReasonCode::PerIpBurst,
+            RateLimitError::PerIpMinute => ReasonCode::PerIpMinute,
+            RateLimitError::PerIpHour => ReasonCode::PerIpHour,
+            RateLimitError::PerIpDay => ReasonCode::PerIpDay,
+            RateLimitError::MinuteBudget => ReasonCode::MinuteBudget,
+            RateLimitError::HourBudget => ReasonCode::HourBudget,
+            RateLimitError::DayBudget => ReasonCode::DayBudget,
+            RateLimitError::MonthBudget => ReasonCode::MonthBudget,
+        }
+    }
+
+    pub fn detail(&self) -> &'static str {
         match self {
-            RateLimitError::PerIpBurst => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "per_ip_burst",
-                "per-second request limit",
-            ),
-            RateLimitError::PerIpMinute => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "per_ip_minute",
-                "per-minute request limit",
-            ),
-            RateLimitError::PerIpHour => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "per_ip_hour",
-                "per-hour request limit",
-            ),
-            RateLimitError::PerIpDay => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "per_ip_day",
-                "per-day request limit",
-            ),
-            RateLimitError::MinuteBudget => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "minute_budget",
-                "per-minute budget",
-            ),
-            RateLimitError::HourBudget => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "hour_budget",
-                "per-hour budget",
-            ),
-            RateLimitError::DayBudget => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "day_budget",
-                "per-day budget",
-            ),
-            RateLimitError::MonthBudget => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "month_budget",
-                "monthly budget",
-            ),
+            RateLimitError::PerIpBurst => "per-second request limit",
+            RateLimitError::PerIpMinute => "per-minute request limit",
+            RateLimitError::PerIpHour => "per-hour request limit",
+            RateLimitError::PerIpDay => "per-day request limit",
+            RateLimitError::MinuteBudget => "per-minute budget",
+            RateLimitError::HourBudget => "per-hour budget",
+            RateLimitError::DayBudget => "per-day budget",
+            RateLimitError::MonthBudget => "monthly budget",
         }
     }
 }
@@ -276,12 +326,12 @@ impl CostWindow {
 }
 
 impl IpWindows {
-    fn new() -> Self {
+    fn new(multiplier: usize) -> Self {
         Self {
-            burst: CountWindow::new(BURST, PER_IP_BURST_MAX),
-            minute: CountWindow::new(MINUTE, PER_IP_MINUTE_MAX),
-            hour: CountWindow::new(HOUR, PER_IP_HOUR_MAX),
-            day: CountWindow::new(DAY, PER_IP_DAY_MAX),
+            burst: CountWindow::new(BURST, PER_IP_BURST_MAX * multiplier),
+            minute: CountWindow::new(MINUTE, PER_IP_MINUTE_MAX * multiplier),
+            hour: CountWindow::new(HOUR, PER_IP_HOUR_MAX * multiplier),
+            day: CountWindow::new(DAY, PER_IP_DAY_MAX * multiplier),
         }
     }
 
@@ -421,4 +471,69 @@ mod tests {
         assert!(limiter.ip_windows_mut(stale_ip).is_none());
         assert!(limiter.ip_windows_mut(active_ip).is_some());
     }
+
+    #[test]
+    fn check_does_not_record_usage() {
+        let mut limiter = RateLimiter::new(0.05, 1.0, 1.0, 1.0);
+        let ip = "192.0.2.10";
+        for _ in 0..(PER_IP_BURST_MAX * 2) {
+            assert!(limiter.check(ip, 0.04).is_ok());
+        }
+        let snapshot = limiter.usage_snapshot(ip);
+        assert_eq!(snapshot.minute_spend, 0.0);
+        assert_eq!(snapshot.ip_burst, 0);
+        assert!(matches!(
+            limiter.check(ip, 0.06).unwrap_err(),
+            RateLimitError::MinuteBudget
+        ));
+    }
+
+    #[test]
+    fn widget_callers_get_wider_but_finite_ip_windows() {
+        let mut limiter = RateLimiter::new(10.0, 10.0, 10.0, 10.0);
+        let ip = "192.0.2.12";
+        let widget_burst = PER_IP_BURST_MAX * WIDGET_IP_LIMIT_MULTIPLIER;
+        for _ in 0..widget_burst {
+            limiter
+                .check_and_record_as(Caller::Widget, ip, 0.001)
+                .unwrap();
+        }
+        assert!(matches!(
+            limiter
+                .check_and_record_as(Caller::Widget, ip, 0.001)
+                .unwrap_err(),
+            RateLimitError::PerIpBurst
+        ));
+        assert!(matches!(
+            limiter
+                .check_and_record_request_as(Caller::Widget, ip)
+                .unwrap_err(),
+            RateLimitError::PerIpBurst
+        ));
+        assert!(
+            limiter.check_and_record(ip, 0.001).is_ok(),
+            "Widget traffic is tracked apart from the IP's own visits"
+        );
+    }
+
+    #[test]
+    fn free_requests_count_per_ip_but_ignore_spent_budgets() {
+        let mut limiter = RateLimiter::new(0.05, 1.0, 1.0, 1.0);
+        let ip = "192.0.2.11";
+        limiter.check_and_record(ip, 0.05).unwrap();
+        for _ in 1..PER_IP_BURST_MAX {
+            limiter
+                .check_and_record_request_as(Caller::Visitor, ip)
+                .unwrap();
+        }
+        let snapshot = limiter.usage_snapshot(ip);
+        assert_eq!(snapshot.ip_burst, PER_IP_BURST_MAX);
+        assert!((snapshot.minute_spend - 0.05).abs() < 1e-9);
+        assert!(matches!(
+            limiter
+                .check_and_record_request_as(Caller::Visitor, ip)
+                .unwrap_err(),
+            RateLimitError::PerIpBurst
+        ));
+    }
 }
@@ -0,0 +1,142 @@
+use axum::http::StatusCode;
+use zqs_model::ReasonCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Degradation {
+    pub status: StatusCode,
+    pub message: &'static str,
+}
+
+// No wildcard arm on purpose: a new reason code does not compile until it
+// has a status and a canonical message here.
+pub fn degradation(reason: ReasonCode) -> Degradation {
+    let (status, message) = match reason {
+        ReasonCode::EmptyQuestion => (
+            StatusCode::BAD_REQUEST,
+            "Please provide a question so the AI can help.",
+        ),
+        ReasonCode::QuestionTooLong => (
+            StatusCode::BAD_REQUEST,
+            "Question is too long for the lightweight AI mode. Please shorten it.",
+        ),
+        ReasonCode::UnintelligibleInput => (
+            StatusCode::BAD_REQUEST,
+            "That question could not be understood. Please rephrase it in plain words.",
+        ),
+        ReasonCode::EmbedTokenMissing | ReasonCode::EmbedTokenInvalid => (
+            StatusCode::FORBIDDEN,
+            "This embedded terminal could not be verified. Reload the page and try again.",
+        ),
+        ReasonCode::PerIpBurst => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-second request limit). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::PerIpMinute => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-minute request limit). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::PerIpHour => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-hour request limit). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::PerIpDay => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-day request limit). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::MinuteBudget => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-minute budget). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::HourBudget => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-hour budget). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::DayBudget => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (per-day budget). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::MonthBudget => (
+            StatusCode::TOO_MANY_REQUESTS,
+            "AI usage limit reached (monthly budget). Switching back to the classic mode for now.",
+        ),
+        ReasonCode::DeadlineExceeded => (
+            StatusCode::GATEWAY_TIMEOUT,
+            "The AI took too long to answer. Please retry in a moment.",
+        ),
+        ReasonCode::BackendError => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "The AI backend is temporarily unavailable. Please retry in a moment.",
+        ),
+        ReasonCode::DryRun => (
+            StatusCode::OK,
+            "Dry run: the limiter was checked but no model was called.",
+        ),
+        ReasonCode::FaqMatch => (
+            StatusCode::OK,
+            "Answered from the FAQ without calling a model.",
+        ),
+        ReasonCode::UnknownSection => (
+            StatusCode::BAD_REQUEST,
+            "Only experience, projects, skills and education can be summarized.",
+        ),
+        ReasonCode::SectionUnavailable => (
+            StatusCode::NOT_FOUND,
+            "There is nothing to summarize in this section yet.",
+        ),
+        ReasonCode::Unknown => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Something went wrong. Please retry in a moment.",
+        ),
+    };
+    Degradation { status, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::RateLimitError;
+
+    const LIMIT_SUFFIX: &str = "Switching back to the classic mode for now.";
+
+    #[test]
+    fn every_reason_has_a_message_and_a_matching_status() {
+        for reason in ReasonCode::ALL {
+            let entry = degradation(reason);
+            assert!(!entry.message.is_empty(), "{reason}");
+            assert!(entry.message.ends_with('.'), "{reason}: {}", entry.message);
+            assert_eq!(
+                entry.status == StatusCode::TOO_MANY_REQUESTS,
+                reason.is_limit(),
+                "{reason}"
+            );
+            assert_eq!(
+                entry.status.is_success(),
+                matches!(reason, ReasonCode::DryRun | ReasonCode::FaqMatch),
+                "{reason}"
+            );
+        }
+    }
+
+    #[test]
+    fn limit_messages_name_the_limiter_detail() {
+        let limits = [
+            RateLimitError::PerIpBurst,
+            RateLimitError::PerIpMinute,
+            RateLimitError::PerIpHour,
+            RateLimitError::PerIpDay,
+            RateLimitError::MinuteBudget,
+            RateLimitError::HourBudget,
+            RateLimitError::DayBudget,
+            RateLimitError::MonthBudget,
+        ];
+        for limit in limits {
+            assert_eq!(
+                degradation(limit.reason()).message,
+                format!(
+                    "AI usage limit reached ({}). {LIMIT_SUFFIX}",
+                    limit.detail()
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::static_data::TerminalDataPayload;
+
+// Informational commands and the data file each one renders from.
+const SECTION_PAGES: [(&str, &str); 9] = [
+    ("about", "profile.json"),
+    ("skills", "skills.json"),
+    ("experience", "experience.json"),
+    ("education", "education.json"),
+    ("projects", "projects.json"),
+    ("testimonials", "testimonials.json"),
+    ("contact", "profile.json"),
+    ("resume", "profile.json"),
+    ("faq", "faq.json"),
+];
+
+pub fn robots_txt(site_url: &str) -> String {
+    format!(
+        "User-agent: *\nAllow: /\n\nSitemap: {}/sitemap.xml\n",
+        site_url.trim_end_matches('/')
+    )
+}
+
+pub fn sitemap_xml(site_url: &str, payload: &TerminalDataPayload) -> String {
+    let root = format!("{}/", site_url.trim_end_matches('/'));
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    push_url(&mut xml, &root, payload.source_mtime.as_deref());
+    for (command, filename) in SECTION_PAGES {
+        let location = format!("{root}?cmd={command}");
+        let lastmod = payload.file_mtimes.get(filename).map(String::as_str);
+        push_url(&mut xml, &location, lastmod);
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn push_url(xml: &mut String, location: &str, lastmod: Option<&str>) {
+    xml.push_str("  <url>\n    <loc>");
+    xml.push_str(&escape_xml(location));
+    xml.push_str("</loc>\n");
+    if let Some(lastmod) = lastmod {
+        xml.push_str("    <lastmod>");
+        xml.push_str(&escape_xml(lastmod));
+        xml.push_str("</lastmod>\n");
+    }
+    xml.push_str("  </url>\n");
+}
+
+fn escape_xml(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload_with_mtimes(mtimes: &[(&'static str, &str)]) -> TerminalDataPayload {
+        TerminalDataPayload {
+            profile: json!({}),
+            skills: json!({}),
+            experiences: json!([]),
+            education: json!([]),
+            projects: json!({}),
+            testimonials: json!([]),
+            faqs: json!([]),
+            generated_at: "2025-01-01T00:00:00Z".to_string(),
+            source_mtime: mtimes.iter().map(|(_, time)| time.to_string()).max(),
+            file_mtimes: mtimes
+                .iter()
+                .map(|(filename, time)| (*filename, time.to_string()))
+                .collect(),
+            counts: Default::default(),
+            prompt_extras: None,
+        }
+    }
+
+    #[test]
+    fn robots_allows_everything_and_points_at_sitemap() {
+        assert_eq!(
+            robots_txt("https://zqsdev.com/"),
+            "User-agent: *\nAllow: /\n\nSitemap: https://zqsdev.com/sitemap.xml\n"
+        );
+    }
+
+    #[test]
+    fn sitemap_lists_root_and_sections_with_lastmod() {
+        let payload = payload_with_mtimes(&[
+            ("profile.json", "2025-03-01T10:00:00Z"),
+            ("skills.json", "2025-02-01T09:30:00Z"),
+            ("experience.json", "2025-04-12T08:00:00Z"),
+            ("education.json", "2024-12-24T18:00:00Z"),
+            ("projects.json", "2025-04-10T12:00:00Z"),
+            ("testimonials.json", "2025-01-15T07:45:00Z"),
+        ]);
+        let expected = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">
+  <url>
+    <loc>https://zqsdev.com/</loc>
+    <lastmod>2025-04-12T08:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=about</loc>
+    <lastmod>2025-03-01T10:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=skills</loc>
+    <lastmod>2025-02-01T09:30:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=experience</loc>
+    <lastmod>2025-04-12T08:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=education</loc>
+    <lastmod>2024-12-24T18:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=projects</loc>
+    <lastmod>2025-04-10T12:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=testimonials</loc>
+    <lastmod>2025-01-15T07:45:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=contact</loc>
+    <lastmod>2025-03-01T10:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=resume</loc>
+    <lastmod>2025-03-01T10:00:00Z</lastmod>
+  </url>
+  <url>
+    <loc>https://zqsdev.com/?cmd=faq</loc>
+  </url>
+</urlset>
+";
+        assert_eq!(sitemap_xml("https://zqsdev.com", &payload), expected);
+    }
+
+    #[test]
+    fn sitemap_escapes_locations() {
+        let payload = payload_with_mtimes(&[]);
+        let xml = sitemap_xml("https://example.com/a&b<'c'>", &payload);
+        assert!(
+            xml.contains("<loc>https://example.com/a&amp;b&lt;&apos;c&apos;&gt;/?cmd=faq</loc>"),
+            "Locations must be XML-escaped:\n{xml}"
+        );
+        assert!(!xml.contains("<lastmod>"), "Missing mtimes are omitted");
+        assert_eq!(xml.matches("<url>").count(), 1 + SECTION_PAGES.len());
+    }
+}
@@ -0,0 +1,192 @@
+use serde::Serialize;
+use std::path::Path;
+
+const INDEX_FILE: &str = "index.html";
+const ASSET_EXTENSIONS: [&str; 3] = [".js", ".wasm", ".css"];
+
+#[derive(Debug, Serialize)]
+pub struct AssetStatus {
+    pub path: String,
+    pub present: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssetReport {
+    pub files: Vec<AssetStatus>,
+    pub missing: Vec<String>,
+}
+
+impl AssetReport {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+// A token scanner rather than an HTML parser: it also catches the wasm and
+// module URLs that the bootstrap script builds in template literals.
+pub fn referenced_assets(html: &str) -> Vec<String> {
+    let mut assets: Vec<String> = Vec::new();
+    let tokens = html.split(|ch: char| {
+        ch.is_whitespace() || matches!(ch, '"' | '\'' | '`' | '(' | ')' | '<' | '>' | '=')
+    });
+    for token in tokens {
+        let path = token.split(['?', '#']).next().unwrap_or_default();
+        if !ASSET_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+            continue;
+        }
+        let Some(path) = local_asset_path(path) else {
+            continue;
+        };
+        if !assets.iter().any(|known| known == path) {
+            assets.push(path.to_string());
+        }
+    }
+    assets
+}
+
+fn local_asset_path(raw: &str) -> Option<&str> {
+    if raw.contains("://") || raw.starts_with("//") || raw.contains(['$', '{', '}', '\\']) {
+        return None;
+    }
+    let path = raw.trim_start_matches("./").trim_start_matches('/');
+    let escapes_root = path
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "." || segment == "..");
+    (!escapes_root).then_some(path)
+}
+
+pub async fn check_assets(static_dir: &Path) -> AssetReport {
+    let mut files = vec![asset_status(static_dir, INDEX_FILE).await];
+    if let Ok(html) = tokio::fs::read_to_string(static_dir.join(INDEX_FILE)).await {
+        for path in referenced_assets(&html) {
+            files.push(asset_status(static_dir, &path).await);
+        }
+    }
+    let missing = files
+        .iter()
+        .filter(|status| !status.present)
+        .map(|status| status.path.clone())
+        .collect();
+    AssetReport { files, missing }
+}
+
+async fn asset_status(static_dir: &Path, path: &str) -> AssetStatus {
+    let size_bytes = tokio::fs::metadata(static_dir.join(path))
+        .await
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len());
+    AssetStatus {
+        path: path.to_string(),
+        present: size_bytes.is_some(),
+        size_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const FIXTURE_HTML: &str = r#"<!doctype html>
+<html>
+<head>
+    <link rel="preload" href="./style.min.css" as="style">
+    <link rel="stylesheet" href="./style.min.css">
+    <link rel="stylesheet" href="https://fonts.example.com/inter.css">
+    <link rel="icon" href="/favicon.ico">
+</head>
+<body>
+    <script src=/build_id.js></script>
+    <script type="module">
+        const moduleUrl = `./pkg/zqs_terminal.js?build=${BUILD_ID}`;
+        const wasmUrl = `./pkg/zqs_terminal_bg.wasm?build=${BUILD_ID}`;
+        const sneaky = "../secrets.js";
+        const dynamic = `./pkg/${name}.js`;
+    </script>
+</body>
+</html>"#;
+
+    struct TempStatic(PathBuf);
+
+    impl TempStatic {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("assets-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(dir.join("pkg")).expect("fixture dir");
+            Self(dir)
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            std::fs::write(self.0.join(path), contents).expect("fixture file");
+        }
+    }
+
+    impl Drop for TempStatic {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn extracts_local_script_style_and_wasm_references() {
+        assert_eq!(
+            referenced_assets(FIXTURE_HTML),
+            vec![
+                "style.min.css",
+                "build_id.js",
+                "pkg/zqs_terminal.js",
+                "pkg/zqs_terminal_bg.wasm",
+            ]
+        );
+    }
+
+    #[test]
+    fn bundled_index_references_the_wasm_bootstrap() {
+        let html = std::fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../static/index.html"),
+        )
+        .expect("bundled index.html");
+        let assets = referenced_assets(&html);
+        assert!(
+            assets.contains(&"pkg/zqs_terminal.js".to_string()),
+            "{assets:?}"
+        );
+        assert!(
+            assets.contains(&"pkg/zqs_terminal_bg.wasm".to_string()),
+            "{assets:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_sizes_and_lists_missing_assets() {
+        let dir = TempStatic::new();
+        dir.write("index.html", FIXTURE_HTML);
+        dir.write("style.min.css", "body{}");
+        dir.write("build_id.js", "window.__BUILD_ID__=\"1\";");
+        dir.write("pkg/zqs_terminal.js", "export default 1;");
+
+        let report = check_assets(&dir.0).await;
+        assert!(!report.is_complete());
+        assert_eq!(report.missing, vec!["pkg/zqs_terminal_bg.wasm"]);
+        let css = report
+            .files
+            .iter()
+            .find(|status| status.path == "style.min.css")
+            .expect("css status");
+        assert!(css.present);
+        assert_eq!(css.size_bytes, Some(6));
+
+        dir.write("pkg/zqs_terminal_bg.wasm", "\0asm");
+        assert!(check_assets(&dir.0).await.is_complete());
+    }
+
+    #[tokio::test]
+    async fn a_missing_index_is_reported_missing() {
+        let dir = TempStatic::new();
+        let report = check_assets(&dir.0).await;
+        assert_eq!(report.missing, vec![INDEX_FILE]);
+        assert_eq!(report.files.len(), 1);
+    }
+}
@@ -1,8 +1,21 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::Serialize;
 use serde_json::{self, Value};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+const DATA_FILES: [&str; 7] = [
+    "profile.json",
+    "skills.json",
+    "experience.json",
+    "education.json",
+    "projects.json",
+    "testimonials.json",
+    "faq.json",
+];
+const PROMPT_EXTRAS_FILE: &str = "prompt_extras.md";
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TerminalDataPayload {
     pub profile: Value,
@@ -12,18 +25,90 @@ pub struct TerminalDataPayload {
     pub projects: Value,
     pub testimonials: Value,
     pub faqs: Value,
+    pub generated_at: String,
+    #[serde(skip)]
+    pub source_mtime: Option<String>,
+    #[serde(skip)]
+    pub file_mtimes: BTreeMap<&'static str, String>,
+    #[serde(skip)]
+    pub counts: DataCounts,
+    #[serde(skip)]
+    pub prompt_extras: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DataCounts {
+    pub experience: usize,
+    pub education: usize,
+    pub projects: usize,
+    pub publications: usize,
+    pub awards: usize,
+    pub skills: usize,
+    pub testimonials: usize,
+    pub faq: usize,
+}
+
+impl DataCounts {
+    fn from_sections(
+        skills: &Value,
+        experiences: &Value,
+        education: &Value,
+        projects: &Value,
+        testimonials: &Value,
+        faqs: &Value,
+    ) -> Self {
+        Self {
+            experience: array_len(experiences),
+            education: array_len(education),
+            projects: projects.get("projects").map(array_len).unwrap_or(0),
+            publications: projects.get("publications").map(array_len).unwrap_or(0),
+            awards: projects.get("awards").map(array_len).unwrap_or(0),
+            skills: skills
+                .as_object()
+                .map(|groups| groups.values().map(array_len).sum())
+                .unwrap_or(0),
+            testimonials: array_len(testimonials),
+            faq: array_len(faqs),
+        }
+    }
 }
 
 impl TerminalDataPayload {
     pub fn load(data_dir: &Path) -> Result<Self> {
+        let skills = load_json(data_dir, "skills.json")?;
+        let experiences = load_json(data_dir, "experience.json")?;
+        let education = load_json(data_dir, "education.json")?;
+        let projects = load_json(data_dir, "projects.json")?;
+        let testimonials = load_json(data_dir, "testimonials.json")?;
+        let faqs = load_json(data_dir, "faq.json")?;
+        let counts = DataCounts::from_sections(
+            &skills,
+            &experiences,
+            &education,
+            &projects,
+            &testimonials,
+            &faqs,
+        );
+        let modified = modified_times(data_dir);
         Ok(Self {
             profile: load_json(data_dir, "profile.json")?,
-            skills: load_json(data_dir, "skills.json")?,
-            experiences: load_json(data_dir, "experience.json")?,
-            education: load_json(data_dir, "education.json")?,
-            projects: load_json(data_dir, "projects.json")?,
-            testimonials: load_json(data_dir, "testimonials.json")?,
-            faqs: load_json(data_dir, "faq.json")?,
+            skills,
+            experiences,
+            education,
+            projects,
+            testimonials,
+            faqs,
+            generated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            source_mtime: modified
+                .values()
+                .max()
+                .map(|latest| latest.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            file_mtimes: modified
+                .into_iter()
+                .map(|(filename, time)| (filename, time.to_rfc3339_opts(SecondsFormat::Secs, true)))
+                .collect(),
+            counts,
+            prompt_extras: load_prompt_extras(data_dir)?,
         })
     }
 
@@ -40,6 +125,10 @@ impl TerminalDataPayload {
     }
 }
 
+fn array_len(value: &Value) -> usize {
+    value.as_array().map(Vec::len).unwrap_or(0)
+}
+
 fn load_json(data_dir: &Path, filename: &str) -> Result<Value> {
     let path = data_dir.join(filename);
     let content = std::fs::read_to_string(&path)
@@ -48,3 +137,25 @@ fn load_json(data_dir: &Path, filename: &str) -> Result<Value> {
         .with_context(|| format!("Failed to parse JSON from {path:?}"))?;
     Ok(value)
 }
+
+// Optional operator notes appended to the system prompt; a missing file is fine.
+fn load_prompt_extras(data_dir: &Path) -> Result<Option<String>> {
+    let path = data_dir.join(PROMPT_EXTRAS_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(Some(content.trim().to_string()).filter(|extras| !extras.is_empty())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {path:?}")),
+    }
+}
+
+fn modified_times(data_dir: &Path) -> BTreeMap<&'static str, DateTime<Utc>> {
+    DATA_FILES
+        .iter()
+        .filter_map(|filename| {
+            std::fs::metadata(data_dir.join(filename))
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(|modified| (*filename, DateTime::<Utc>::from(modified)))
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+// On-demand recaps of a résumé section. The client only names the section;
+// the text sent to the model always comes from the server-side payload.
+
+pub const SUMMARY_MAX_COMPLETION_TOKENS: usize = 160;
+pub const SUMMARY_BULLETS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummarySection {
+    Experience,
+    Projects,
+    Skills,
+    Education,
+}
+
+impl SummarySection {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "experience" => Some(Self::Experience),
+            "projects" => Some(Self::Projects),
+            "skills" => Some(Self::Skills),
+            "education" => Some(Self::Education),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Experience => "experience",
+            Self::Projects => "projects",
+            Self::Skills => "skills",
+            Self::Education => "education",
+        }
+    }
+
+    pub fn instruction(self) -> String {
+        format!(
+            "Summarize the {} section above in exactly {SUMMARY_BULLETS} short bullet points starting with \"- \". No introduction, no conclusion.",
+            self.as_str()
+        )
+    }
+}
+
+// Models like to add a lead-in sentence or a fourth bullet; keep only the
+// first three list items and normalize their markers. Answers without any
+// list are returned as-is rather than dropped.
+pub fn three_bullets(answer: &str) -> String {
+    let bullets: Vec<&str> = answer
+        .lines()
+        .filter_map(bullet_text)
+        .take(SUMMARY_BULLETS)
+        .collect();
+    if bullets.is_empty() {
+        return answer.trim().to_string();
+    }
+    bullets
+        .iter()
+        .map(|text| format!("- {text}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn bullet_text(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("• "))
+        .or_else(|| {
+            let digits = line.find(|ch: char| !ch.is_ascii_digit())?;
+            let rest = line[digits..]
+                .strip_prefix(". ")
+                .or_else(|| line[digits..].strip_prefix(") "))?;
+            (digits > 0).then_some(rest)
+        })?;
+    let rest = rest.trim();
+    (!rest.is_empty()).then_some(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_parse_case_insensitively() {
+        assert_eq!(
+            SummarySection::parse(" Experience "),
+            Some(SummarySection::Experience)
+        );
+        assert_eq!(
+            SummarySection::parse("projects"),
+            Some(SummarySection::Projects)
+        );
+        assert_eq!(SummarySection::parse("contact"), None);
+        assert!(SummarySection::Skills
+            .instruction()
+            .contains("skills section"));
+    }
+
+    #[test]
+    fn answers_are_reduced_to_three_bullets() {
+        let answer = "Here is a summary:\n* Led platform work\n2. Shipped Rust services\n- Mentored engineers\n- Extra point";
+        assert_eq!(
+            three_bullets(answer),
+            "- Led platform work\n- Shipped Rust services\n- Mentored engineers"
+        );
+    }
+
+    #[test]
+    fn answers_without_a_list_are_kept() {
+        assert_eq!(
+            three_bullets("  Just one sentence.  "),
+            "Just one sentence."
+        );
+        assert_eq!(three_bullets("2024 was busy"), "2024 was busy");
+    }
+}
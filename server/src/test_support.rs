@@ -0,0 +1,134 @@
+use crate::ai_client::{parse_extra_backends, AiClient, GoogleGenerateRequest};
+use crate::handlers::AppState;
+use crate::prompt::{ConversationTurn, KnowledgeBase};
+use crate::static_data::TerminalDataPayload;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use tokio::net::TcpListener;
+
+pub fn turn(question: &str, answer: &str) -> ConversationTurn {
+    ConversationTurn {
+        question: question.to_string(),
+        answer: answer.to_string(),
+    }
+}
+
+pub fn serialized_contents(history: &[ConversationTurn]) -> Value {
+    let request = GoogleGenerateRequest::new("system", history, "Latest question?");
+    serde_json::to_value(&request).expect("request serializes")["contents"].clone()
+}
+
+pub fn load_terminal_payload(data_dir: &Path) -> TerminalDataPayload {
+    TerminalDataPayload::load(data_dir).expect("should load knowledge base from static data")
+}
+
+pub fn bundled_static_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../static")
+}
+
+pub fn temp_log_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("zqs-{name}-{}.log", uuid::Uuid::new_v4()))
+}
+
+pub fn empty_terminal_data() -> std::sync::Arc<TerminalDataPayload> {
+    std::sync::Arc::new(TerminalDataPayload {
+        profile: json!({}),
+        skills: json!([]),
+        experiences: json!([]),
+        education: json!([]),
+        projects: json!([]),
+        testimonials: json!([]),
+        faqs: json!([]),
+        generated_at: "2024-01-01T00:00:00Z".to_string(),
+        source_mtime: None,
+        file_mtimes: Default::default(),
+        counts: Default::default(),
+        prompt_extras: None,
+    })
+}
+
+pub fn all_builtin_client() -> AiClient {
+    AiClient::new(
+        Some("google-key".to_string()),
+        Some("groq-key".to_string()),
+        Some("openai-key".to_string()),
+        Vec::new(),
+    )
+    .expect("client should construct")
+}
+
+pub fn extra_backend_keys(name: &str) -> Option<String> {
+    match name {
+        "OPENROUTER_API_KEY" => Some("or-key".to_string()),
+        "TOGETHER_API_KEY" => Some("together-key".to_string()),
+        _ => None,
+    }
+}
+
+pub async fn spawn_mock_backend(
+    status: StatusCode,
+    hits: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+) -> SocketAddr {
+    let mock = Router::new().route(
+        "/v1/chat/completions",
+        post(move || {
+            let hits = std::sync::Arc::clone(&hits);
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                (
+                    status,
+                    Json(json!({ "choices": [{ "message": { "content": "pong" } }] })),
+                )
+            }
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("mock listener should bind");
+    let addr = listener.local_addr().expect("mock address");
+    tokio::spawn(async move {
+        axum::serve(listener, mock).await.expect("mock server");
+    });
+    addr
+}
+
+pub fn mock_client(backends: &[(&str, SocketAddr, i32)]) -> AiClient {
+    let entries = backends
+        .iter()
+        .map(|(name, addr, priority)| {
+            json!({
+                "name": name,
+                "endpoint": format!("http://{addr}/v1/chat/completions"),
+                "model": format!("{name}/model"),
+                "api_key_env": "OPENROUTER_API_KEY",
+                "priority": priority,
+            })
+        })
+        .collect::<Vec<_>>();
+    let extras = parse_extra_backends(&Value::Array(entries).to_string(), extra_backend_keys)
+        .expect("mock backends parse");
+    AiClient::new(None, None, None, extras).expect("client should construct")
+}
+
+pub fn chat_completion(content: &str, finish_reason: &str) -> Value {
+    json!({ "choices": [{ "message": { "content": content }, "finish_reason": finish_reason }] })
+}
+
+pub fn test_app_state(client: AiClient) -> std::sync::Arc<AppState> {
+    std::sync::Arc::new(AppState::new(
+        KnowledgeBase {
+            system_prompt: "prompt".to_string(),
+            system_tokens: 8,
+        },
+        client,
+        empty_terminal_data(),
+        bundled_static_dir(),
+        temp_log_path("test-questions"),
+        temp_log_path("test-answers"),
+    ))
+}
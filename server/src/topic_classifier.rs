@@ -0,0 +1,318 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    Experience,
+    Skills,
+    Projects,
+    Education,
+    Availability,
+    Contact,
+    Other,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 7] = [
+        Topic::Experience,
+        Topic::Skills,
+        Topic::Projects,
+        Topic::Education,
+        Topic::Availability,
+        Topic::Contact,
+        Topic::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Topic::Experience => "experience",
+            Topic::Skills => "skills",
+            Topic::Projects => "projects",
+            Topic::Education => "education",
+            Topic::Availability => "availability",
+            Topic::Contact => "contact",
+            Topic::Other => "other",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// Patterns ending in `*` match any word starting with the stem, patterns with a
+// space match the phrase, everything else must match a whole word. Rules are
+// listed in tie-break order: availability and contact questions usually mention
+// work or projects too, so they win when scores are equal.
+const RULES: &[(Topic, &[&str])] = &[
+    (
+        Topic::Availability,
+        &[
+            "availab*",
+            "disponib*",
+            "hire",
+            "hiring",
+            "recrut*",
+            "freelance*",
+            "open to",
+            "looking for",
+            "relocat*",
+            "remote",
+            "télétravail",
+            "notice",
+            "salary",
+            "salaire",
+            "rate",
+            "tjm",
+            "contract",
+            "mission",
+            "when can",
+        ],
+    ),
+    (
+        Topic::Contact,
+        &[
+            "contact*", "email*", "mail", "reach", "linkedin", "phone", "joindre", "touch",
+            "message",
+        ],
+    ),
+    (
+        Topic::Education,
+        &[
+            "educat*",
+            "study",
+            "studied",
+            "studies",
+            "étud*",
+            "degree*",
+            "diplom*",
+            "diplôm*",
+            "school",
+            "école",
+            "ecole",
+            "universit*",
+            "formation",
+            "bachelor",
+            "graduat*",
+            "engineering school",
+        ],
+    ),
+    (
+        Topic::Projects,
+        &[
+            "project*",
+            "projet*",
+            "portfolio",
+            "side",
+            "github",
+            "open source",
+            "build*",
+            "built",
+            "port",
+            "game*",
+            "jeu*",
+            "demo",
+        ],
+    ),
+    (
+        Topic::Skills,
+        &[
+            "skill*",
+            "compétence*",
+            "competence*",
+            "language*",
+            "langage*",
+            "stack",
+            "tech*",
+            "framework*",
+            "tool*",
+            "outil*",
+            "proficien*",
+            "expert*",
+            "rust",
+            "python",
+            "kubernetes",
+            "docker",
+            "aws",
+            "devops",
+            "know",
+        ],
+    ),
+    (
+        Topic::Experience,
+        &[
+            "experien*",
+            "expérien*",
+            "job*",
+            "work*",
+            "career",
+            "carrière",
+            "role*",
+            "poste",
+            "position*",
+            "employer*",
+            "company",
+            "companies",
+            "entreprise*",
+            "parcours",
+            "playstation",
+            "sony",
+            "background",
+        ],
+    ),
+];
+
+pub fn classify(question: &str, chunk_topics: &[&str]) -> Topic {
+    let question_scores = score(question);
+    let best_score = question_scores.iter().copied().max().unwrap_or(0);
+    if best_score == 0 {
+        return Topic::Other;
+    }
+
+    // Retrieved chunks only break ties between topics the question itself
+    // points at, so a vague greeting still lands in `other`.
+    let mut chunk_scores = [0usize; RULES.len()];
+    for topic in chunk_topics {
+        for (idx, hits) in score(topic).iter().enumerate() {
+            chunk_scores[idx] += hits;
+        }
+    }
+    let mut winner: Option<usize> = None;
+    for (idx, _) in question_scores
+        .iter()
+        .enumerate()
+        .filter(|(_, score)| **score == best_score)
+    {
+        match winner {
+            Some(current) if chunk_scores[idx] <= chunk_scores[current] => {}
+            _ => winner = Some(idx),
+        }
+    }
+    winner.map_or(Topic::Other, |idx| RULES[idx].0)
+}
+
+fn score(text: &str) -> [usize; RULES.len()] {
+    let words = text
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let normalized = format!(" {} ", words.join(" "));
+
+    let mut scores = [0usize; RULES.len()];
+    for (idx, (_, patterns)) in RULES.iter().enumerate() {
+        scores[idx] = patterns
+            .iter()
+            .filter(|pattern| matches_pattern(pattern, &words, &normalized))
+            .count();
+    }
+    scores
+}
+
+fn matches_pattern(pattern: &str, words: &[String], normalized: &str) -> bool {
+    if let Some(stem) = pattern.strip_suffix('*') {
+        words.iter().any(|word| word.starts_with(stem))
+    } else if pattern.contains(' ') {
+        normalized.contains(&format!(" {pattern} "))
+    } else {
+        words.iter().any(|word| word == pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELED_QUESTIONS: &[(&str, Topic)] = &[
+        (
+            "What is Alexandre's professional experience?",
+            Topic::Experience,
+        ),
+        ("Where did he work before?", Topic::Experience),
+        ("What was his role at PlayStation?", Topic::Experience),
+        (
+            "How many years has he been working in DevOps jobs?",
+            Topic::Experience,
+        ),
+        ("Tell me about his career path", Topic::Experience),
+        (
+            "Quelle est son expérience professionnelle ?",
+            Topic::Experience,
+        ),
+        ("Which companies has he worked for?", Topic::Experience),
+        ("What programming languages does he know?", Topic::Skills),
+        ("Is he proficient in Rust?", Topic::Skills),
+        ("What is his tech stack?", Topic::Skills),
+        ("Quelles sont ses compétences ?", Topic::Skills),
+        ("Does he use Kubernetes and Docker?", Topic::Skills),
+        ("Which tools does he master for CI/CD?", Topic::Skills),
+        ("What side projects has he built?", Topic::Projects),
+        ("Tell me about the Micro Mages port", Topic::Projects),
+        ("Show me his open source work on GitHub", Topic::Projects),
+        ("Quels projets a-t-il réalisés ?", Topic::Projects),
+        ("Which games did he ship?", Topic::Projects),
+        ("Where did he study?", Topic::Education),
+        ("What degree does he have?", Topic::Education),
+        ("Which engineering school did he attend?", Topic::Education),
+        ("Quelle est sa formation ?", Topic::Education),
+        ("Is he available for a new job?", Topic::Availability),
+        ("Is Alexandre open to remote work?", Topic::Availability),
+        (
+            "Est-il disponible pour une mission freelance ?",
+            Topic::Availability,
+        ),
+        ("Would he relocate to Paris?", Topic::Availability),
+        (
+            "Are you hiring or looking for a position?",
+            Topic::Availability,
+        ),
+        ("How can I contact him?", Topic::Contact),
+        ("What is his email address?", Topic::Contact),
+        ("Comment le joindre sur LinkedIn ?", Topic::Contact),
+        ("Hello there!", Topic::Other),
+        ("What is the meaning of life?", Topic::Other),
+        ("Tell me a joke", Topic::Other),
+    ];
+
+    #[test]
+    fn labeled_sample_set_is_classified() {
+        let misses = LABELED_QUESTIONS
+            .iter()
+            .filter_map(|(question, expected)| {
+                let actual = classify(question, &[]);
+                (actual != *expected).then(|| format!("{question:?}: {actual:?} != {expected:?}"))
+            })
+            .collect::<Vec<_>>();
+        assert!(misses.is_empty(), "Misclassified questions: {misses:#?}");
+    }
+
+    #[test]
+    fn chunk_topics_break_ties_but_do_not_invent_a_topic() {
+        assert_eq!(
+            classify("What did he build at work?", &[]),
+            Topic::Projects,
+            "Rule order breaks ties without chunk context"
+        );
+        assert_eq!(
+            classify(
+                "What did he build at work?",
+                &["experience", "Sony Interactive Entertainment"]
+            ),
+            Topic::Experience
+        );
+        assert_eq!(
+            classify("Hello there!", &["projects", "Micro Mages"]),
+            Topic::Other,
+            "Chunks alone never pick a topic"
+        );
+    }
+
+    #[test]
+    fn labels_serialize_as_snake_case() {
+        for topic in Topic::ALL {
+            let json = serde_json::to_string(&topic).unwrap();
+            assert_eq!(json, format!("\"{}\"", topic.as_str()));
+            assert_eq!(Topic::ALL[topic.index()], topic);
+        }
+    }
+}
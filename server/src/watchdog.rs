@@ -0,0 +1,412 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const DEFAULT_MIN_FREE_DISK_MB: u64 = 512;
+pub const BYTES_PER_MB: u64 = 1024 * 1024;
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+const SUMMARY_LOG_INTERVAL_SECS: i64 = 60 * 60;
+
+// `None` means the value could not be read.
+pub trait ResourceProbe: Send + 'static {
+    fn dir_size(&self, path: &Path) -> Option<u64>;
+    fn file_size(&self, path: &Path) -> Option<u64>;
+    fn free_disk(&self, path: &Path) -> Option<u64>;
+    fn rss_bytes(&self) -> Option<u64>;
+}
+
+pub struct SystemProbe;
+
+impl ResourceProbe for SystemProbe {
+    fn dir_size(&self, path: &Path) -> Option<u64> {
+        dir_size(path).ok()
+    }
+
+    fn file_size(&self, path: &Path) -> Option<u64> {
+        std::fs::metadata(path)
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+    }
+
+    fn free_disk(&self, path: &Path) -> Option<u64> {
+        free_disk(path)
+    }
+
+    fn rss_bytes(&self) -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        vm_rss_bytes(&status)
+    }
+}
+
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            total += dir_size(&entry.path())?;
+        } else if kind.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(unix)]
+fn free_disk(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `stats` is only read once statvfs has succeeded and filled it.
+    let stats = unsafe {
+        if libc::statvfs(c_path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+    #[allow(clippy::useless_conversion)]
+    u64::from(stats.f_bavail).checked_mul(u64::from(stats.f_frsize))
+}
+
+#[cfg(not(unix))]
+fn free_disk(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn vm_rss_bytes(status: &str) -> Option<u64> {
+    let value = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?;
+    let kilobytes: u64 = value.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    kilobytes.checked_mul(1024)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceSnapshot {
+    pub sampled_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_dir_bytes: Option<u64>,
+    pub files: BTreeMap<&'static str, u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_disk_bytes: Option<u64>,
+    pub min_free_disk_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rss_bytes: Option<u64>,
+    pub disk_low: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ResourceMonitor {
+    latest: RwLock<Option<ResourceSnapshot>>,
+}
+
+impl ResourceMonitor {
+    pub fn latest(&self) -> Option<ResourceSnapshot> {
+        self.latest
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn disk_low(&self) -> bool {
+        self.latest
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.disk_low)
+    }
+
+    pub fn publish(&self, snapshot: ResourceSnapshot) {
+        *self
+            .latest
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(snapshot);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    pub data_dir: PathBuf,
+    pub files: Vec<(&'static str, PathBuf)>,
+    pub min_free_disk_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    Summary,
+    DiskLow,
+    DiskRecovered,
+}
+
+pub struct Watchdog<P> {
+    probe: P,
+    config: WatchdogConfig,
+    monitor: Arc<ResourceMonitor>,
+    last_summary: Option<DateTime<Utc>>,
+    disk_low: bool,
+}
+
+impl<P: ResourceProbe> Watchdog<P> {
+    pub fn new(probe: P, config: WatchdogConfig, monitor: Arc<ResourceMonitor>) -> Self {
+        Self {
+            probe,
+            config,
+            monitor,
+            last_summary: None,
+            disk_low: false,
+        }
+    }
+
+    // An unreadable free-disk figure never counts as low.
+    pub fn sample(&self, now: DateTime<Utc>) -> ResourceSnapshot {
+        let free_disk_bytes = self.probe.free_disk(&self.config.data_dir);
+        ResourceSnapshot {
+            sampled_at: now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            data_dir_bytes: self.probe.dir_size(&self.config.data_dir),
+            files: self
+                .config
+                .files
+                .iter()
+                .filter_map(|(name, path)| Some((*name, self.probe.file_size(path)?)))
+                .collect(),
+            free_disk_bytes,
+            min_free_disk_bytes: self.config.min_free_disk_bytes,
+            rss_bytes: self.probe.rss_bytes(),
+            disk_low: free_disk_bytes.is_some_and(|free| free < self.config.min_free_disk_bytes),
+        }
+    }
+
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<WatchdogEvent> {
+        let snapshot = self.sample(now);
+        let mut events = Vec::new();
+        if snapshot.disk_low != self.disk_low {
+            self.disk_low = snapshot.disk_low;
+            if snapshot.disk_low {
+                warn!(
+                    target: "watchdog",
+                    free_disk_bytes = ?snapshot.free_disk_bytes,
+                    min_free_disk_bytes = snapshot.min_free_disk_bytes,
+                    "Free disk space below threshold; health reports degraded"
+                );
+                events.push(WatchdogEvent::DiskLow);
+            } else {
+                info!(
+                    target: "watchdog",
+                    free_disk_bytes = ?snapshot.free_disk_bytes,
+                    "Free disk space back above threshold"
+                );
+                events.push(WatchdogEvent::DiskRecovered);
+            }
+        }
+        let summary_due = self.last_summary.is_none_or(|last| {
+            now.signed_duration_since(last).num_seconds() >= SUMMARY_LOG_INTERVAL_SECS
+        });
+        if summary_due {
+            self.last_summary = Some(now);
+            info!(
+                target: "watchdog",
+                data_dir_bytes = ?snapshot.data_dir_bytes,
+                files = ?snapshot.files,
+                free_disk_bytes = ?snapshot.free_disk_bytes,
+                rss_bytes = ?snapshot.rss_bytes,
+                "Resource usage"
+            );
+            events.push(WatchdogEvent::Summary);
+        }
+        self.monitor.publish(snapshot);
+        events
+    }
+}
+
+pub fn spawn<P: ResourceProbe>(mut watchdog: Watchdog<P>, every: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+            let tick = tokio::task::spawn_blocking(move || {
+                watchdog.tick(Utc::now());
+                watchdog
+            });
+            match tick.await {
+                Ok(returned) => watchdog = returned,
+                Err(err) => {
+                    warn!(target: "watchdog", error = %err, "Resource watchdog stopped");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct FakeProbe(Arc<Mutex<FakeValues>>);
+
+    #[derive(Default)]
+    struct FakeValues {
+        dir_size: Option<u64>,
+        files: BTreeMap<PathBuf, u64>,
+        free_disk: Option<u64>,
+        rss: Option<u64>,
+    }
+
+    impl FakeProbe {
+        fn set_free_disk(&self, bytes: Option<u64>) {
+            self.0.lock().unwrap().free_disk = bytes;
+        }
+    }
+
+    impl ResourceProbe for FakeProbe {
+        fn dir_size(&self, _path: &Path) -> Option<u64> {
+            self.0.lock().unwrap().dir_size
+        }
+
+        fn file_size(&self, path: &Path) -> Option<u64> {
+            self.0.lock().unwrap().files.get(path).copied()
+        }
+
+        fn free_disk(&self, _path: &Path) -> Option<u64> {
+            self.0.lock().unwrap().free_disk
+        }
+
+        fn rss_bytes(&self) -> Option<u64> {
+            self.0.lock().unwrap().rss
+        }
+    }
+
+    fn config() -> WatchdogConfig {
+        WatchdogConfig {
+            data_dir: PathBuf::from("static/data"),
+            files: vec![
+                ("qa_log", PathBuf::from("qa_log.db")),
+                ("rag_chunks", PathBuf::from("static/data/rag_chunks.db")),
+            ],
+            min_free_disk_bytes: 100 * BYTES_PER_MB,
+        }
+    }
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap() + chrono::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn snapshot_reports_probe_values_and_skips_missing_files() {
+        let probe = FakeProbe::default();
+        {
+            let mut values = probe.0.lock().unwrap();
+            values.dir_size = Some(4_096);
+            values.files.insert(PathBuf::from("qa_log.db"), 2_048);
+            values.free_disk = Some(200 * BYTES_PER_MB);
+            values.rss = Some(30 * BYTES_PER_MB);
+        }
+        let watchdog = Watchdog::new(probe, config(), Arc::default());
+        let snapshot = watchdog.sample(at(0));
+        assert_eq!(snapshot.sampled_at, "2024-05-01T08:00:00Z");
+        assert_eq!(snapshot.data_dir_bytes, Some(4_096));
+        assert_eq!(snapshot.files, BTreeMap::from([("qa_log", 2_048)]));
+        assert_eq!(snapshot.free_disk_bytes, Some(200 * BYTES_PER_MB));
+        assert_eq!(snapshot.rss_bytes, Some(30 * BYTES_PER_MB));
+        assert!(!snapshot.disk_low);
+    }
+
+    #[test]
+    fn disk_threshold_transitions_are_reported_once_each_way() {
+        let probe = FakeProbe::default();
+        let monitor = Arc::new(ResourceMonitor::default());
+        let mut watchdog = Watchdog::new(probe.clone(), config(), Arc::clone(&monitor));
+        assert!(monitor.latest().is_none());
+        assert!(!monitor.disk_low());
+
+        probe.set_free_disk(Some(500 * BYTES_PER_MB));
+        assert!(!watchdog.tick(at(0)).contains(&WatchdogEvent::DiskLow));
+        assert!(!monitor.disk_low());
+
+        probe.set_free_disk(Some(99 * BYTES_PER_MB));
+        assert_eq!(watchdog.tick(at(1)), vec![WatchdogEvent::DiskLow]);
+        assert!(monitor.disk_low());
+        assert_eq!(watchdog.tick(at(2)), Vec::new());
+        assert!(monitor.disk_low());
+
+        probe.set_free_disk(Some(100 * BYTES_PER_MB));
+        assert_eq!(watchdog.tick(at(3)), vec![WatchdogEvent::DiskRecovered]);
+        assert!(!monitor.disk_low());
+    }
+
+    #[test]
+    fn unreadable_free_disk_never_counts_as_low() {
+        let probe = FakeProbe::default();
+        let monitor = Arc::new(ResourceMonitor::default());
+        let mut watchdog = Watchdog::new(probe.clone(), config(), Arc::clone(&monitor));
+        probe.set_free_disk(Some(0));
+        watchdog.tick(at(0));
+        assert!(monitor.disk_low());
+
+        probe.set_free_disk(None);
+        assert_eq!(watchdog.tick(at(1)), vec![WatchdogEvent::DiskRecovered]);
+        assert!(!monitor.disk_low());
+        assert_eq!(monitor.latest().unwrap().free_disk_bytes, None);
+    }
+
+    #[test]
+    fn summary_is_logged_at_most_once_an_hour() {
+        let mut watchdog = Watchdog::new(FakeProbe::default(), config(), Arc::default());
+        let summaries = (0..=150)
+            .filter(|minute| watchdog.tick(at(*minute)).contains(&WatchdogEvent::Summary))
+            .collect::<Vec<_>>();
+        assert_eq!(summaries, vec![0, 60, 120]);
+    }
+
+    #[test]
+    fn every_tick_publishes_the_latest_sample() {
+        let probe = FakeProbe::default();
+        let monitor = Arc::new(ResourceMonitor::default());
+        let mut watchdog = Watchdog::new(probe.clone(), config(), Arc::clone(&monitor));
+        watchdog.tick(at(0));
+        probe.0.lock().unwrap().rss = Some(64);
+        watchdog.tick(at(1));
+        let latest = monitor.latest().expect("published snapshot");
+        assert_eq!(latest.sampled_at, "2024-05-01T08:01:00Z");
+        assert_eq!(latest.rss_bytes, Some(64));
+    }
+
+    #[test]
+    fn vm_rss_line_is_parsed() {
+        let status = "Name:\tzqs_terminal_server\nVmPeak:\t  90000 kB\nVmRSS:\t   1234 kB\n";
+        assert_eq!(vm_rss_bytes(status), Some(1234 * 1024));
+        assert_eq!(vm_rss_bytes("Name:\tzqs\n"), None);
+        assert_eq!(vm_rss_bytes("VmRSS:\t12 MB\n"), None);
+    }
+
+    #[test]
+    fn system_probe_sums_nested_files() {
+        let dir = std::env::temp_dir().join(format!("watchdog-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("nested")).expect("fixture dir");
+        std::fs::write(dir.join("a.json"), [0u8; 10]).expect("file");
+        std::fs::write(dir.join("nested/b.db"), [0u8; 32]).expect("file");
+
+        let probe = SystemProbe;
+        assert_eq!(probe.dir_size(&dir), Some(42));
+        assert_eq!(probe.file_size(&dir.join("nested/b.db")), Some(32));
+        assert_eq!(probe.file_size(&dir.join("missing.db")), None);
+        assert_eq!(probe.file_size(&dir), None);
+        assert_eq!(probe.dir_size(&dir.join("missing")), None);
+        #[cfg(unix)]
+        assert!(probe.free_disk(&dir).is_some());
+        #[cfg(target_os = "linux")]
+        assert!(probe.rss_bytes().is_some_and(|bytes| bytes > 0));
+
+        std::fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
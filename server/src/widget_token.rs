@@ -0,0 +1,233 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Tokens look like `<expires_unix>.<hex hmac>`. The signature covers the
+// allowed origin and the expiry, so a token only works from the page it was
+// minted for and the browser-supplied `Origin` header is part of the check.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WidgetTokenError {
+    #[error("widget token is malformed")]
+    Malformed,
+    #[error("widget request has no Origin header")]
+    MissingOrigin,
+    #[error("widget token expired")]
+    Expired,
+    #[error("widget token signature does not match")]
+    BadSignature,
+}
+
+// Embed tokens are minted on demand for allowlisted pages and live minutes,
+// not months. Their signatures carry a scope prefix so neither kind of token
+// can stand in for the other.
+pub const EMBED_TOKEN_TTL_SECS: i64 = 600;
+const WIDGET_SCOPE: &str = "";
+const EMBED_SCOPE: &str = "embed|";
+
+pub fn sign(secret: &str, origin: &str, expires_at: i64) -> String {
+    sign_scoped(secret, WIDGET_SCOPE, origin, expires_at)
+}
+
+pub fn verify(
+    secret: &str,
+    origin: Option<&str>,
+    token: &str,
+    now: i64,
+) -> Result<(), WidgetTokenError> {
+    verify_scoped(secret, WIDGET_SCOPE, origin, token, now)
+}
+
+pub fn sign_embed(secret: &str, origin: &str, expires_at: i64) -> String {
+    sign_scoped(secret, EMBED_SCOPE, origin, expires_at)
+}
+
+pub fn verify_embed(
+    secret: &str,
+    origin: Option<&str>,
+    token: &str,
+    now: i64,
+) -> Result<(), WidgetTokenError> {
+    verify_scoped(secret, EMBED_SCOPE, origin, token, now)
+}
+
+// The `Origin` header when the browser sent one, else the origin part of the
+// `Referer` URL.
+pub fn request_origin(origin: Option<&str>, referer: Option<&str>) -> Option<String> {
+    let origin = origin
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && *value != "null")
+        .map(|value| value.trim_end_matches('/').to_string());
+    origin.or_else(|| {
+        let referer = referer?.trim();
+        let (scheme, rest) = referer.split_once("://")?;
+        let host = rest.split(['/', '?', '#']).next()?;
+        (matches!(scheme, "http" | "https") && !host.is_empty())
+            .then(|| format!("{scheme}://{host}"))
+    })
+}
+
+pub fn origin_allowed(allowlist: &[String], origin: &str) -> bool {
+    let origin = origin.trim_end_matches('/');
+    allowlist
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+}
+
+fn sign_scoped(secret: &str, scope: &str, origin: &str, expires_at: i64) -> String {
+    let signature = mac(secret, scope, origin, expires_at)
+        .finalize()
+        .into_bytes();
+    format!("{expires_at}.{}", hex::encode(signature))
+}
+
+fn verify_scoped(
+    secret: &str,
+    scope: &str,
+    origin: Option<&str>,
+    token: &str,
+    now: i64,
+) -> Result<(), WidgetTokenError> {
+    let (expires_at, signature) = token
+        .trim()
+        .split_once('.')
+        .ok_or(WidgetTokenError::Malformed)?;
+    let expires_at = expires_at
+        .parse::<i64>()
+        .map_err(|_| WidgetTokenError::Malformed)?;
+    let signature = hex::decode(signature).map_err(|_| WidgetTokenError::Malformed)?;
+    let origin = origin
+        .map(|value| value.trim().trim_end_matches('/'))
+        .filter(|value| !value.is_empty())
+        .ok_or(WidgetTokenError::MissingOrigin)?;
+    if expires_at <= now {
+        return Err(WidgetTokenError::Expired);
+    }
+    mac(secret, scope, origin, expires_at)
+        .verify_slice(&signature)
+        .map_err(|_| WidgetTokenError::BadSignature)
+}
+
+fn mac(secret: &str, scope: &str, origin: &str, expires_at: i64) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(scope.as_bytes());
+    mac.update(origin.trim_end_matches('/').as_bytes());
+    mac.update(b"|");
+    mac.update(expires_at.to_string().as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "widget-secret";
+    const ORIGIN: &str = "https://blog.zqsdev.com";
+    const NOW: i64 = 1_750_000_000;
+
+    #[test]
+    fn signed_tokens_verify_for_their_origin_until_expiry() {
+        let token = sign(SECRET, ORIGIN, NOW + 3600);
+        assert_eq!(verify(SECRET, Some(ORIGIN), &token, NOW), Ok(()));
+        assert_eq!(
+            verify(SECRET, Some("https://blog.zqsdev.com/"), &token, NOW),
+            Ok(()),
+            "A trailing slash on the origin is ignored"
+        );
+        assert_eq!(
+            verify(SECRET, Some(ORIGIN), &token, NOW + 3600),
+            Err(WidgetTokenError::Expired)
+        );
+    }
+
+    #[test]
+    fn forged_or_misused_tokens_are_rejected() {
+        let token = sign(SECRET, ORIGIN, NOW + 3600);
+        assert_eq!(
+            verify(SECRET, Some("https://evil.example"), &token, NOW),
+            Err(WidgetTokenError::BadSignature)
+        );
+        assert_eq!(
+            verify("other-secret", Some(ORIGIN), &token, NOW),
+            Err(WidgetTokenError::BadSignature)
+        );
+        let extended = token.replacen(&(NOW + 3600).to_string(), &(NOW + 7200).to_string(), 1);
+        assert_eq!(
+            verify(SECRET, Some(ORIGIN), &extended, NOW),
+            Err(WidgetTokenError::BadSignature)
+        );
+        assert_eq!(
+            verify(SECRET, None, &token, NOW),
+            Err(WidgetTokenError::MissingOrigin)
+        );
+        for malformed in ["", "abc", "123.zz", "soon.00ff"] {
+            assert_eq!(
+                verify(SECRET, Some(ORIGIN), malformed, NOW),
+                Err(WidgetTokenError::Malformed),
+                "{malformed:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn embed_tokens_are_bound_to_origin_expiry_and_scope() {
+        let token = sign_embed(SECRET, ORIGIN, NOW + EMBED_TOKEN_TTL_SECS);
+        assert_eq!(verify_embed(SECRET, Some(ORIGIN), &token, NOW), Ok(()));
+        assert_eq!(
+            verify_embed(SECRET, Some(ORIGIN), &token, NOW + EMBED_TOKEN_TTL_SECS),
+            Err(WidgetTokenError::Expired)
+        );
+        assert_eq!(
+            verify_embed(SECRET, Some("https://partner.example"), &token, NOW),
+            Err(WidgetTokenError::BadSignature)
+        );
+        assert_eq!(
+            verify(SECRET, Some(ORIGIN), &token, NOW),
+            Err(WidgetTokenError::BadSignature),
+            "An embed token must not pass as a long-lived widget token"
+        );
+        let widget = sign(SECRET, ORIGIN, NOW + 3600);
+        assert_eq!(
+            verify_embed(SECRET, Some(ORIGIN), &widget, NOW),
+            Err(WidgetTokenError::BadSignature)
+        );
+        assert_eq!(
+            verify_embed(SECRET, None, &token, NOW),
+            Err(WidgetTokenError::MissingOrigin)
+        );
+    }
+
+    #[test]
+    fn request_origin_prefers_the_origin_header_over_the_referer() {
+        assert_eq!(
+            request_origin(
+                Some("https://partner.example/"),
+                Some("https://other.example/page")
+            ),
+            Some("https://partner.example".to_string())
+        );
+        assert_eq!(
+            request_origin(None, Some("https://partner.example:8443/cv/?tab=ai#top")),
+            Some("https://partner.example:8443".to_string())
+        );
+        assert_eq!(
+            request_origin(Some("null"), Some("http://localhost:8080/")),
+            Some("http://localhost:8080".to_string()),
+            "Opaque origins fall back to the referer"
+        );
+        assert_eq!(request_origin(None, Some("file:///tmp/page.html")), None);
+        assert_eq!(request_origin(None, Some("not a url")), None);
+        assert_eq!(request_origin(None, None), None);
+    }
+
+    #[test]
+    fn origin_allowlist_ignores_case_and_trailing_slashes() {
+        let allowlist = vec!["https://partner.example/".to_string()];
+        assert!(origin_allowed(&allowlist, "https://partner.example"));
+        assert!(origin_allowed(&allowlist, "https://PARTNER.example/"));
+        assert!(!origin_allowed(&allowlist, "https://partner.example.evil"));
+        assert!(!origin_allowed(&[], "https://partner.example"));
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Datelike, FixedOffset, Timelike, Utc, Weekday};
+use serde::Serialize;
+use thiserror::Error;
+
+pub const DEFAULT_WINDOW: &str = "09:00-18:00";
+pub const DEFAULT_UTC_OFFSET: &str = "+01:00";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WorkingHoursError {
+    #[error("working hours must look like 09:00-18:00, got {0:?}")]
+    BadWindow(String),
+    #[error("UTC offset must look like +01:00, got {0:?}")]
+    BadOffset(String),
+}
+
+// The owner's stated availability: a daily window in their own timezone,
+// Monday to Friday. Windows may wrap past midnight (e.g. 22:00-02:00).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingHours {
+    start_minute: u32,
+    end_minute: u32,
+    offset: FixedOffset,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TimePayload {
+    pub now: String,
+    pub local_time: String,
+    pub utc_offset: String,
+    pub working_hours: String,
+    pub within_working_hours: bool,
+}
+
+impl WorkingHours {
+    pub fn parse(window: &str, utc_offset: &str) -> Result<Self, WorkingHoursError> {
+        let bad_window = || WorkingHoursError::BadWindow(window.to_string());
+        let (start, end) = window.trim().split_once('-').ok_or_else(bad_window)?;
+        let start_minute = parse_clock(start).ok_or_else(bad_window)?;
+        let end_minute = parse_clock(end).ok_or_else(bad_window)?;
+        if start_minute == end_minute {
+            return Err(bad_window());
+        }
+        let offset = parse_offset(utc_offset)
+            .ok_or_else(|| WorkingHoursError::BadOffset(utc_offset.to_string()))?;
+        Ok(Self {
+            start_minute,
+            end_minute,
+            offset,
+        })
+    }
+
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.offset);
+        if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        let minute = local.hour() * 60 + local.minute();
+        if self.start_minute < self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute)
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> TimePayload {
+        let local = now.with_timezone(&self.offset);
+        TimePayload {
+            now: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            local_time: local.format("%H:%M").to_string(),
+            utc_offset: self.offset.to_string(),
+            working_hours: format!(
+                "{}-{}",
+                format_clock(self.start_minute),
+                format_clock(self.end_minute)
+            ),
+            within_working_hours: self.contains(now),
+        }
+    }
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self::parse(DEFAULT_WINDOW, DEFAULT_UTC_OFFSET).expect("default working hours are valid")
+    }
+}
+
+fn parse_clock(raw: &str) -> Option<u32> {
+    let (hours, minutes) = raw.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+fn format_clock(minute: u32) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+fn parse_offset(raw: &str) -> Option<FixedOffset> {
+    let raw = raw.trim();
+    let (sign, rest) = match raw.chars().next()? {
+        '+' => (1, &raw[1..]),
+        '-' => (-1, &raw[1..]),
+        _ => return None,
+    };
+    let minutes = parse_clock(rest)?;
+    FixedOffset::east_opt(sign * minutes as i32 * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn weekday_times_are_checked_in_the_owner_timezone() {
+        let hours = WorkingHours::parse("09:00-18:00", "+01:00").expect("valid config");
+        // Wednesday 08:30 UTC is 09:30 in UTC+1.
+        assert!(hours.contains(at("2026-10-14T08:30:00Z")));
+        assert!(!hours.contains(at("2026-10-14T07:59:00Z")));
+        assert!(
+            !hours.contains(at("2026-10-14T17:00:00Z")),
+            "End is exclusive"
+        );
+        assert!(
+            !hours.contains(at("2026-10-17T10:00:00Z")),
+            "Saturdays are off"
+        );
+    }
+
+    #[test]
+    fn windows_can_wrap_past_midnight() {
+        let hours = WorkingHours::parse("22:00-02:00", "-05:00").expect("valid config");
+        assert!(hours.contains(at("2026-10-15T03:30:00Z")));
+        assert!(hours.contains(at("2026-10-15T06:59:00Z")));
+        assert!(!hours.contains(at("2026-10-15T12:00:00Z")));
+    }
+
+    #[test]
+    fn status_reports_the_local_clock_and_window() {
+        let hours = WorkingHours::default();
+        let status = hours.status(at("2026-10-14T12:05:00Z"));
+        assert_eq!(status.local_time, "13:05");
+        assert_eq!(status.utc_offset, "+01:00");
+        assert_eq!(status.working_hours, "09:00-18:00");
+        assert!(status.within_working_hours);
+    }
+
+    #[test]
+    fn malformed_config_is_rejected() {
+        assert!(matches!(
+            WorkingHours::parse("9-18", "+01:00"),
+            Err(WorkingHoursError::BadWindow(_))
+        ));
+        assert!(matches!(
+            WorkingHours::parse("09:00-09:00", "+01:00"),
+            Err(WorkingHoursError::BadWindow(_))
+        ));
+        assert!(matches!(
+            WorkingHours::parse("09:00-18:00", "CET"),
+            Err(WorkingHoursError::BadOffset(_))
+        ));
+    }
+}
@@ -1,17 +1,65 @@
+use crate::embed;
 use crate::utils;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Request, RequestInit, RequestMode, Response};
+use zqs_model::ReasonCode;
 
 const AI_API_ENDPOINT: &str = "/api/ai";
+const AI_SUMMARY_ENDPOINT: &str = "/api/ai/summarize";
+const SUMMARIZABLE_SECTIONS: [&str; 4] = ["experience", "projects", "skills", "education"];
+const SUMMARY_MIN_LINES: usize = 12;
 
 #[derive(Debug, Deserialize)]
 pub struct AiServerResponse {
     pub answer: String,
     pub ai_enabled: bool,
-    pub reason: Option<String>,
+    pub reason: Option<ReasonCode>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub estimate: Option<AiCostEstimate>,
+    #[serde(default)]
+    pub failures: Vec<AiBackendFailure>,
+    #[serde(default)]
+    pub topic: Option<String>,
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default)]
+    pub backend: Option<String>,
+    #[serde(default)]
+    pub preference_overridden: bool,
+    #[serde(default)]
+    pub backend_order: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiBackendFailure {
+    pub backend: String,
+    pub kind: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiCostEstimate {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_eur: f64,
+    pub allowed: bool,
+    #[serde(default)]
+    pub limit_reason: Option<ReasonCode>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiSummaryResponse {
+    pub summary: String,
+    pub ai_enabled: bool,
+    #[serde(default)]
+    pub reason: Option<ReasonCode>,
     #[serde(default)]
     pub model: Option<String>,
 }
@@ -19,28 +67,68 @@ pub struct AiServerResponse {
 #[derive(Serialize)]
 struct AiClientRequest<'a> {
     question: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferred_model: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+}
+
+pub async fn ask_ai(
+    question: &str,
+    preferred_model: Option<&str>,
+) -> Result<AiServerResponse, String> {
+    send_ai_request(question, preferred_model, false).await
 }
 
-pub async fn ask_ai(question: &str) -> Result<AiServerResponse, String> {
+pub async fn estimate_ai(
+    question: &str,
+    preferred_model: Option<&str>,
+) -> Result<AiServerResponse, String> {
+    send_ai_request(question, preferred_model, true).await
+}
+
+async fn send_ai_request(
+    question: &str,
+    preferred_model: Option<&str>,
+    dry_run: bool,
+) -> Result<AiServerResponse, String> {
     if question.trim().is_empty() {
         return Err("Please type a question before hitting enter.".to_string());
     }
 
+    let body = build_request_body(question, preferred_model, dry_run)?;
+    let embed = embed::ai_request_credentials().await;
+    post_json(AI_API_ENDPOINT, &body, embed.as_ref()).await
+}
+
+// Only the section name travels; the server builds the context from its own
+// copy of the résumé data.
+pub async fn summarize_section(section: &str) -> Result<AiSummaryResponse, String> {
+    let embed = embed::ai_request_credentials().await;
+    post_json(&summary_endpoint(section), "{}", embed.as_ref()).await
+}
+
+async fn post_json<T: DeserializeOwned>(
+    endpoint: &str,
+    body: &str,
+    embed: Option<&embed::EmbedCredentials>,
+) -> Result<T, String> {
     let window = utils::window().ok_or_else(|| "Window unavailable.".to_string())?;
 
-    let body = build_request_body(question)?;
     let opts = RequestInit::new();
     opts.set_method("POST");
     opts.set_mode(RequestMode::SameOrigin);
-    let body_js = JsValue::from_str(&body);
+    let body_js = JsValue::from_str(body);
     opts.set_body(&body_js);
 
-    let request = Request::new_with_str_and_init(AI_API_ENDPOINT, &opts)
+    let request = Request::new_with_str_and_init(endpoint, &opts)
         .map_err(|err| format_js_error("Failed to create AI request", err))?;
     request
         .headers()
         .set("Content-Type", "application/json")
         .map_err(|err| format_js_error("Failed to set request header", err))?;
+    embed::apply_headers(&request.headers(), embed)
+        .map_err(|err| format_js_error("Failed to set embed headers", err))?;
 
     let response_value = JsFuture::from(window.fetch_with_request(&request))
         .await
@@ -55,10 +143,9 @@ pub async fn ask_ai(question: &str) -> Result<AiServerResponse, String> {
         .map_err(|err| format_js_error("Failed to read AI response body", err))?;
     match JsFuture::from(json_future).await {
         Ok(value) => {
-            let parsed: AiServerResponse =
-                serde_wasm_bindgen::from_value(value).map_err(|err| {
-                    format!("AI response deserialisation error (status {status}): {err}")
-                })?;
+            let parsed: T = serde_wasm_bindgen::from_value(value).map_err(|err| {
+                format!("AI response deserialisation error (status {status}): {err}")
+            })?;
             Ok(parsed)
         }
         Err(err) => {
@@ -78,9 +165,82 @@ pub async fn ask_ai(question: &str) -> Result<AiServerResponse, String> {
     }
 }
 
-fn build_request_body(question: &str) -> Result<String, String> {
-    to_string(&AiClientRequest { question })
-        .map_err(|err| format!("Failed to encode AI request: {err}"))
+pub fn summary_endpoint(section: &str) -> String {
+    format!("{AI_SUMMARY_ENDPOINT}?section={section}")
+}
+
+// Long outputs of the sections the server can summarize get the chip.
+pub fn summarizable_section(command: &str, output: &str) -> Option<&'static str> {
+    let command = command.trim().to_ascii_lowercase();
+    let section = SUMMARIZABLE_SECTIONS
+        .iter()
+        .copied()
+        .find(|section| *section == command)?;
+    (output.lines().count() >= SUMMARY_MIN_LINES).then_some(section)
+}
+
+pub fn failure_summary(failures: &[AiBackendFailure]) -> Option<String> {
+    if failures.is_empty() {
+        return None;
+    }
+    let summary = failures
+        .iter()
+        .map(|failure| match failure.detail.as_deref() {
+            Some(detail) => format!("{}: {} ({detail})", failure.backend, failure.kind),
+            None => format!("{}: {}", failure.backend, failure.kind),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+    Some(summary)
+}
+
+pub fn topic_badge(response: &AiServerResponse) -> Option<String> {
+    if response.reason.is_some() {
+        return None;
+    }
+    match response.topic.as_deref() {
+        None | Some("other") => None,
+        Some(topic) => Some(format!("🏷️ Topic: {topic}")),
+    }
+}
+
+pub fn answered_from_faq(response: &AiServerResponse) -> bool {
+    response.reason == Some(ReasonCode::FaqMatch)
+}
+
+pub fn faq_badge(response: &AiServerResponse) -> Option<&'static str> {
+    answered_from_faq(response).then_some("📌 Answered straight from the FAQ (no AI cost).")
+}
+
+pub fn truncation_notice(response: &AiServerResponse) -> Option<&'static str> {
+    response
+        .truncated
+        .then_some("…answer truncated, ask to continue.")
+}
+
+// The server keeps paid backends behind the free ones unless it is told
+// otherwise, so a paid `model` choice is only a fallback.
+pub fn preference_notice(response: &AiServerResponse, preferred: Option<&str>) -> Option<String> {
+    if !response.preference_overridden {
+        return None;
+    }
+    let preferred = preferred.unwrap_or("your preferred backend");
+    Some(format!(
+        "💸 {preferred} is a paid backend, so free backends answer first; it is only used if they fail."
+    ))
+}
+
+fn build_request_body(
+    question: &str,
+    preferred_model: Option<&str>,
+    dry_run: bool,
+) -> Result<String, String> {
+    to_string(&AiClientRequest {
+        question,
+        preferred_model,
+        dry_run,
+    })
+    .map_err(|err| format!("Failed to encode AI request: {err}"))
 }
 
 fn format_js_error(context: &str, err: JsValue) -> String {
@@ -97,7 +257,7 @@ mod tests {
 
     #[test]
     fn build_request_body_includes_question() {
-        let payload = build_request_body("Who is Alex?").expect("payload");
+        let payload = build_request_body("Who is Alex?", None, false).expect("payload");
         assert!(
             payload.contains("Who is Alex?"),
             "Request payload should embed the original question: {payload}"
@@ -106,5 +266,190 @@ mod tests {
             payload.starts_with('{') && payload.ends_with('}'),
             "Payload should be JSON: {payload}"
         );
+        assert!(
+            !payload.contains("preferred_model"),
+            "Payload should omit the preferred model when none is selected: {payload}"
+        );
+        assert!(
+            !payload.contains("dry_run"),
+            "Regular questions should not be flagged as dry runs: {payload}"
+        );
+    }
+
+    #[test]
+    fn build_request_body_includes_preferred_model() {
+        let payload = build_request_body("Who is Alex?", Some("groq"), false).expect("payload");
+        assert!(
+            payload.contains("\"preferred_model\":\"groq\""),
+            "Request payload should forward the preferred model: {payload}"
+        );
+    }
+
+    #[test]
+    fn build_request_body_flags_dry_runs() {
+        let payload = build_request_body("Who is Alex?", None, true).expect("payload");
+        assert!(
+            payload.contains("\"dry_run\":true"),
+            "Estimate requests should ask the server for a dry run: {payload}"
+        );
+    }
+
+    #[test]
+    fn server_response_parses_cost_estimate() {
+        let response: AiServerResponse = serde_json::from_str(
+            r#"{"answer":"Estimated","ai_enabled":true,"reason":"dry_run","model":"gpt-4o-mini",
+                "estimate":{"input_tokens":1200,"output_tokens":384,"cost_eur":0.0015,"allowed":false,"limit_reason":"minute_budget"}}"#,
+        )
+        .expect("response should parse");
+        let estimate = response.estimate.expect("estimate should be present");
+        assert_eq!(estimate.input_tokens, 1200);
+        assert!(!estimate.allowed);
+        assert_eq!(estimate.limit_reason, Some(ReasonCode::MinuteBudget));
+        assert!(response.failures.is_empty());
+    }
+
+    #[test]
+    fn server_response_parses_backend_failures() {
+        let response: AiServerResponse = serde_json::from_str(
+            r#"{"answer":"Unavailable","ai_enabled":true,"reason":"backend_error","model":null,
+                "failures":[{"backend":"Groq","kind":"quota","status":429},
+                            {"backend":"Google","kind":"timeout","status":null},
+                            {"backend":"OpenAI","kind":"auth","status":401,"detail":"api failure"}]}"#,
+        )
+        .expect("response should parse");
+        assert_eq!(response.failures.len(), 3);
+        assert_eq!(response.failures[0].kind, "quota");
+        assert_eq!(
+            failure_summary(&response.failures[..2]).as_deref(),
+            Some("Groq: quota · Google: timeout")
+        );
+        assert_eq!(
+            failure_summary(&response.failures[2..]).as_deref(),
+            Some("OpenAI: auth (api failure)"),
+            "Raw detail is only present when the server runs in debug mode"
+        );
+        assert_eq!(failure_summary(&[]), None);
+    }
+
+    #[test]
+    fn served_answers_expose_a_topic_badge() {
+        let parse = |raw: &str| -> AiServerResponse {
+            serde_json::from_str(raw).expect("response should parse")
+        };
+        let served = parse(
+            r#"{"answer":"Yes","ai_enabled":true,"reason":null,"model":"gpt-4o-mini","topic":"availability"}"#,
+        );
+        assert_eq!(
+            topic_badge(&served).as_deref(),
+            Some("🏷️ Topic: availability")
+        );
+        let other = parse(r#"{"answer":"Hi","ai_enabled":true,"reason":null,"topic":"other"}"#);
+        assert_eq!(
+            topic_badge(&other),
+            None,
+            "The catch-all label is not badged"
+        );
+        let failed = parse(
+            r#"{"answer":"Unavailable","ai_enabled":true,"reason":"backend_error","topic":"skills"}"#,
+        );
+        assert_eq!(topic_badge(&failed), None);
+        let legacy = parse(r#"{"answer":"Yes","ai_enabled":true,"reason":null}"#);
+        assert_eq!(topic_badge(&legacy), None);
+    }
+
+    #[test]
+    fn faq_matches_are_badged_instead_of_topics() {
+        let faq: AiServerResponse = serde_json::from_str(
+            r#"{"answer":"Yes","ai_enabled":true,"reason":"faq_match","model":"faq-direct","topic":"availability"}"#,
+        )
+        .expect("response should parse");
+        assert!(answered_from_faq(&faq));
+        assert_eq!(
+            faq_badge(&faq),
+            Some("📌 Answered straight from the FAQ (no AI cost).")
+        );
+        assert_eq!(topic_badge(&faq), None);
+        let served: AiServerResponse = serde_json::from_str(
+            r#"{"answer":"Yes","ai_enabled":true,"reason":null,"model":"gpt-4o-mini"}"#,
+        )
+        .expect("response should parse");
+        assert_eq!(faq_badge(&served), None);
+    }
+
+    #[test]
+    fn truncated_answers_carry_a_continue_hint() {
+        let parse = |raw: &str| -> AiServerResponse {
+            serde_json::from_str(raw).expect("response should parse")
+        };
+        let cut = parse(
+            r#"{"answer":"He builds Rust","ai_enabled":true,"reason":null,"truncated":true}"#,
+        );
+        assert_eq!(
+            truncation_notice(&cut),
+            Some("…answer truncated, ask to continue.")
+        );
+        let complete = parse(r#"{"answer":"Yes","ai_enabled":true,"reason":null}"#);
+        assert!(!complete.truncated);
+        assert_eq!(truncation_notice(&complete), None);
+    }
+
+    #[test]
+    fn held_back_paid_preferences_are_explained() {
+        let parse = |raw: &str| -> AiServerResponse {
+            serde_json::from_str(raw).expect("response should parse")
+        };
+        let overridden = parse(
+            r#"{"answer":"Yes","ai_enabled":true,"reason":null,"backend":"groq","preference_overridden":true}"#,
+        );
+        let notice = preference_notice(&overridden, Some("openai")).expect("notice");
+        assert!(notice.contains("openai is a paid backend"), "{notice}");
+        let honoured =
+            parse(r#"{"answer":"Yes","ai_enabled":true,"reason":null,"backend":"openai"}"#);
+        assert_eq!(preference_notice(&honoured, Some("openai")), None);
+    }
+
+    #[test]
+    fn only_long_known_sections_offer_a_summary() {
+        let long = "line\n".repeat(SUMMARY_MIN_LINES);
+        assert_eq!(
+            summarizable_section("Experience", &long),
+            Some("experience")
+        );
+        assert_eq!(summarizable_section("projects", &long), Some("projects"));
+        assert_eq!(summarizable_section("experience", "one\ntwo"), None);
+        assert_eq!(summarizable_section("contact", &long), None);
+        assert_eq!(
+            summary_endpoint("experience"),
+            "/api/ai/summarize?section=experience"
+        );
+    }
+
+    #[test]
+    fn summary_response_parses_served_and_limited_payloads() {
+        let served: AiSummaryResponse = serde_json::from_str(
+            r#"{"section":"experience","summary":"- One\n- Two\n- Three","ai_enabled":true,"reason":null,"model":"gpt-4o-mini"}"#,
+        )
+        .expect("response should parse");
+        assert_eq!(served.model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(served.summary.lines().count(), 3);
+        assert!(served.ai_enabled);
+
+        let limited: AiSummaryResponse = serde_json::from_str(
+            r#"{"section":"experience","summary":"AI usage limit reached (per-minute budget).","ai_enabled":false,"reason":"minute_budget","model":null}"#,
+        )
+        .expect("response should parse");
+        assert!(!limited.ai_enabled);
+        assert_eq!(limited.reason, Some(ReasonCode::MinuteBudget));
+    }
+
+    #[test]
+    fn reasons_from_a_newer_server_still_parse() {
+        let response: AiServerResponse = serde_json::from_str(
+            r#"{"answer":"Served from cache","ai_enabled":true,"reason":"cached","model":null}"#,
+        )
+        .expect("response should parse");
+        assert_eq!(response.reason, Some(ReasonCode::Unknown));
+        assert!(!answered_from_faq(&response));
+        assert_eq!(topic_badge(&response), None);
     }
 }
@@ -3,3 +3,7 @@ pub const FRONTEND_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub fn frontend_commit() -> &'static str {
     option_env!("GIT_COMMIT_HASH").unwrap_or("unknown")
 }
+
+pub fn frontend_built_at() -> Option<&'static str> {
+    option_env!("BUILD_TIMESTAMP")
+}
@@ -1,9 +1,15 @@
 use crate::build_info;
+#[cfg(debug_assertions)]
+use crate::debug_tools::{self, DebugAction};
+use crate::format::{self, Locale};
+use crate::keyword_icons::{self, Segment};
+use crate::schedule;
 use crate::state::{
-    AppState, Award, Education, Experience, Profile, ProjectsCollection, TerminalData,
+    AppState, Award, DataSaverSetting, Education, Experience, OutputWidth, OwnerAvailability,
+    Profile, ProjectsCollection, TerminalData, OUTPUT_WIDTH_COLUMNS, OUTPUT_WIDTH_PIXELS,
 };
 use crate::utils;
-use js_sys::Math;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
 pub struct CommandDefinition {
@@ -14,6 +20,13 @@ pub struct CommandDefinition {
 
 const AI_MODEL_NAME: &str = "llama-3.1-8b-instant";
 const REPO_URL: &str = "https://github.com/Aleqsd/zqsdev.com";
+const AI_ESTIMATE_FLAG: &str = "--estimate";
+const AI_HISTORY_FLAG: &str = "--history";
+const CLEAR_USAGE: &str = "Usage: clear [ai|effects|all]";
+const AI_USAGE: &str = "Usage: ai [--estimate <question> | --history]";
+const AI_HISTORY_ANSWER_PREVIEW_CHARS: usize = 160;
+const SLEEP_STANDALONE_HINT: &str =
+    "💤 sleep pauses between chained commands — try `sleep 2 && about`.";
 
 pub const COMMAND_DEFINITIONS: &[CommandDefinition] = &[
     CommandDefinition {
@@ -28,7 +41,7 @@ pub const COMMAND_DEFINITIONS: &[CommandDefinition] = &[
     },
     CommandDefinition {
         name: "skills",
-        description: "Show skills grouped by category.",
+        description: "Show skills grouped by category (--cloud).",
         icon: "🛠️",
     },
     CommandDefinition {
@@ -38,12 +51,12 @@ pub const COMMAND_DEFINITIONS: &[CommandDefinition] = &[
     },
     CommandDefinition {
         name: "education",
-        description: "Show education background.",
+        description: "Show education background (--school <name>, --detail, --verbose).",
         icon: "🎓",
     },
     CommandDefinition {
         name: "projects",
-        description: "List main projects.",
+        description: "List main projects (--sort date|name).",
         icon: "🗂️",
     },
     CommandDefinition {
@@ -86,22 +99,84 @@ pub const COMMAND_DEFINITIONS: &[CommandDefinition] = &[
         description: "Summon a secret cookie clicker mini game.",
         icon: "🍪",
     },
+    CommandDefinition {
+        name: "banner",
+        description: "Replay the welcome banner.",
+        icon: "👋",
+    },
+    CommandDefinition {
+        name: "demo",
+        description: "Play a guided tour of the main sections (any key stops it).",
+        icon: "🎬",
+    },
+    CommandDefinition {
+        name: schedule::SLEEP_COMMAND,
+        description: "Pause a chain of commands (`sleep 2 && about`, up to 10 seconds).",
+        icon: "💤",
+    },
+    CommandDefinition {
+        name: "update",
+        description: "Reload the page, bypassing any cached copy of the terminal.",
+        icon: "🔄",
+    },
     CommandDefinition {
         name: "clear",
-        description: "Clear the terminal output.",
+        description: "Clear the terminal output (`clear ai`, `clear effects`, `clear all`).",
         icon: "🧹",
     },
+    CommandDefinition {
+        name: "width",
+        description: "Limit the output width (`width 100`, `width 960px`, `width auto`).",
+        icon: "📏",
+    },
+    CommandDefinition {
+        name: "lang",
+        description: "Switch the interface language (`lang en`, `lang fr`, `lang auto`).",
+        icon: "🌐",
+    },
+    CommandDefinition {
+        name: "settings",
+        description: "Show or change settings (`settings datasaver on|off|auto`, `settings readingtime on|off|<words>`).",
+        icon: "⚙️",
+    },
+    #[cfg(debug_assertions)]
+    CommandDefinition {
+        name: DEBUG_COMMAND,
+        description: "Trigger renderer effects for testing (debug builds only).",
+        icon: "🛠️",
+    },
 ];
 
+// Developer-only command; never offered as a suggestion even when compiled in.
+pub const DEBUG_COMMAND: &str = "debug";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearScope {
+    Screen,
+    Ai,
+    Effects,
+    All,
+}
+
 #[derive(Debug)]
 pub enum CommandAction {
     Output(String),
     OutputHtml(String),
-    Clear,
+    Clear(ClearScope),
     Download(String),
     ShawEffect,
     PokemonAttempt(PokemonAttemptOutcome),
     CookieClicker,
+    Banner,
+    Demo,
+    Update,
+    AiEstimate(String),
+    SetOutputWidth(OutputWidth),
+    SetLocale(Option<Locale>),
+    SetDataSaver(DataSaverSetting),
+    SetReadingTime(Option<usize>),
+    #[cfg(debug_assertions)]
+    Debug(DebugAction),
 }
 
 #[derive(Debug)]
@@ -123,11 +198,16 @@ pub fn command_names() -> Vec<&'static str> {
 
 pub fn suggestions(prefix: &str) -> Vec<&'static str> {
     let lower = prefix.to_ascii_lowercase();
+    suggestible_names()
+        .filter(|name| name.starts_with(&lower))
+        .collect()
+}
+
+fn suggestible_names() -> impl Iterator<Item = &'static str> {
     COMMAND_DEFINITIONS
         .iter()
         .map(|cmd| cmd.name)
-        .filter(|name| name.starts_with(&lower))
-        .collect()
+        .filter(|name| *name != DEBUG_COMMAND)
 }
 
 pub fn autocomplete(prefix: &str) -> Option<&'static str> {
@@ -135,10 +215,7 @@ pub fn autocomplete(prefix: &str) -> Option<&'static str> {
         return None;
     }
     let lower = prefix.to_ascii_lowercase();
-    let mut matches = COMMAND_DEFINITIONS
-        .iter()
-        .map(|cmd| cmd.name)
-        .filter(|name| name.starts_with(&lower));
+    let mut matches = suggestible_names().filter(|name| name.starts_with(&lower));
     let first = matches.next()?;
     if matches.next().is_none() {
         Some(first)
@@ -150,16 +227,16 @@ pub fn autocomplete(prefix: &str) -> Option<&'static str> {
 pub fn execute(
     command: &str,
     state: &AppState,
-    _args: &[&str],
+    args: &[&str],
 ) -> Result<CommandAction, CommandError> {
     let normalized = command.trim().to_ascii_lowercase();
     let result = match normalized.as_str() {
         "help" => Ok(CommandAction::Output(render_help())),
         "about" => execute_about(state),
-        "skills" => execute_skills(state),
+        "skills" => execute_skills(state, args),
         "experience" => execute_experience(state),
-        "education" => execute_education(state),
-        "projects" => execute_projects(state),
+        "education" => execute_education(state, args),
+        "projects" => execute_projects(state, args),
         "testimonials" => execute_testimonials(state),
         "contact" => execute_contact(state),
         "resume" => execute_resume(state),
@@ -167,9 +244,21 @@ pub fn execute(
         "shaw" | "sha" => execute_shaw(),
         "pokemon" | "pokeball" => execute_pokemon(state),
         "cookie" => execute_cookie(),
-        "ai" => execute_ai(state),
-        "clear" => Ok(CommandAction::Clear),
+        "banner" => Ok(CommandAction::Banner),
+        "demo" => Ok(CommandAction::Demo),
+        schedule::SLEEP_COMMAND => execute_sleep(args),
+        "update" => Ok(CommandAction::Update),
+        "ai" => execute_ai(state, args),
+        "clear" => parse_clear_scope(args).map(CommandAction::Clear),
+        "width" => execute_width(state, args),
+        "lang" => execute_lang(state, args),
+        "settings" => execute_settings(state, args),
         "version" | "ver" => execute_version(state),
+        DEBUG_COMMAND if is_debug_snapshot(args) => Ok(CommandAction::Output(
+            serde_json::to_string_pretty(&state.debug_snapshot()).unwrap_or_default(),
+        )),
+        #[cfg(debug_assertions)]
+        DEBUG_COMMAND => debug_tools::parse(args).map(CommandAction::Debug),
         _ => {
             return Err(CommandError::NotFound {
                 command: normalized,
@@ -179,6 +268,133 @@ pub fn execute(
     result.map_err(CommandError::Message)
 }
 
+const SHAREABLE_COMMANDS: [&str; 9] = [
+    "about",
+    "skills",
+    "experience",
+    "education",
+    "projects",
+    "testimonials",
+    "contact",
+    "resume",
+    "faq",
+];
+
+pub fn is_shareable(command_line: &str) -> bool {
+    command_line
+        .split_whitespace()
+        .next()
+        .map(|command| {
+            SHAREABLE_COMMANDS
+                .iter()
+                .any(|shareable| shareable.eq_ignore_ascii_case(command))
+        })
+        .unwrap_or(false)
+}
+
+const FUZZY_MAX_DISTANCE: usize = 2;
+const FUZZY_MIN_SHARED_PREFIX: usize = 3;
+const FUZZY_MAX_SUGGESTIONS: usize = 3;
+const AUTO_CORRECT_MIN_CHARS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub command: String,
+    pub distance: usize,
+}
+
+pub fn fuzzy_matches(input: &str, history: &[String]) -> Vec<FuzzyMatch> {
+    let input = input.trim().to_ascii_lowercase();
+    let mut words = input.split_whitespace();
+    let Some(typed) = words.next() else {
+        return Vec::new();
+    };
+    let args = words.collect::<Vec<_>>().join(" ");
+
+    // Command names keep the typed arguments; recent history lines are offered
+    // as-is when they start with a real command.
+    let names = suggestible_names().map(|name| {
+        if args.is_empty() {
+            name.to_string()
+        } else {
+            format!("{name} {args}")
+        }
+    });
+    let recent = history.iter().rev().filter_map(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        let command = entry.split_whitespace().next()?;
+        find_definition(command).map(|_| entry.clone())
+    });
+
+    let input_line = if args.is_empty() {
+        typed.to_string()
+    } else {
+        format!("{typed} {args}")
+    };
+    let mut matches: Vec<FuzzyMatch> = Vec::new();
+    for candidate in names.chain(recent) {
+        if candidate == input_line || matches.iter().any(|found| found.command == candidate) {
+            continue;
+        }
+        let distance = damerau_levenshtein(&input_line, &candidate);
+        if distance <= FUZZY_MAX_DISTANCE
+            || shared_prefix_len(&input_line, &candidate) >= FUZZY_MIN_SHARED_PREFIX
+        {
+            matches.push(FuzzyMatch {
+                command: candidate,
+                distance,
+            });
+        }
+    }
+    // Stable sort keeps command-table order, then recency, among equal distances.
+    matches.sort_by_key(|found| found.distance);
+    matches.truncate(FUZZY_MAX_SUGGESTIONS);
+    matches
+}
+
+pub fn auto_correction<'a>(input: &str, matches: &'a [FuzzyMatch]) -> Option<&'a str> {
+    let typed = input.split_whitespace().next()?;
+    if typed.chars().count() < AUTO_CORRECT_MIN_CHARS {
+        return None;
+    }
+    let mut close = matches.iter().filter(|found| found.distance == 1);
+    let only = close.next()?;
+    close.next().is_none().then_some(only.command.as_str())
+}
+
+pub fn damerau_levenshtein(left: &str, right: &str) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let width = right.len() + 1;
+    let mut table = vec![0usize; (left.len() + 1) * width];
+    for (i, cell) in table.iter_mut().step_by(width).enumerate() {
+        *cell = i;
+    }
+    for (j, cell) in table.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+    for i in 1..=left.len() {
+        for j in 1..=right.len() {
+            let cost = usize::from(left[i - 1] != right[j - 1]);
+            let mut best = (table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1)
+                .min(table[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                best = best.min(table[(i - 2) * width + j - 2] + 1);
+            }
+            table[i * width + j] = best;
+        }
+    }
+    table[left.len() * width + right.len()]
+}
+
+fn shared_prefix_len(left: &str, right: &str) -> usize {
+    left.chars()
+        .zip(right.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
 fn find_definition(name: &str) -> Option<&'static CommandDefinition> {
     COMMAND_DEFINITIONS
         .iter()
@@ -243,29 +459,363 @@ fn execute_about(state: &AppState) -> Result<CommandAction, String> {
         lines.push(format!("  {summary_fr}"));
     }
 
+    if let Some(availability) = &state.owner_availability {
+        lines.push(String::new());
+        lines.push(availability_note(availability, &profile.name));
+    }
+
+    if let Some(freshness) = data.freshness_timestamp().and_then(data_freshness_line) {
+        lines.push(String::new());
+        lines.push(freshness);
+    }
+
     Ok(CommandAction::Output(lines.join("\n")))
 }
 
-fn execute_skills(state: &AppState) -> Result<CommandAction, String> {
+pub fn availability_note(availability: &OwnerAvailability, name: &str) -> String {
+    let first_name = name.split_whitespace().next().unwrap_or(name);
+    let clock = format!(
+        "It's {} for {first_name} (UTC{})",
+        availability.local_time, availability.utc_offset
+    );
+    let window = availability.working_hours.replace('-', "–");
+    if availability.within_working_hours {
+        format!("🕘 {clock}, within working hours ({window}, Mon–Fri): likely to reply within a few hours.")
+    } else {
+        format!("🌙 {clock}, outside working hours ({window}, Mon–Fri): expect a reply on the next working day.")
+    }
+}
+
+pub fn data_freshness_line(generated_at: &str) -> Option<String> {
+    if generated_at.trim().is_empty() {
+        return None;
+    }
+    Some(format!(
+        "🕒 Data as of {}",
+        format_utc_timestamp(generated_at)
+    ))
+}
+
+fn format_utc_timestamp(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let bytes = trimmed.as_bytes();
+    let looks_like_timestamp = bytes.len() >= 16
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && (bytes[10] == b'T' || bytes[10] == b' ')
+        && bytes[13] == b':';
+    if looks_like_timestamp {
+        format!("{} {} UTC", &trimmed[..10], &trimmed[11..16])
+    } else {
+        trimmed.to_string()
+    }
+}
+
+const SKILLS_USAGE: &str = "Usage: skills [--cloud]";
+const SKILL_CLOUD_BUCKETS: u32 = 5;
+
+fn execute_skills(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
     let data = ensure_data(state)?;
+    let mut cloud = false;
+    for arg in args
+        .iter()
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty())
+    {
+        if arg == "--cloud" {
+            cloud = true;
+        } else {
+            return Err(format!("Unknown option `{arg}`. {SKILLS_USAGE}"));
+        }
+    }
+    if cloud {
+        let counts = skill_mentions(data);
+        if counts.is_empty() {
+            return Err("No skills are referenced in the experience or projects yet.".to_string());
+        }
+        return Ok(CommandAction::OutputHtml(render_skill_cloud(&counts)));
+    }
+    if data.skills.is_empty() {
+        return Ok(CommandAction::Output("No skills listed yet.".to_string()));
+    }
     Ok(CommandAction::Output(format_skills(&data.skills)))
 }
 
+// Mentions are keyed by icon so "JS"/"JavaScript"-style spellings that share a
+// keyword collapse into one chip; the first spelling seen becomes the label.
+fn skill_mentions(data: &TerminalData) -> Vec<(String, u32)> {
+    let texts =
+        data.experiences
+            .iter()
+            .flat_map(|experience| experience.highlights.iter())
+            .chain(data.projects.projects.iter().flat_map(|project| {
+                std::iter::once(&project.description).chain(project.tech.iter())
+            }));
+    let mut counts: BTreeMap<&'static str, (String, u32)> = BTreeMap::new();
+    for text in texts {
+        for segment in keyword_icons::tokenize(text) {
+            if let Segment::Icon(icon) = segment {
+                counts
+                    .entry(icon.icon_path)
+                    .or_insert_with(|| (icon.token.clone(), 0))
+                    .1 += 1;
+            }
+        }
+    }
+    let mut mentions = counts.into_values().collect::<Vec<_>>();
+    mentions.sort_by(|a, b| {
+        a.0.to_ascii_lowercase()
+            .cmp(&b.0.to_ascii_lowercase())
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    mentions
+}
+
+// Linear scale from 1 (single mention) to SKILL_CLOUD_BUCKETS (most mentioned).
+fn skill_size_bucket(count: u32, max: u32) -> u32 {
+    if max <= 1 || count <= 1 {
+        return 1;
+    }
+    let count = count.min(max);
+    1 + ((count - 1) * (SKILL_CLOUD_BUCKETS - 1) + (max - 1) / 2) / (max - 1)
+}
+
+fn render_skill_cloud(mentions: &[(String, u32)]) -> String {
+    let max = mentions.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let mut html = String::from("<div class=\"skill-cloud\">");
+    for (label, count) in mentions {
+        let noun = if *count == 1 { "mention" } else { "mentions" };
+        html.push_str(&format!(
+            "<span class=\"skill-cloud__chip skill-cloud__chip--{}\" title=\"{count} {noun}\">{}</span>",
+            skill_size_bucket(*count, max),
+            utils::escape_html(label)
+        ));
+    }
+    html.push_str("</div>");
+    html
+}
+
 fn execute_experience(state: &AppState) -> Result<CommandAction, String> {
     let data = ensure_data(state)?;
+    if data.experiences.is_empty() {
+        return Ok(CommandAction::Output(
+            "No experience listed yet. Check back soon.".to_string(),
+        ));
+    }
     Ok(CommandAction::Output(format_experience(&data.experiences)))
 }
 
-fn execute_education(state: &AppState) -> Result<CommandAction, String> {
+fn execute_education(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
     let data = ensure_data(state)?;
-    Ok(CommandAction::Output(format_education(&data.education)))
+    let options = parse_education_options(args)?;
+    if data.education.is_empty() {
+        return Ok(CommandAction::Output(
+            "No education entries listed yet.".to_string(),
+        ));
+    }
+    let entries = filter_education(&data.education, options.school.as_deref());
+    if let (true, Some(school)) = (entries.is_empty(), options.school.as_deref()) {
+        return Err(format!("No education entry matches school `{school}`."));
+    }
+    if options.verbose {
+        return Ok(CommandAction::OutputHtml(render_education_html(&entries)));
+    }
+    Ok(CommandAction::Output(format_education(
+        &entries,
+        options.detail,
+    )))
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct EducationOptions {
+    school: Option<String>,
+    detail: bool,
+    verbose: bool,
 }
 
-fn execute_projects(state: &AppState) -> Result<CommandAction, String> {
+const EDUCATION_USAGE: &str = "Usage: education [--school <name>] [--detail] [--verbose]";
+
+fn parse_education_options(args: &[&str]) -> Result<EducationOptions, String> {
+    let mut options = EducationOptions::default();
+    let mut iter = args
+        .iter()
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty())
+        .peekable();
+    while let Some(arg) = iter.next() {
+        if arg == "--detail" {
+            options.detail = true;
+            continue;
+        }
+        if arg == "--verbose" {
+            options.verbose = true;
+            continue;
+        }
+        let mut words = Vec::new();
+        if let Some(value) = arg.strip_prefix("--school=") {
+            words.push(value);
+        } else if arg != "--school" {
+            return Err(format!("Unknown option `{arg}`. {EDUCATION_USAGE}"));
+        }
+        while let Some(word) = iter.next_if(|next| !next.starts_with("--")) {
+            words.push(word);
+        }
+        let school = words.join(" ");
+        if school.trim().is_empty() {
+            return Err(EDUCATION_USAGE.to_string());
+        }
+        options.school = Some(school);
+    }
+    Ok(options)
+}
+
+fn filter_education<'a>(education: &'a [Education], school: Option<&str>) -> Vec<&'a Education> {
+    let needle = school.map(|value| value.trim().to_lowercase());
+    education
+        .iter()
+        .filter(|entry| match needle.as_deref() {
+            Some(needle) => entry.school.to_lowercase().contains(needle),
+            None => true,
+        })
+        .collect()
+}
+
+fn execute_projects(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
     let data = ensure_data(state)?;
-    Ok(CommandAction::OutputHtml(render_projects_html(
-        &data.projects,
-    )))
+    let order = parse_project_sort(args)?;
+    let projects = &data.projects;
+    if projects.projects.is_empty()
+        && projects.publications.is_empty()
+        && projects.awards.is_empty()
+    {
+        return Ok(CommandAction::Output(
+            "No projects published yet. Check back soon.".to_string(),
+        ));
+    }
+    let collection = match order {
+        Some(order) => sort_projects_collection(&data.projects, order),
+        None => data.projects.clone(),
+    };
+    Ok(CommandAction::OutputHtml(render_projects_html(&collection)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectSort {
+    Date,
+    Name,
+}
+
+const PROJECTS_USAGE: &str = "Usage: projects [--sort date|name]";
+
+fn parse_project_sort(args: &[&str]) -> Result<Option<ProjectSort>, String> {
+    let mut order = None;
+    let mut iter = args
+        .iter()
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty());
+    while let Some(arg) = iter.next() {
+        let value = if let Some(value) = arg.strip_prefix("--sort=") {
+            value
+        } else if arg == "--sort" {
+            iter.next().ok_or_else(|| PROJECTS_USAGE.to_string())?
+        } else {
+            return Err(format!("Unknown option `{arg}`. {PROJECTS_USAGE}"));
+        };
+        order = Some(match value.to_ascii_lowercase().as_str() {
+            "date" => ProjectSort::Date,
+            "name" => ProjectSort::Name,
+            other => return Err(format!("Unknown sort `{other}`. {PROJECTS_USAGE}")),
+        });
+    }
+    Ok(order)
+}
+
+fn sort_projects_collection(
+    collection: &ProjectsCollection,
+    order: ProjectSort,
+) -> ProjectsCollection {
+    let mut sorted = collection.clone();
+    sort_project_entries(&mut sorted.projects, order, |project| {
+        (project.title.as_str(), project.date.as_deref())
+    });
+    sort_project_entries(&mut sorted.publications, order, |publication| {
+        (publication.title.as_str(), publication.date.as_deref())
+    });
+    sorted
+}
+
+fn sort_project_entries<T, F>(entries: &mut [T], order: ProjectSort, fields: F)
+where
+    F: Fn(&T) -> (&str, Option<&str>),
+{
+    match order {
+        ProjectSort::Date => entries.sort_by(|a, b| {
+            let a_key = project_date_key(fields(a).1);
+            let b_key = project_date_key(fields(b).1);
+            match (a_key, b_key) {
+                (Some(a_key), Some(b_key)) => b_key.cmp(&a_key),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        }),
+        ProjectSort::Name => entries.sort_by_cached_key(|entry| project_name_key(fields(entry).0)),
+    }
+}
+
+fn project_name_key(title: &str) -> String {
+    title
+        .trim_start_matches(|ch: char| !ch.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn project_date_key(date: Option<&str>) -> Option<(u16, u8, u8)> {
+    let date = date?.trim();
+    if date.is_empty() {
+        return None;
+    }
+    let mut parts: Vec<&str> = date
+        .split(['–', '—'])
+        .flat_map(|part| part.split(" - "))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    while let Some(part) = parts.pop() {
+        let lower = part.to_ascii_lowercase();
+        if matches!(lower.as_str(), "present" | "now" | "current" | "ongoing") {
+            return Some((u16::MAX, 12, 31));
+        }
+        if let Some(key) = parse_date_fragment(part) {
+            return Some(key);
+        }
+    }
+    None
+}
+
+fn parse_date_fragment(fragment: &str) -> Option<(u16, u8, u8)> {
+    let mut numbers = fragment
+        .split(|ch: char| !ch.is_ascii_digit())
+        .filter(|chunk| !chunk.is_empty());
+    let year_chunk = numbers.next()?;
+    if year_chunk.len() != 4 {
+        return None;
+    }
+    let year = year_chunk.parse().ok()?;
+    let month = numbers
+        .next()
+        .and_then(|value| value.parse::<u8>().ok())
+        .filter(|value| (1..=12).contains(value))
+        .unwrap_or(0);
+    let day = if month == 0 {
+        0
+    } else {
+        numbers
+            .next()
+            .and_then(|value| value.parse::<u8>().ok())
+            .filter(|value| (1..=31).contains(value))
+            .unwrap_or(0)
+    };
+    Some((year, month, day))
 }
 
 fn execute_testimonials(state: &AppState) -> Result<CommandAction, String> {
@@ -305,9 +855,15 @@ fn execute_testimonials(state: &AppState) -> Result<CommandAction, String> {
 
 fn execute_contact(state: &AppState) -> Result<CommandAction, String> {
     let data = ensure_data(state)?;
-    Ok(CommandAction::OutputHtml(render_contact_html(
-        &data.profile,
-    )))
+    let mut html = render_contact_html(&data.profile);
+    if let Some(availability) = &state.owner_availability {
+        let note = format!(
+            "<div class=\"contact-availability\">{}</div>",
+            utils::escape_html(&availability_note(availability, &data.profile.name))
+        );
+        html.insert_str(html.len() - "</div>".len(), &note);
+    }
+    Ok(CommandAction::OutputHtml(html))
 }
 
 fn execute_resume(state: &AppState) -> Result<CommandAction, String> {
@@ -346,7 +902,21 @@ fn execute_faq(state: &AppState) -> Result<CommandAction, String> {
     Ok(CommandAction::Output(lines.join("\n")))
 }
 
-fn execute_ai(state: &AppState) -> Result<CommandAction, String> {
+fn execute_ai(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
+    if let Some(flag) = args.first() {
+        if flag.eq_ignore_ascii_case(AI_HISTORY_FLAG) && args.len() == 1 {
+            return Ok(CommandAction::Output(render_ai_history(state)));
+        }
+        if !flag.eq_ignore_ascii_case(AI_ESTIMATE_FLAG) {
+            return Err(AI_USAGE.to_string());
+        }
+        let question = args[1..].join(" ");
+        if question.trim().is_empty() {
+            return Err(AI_USAGE.to_string());
+        }
+        return Ok(CommandAction::AiEstimate(question));
+    }
+
     let mut lines = Vec::new();
     lines.push("🧠 AI Mode quick reference:".to_string());
     lines.push(
@@ -359,6 +929,12 @@ fn execute_ai(state: &AppState) -> Result<CommandAction, String> {
     lines.push(format!(
         "  • Model in use: {AI_MODEL_NAME} (Groq primary with Gemini then OpenAI fallback)."
     ));
+    lines.push(format!(
+        "  • Run `ai {AI_ESTIMATE_FLAG} <question>` to preview the token count and cost without asking."
+    ));
+    lines.push(format!(
+        "  • Run `ai {AI_HISTORY_FLAG}` to review the questions asked this session."
+    ));
     lines.push(String::new());
     if state.ai_mode {
         lines.push("AI Mode is currently active. Ask your question or type `quit` to return to classic mode.".to_string());
@@ -371,7 +947,180 @@ fn execute_ai(state: &AppState) -> Result<CommandAction, String> {
     Ok(CommandAction::Output(lines.join("\n")))
 }
 
+pub fn render_ai_history(state: &AppState) -> String {
+    if state.ai_history.is_empty() {
+        return "🗂️ No AI questions asked yet this session.".to_string();
+    }
+    let mut lines = vec![format!(
+        "🗂️ AI session history ({}):",
+        state.ai_history.len()
+    )];
+    for (index, exchange) in state.ai_history.iter().enumerate() {
+        lines.push(format!("  {}. Q: {}", index + 1, exchange.question.trim()));
+        let answer = exchange
+            .answer
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut preview = truncate_preview(&answer, AI_HISTORY_ANSWER_PREVIEW_CHARS);
+        if let Some(model) = exchange.model.as_deref() {
+            preview.push_str(&format!(" ({model})"));
+        }
+        lines.push(format!("     A: {preview}"));
+    }
+    lines.join("\n")
+}
+
+fn truncate_preview(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut preview = text.chars().take(max_chars).collect::<String>();
+    preview.truncate(preview.trim_end().len());
+    preview.push('…');
+    preview
+}
+
+fn parse_clear_scope(args: &[&str]) -> Result<ClearScope, String> {
+    match args {
+        [] => Ok(ClearScope::Screen),
+        [scope] => match scope.to_ascii_lowercase().as_str() {
+            "ai" => Ok(ClearScope::Ai),
+            "effects" => Ok(ClearScope::Effects),
+            "all" => Ok(ClearScope::All),
+            _ => Err(CLEAR_USAGE.to_string()),
+        },
+        _ => Err(CLEAR_USAGE.to_string()),
+    }
+}
+
+fn execute_width(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
+    let usage = format!(
+        "Usage: width <columns|pixels px|auto> — columns {}-{}, pixels {}-{}.",
+        OUTPUT_WIDTH_COLUMNS.0,
+        OUTPUT_WIDTH_COLUMNS.1,
+        OUTPUT_WIDTH_PIXELS.0,
+        OUTPUT_WIDTH_PIXELS.1
+    );
+    match args {
+        [] => Ok(CommandAction::Output(format!(
+            "📏 Output width: {}.\n{usage}",
+            state.output_width.label()
+        ))),
+        [raw] => OutputWidth::parse(raw)
+            .map(CommandAction::SetOutputWidth)
+            .ok_or(usage),
+        _ => Err(usage),
+    }
+}
+
+fn execute_lang(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
+    let usage = "Usage: lang <en|fr|auto> — `auto` follows the browser language.";
+    match args {
+        [] => Ok(CommandAction::Output(format!(
+            "🌐 Language: {}.\n{usage}",
+            state.locale.tag()
+        ))),
+        [raw] if raw.eq_ignore_ascii_case("auto") => Ok(CommandAction::SetLocale(None)),
+        [raw] => Locale::from_tag(raw)
+            .filter(|locale| locale.tag().eq_ignore_ascii_case(raw))
+            .map(|locale| CommandAction::SetLocale(Some(locale)))
+            .ok_or_else(|| usage.to_string()),
+        _ => Err(usage.to_string()),
+    }
+}
+
+// `debug` / `debug state` prints the bug-report snapshot in every build; the
+// effect triggers behind other subcommands exist only in debug builds.
+fn is_debug_snapshot(args: &[&str]) -> bool {
+    match args {
+        [] => true,
+        [subcommand] => subcommand.eq_ignore_ascii_case("state"),
+        _ => false,
+    }
+}
+
+const SETTINGS_USAGE: &str =
+    "Usage: settings datasaver <on|off|auto> | settings readingtime <on|off|min words>";
+
+fn execute_settings(state: &AppState, args: &[&str]) -> Result<CommandAction, String> {
+    match args {
+        [] => Ok(CommandAction::Output(format!(
+            "⚙️ Settings:\n  datasaver    {}\n  readingtime  {}\n{SETTINGS_USAGE}",
+            describe_data_saver(state.data_saver_setting, state.data_saver),
+            describe_reading_time(state.reading_time_min_words)
+        ))),
+        [name, value] if name.eq_ignore_ascii_case("datasaver") => DataSaverSetting::parse(value)
+            .map(CommandAction::SetDataSaver)
+            .ok_or_else(|| SETTINGS_USAGE.to_string()),
+        [name, value] if name.eq_ignore_ascii_case("readingtime") => parse_reading_time(value)
+            .map(CommandAction::SetReadingTime)
+            .ok_or_else(|| SETTINGS_USAGE.to_string()),
+        _ => Err(SETTINGS_USAGE.to_string()),
+    }
+}
+
+// `on` restores the default threshold; a number sets the minimum word count
+// an output needs before its reading time is shown.
+pub fn parse_reading_time(value: &str) -> Option<Option<usize>> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" => Some(Some(format::READING_TIME_DEFAULT_MIN_WORDS)),
+        "off" => Some(None),
+        other => other
+            .parse::<usize>()
+            .ok()
+            .filter(|words| *words > 0)
+            .map(Some),
+    }
+}
+
+pub fn describe_reading_time(min_words: Option<usize>) -> String {
+    match min_words {
+        Some(words) => format!("on (outputs over {words} words)"),
+        None => "off".to_string(),
+    }
+}
+
+pub fn describe_data_saver(setting: DataSaverSetting, enabled: bool) -> String {
+    let status = if enabled { "on" } else { "off" };
+    match setting {
+        DataSaverSetting::Auto => format!("{status} (auto, from your connection)"),
+        _ => status.to_string(),
+    }
+}
+
 fn execute_version(state: &AppState) -> Result<CommandAction, String> {
+    let mut data_lines = Vec::new();
+    if let Some(data) = state.data.as_ref() {
+        if let Some(freshness) = data.freshness_timestamp().and_then(data_freshness_line) {
+            data_lines.push(freshness);
+        }
+        if let Some(counts) = data_counts_line(&data.counts, state.locale) {
+            data_lines.push(counts);
+        }
+    }
+
+    if let Some(info) = state
+        .backend_version()
+        .filter(|info| info.version != build_info::FRONTEND_VERSION)
+    {
+        let frontend = VersionSide {
+            version: build_info::FRONTEND_VERSION,
+            commit: build_info::frontend_commit(),
+            built_at: build_info::frontend_built_at(),
+        };
+        let backend = VersionSide {
+            version: &info.version,
+            commit: &info.commit,
+            built_at: info.built_at.as_deref(),
+        };
+        return Ok(CommandAction::OutputHtml(render_version_mismatch_html(
+            &frontend,
+            &backend,
+            &data_lines,
+        )));
+    }
+
     let mut lines = Vec::new();
     lines.push("Deployment versions:".to_string());
     lines.push(format_version_line(
@@ -380,26 +1129,216 @@ fn execute_version(state: &AppState) -> Result<CommandAction, String> {
         build_info::frontend_commit(),
         None,
     ));
-
     if let Some(info) = state.backend_version() {
-        let parity = if info.version == build_info::FRONTEND_VERSION {
-            "in sync"
-        } else {
-            "version mismatch"
-        };
         lines.push(format_version_line(
             "Backend",
             &info.version,
             &info.commit,
-            Some(parity),
+            Some("in sync"),
         ));
     } else {
         lines.push("  Backend: unavailable (version endpoint unreachable)".to_string());
     }
+    lines.extend(data_lines.into_iter().map(|line| format!("  {line}")));
 
     Ok(CommandAction::Output(lines.join("\n")))
 }
 
+struct VersionSide<'a> {
+    version: &'a str,
+    commit: &'a str,
+    built_at: Option<&'a str>,
+}
+
+const VERSION_HINT_FRONTEND_OLDER: &str =
+    "The frontend is older than the backend, so your browser is likely serving a cached copy. Try a hard refresh (Ctrl+Shift+R, or Cmd+Shift+R on macOS).";
+const VERSION_UPDATE_HINT_HTML: &str = r#"<p class="version-diff__hint">🔄 Run <button type="button" class="command-chip" data-command="update">update</button> to reload the latest release.</p>"#;
+const VERSION_HINT_BACKEND_OLDER: &str =
+    "The backend is older than the frontend; a server deploy is probably still rolling out.";
+
+// Semver ordering (`v` prefix and build metadata ignored, missing minor or
+// patch read as 0, pre-releases before releases); anything else falls back
+// to comparing the raw strings.
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    match (parse_semver(left), parse_semver(right)) {
+        (Some((left_core, left_pre)), Some((right_core, right_pre))) => left_core
+            .cmp(&right_core)
+            .then_with(|| match (left_pre, right_pre) {
+                (None, None) => Ordering::Equal,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(left), Some(right)) => compare_prerelease(left, right),
+            }),
+        _ => left.trim().cmp(right.trim()),
+    }
+}
+
+// A cached page is only worth interrupting the visitor for when the backend is
+// ahead by a minor or major release; patch drift is expected mid-deploy.
+pub fn frontend_is_stale(frontend: &str, backend: &str) -> bool {
+    match (parse_semver(frontend), parse_semver(backend)) {
+        (Some((frontend_core, _)), Some((backend_core, _))) => {
+            backend_core[..2] > frontend_core[..2]
+        }
+        _ => false,
+    }
+}
+
+fn parse_semver(raw: &str) -> Option<([u64; 3], Option<&str>)> {
+    let trimmed = raw.trim();
+    let trimmed = trimmed
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(trimmed)
+        .split('+')
+        .next()
+        .unwrap_or_default();
+    let (core, pre) = match trimmed.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (trimmed, None),
+    };
+    let mut parts = [0u64; 3];
+    let mut segments = core.split('.');
+    parts[0] = segments.next()?.parse().ok()?;
+    for part in parts.iter_mut().skip(1) {
+        if let Some(segment) = segments.next() {
+            *part = segment.parse().ok()?;
+        }
+    }
+    if segments.next().is_some() {
+        return None;
+    }
+    Some((parts, pre))
+}
+
+fn compare_prerelease(left: &str, right: &str) -> Ordering {
+    let mut left_ids = left.split('.');
+    let mut right_ids = right.split('.');
+    loop {
+        match (left_ids.next(), right_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => {
+                let order = match (left.parse::<u64>(), right.parse::<u64>()) {
+                    (Ok(left), Ok(right)) => left.cmp(&right),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => left.cmp(right),
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+        }
+    }
+}
+
+// Equal versions (e.g. `1.2` against `1.2.0`) fall back to the build times.
+fn version_hint(frontend: &VersionSide, backend: &VersionSide) -> Option<&'static str> {
+    let order = compare_versions(frontend.version, backend.version).then_with(|| {
+        match (frontend.built_at, backend.built_at) {
+            (Some(frontend), Some(backend)) => frontend.cmp(backend),
+            _ => Ordering::Equal,
+        }
+    });
+    match order {
+        Ordering::Less => Some(VERSION_HINT_FRONTEND_OLDER),
+        Ordering::Greater => Some(VERSION_HINT_BACKEND_OLDER),
+        Ordering::Equal => None,
+    }
+}
+
+fn render_version_mismatch_html(
+    frontend: &VersionSide,
+    backend: &VersionSide,
+    data_lines: &[String],
+) -> String {
+    let mut html = String::from(
+        "<div class=\"version-diff\"><p>Deployment versions: <strong>version mismatch</strong></p>",
+    );
+    html.push_str(
+        "<table><thead><tr><th></th><th>Frontend</th><th>Backend</th></tr></thead><tbody>",
+    );
+    push_version_row(
+        &mut html,
+        "Version",
+        |side| format!("v{}", utils::escape_html(side.version)),
+        frontend,
+        backend,
+    );
+    push_version_row(
+        &mut html,
+        "Commit",
+        |side| {
+            let commit = utils::escape_html(side.commit.trim());
+            match commit_link(side.commit) {
+                Some(link) => format!(
+                    "<a href=\"{}\" target=\"_blank\" rel=\"noopener noreferrer\">{commit}</a>",
+                    utils::escape_html(&link)
+                ),
+                None => "unknown".to_string(),
+            }
+        },
+        frontend,
+        backend,
+    );
+    push_version_row(
+        &mut html,
+        "Built",
+        |side| {
+            side.built_at
+                .map(|built_at| utils::escape_html(&format_utc_timestamp(built_at)))
+                .unwrap_or_else(|| "unknown".to_string())
+        },
+        frontend,
+        backend,
+    );
+    html.push_str("</tbody></table>");
+    if let Some(hint) = version_hint(frontend, backend) {
+        html.push_str("<p class=\"version-diff__hint\">💡 ");
+        html.push_str(&utils::escape_html(hint));
+        html.push_str("</p>");
+    }
+    if frontend_is_stale(frontend.version, backend.version) {
+        html.push_str(VERSION_UPDATE_HINT_HTML);
+    }
+    for line in data_lines {
+        html.push_str("<p>");
+        html.push_str(&utils::escape_html(line));
+        html.push_str("</p>");
+    }
+    html.push_str("</div>");
+    html
+}
+
+fn push_version_row(
+    html: &mut String,
+    label: &str,
+    cell: impl Fn(&VersionSide) -> String,
+    frontend: &VersionSide,
+    backend: &VersionSide,
+) {
+    html.push_str("<tr><th>");
+    html.push_str(label);
+    html.push_str("</th><td>");
+    html.push_str(&cell(frontend));
+    html.push_str("</td><td>");
+    html.push_str(&cell(backend));
+    html.push_str("</td></tr>");
+}
+
+pub fn data_counts_line(counts: &BTreeMap<String, u64>, locale: Locale) -> Option<String> {
+    if counts.is_empty() {
+        return None;
+    }
+    let sections = counts
+        .iter()
+        .map(|(section, count)| format!("{section} {}", format::format_count(*count, locale)))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    Some(format!("📚 Sections: {sections}"))
+}
+
 fn render_help() -> String {
     let mut lines = Vec::new();
     lines.push("Available commands:".to_string());
@@ -422,6 +1361,9 @@ fn render_help() -> String {
         "Tip: Toggle the AI Mode button to ask the assistant questions about Alexandre."
             .to_string(),
     );
+    lines.push(
+        "Shortcuts: Ctrl+I toggles AI Mode · Tab autocompletes · ↑/↓ browse history.".to_string(),
+    );
     lines.push(
         "Developed in Rust by Alexandre DO-O ALMEIDA (Open source: https://github.com/Aleqsd/zqsdev.com)"
             .to_string(),
@@ -435,7 +1377,7 @@ fn execute_shaw() -> Result<CommandAction, String> {
 
 fn execute_pokemon(state: &AppState) -> Result<CommandAction, String> {
     let chance = state.pokemon_capture_chance();
-    let roll = (Math::random() * 100.0).floor() as u8;
+    let roll = (state.random.next_unit() * 100.0).floor() as u8;
     let success = roll < chance;
     let next_chance = if success {
         1
@@ -454,6 +1396,17 @@ fn execute_cookie() -> Result<CommandAction, String> {
     Ok(CommandAction::CookieClicker)
 }
 
+// On its own a sleep has nothing to delay, so it only explains where it helps;
+// chains schedule valid sleeps before they ever reach this point.
+fn execute_sleep(args: &[&str]) -> Result<CommandAction, String> {
+    let line = format!("{} {}", schedule::SLEEP_COMMAND, args.join(" "));
+    match schedule::parse_sleep(&line) {
+        Some(Ok(_)) => Ok(CommandAction::Output(SLEEP_STANDALONE_HINT.to_string())),
+        Some(Err(usage)) => Err(usage),
+        None => Err(schedule::SLEEP_USAGE.to_string()),
+    }
+}
+
 fn format_version_line(label: &str, version: &str, commit: &str, parity: Option<&str>) -> String {
     let mut line = match commit_link(commit) {
         Some(link) => format!(
@@ -519,17 +1472,23 @@ fn format_experience(experiences: &[Experience]) -> String {
     lines.join("\n")
 }
 
-fn format_education(education: &[Education]) -> String {
+fn format_education(education: &[&Education], detail: bool) -> String {
     let mut lines = Vec::new();
     for entry in education {
         lines.push(entry.degree.clone());
         lines.push(format!("  {}", entry.school));
+        if !detail {
+            continue;
+        }
         if let Some(years) = &entry.years {
             lines.push(format!("  Years: {years}"));
         }
         if let Some(location) = &entry.location {
             lines.push(format!("  Location: {location}"));
         }
+        for detail in &entry.details {
+            lines.push(format!("  • {detail}"));
+        }
         lines.push(String::new());
     }
     if let Some(last) = lines.last() {
@@ -623,6 +1582,88 @@ mod tests {
         assert_eq!(result, vec!["projects"]);
     }
 
+    #[test]
+    fn damerau_levenshtein_counts_transpositions_once() {
+        assert_eq!(damerau_levenshtein("porjects", "projects"), 1);
+        assert_eq!(damerau_levenshtein("experiance", "experience"), 1);
+        assert_eq!(damerau_levenshtein("skils", "skills"), 1);
+        assert_eq!(damerau_levenshtein("contcat", "contact"), 1);
+        assert_eq!(damerau_levenshtein("eduation", "education"), 1);
+        assert_eq!(damerau_levenshtein("", "faq"), 3);
+        assert_eq!(damerau_levenshtein("help", "help"), 0);
+    }
+
+    #[test]
+    fn fuzzy_matches_rank_typical_typos() {
+        let first = |input: &str| {
+            fuzzy_matches(input, &[])
+                .first()
+                .map(|found| found.command.clone())
+        };
+        assert_eq!(first("porjects").as_deref(), Some("projects"));
+        assert_eq!(first("experiance").as_deref(), Some("experience"));
+        assert_eq!(first("skils").as_deref(), Some("skills"));
+        assert_eq!(first("Resmue").as_deref(), Some("resume"));
+        assert_eq!(
+            first("porjects --sort date").as_deref(),
+            Some("projects --sort date"),
+            "Arguments are carried over to the correction"
+        );
+        assert_eq!(
+            first("testim").as_deref(),
+            Some("testimonials"),
+            "A shared prefix is enough"
+        );
+        assert!(fuzzy_matches("xyzzy", &[]).is_empty());
+        assert!(fuzzy_matches("   ", &[]).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_matches_include_recent_history() {
+        let history = vec![
+            "education --school Paris".to_string(),
+            "porjects".to_string(),
+        ];
+        let matches = fuzzy_matches("educaton --school Paris", &history);
+        assert_eq!(matches[0].command, "education --school paris");
+        assert_eq!(matches[0].distance, 1);
+        assert!(
+            fuzzy_matches("porjects", &history)
+                .iter()
+                .all(|found| found.command != "porjects"),
+            "Earlier typos in history are never offered"
+        );
+    }
+
+    #[test]
+    fn auto_correction_requires_a_single_close_long_match() {
+        let matches = fuzzy_matches("porjects", &[]);
+        assert_eq!(auto_correction("porjects", &matches), Some("projects"));
+
+        let short = fuzzy_matches("skils", &[]);
+        assert_eq!(
+            auto_correction("skil", &fuzzy_matches("skil", &[])),
+            None,
+            "Inputs under five characters are never auto-corrected"
+        );
+        assert_eq!(auto_correction("skils", &short), Some("skills"));
+
+        let ambiguous = vec![
+            FuzzyMatch {
+                command: "about".to_string(),
+                distance: 1,
+            },
+            FuzzyMatch {
+                command: "abort".to_string(),
+                distance: 1,
+            },
+        ];
+        assert_eq!(auto_correction("abott", &ambiguous), None);
+        let distant = fuzzy_matches("expernce", &[]);
+        assert_eq!(distant[0].distance, 2);
+        assert_eq!(auto_correction("expernce", &distant), None);
+    }
+
     #[wasm_bindgen_test]
     fn autocomplete_requires_unique_match() {
         assert_eq!(autocomplete("sk"), Some("skills"));
@@ -630,6 +1671,133 @@ mod tests {
         assert_eq!(autocomplete("c"), None);
     }
 
+    #[wasm_bindgen_test]
+    fn ai_history_lists_questions_in_order() {
+        use crate::state::AiExchange;
+
+        let mut state = stub_state();
+        assert!(render_ai_history(&state).contains("No AI questions"));
+        state.record_ai_exchange(AiExchange {
+            question: "Where did he work?".to_string(),
+            answer: "At **PlayStation**\nin Paris.".to_string(),
+            model: Some("groq".to_string()),
+            backend: Some("groq".to_string()),
+        });
+        state.record_ai_exchange(AiExchange {
+            question: "Is he available?".to_string(),
+            answer: "Yes. ".repeat(100),
+            model: None,
+            backend: None,
+        });
+
+        let history = match execute_ai(&state, &["--history"]) {
+            Ok(CommandAction::Output(text)) => text,
+            _ => panic!("ai --history should render text"),
+        };
+        let first = history.find("1. Q: Where did he work?").expect("first");
+        let second = history.find("2. Q: Is he available?").expect("second");
+        assert!(first < second, "Questions keep their order:\n{history}");
+        assert!(history.contains("A: At **PlayStation** in Paris. (groq)"));
+        assert!(history.contains("Yes. Yes.") && history.contains('…'));
+    }
+
+    #[wasm_bindgen_test]
+    fn clear_accepts_targeted_scopes() {
+        assert_eq!(parse_clear_scope(&[]), Ok(ClearScope::Screen));
+        assert_eq!(parse_clear_scope(&["AI"]), Ok(ClearScope::Ai));
+        assert_eq!(parse_clear_scope(&["effects"]), Ok(ClearScope::Effects));
+        assert_eq!(parse_clear_scope(&["all"]), Ok(ClearScope::All));
+        assert_eq!(
+            parse_clear_scope(&["history"]),
+            Err(CLEAR_USAGE.to_string())
+        );
+        assert_eq!(
+            parse_clear_scope(&["ai", "effects"]),
+            Err(CLEAR_USAGE.to_string())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn width_command_parses_the_requested_width() {
+        let state = stub_state();
+        assert!(matches!(
+            execute("width", &state, &["100"]),
+            Ok(CommandAction::SetOutputWidth(OutputWidth::Columns(100)))
+        ));
+        assert!(matches!(
+            execute("width", &state, &["auto"]),
+            Ok(CommandAction::SetOutputWidth(OutputWidth::Auto))
+        ));
+        match execute("width", &state, &["huge"]) {
+            Err(CommandError::Message(message)) => assert!(message.starts_with("Usage: width")),
+            _ => panic!("Invalid widths should print the usage"),
+        }
+    }
+
+    #[test]
+    fn lang_command_parses_the_requested_locale() {
+        let state = stub_state();
+        assert!(matches!(
+            execute("lang", &state, &["FR"]),
+            Ok(CommandAction::SetLocale(Some(Locale::Fr)))
+        ));
+        assert!(matches!(
+            execute("lang", &state, &["auto"]),
+            Ok(CommandAction::SetLocale(None))
+        ));
+        for args in [&["de"][..], &["en-US"], &["en", "fr"]] {
+            match execute("lang", &state, args) {
+                Err(CommandError::Message(message)) => {
+                    assert!(message.starts_with("Usage: lang"), "{args:?}: {message}")
+                }
+                other => panic!("`lang {args:?}` should print the usage, got {other:?}"),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn debug_command_is_never_suggested() {
+        assert!(!suggestions("d").contains(&DEBUG_COMMAND));
+        assert_eq!(suggestions("de"), vec!["demo"]);
+        assert_eq!(autocomplete("deb"), None);
+        assert!(fuzzy_matches("debgu", &[])
+            .iter()
+            .all(|found| !found.command.starts_with(DEBUG_COMMAND)));
+        assert_eq!(
+            command_names().contains(&DEBUG_COMMAND),
+            cfg!(debug_assertions),
+            "Only debug builds register the command"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn debug_state_prints_the_snapshot_in_every_build() {
+        let state = AppState::new();
+        for args in [&[][..], &["STATE"][..]] {
+            match execute(DEBUG_COMMAND, &state, args) {
+                Ok(CommandAction::Output(text)) => {
+                    let snapshot: serde_json::Value =
+                        serde_json::from_str(&text).expect("snapshot is JSON");
+                    assert_eq!(
+                        snapshot["version"]["frontend"],
+                        build_info::FRONTEND_VERSION
+                    );
+                }
+                _ => panic!("`debug {args:?}` should print the snapshot"),
+            }
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[wasm_bindgen_test]
+    fn release_builds_reject_debug_command() {
+        let state = AppState::new();
+        assert!(matches!(
+            execute(DEBUG_COMMAND, &state, &["toast"]),
+            Err(CommandError::NotFound { .. })
+        ));
+    }
+
     #[wasm_bindgen_test]
     fn helper_label_uses_icon_when_available() {
         let label = helper_label("help");
@@ -644,22 +1812,236 @@ mod tests {
     }
 
     #[wasm_bindgen_test]
-    fn helper_label_falls_back_to_command_name() {
-        let label = helper_label("unknown");
-        assert_eq!(label, "unknown");
+    fn helper_label_falls_back_to_command_name() {
+        let label = helper_label("unknown");
+        assert_eq!(label, "unknown");
+    }
+
+    #[wasm_bindgen_test]
+    fn about_command_includes_focus_section() {
+        let state = stub_state();
+        let output = match execute("about", &state, &[]) {
+            Ok(CommandAction::Output(text)) => text,
+            other => panic!("unexpected action for about: {other:?}"),
+        };
+        assert!(
+            output.contains("Focus:"),
+            "About output should include focus section:\n{output}"
+        );
+    }
+
+    #[test]
+    fn data_freshness_line_formats_timestamps() {
+        assert_eq!(
+            data_freshness_line("2025-03-14T09:26:53Z").as_deref(),
+            Some("🕒 Data as of 2025-03-14 09:26 UTC")
+        );
+        assert_eq!(
+            data_freshness_line("build 42").as_deref(),
+            Some("🕒 Data as of build 42")
+        );
+        assert_eq!(data_freshness_line("  "), None);
+    }
+
+    #[test]
+    fn compare_versions_orders_semver_and_falls_back_to_strings() {
+        assert_eq!(compare_versions("0.9.0", "0.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.2.3", "1.2.3"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.3+build.7", "1.2.3"), Ordering::Equal);
+        assert_eq!(compare_versions("2.0.0-rc.1", "2.0.0"), Ordering::Less);
+        assert_eq!(
+            compare_versions("2.0.0-rc.10", "2.0.0-rc.2"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_versions("2.0.0-alpha", "2.0.0-alpha.1"),
+            Ordering::Less
+        );
+        assert_eq!(compare_versions("2.0.0-1", "2.0.0-beta"), Ordering::Less);
+        assert_eq!(
+            compare_versions("nightly-b", "nightly-a"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_versions("1.2.3.4", "1.2.3"), Ordering::Greater);
+    }
+
+    #[test]
+    fn version_hint_blames_the_older_side() {
+        let side = |version, built_at| VersionSide {
+            version,
+            commit: "abc1234",
+            built_at,
+        };
+        assert_eq!(
+            version_hint(&side("0.9.0", None), &side("0.10.0", None)),
+            Some(VERSION_HINT_FRONTEND_OLDER)
+        );
+        assert_eq!(
+            version_hint(&side("1.1.0", None), &side("1.0.9", None)),
+            Some(VERSION_HINT_BACKEND_OLDER)
+        );
+        assert_eq!(
+            version_hint(
+                &side("1.2", Some("2025-03-14T09:00:00Z")),
+                &side("1.2.0", Some("2025-03-15T09:00:00Z"))
+            ),
+            Some(VERSION_HINT_FRONTEND_OLDER),
+            "Equal versions should fall back to build times"
+        );
+        assert_eq!(version_hint(&side("1.2", None), &side("1.2.0", None)), None);
+    }
+
+    #[test]
+    fn frontend_is_stale_ignores_patch_drift() {
+        assert!(!frontend_is_stale("1.0.11", "1.0.12"));
+        assert!(!frontend_is_stale("1.0.11", "1.0.11"));
+        assert!(!frontend_is_stale("1.1.0", "1.0.12"));
+        assert!(frontend_is_stale("1.0.11", "1.1.0"));
+        assert!(frontend_is_stale("1.9.3", "2.0.0"));
+        assert!(frontend_is_stale("v1.0.0", "1.1.0-rc.1"));
+        assert!(!frontend_is_stale("1.0.0", "nightly"));
+    }
+
+    #[test]
+    fn version_mismatch_html_snapshot() {
+        let frontend = VersionSide {
+            version: "0.9.0",
+            commit: "abc1234",
+            built_at: Some("2025-03-14T09:26:53Z"),
+        };
+        let backend = VersionSide {
+            version: "0.10.0",
+            commit: "unknown",
+            built_at: None,
+        };
+        let html = render_version_mismatch_html(
+            &frontend,
+            &backend,
+            &["🕒 Data as of 2025-03-14 09:26 UTC".to_string()],
+        );
+        assert_eq!(
+            html,
+            concat!(
+                "<div class=\"version-diff\"><p>Deployment versions: <strong>version mismatch</strong></p>",
+                "<table><thead><tr><th></th><th>Frontend</th><th>Backend</th></tr></thead><tbody>",
+                "<tr><th>Version</th><td>v0.9.0</td><td>v0.10.0</td></tr>",
+                "<tr><th>Commit</th><td><a href=\"https://github.com/Aleqsd/zqsdev.com/commit/abc1234\" target=\"_blank\" rel=\"noopener noreferrer\">abc1234</a></td><td>unknown</td></tr>",
+                "<tr><th>Built</th><td>2025-03-14 09:26 UTC</td><td>unknown</td></tr>",
+                "</tbody></table>",
+                "<p class=\"version-diff__hint\">💡 The frontend is older than the backend, so your browser is likely serving a cached copy. Try a hard refresh (Ctrl+Shift+R, or Cmd+Shift+R on macOS).</p>",
+                "<p class=\"version-diff__hint\">🔄 Run <button type=\"button\" class=\"command-chip\" data-command=\"update\">update</button> to reload the latest release.</p>",
+                "<p>🕒 Data as of 2025-03-14 09:26 UTC</p>",
+                "</div>",
+            )
+        );
+    }
+
+    #[test]
+    fn version_mismatch_html_escapes_backend_values() {
+        let frontend = VersionSide {
+            version: "1.0.0",
+            commit: "unknown",
+            built_at: None,
+        };
+        let backend = VersionSide {
+            version: "1.0.0<script>",
+            commit: "\"><img src=x>",
+            built_at: Some("<b>soon</b>"),
+        };
+        let html = render_version_mismatch_html(&frontend, &backend, &[]);
+        assert!(!html.contains("<script>"), "{html}");
+        assert!(!html.contains("<img"), "{html}");
+        assert!(!html.contains("<b>"), "{html}");
+        assert!(html.contains("v1.0.0&lt;script&gt;"), "{html}");
+        assert!(html.contains("&lt;b&gt;soon&lt;/b&gt;"), "{html}");
+    }
+
+    #[test]
+    fn data_counts_line_lists_sections() {
+        let mut counts = BTreeMap::new();
+        assert_eq!(data_counts_line(&counts, Locale::En), None);
+        counts.insert("projects".to_string(), 6);
+        counts.insert("experience".to_string(), 5);
+        counts.insert("testimonials".to_string(), 0);
+        assert_eq!(
+            data_counts_line(&counts, Locale::En).as_deref(),
+            Some("📚 Sections: experience 5 · projects 6 · testimonials 0")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn list_commands_explain_empty_sections() {
+        let mut state = stub_state();
+        if let Some(data) = state.data.as_mut() {
+            data.skills.clear();
+        }
+        for (command, args, expected) in [
+            ("experience", &[][..], "No experience listed yet"),
+            ("education", &[][..], "No education entries"),
+            ("education", &["--verbose"][..], "No education entries"),
+            ("skills", &[][..], "No skills listed yet"),
+            ("projects", &[][..], "No projects published yet"),
+            (
+                "projects",
+                &["--sort", "date"][..],
+                "No projects published yet",
+            ),
+        ] {
+            match execute(command, &state, args) {
+                Ok(CommandAction::Output(text)) => {
+                    assert!(text.starts_with(expected), "{command}: {text:?}")
+                }
+                other => panic!("Unexpected {command} result: {other:?}"),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn version_command_reports_data_freshness_and_counts() {
+        let mut state = stub_state();
+        if let Some(data) = state.data.as_mut() {
+            data.generated_at = Some("2025-03-20T08:00:00Z".to_string());
+            data.source_mtime = Some("2025-03-14T09:26:53Z".to_string());
+            data.counts.insert("projects".to_string(), 12);
+        }
+        match execute("version", &state, &[]) {
+            Ok(CommandAction::Output(text)) => {
+                assert!(
+                    text.contains("Data as of 2025-03-14 09:26 UTC"),
+                    "Version output should prefer the source modification time: {text}"
+                );
+                assert!(
+                    text.contains("📚 Sections: projects 12"),
+                    "Version output should list section counts: {text}"
+                );
+            }
+            other => panic!("Unexpected version result: {other:?}"),
+        }
     }
 
     #[wasm_bindgen_test]
-    fn about_command_includes_focus_section() {
-        let state = stub_state();
-        let output = match execute("about", &state, &[]) {
-            Ok(CommandAction::Output(text)) => text,
-            other => panic!("unexpected action for about: {other:?}"),
-        };
-        assert!(
-            output.contains("Focus:"),
-            "About output should include focus section:\n{output}"
-        );
+    fn about_command_renders_data_freshness_indicator() {
+        let mut state = stub_state();
+        if let Some(data) = state.data.as_mut() {
+            data.generated_at = Some("2025-03-14T09:26:53Z".to_string());
+        }
+        match execute("about", &state, &[]) {
+            Ok(CommandAction::Output(text)) => assert!(
+                text.contains("Data as of 2025-03-14 09:26 UTC"),
+                "About output should surface the data freshness: {text}"
+            ),
+            other => panic!("Unexpected about result: {other:?}"),
+        }
+
+        let without = stub_state();
+        match execute("about", &without, &[]) {
+            Ok(CommandAction::Output(text)) => assert!(
+                !text.contains("Data as of"),
+                "About output should omit freshness when unknown: {text}"
+            ),
+            other => panic!("Unexpected about result: {other:?}"),
+        }
     }
 
     #[wasm_bindgen_test]
@@ -707,6 +2089,27 @@ mod tests {
         }
     }
 
+    #[wasm_bindgen_test]
+    fn demo_and_sleep_commands_map_to_their_actions() {
+        let state = stub_state();
+        assert!(matches!(
+            execute("demo", &state, &[]),
+            Ok(CommandAction::Demo)
+        ));
+        match execute("sleep", &state, &["2"]) {
+            Ok(CommandAction::Output(text)) => assert_eq!(text, SLEEP_STANDALONE_HINT),
+            other => panic!("expected the standalone sleep hint, got {other:?}"),
+        }
+        for args in [&[][..], &["11"][..], &["soon"][..]] {
+            match execute("sleep", &state, args) {
+                Err(CommandError::Message(message)) => {
+                    assert_eq!(message, schedule::SLEEP_USAGE)
+                }
+                other => panic!("expected sleep usage for {args:?}, got {other:?}"),
+            }
+        }
+    }
+
     #[wasm_bindgen_test]
     fn contact_command_includes_profile_details() {
         let state = stub_state();
@@ -739,6 +2142,44 @@ mod tests {
         );
     }
 
+    #[wasm_bindgen_test]
+    fn contact_and_about_note_the_owner_working_hours() {
+        let mut state = stub_state();
+        let action = execute("contact", &state, &[]).expect("contact should succeed");
+        let CommandAction::OutputHtml(html) = action else {
+            panic!("expected html output");
+        };
+        assert!(
+            !html.contains("contact-availability"),
+            "No note before /api/time answered"
+        );
+
+        state.owner_availability = Some(OwnerAvailability {
+            local_time: "14:05".to_string(),
+            utc_offset: "+01:00".to_string(),
+            working_hours: "09:00-18:00".to_string(),
+            within_working_hours: true,
+        });
+        let action = execute("contact", &state, &[]).expect("contact should succeed");
+        let CommandAction::OutputHtml(html) = action else {
+            panic!("expected html output");
+        };
+        assert!(html.contains("<div class=\"contact-availability\">🕘 It&#39;s 14:05"));
+        assert!(html.contains("likely to reply within a few hours"));
+        assert!(html.ends_with("</div></div>"));
+
+        if let Some(availability) = state.owner_availability.as_mut() {
+            availability.within_working_hours = false;
+        }
+        let action = execute("about", &state, &[]).expect("about should succeed");
+        let CommandAction::Output(text) = action else {
+            panic!("expected text output");
+        };
+        assert!(text.contains(
+            "🌙 It's 14:05 for Alex (UTC+01:00), outside working hours (09:00–18:00, Mon–Fri)"
+        ));
+    }
+
     #[wasm_bindgen_test]
     fn contact_command_handles_missing_french_summary() {
         let mut state = stub_state();
@@ -790,6 +2231,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ai_estimate_flag_requests_a_cost_preview() {
+        let state = AppState::new();
+        let action = execute("ai", &state, &["--estimate", "Who", "is", "Alexandre?"])
+            .expect("estimate should parse");
+        assert!(
+            matches!(&action, CommandAction::AiEstimate(question) if question == "Who is Alexandre?"),
+            "Expected an estimate action, got {action:?}"
+        );
+        for args in [&["--estimate"][..], &["--bogus", "question"][..]] {
+            let Err(CommandError::Message(message)) = execute("ai", &state, args) else {
+                panic!("`ai {args:?}` should be rejected");
+            };
+            assert!(message.contains(AI_USAGE), "Unexpected message: {message}");
+        }
+    }
+
     #[test]
     fn help_command_columns_align() {
         let output = super::render_help();
@@ -856,6 +2314,304 @@ mod tests {
         );
     }
 
+    fn project(title: &str, date: Option<&str>) -> Project {
+        Project {
+            title: title.to_string(),
+            date: date.map(str::to_string),
+            description: "Description".to_string(),
+            tech: Vec::new(),
+            link: None,
+        }
+    }
+
+    #[test]
+    fn project_date_key_handles_ranges_and_precision() {
+        assert_eq!(project_date_key(Some("2018")), Some((2018, 0, 0)));
+        assert_eq!(project_date_key(Some("2024-03-12")), Some((2024, 3, 12)));
+        assert_eq!(project_date_key(Some("2023 - 2024")), Some((2024, 0, 0)));
+        assert_eq!(
+            project_date_key(Some("2023 - Present")),
+            Some((u16::MAX, 12, 31))
+        );
+        assert_eq!(project_date_key(Some("Spring")), None);
+        assert_eq!(project_date_key(Some("  ")), None);
+        assert_eq!(project_date_key(None), None);
+        assert!(
+            project_date_key(Some("2024-03-12")) > project_date_key(Some("2024")),
+            "A full date should sort as newer than a year-only date in the same year"
+        );
+    }
+
+    #[test]
+    fn sort_projects_by_date_puts_newest_first_and_undated_last() {
+        let collection = ProjectsCollection {
+            projects: vec![
+                project("Undated", None),
+                project("Old", Some("2010")),
+                project("Ongoing", Some("2023 - Present")),
+                project("Precise", Some("2019-06-01")),
+                project("Year", Some("2019")),
+            ],
+            publications: vec![
+                Publication {
+                    title: "Paper A".to_string(),
+                    date: Some("2012".to_string()),
+                    description: String::new(),
+                    tech: Vec::new(),
+                    link: None,
+                },
+                Publication {
+                    title: "Paper B".to_string(),
+                    date: Some("2020".to_string()),
+                    description: String::new(),
+                    tech: Vec::new(),
+                    link: None,
+                },
+            ],
+            awards: Vec::new(),
+        };
+
+        let sorted = sort_projects_collection(&collection, ProjectSort::Date);
+        let titles: Vec<&str> = sorted.projects.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Ongoing", "Precise", "Year", "Old", "Undated"]);
+        assert_eq!(sorted.publications[0].title, "Paper B");
+    }
+
+    #[test]
+    fn sort_projects_by_name_ignores_leading_emoji() {
+        let collection = ProjectsCollection {
+            projects: vec![
+                project("🏰 mistale", None),
+                project("🎮 Micro Mages", None),
+                project("Alpha", None),
+            ],
+            publications: Vec::new(),
+            awards: Vec::new(),
+        };
+        let sorted = sort_projects_collection(&collection, ProjectSort::Name);
+        let titles: Vec<&str> = sorted.projects.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Alpha", "🎮 Micro Mages", "🏰 mistale"]);
+    }
+
+    #[test]
+    fn settings_command_parses_data_saver_values() {
+        let state = AppState::new();
+        assert!(matches!(
+            execute_settings(&state, &["datasaver", "ON"]),
+            Ok(CommandAction::SetDataSaver(DataSaverSetting::On))
+        ));
+        assert!(matches!(
+            execute_settings(&state, &["DataSaver", "auto"]),
+            Ok(CommandAction::SetDataSaver(DataSaverSetting::Auto))
+        ));
+        assert_eq!(
+            execute_settings(&state, &["datasaver"]).err().as_deref(),
+            Some(SETTINGS_USAGE)
+        );
+        assert!(execute_settings(&state, &["theme", "dark"]).is_err());
+        assert!(matches!(
+            execute_settings(&state, &["readingtime", "Off"]),
+            Ok(CommandAction::SetReadingTime(None))
+        ));
+        assert!(matches!(
+            execute_settings(&state, &["readingtime", "300"]),
+            Ok(CommandAction::SetReadingTime(Some(300)))
+        ));
+        assert!(execute_settings(&state, &["readingtime", "0"]).is_err());
+        assert_eq!(
+            describe_reading_time(Some(format::READING_TIME_DEFAULT_MIN_WORDS)),
+            "on (outputs over 150 words)"
+        );
+        assert_eq!(
+            describe_data_saver(DataSaverSetting::Auto, true),
+            "on (auto, from your connection)"
+        );
+        assert_eq!(describe_data_saver(DataSaverSetting::Off, false), "off");
+    }
+
+    #[test]
+    fn skill_size_bucket_scales_counts_to_five_levels() {
+        let buckets = [1, 2, 3, 5, 7, 9, 10].map(|count| skill_size_bucket(count, 10));
+        assert_eq!(buckets, [1, 1, 2, 3, 4, 5, 5]);
+        assert_eq!(skill_size_bucket(2, 2), 5);
+        assert_eq!(skill_size_bucket(1, 1), 1, "A flat cloud stays small");
+        assert_eq!(skill_size_bucket(12, 10), 5, "Counts above max clamp");
+    }
+
+    #[test]
+    fn render_skill_cloud_escapes_labels_and_sizes_chips() {
+        let html = render_skill_cloud(&[("<Rust>".to_string(), 4), ("Go".to_string(), 1)]);
+        assert!(
+            html.contains(r#"skill-cloud__chip--5" title="4 mentions">&lt;Rust&gt;</span>"#),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#"skill-cloud__chip--1" title="1 mention">Go</span>"#),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn parse_project_sort_validates_arguments() {
+        assert_eq!(parse_project_sort(&[]), Ok(None));
+        assert_eq!(
+            parse_project_sort(&["--sort", "DATE"]),
+            Ok(Some(ProjectSort::Date))
+        );
+        assert_eq!(
+            parse_project_sort(&["--sort=name"]),
+            Ok(Some(ProjectSort::Name))
+        );
+        assert!(parse_project_sort(&["--sort"]).is_err());
+        assert!(parse_project_sort(&["--sort", "size"]).is_err());
+        assert!(parse_project_sort(&["--verbose"]).is_err());
+    }
+
+    fn stub_education() -> Vec<Education> {
+        vec![
+            Education {
+                degree: "MSc Computer Science".to_string(),
+                school: "Université Paris-Saclay".to_string(),
+                years: Some("2016 – 2018".to_string()),
+                location: Some("Orsay, France".to_string()),
+                details: vec![
+                    "Distributed systems".to_string(),
+                    "Thesis on <compilers>".to_string(),
+                ],
+                link: Some("https://example.com/msc?a=1&b=2".to_string()),
+            },
+            Education {
+                degree: "BSc Mathematics".to_string(),
+                school: "Sorbonne Université".to_string(),
+                years: None,
+                location: Some("Paris, France".to_string()),
+                details: Vec::new(),
+                link: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn only_data_commands_are_shareable() {
+        assert!(is_shareable("projects"));
+        assert!(is_shareable("  Education --detail"));
+        assert!(!is_shareable("cookie"));
+        assert!(!is_shareable("clear"));
+        assert!(!is_shareable("ai --estimate hello"));
+        assert!(!is_shareable(""));
+    }
+
+    #[test]
+    fn parse_education_options_validates_arguments() {
+        assert_eq!(
+            parse_education_options(&[]),
+            Ok(EducationOptions::default())
+        );
+        assert_eq!(
+            parse_education_options(&["--school", "Paris", "Saclay", "--detail"]),
+            Ok(EducationOptions {
+                school: Some("Paris Saclay".to_string()),
+                detail: true,
+                verbose: false,
+            })
+        );
+        assert_eq!(
+            parse_education_options(&["--detail", "--school=Sorbonne"]),
+            Ok(EducationOptions {
+                school: Some("Sorbonne".to_string()),
+                detail: true,
+                verbose: false,
+            })
+        );
+        assert_eq!(
+            parse_education_options(&["--verbose"]),
+            Ok(EducationOptions {
+                verbose: true,
+                ..EducationOptions::default()
+            })
+        );
+        assert!(parse_education_options(&["--school"]).is_err());
+        assert!(parse_education_options(&["--school", "--detail"]).is_err());
+        assert!(parse_education_options(&["--years"]).is_err());
+    }
+
+    #[test]
+    fn education_school_filter_matches_case_insensitively() {
+        let education = stub_education();
+        let matches = filter_education(&education, Some("paris-saclay"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].degree, "MSc Computer Science");
+        assert_eq!(filter_education(&education, None).len(), 2);
+        assert!(filter_education(&education, Some("MIT")).is_empty());
+    }
+
+    #[test]
+    fn education_detail_toggle_controls_extended_fields() {
+        let education = stub_education();
+        let entries: Vec<&Education> = education.iter().collect();
+
+        let compact = format_education(&entries, false);
+        assert_eq!(
+            compact,
+            "MSc Computer Science\n  Université Paris-Saclay\nBSc Mathematics\n  Sorbonne Université"
+        );
+
+        let detailed = format_education(&entries, true);
+        assert!(detailed.contains("  Years: 2016 – 2018"), "{detailed}");
+        assert!(detailed.contains("  Location: Paris, France"), "{detailed}");
+        assert!(detailed.contains("  • Thesis on <compilers>"), "{detailed}");
+        assert!(
+            !detailed.ends_with('\n'),
+            "Trailing blank line should be trimmed"
+        );
+    }
+
+    #[test]
+    fn education_tolerates_missing_details_and_link() {
+        let entry: Education = serde_json::from_str(
+            r#"{"degree":"BSc","school":"Sorbonne","years":"2014","location":"Paris"}"#,
+        )
+        .expect("legacy entries still parse");
+        assert!(entry.details.is_empty());
+        assert_eq!(entry.link, None);
+
+        let entry: Education = serde_json::from_str(
+            r#"{"degree":"MSc","school":"Saclay","years":null,"location":null,"details":["Compilers"],"link":"https://example.com"}"#,
+        )
+        .expect("extended entries parse");
+        assert_eq!(entry.details, ["Compilers"]);
+        assert_eq!(entry.link.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn render_education_html_lists_details_and_escaped_link() {
+        let education = stub_education();
+        let entries: Vec<&Education> = education.iter().collect();
+        let html = render_education_html(&entries);
+        assert!(
+            html.contains("<small>2016 – 2018 · Orsay, France</small>"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<li>Thesis on &lt;compilers&gt;</li>"),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#"<a href="https://example.com/msc?a=1&amp;b=2" target="_blank""#),
+            "{html}"
+        );
+        assert_eq!(
+            html.matches("<a ").count(),
+            1,
+            "Only linked entries get an anchor"
+        );
+        assert_eq!(
+            html.matches("<ul>").count(),
+            1,
+            "Empty details render no list"
+        );
+    }
+
     #[test]
     fn render_projects_html_includes_clickable_link() {
         let collection = ProjectsCollection {
@@ -930,7 +2686,7 @@ mod tests {
 
         let output = super::render_projects_html(&collection);
         assert!(
-            output.contains("<h2>Publications</h2>"),
+            output.contains("<h2 id=\"section-publications\">Publications</h2>"),
             "Section heading should render for publications:\n{output}"
         );
         assert!(
@@ -962,7 +2718,7 @@ mod tests {
 
         let output = super::render_projects_html(&collection);
         assert!(
-            output.contains("<h2>Awards</h2>"),
+            output.contains("<h2 id=\"section-awards\">Awards</h2>"),
             "Section heading should render for awards:\n{output}"
         );
         assert!(
@@ -983,6 +2739,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_projects_html_adds_section_toc_when_multiple_groups() {
+        let award = Award {
+            title: "Top Innovator".to_string(),
+            issuer: None,
+            date: None,
+            description: None,
+        };
+        let publication = Publication {
+            title: "Whitepaper".to_string(),
+            date: None,
+            description: "Rendering notes.".to_string(),
+            tech: Vec::new(),
+            link: None,
+        };
+        let collection = ProjectsCollection {
+            projects: Vec::new(),
+            publications: vec![publication],
+            awards: vec![award.clone()],
+        };
+
+        let output = super::render_projects_html(&collection);
+        assert!(
+            output.contains("<nav class=\"output-toc\" aria-label=\"Sections\">"),
+            "Multiple groups should get a table of contents:\n{output}"
+        );
+        assert!(output.contains("data-anchor=\"section-publications\">Publications</button>"));
+        assert!(output.contains("data-anchor=\"section-awards\">Awards</button>"));
+        assert!(
+            output.find("output-toc") < output.find("<section"),
+            "The table of contents should precede the sections:\n{output}"
+        );
+
+        let single = super::render_projects_html(&ProjectsCollection {
+            projects: Vec::new(),
+            publications: Vec::new(),
+            awards: vec![award],
+        });
+        assert!(
+            !single.contains("output-toc"),
+            "A single group needs no table of contents:\n{single}"
+        );
+        assert_eq!(
+            section_anchor("Side Projects & Labs"),
+            "section-side-projects-labs"
+        );
+    }
+
     #[test]
     fn contact_html_escapes_special_characters() {
         let profile = crate::state::Profile {
@@ -1021,6 +2825,61 @@ mod tests {
         );
     }
 
+    fn reparsed(html: &str) -> String {
+        let template = utils::document()
+            .unwrap()
+            .create_element("template")
+            .unwrap();
+        template.set_inner_html(html);
+        template.inner_html()
+    }
+
+    #[wasm_bindgen_test]
+    fn strict_html_leaves_projects_and_contact_output_unchanged() {
+        let projects = super::render_projects_html(&ProjectsCollection {
+            projects: vec![Project {
+                title: "Linked <Project>".to_string(),
+                date: Some("2024".to_string()),
+                description: "Has a URL & a stack.".to_string(),
+                tech: vec!["Rust".to_string(), "WASM".to_string()],
+                link: Some("https://example.com/demo?a=1&b=2".to_string()),
+            }],
+            publications: vec![Publication {
+                title: "Whitepaper".to_string(),
+                date: None,
+                description: "Rendering notes.".to_string(),
+                tech: Vec::new(),
+                link: None,
+            }],
+            awards: vec![Award {
+                title: "Top Innovator".to_string(),
+                issuer: Some("TechConf".to_string()),
+                date: Some("2022".to_string()),
+                description: None,
+            }],
+        });
+        let contact = super::render_contact_html(&crate::state::Profile {
+            name: "Alex".to_string(),
+            headline: "Dev & Lead".to_string(),
+            summary_fr: Some("Résumé".to_string()),
+            summary_en: Some("Summary".to_string()),
+            location: Some("Paris".to_string()),
+            email: Some("alex@example.com".to_string()),
+            links: crate::state::ProfileLinks {
+                github: Some("https://github.com/example".to_string()),
+                linkedin: None,
+                website: Some("https://zqsdev.com".to_string()),
+                resume_url: Some("https://cv.zqsdev.com".to_string()),
+            },
+            resume_variants: Vec::new(),
+            languages: Some(vec!["en".to_string(), "fr".to_string()]),
+        });
+
+        for html in [projects, contact] {
+            assert_eq!(utils::sanitize_fragment(&html).unwrap(), reparsed(&html));
+        }
+    }
+
     #[test]
     fn links_html_includes_resume_link() {
         let links = crate::state::ProfileLinks {
@@ -1048,24 +2907,66 @@ mod tests {
             "LinkedIn label should appear in links HTML: {html}"
         );
     }
-}
 
-fn render_projects_html(collection: &ProjectsCollection) -> String {
-    let has_projects = !collection.projects.is_empty();
-    let has_publications = !collection.publications.is_empty();
-    let has_awards = !collection.awards.is_empty();
+    #[derive(Debug)]
+    struct FixedRandom(f64);
 
-    if !has_projects && !has_publications && !has_awards {
-        return String::new();
+    impl crate::utils::random::RandomSource for FixedRandom {
+        fn next_unit(&self) -> f64 {
+            self.0
+        }
     }
 
-    let mut html = String::from("<div class=\"projects\">");
-    if has_projects {
-        html.push_str("<section class=\"projects-group\">");
-        html.push_str("<h2>Projects</h2>");
+    fn pokemon_attempt(chance: u8, roll: f64) -> PokemonAttemptOutcome {
+        let mut state = AppState::new();
+        state.set_pokemon_capture_chance(chance);
+        state.random = std::rc::Rc::new(FixedRandom(roll));
+        match execute("pokemon", &state, &[]) {
+            Ok(CommandAction::PokemonAttempt(outcome)) => outcome,
+            other => panic!("Expected a capture attempt, got {other:?}"),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn fixed_rolls_decide_pokemon_captures() {
+        let caught = pokemon_attempt(5, 0.04);
+        assert!(caught.success, "Roll 4 beats a 5% chance");
+        assert_eq!((caught.current_chance, caught.next_chance), (5, 1));
+
+        let missed = pokemon_attempt(5, 0.05);
+        assert!(!missed.success, "Roll 5 misses a 5% chance");
+        assert_eq!(missed.next_chance, 6);
+
+        let capped = pokemon_attempt(100, 0.999);
+        assert!(capped.success, "A 100% chance always catches");
+    }
+
+    #[wasm_bindgen_test]
+    fn seeded_pokemon_runs_are_reproducible() {
+        let run = |seed: u64| {
+            let mut state = AppState::new();
+            state.random = std::rc::Rc::new(crate::utils::random::SeededRandom::new(seed));
+            (0..20)
+                .map(|_| match execute("pokemon", &state, &[]) {
+                    Ok(CommandAction::PokemonAttempt(outcome)) => {
+                        state.set_pokemon_capture_chance(outcome.next_chance);
+                        outcome.success
+                    }
+                    other => panic!("Expected a capture attempt, got {other:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(2481), run(2481));
+    }
+}
+
+fn render_projects_html(collection: &ProjectsCollection) -> String {
+    let mut sections: Vec<(&str, String)> = Vec::new();
+    if !collection.projects.is_empty() {
+        let mut body = String::new();
         for project in &collection.projects {
             push_project_like(
-                &mut html,
+                &mut body,
                 "project",
                 &project.title,
                 project.date.as_deref(),
@@ -1074,15 +2975,14 @@ fn render_projects_html(collection: &ProjectsCollection) -> String {
                 project.link.as_deref(),
             );
         }
-        html.push_str("</section>");
+        sections.push(("Projects", body));
     }
 
-    if has_publications {
-        html.push_str("<section class=\"projects-group\">");
-        html.push_str("<h2>Publications</h2>");
+    if !collection.publications.is_empty() {
+        let mut body = String::new();
         for publication in &collection.publications {
             push_project_like(
-                &mut html,
+                &mut body,
                 "publication",
                 &publication.title,
                 publication.date.as_deref(),
@@ -1091,21 +2991,61 @@ fn render_projects_html(collection: &ProjectsCollection) -> String {
                 publication.link.as_deref(),
             );
         }
-        html.push_str("</section>");
+        sections.push(("Publications", body));
     }
 
-    if has_awards {
-        html.push_str("<section class=\"projects-group\">");
-        html.push_str("<h2>Awards</h2>");
+    if !collection.awards.is_empty() {
+        let mut body = String::new();
         for award in &collection.awards {
-            push_award(&mut html, award);
+            push_award(&mut body, award);
         }
+        sections.push(("Awards", body));
+    }
+
+    if sections.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::from("<div class=\"projects\">");
+    if sections.len() > 1 {
+        push_section_toc(&mut html, sections.iter().map(|(title, _)| *title));
+    }
+    for (title, body) in &sections {
+        html.push_str("<section class=\"projects-group\">");
+        html.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>",
+            section_anchor(title),
+            utils::escape_html(title)
+        ));
+        html.push_str(body);
         html.push_str("</section>");
     }
     html.push_str("</div>");
     html
 }
 
+pub fn section_anchor(title: &str) -> String {
+    let slug = title
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("section-{slug}")
+}
+
+fn push_section_toc<'a>(html: &mut String, titles: impl Iterator<Item = &'a str>) {
+    html.push_str("<nav class=\"output-toc\" aria-label=\"Sections\">");
+    for title in titles {
+        html.push_str(&format!(
+            "<button type=\"button\" class=\"output-toc__link\" data-anchor=\"{}\">{}</button>",
+            section_anchor(title),
+            utils::escape_html(title)
+        ));
+    }
+    html.push_str("</nav>");
+}
+
 fn push_project_like(
     html: &mut String,
     class_name: &str,
@@ -1160,6 +3100,60 @@ fn push_project_like(
     html.push_str("</article>");
 }
 
+fn render_education_html(education: &[&Education]) -> String {
+    let mut html = String::from("<div class=\"education\">");
+    for entry in education {
+        html.push_str("<article class=\"education-entry\"><h3>");
+        html.push_str(&utils::escape_html(&entry.degree));
+        html.push_str("</h3><p>");
+        html.push_str(&utils::escape_html(&entry.school));
+        let meta = [entry.years.as_deref(), entry.location.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|value| !value.trim().is_empty())
+            .map(utils::escape_html)
+            .collect::<Vec<_>>();
+        if !meta.is_empty() {
+            html.push_str(" <small>");
+            html.push_str(&meta.join(" · "));
+            html.push_str("</small>");
+        }
+        html.push_str("</p>");
+
+        let details = entry
+            .details
+            .iter()
+            .map(|detail| detail.trim())
+            .filter(|detail| !detail.is_empty())
+            .collect::<Vec<_>>();
+        if !details.is_empty() {
+            html.push_str("<ul>");
+            for detail in details {
+                html.push_str("<li>");
+                html.push_str(&utils::escape_html(detail));
+                html.push_str("</li>");
+            }
+            html.push_str("</ul>");
+        }
+
+        if let Some(link) = entry
+            .link
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+        {
+            let safe_link = utils::escape_html(link);
+            html.push_str("<p><a href=\"");
+            html.push_str(&safe_link);
+            html.push_str("\" target=\"_blank\" rel=\"noopener noreferrer\">");
+            html.push_str(&safe_link);
+            html.push_str("</a></p>");
+        }
+        html.push_str("</article>");
+    }
+    html.push_str("</div>");
+    html
+}
+
 fn push_award(html: &mut String, award: &Award) {
     html.push_str("<article class=\"award\">");
     html.push_str("<h3>");
@@ -0,0 +1,79 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmAction {
+    OpenLink(String),
+    ResumeAiSession,
+    ClearPins,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingConfirmation {
+    pub prompt: String,
+    pub action: ConfirmAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmOutcome {
+    Accepted(ConfirmAction),
+    Declined,
+}
+
+impl PendingConfirmation {
+    pub fn new(prompt: impl Into<String>, action: ConfirmAction) -> Self {
+        Self {
+            prompt: prompt.into(),
+            action,
+        }
+    }
+
+    // The default answer is "no": anything other than an explicit yes cancels.
+    pub fn resolve(self, reply: &str) -> ConfirmOutcome {
+        if is_affirmative(reply) {
+            ConfirmOutcome::Accepted(self.action)
+        } else {
+            ConfirmOutcome::Declined
+        }
+    }
+}
+
+pub fn is_affirmative(reply: &str) -> bool {
+    matches!(
+        reply.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes" | "o" | "oui"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_link() -> PendingConfirmation {
+        PendingConfirmation::new(
+            "Open the link anyway? [y/N]",
+            ConfirmAction::OpenLink("https://zqsdev.com/cv.pdf".to_string()),
+        )
+    }
+
+    #[test]
+    fn explicit_yes_runs_the_action() {
+        for reply in ["y", "Y", " yes ", "oui", "O"] {
+            assert_eq!(
+                open_link().resolve(reply),
+                ConfirmOutcome::Accepted(ConfirmAction::OpenLink(
+                    "https://zqsdev.com/cv.pdf".to_string()
+                )),
+                "{reply:?} should confirm"
+            );
+        }
+    }
+
+    #[test]
+    fn anything_else_declines() {
+        for reply in ["", "n", "no", "N", "yes please", "projects"] {
+            assert_eq!(
+                open_link().resolve(reply),
+                ConfirmOutcome::Declined,
+                "{reply:?} should decline"
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use wasm_bindgen::JsValue;
+
+const USAGE: &str =
+    "Usage: debug [state | toast | rain [drops] | shaw | explode | markdown <fixture> | fill [lines] | seed <n>]";
+const DEFAULT_RAIN_DROPS: usize = 120;
+const DEFAULT_FILL_LINES: usize = 200;
+const MAX_REPEAT: usize = 2000;
+
+const MARKDOWN_FIXTURES: &[(&str, &str)] = &[
+    (
+        "headings",
+        "# Heading 1\n## Heading 2\n### Heading 3\n\nParagraph with **bold**, *italic* and `code`.",
+    ),
+    (
+        "lists",
+        "- First item\n- Second item with a [link](https://zqsdev.com)\n  - Nested item\n\n1. One\n2. Two\n3. Three",
+    ),
+    (
+        "code",
+        "Some Rust:\n\n```rust\nfn main() {\n    println!(\"Hello, terminal!\");\n}\n```",
+    ),
+    (
+        "table",
+        "| Skill | Level |\n| --- | --- |\n| Rust | Advanced |\n| Kubernetes | Advanced |\n| Go | Intermediate |",
+    ),
+    (
+        "long",
+        "Line 1\n\nLine 2\n\nLine 3\n\nLine 4\n\nLine 5\n\nLine 6\n\nLine 7\n\nLine 8\n\nLine 9\n\nLine 10\n\nLine 11\n\nLine 12\n\nLine 13\n\nLine 14\n\nLine 15\n\nLine 16\n\nLine 17\n\nLine 18\n\nLine 19\n\nLine 20",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugAction {
+    Toast,
+    Rain(usize),
+    Shaw,
+    Explode,
+    Markdown(&'static str),
+    Fill(usize),
+    Seed(u64),
+}
+
+// Renderer entry points reachable from the `debug` command. Implemented by the
+// terminal so effects get the same cleanup timers as the real triggers.
+pub trait DebugEffects {
+    fn debug_toast(&self) -> Result<(), JsValue>;
+    fn debug_rain(&self, drops: usize) -> Result<(), JsValue>;
+    fn debug_shaw(&self) -> Result<(), JsValue>;
+    fn debug_explode(&self) -> Result<(), JsValue>;
+    fn debug_markdown(&self, source: &str) -> Result<(), JsValue>;
+    fn debug_fill(&self, lines: usize) -> Result<(), JsValue>;
+    fn debug_seed(&self, seed: u64) -> Result<(), JsValue>;
+}
+
+pub fn parse(args: &[&str]) -> Result<DebugAction, String> {
+    let Some((subcommand, rest)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    match subcommand.to_ascii_lowercase().as_str() {
+        "toast" => Ok(DebugAction::Toast),
+        "rain" => parse_count(rest, DEFAULT_RAIN_DROPS).map(DebugAction::Rain),
+        "shaw" => Ok(DebugAction::Shaw),
+        "explode" => Ok(DebugAction::Explode),
+        "markdown" | "md" => parse_fixture(rest).map(DebugAction::Markdown),
+        "fill" => parse_count(rest, DEFAULT_FILL_LINES).map(DebugAction::Fill),
+        "seed" => parse_seed(rest).map(DebugAction::Seed),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+pub fn dispatch(effects: &impl DebugEffects, action: DebugAction) -> Result<(), JsValue> {
+    match action {
+        DebugAction::Toast => effects.debug_toast(),
+        DebugAction::Rain(drops) => effects.debug_rain(drops),
+        DebugAction::Shaw => effects.debug_shaw(),
+        DebugAction::Explode => effects.debug_explode(),
+        DebugAction::Markdown(source) => effects.debug_markdown(source),
+        DebugAction::Fill(lines) => effects.debug_fill(lines),
+        DebugAction::Seed(seed) => effects.debug_seed(seed),
+    }
+}
+
+fn parse_count(args: &[&str], default: usize) -> Result<usize, String> {
+    match args.first() {
+        None => Ok(default),
+        Some(raw) => raw
+            .parse::<usize>()
+            .ok()
+            .filter(|count| (1..=MAX_REPEAT).contains(count))
+            .ok_or_else(|| format!("Expected a count between 1 and {MAX_REPEAT}, got `{raw}`.")),
+    }
+}
+
+fn parse_seed(args: &[&str]) -> Result<u64, String> {
+    let raw = args
+        .first()
+        .ok_or_else(|| "Usage: debug seed <n>".to_string())?;
+    raw.parse::<u64>()
+        .map_err(|_| format!("Expected a numeric seed, got `{raw}`."))
+}
+
+fn parse_fixture(args: &[&str]) -> Result<&'static str, String> {
+    let names = MARKDOWN_FIXTURES
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let Some(requested) = args.first() else {
+        return Err(format!(
+            "Usage: debug markdown <fixture>. Fixtures: {names}."
+        ));
+    };
+    MARKDOWN_FIXTURES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(requested))
+        .map(|(_, source)| *source)
+        .ok_or_else(|| format!("Unknown fixture `{requested}`. Fixtures: {names}."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{self, CommandAction};
+    use crate::state::AppState;
+    use std::cell::RefCell;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[derive(Default)]
+    struct RecordingEffects {
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl RecordingEffects {
+        fn record(&self, call: String) -> Result<(), JsValue> {
+            self.calls.borrow_mut().push(call);
+            Ok(())
+        }
+    }
+
+    impl DebugEffects for RecordingEffects {
+        fn debug_toast(&self) -> Result<(), JsValue> {
+            self.record("toast".to_string())
+        }
+        fn debug_rain(&self, drops: usize) -> Result<(), JsValue> {
+            self.record(format!("rain {drops}"))
+        }
+        fn debug_shaw(&self) -> Result<(), JsValue> {
+            self.record("shaw".to_string())
+        }
+        fn debug_explode(&self) -> Result<(), JsValue> {
+            self.record("explode".to_string())
+        }
+        fn debug_markdown(&self, source: &str) -> Result<(), JsValue> {
+            self.record(format!("markdown {}", source.lines().next().unwrap_or("")))
+        }
+        fn debug_fill(&self, lines: usize) -> Result<(), JsValue> {
+            self.record(format!("fill {lines}"))
+        }
+        fn debug_seed(&self, seed: u64) -> Result<(), JsValue> {
+            self.record(format!("seed {seed}"))
+        }
+    }
+
+    fn run(line: &str, effects: &RecordingEffects) {
+        let args = line.split_whitespace().collect::<Vec<_>>();
+        let state = AppState::new();
+        match commands::execute(args[0], &state, &args[1..]) {
+            Ok(CommandAction::Debug(action)) => dispatch(effects, action).expect("dispatch"),
+            _ => panic!("`{line}` should produce a debug action"),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn dispatcher_reaches_each_renderer_call() {
+        let effects = RecordingEffects::default();
+        for line in [
+            "debug toast",
+            "debug rain 50",
+            "debug shaw",
+            "debug explode",
+            "debug markdown table",
+            "debug fill 200",
+            "debug fill",
+            "debug seed 42",
+        ] {
+            run(line, &effects);
+        }
+        assert_eq!(
+            effects.calls.into_inner(),
+            vec![
+                "toast",
+                "rain 50",
+                "shaw",
+                "explode",
+                "markdown | Skill | Level |",
+                "fill 200",
+                "fill 200",
+                "seed 42",
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn invalid_arguments_are_reported() {
+        assert_eq!(parse(&[]), Err(USAGE.to_string()));
+        assert_eq!(parse(&["nope"]), Err(USAGE.to_string()));
+        assert!(parse(&["rain", "0"]).is_err());
+        assert!(parse(&["fill", "lots"]).is_err());
+        assert!(parse(&["seed"]).is_err());
+        assert!(parse(&["seed", "-1"]).is_err());
+        let missing = parse(&["markdown", "nope"]).unwrap_err();
+        assert!(missing.contains("table"), "Lists fixtures: {missing}");
+    }
+}
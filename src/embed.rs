@@ -0,0 +1,496 @@
+use crate::terminal::Terminal;
+use crate::utils;
+use gloo_timers::future::TimeoutFuture;
+use serde::Deserialize;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Headers, MessageEvent};
+
+const EMBED_TOKEN_HEADER: &str = "X-Embed-Token";
+const EMBED_ORIGIN_HEADER: &str = "X-Embed-Origin";
+const EMBED_TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+const TOKEN_MESSAGE: &str = "zqs-embed-token";
+const TOKEN_REQUEST_MESSAGE: &str = "zqs-embed-token-request";
+const TOKEN_WAIT_MS: u32 = 1_500;
+const TOKEN_POLL_MS: u32 = 100;
+
+// Host pages (cv.zqsdev.com…) drive the terminal through these exports. Calls
+// made before the résumé data is ready are queued and replayed in order.
+pub trait EmbedTarget {
+    fn run_command(&self, command: &str) -> Result<(), JsValue>;
+    fn set_theme(&self, name: &str) -> Result<(), JsValue>;
+    fn activate_ai_mode(&self) -> Result<(), JsValue>;
+}
+
+impl EmbedTarget for Terminal {
+    fn run_command(&self, command: &str) -> Result<(), JsValue> {
+        self.execute_suggestion(command)
+    }
+
+    fn set_theme(&self, name: &str) -> Result<(), JsValue> {
+        self.set_theme(name)
+    }
+
+    fn activate_ai_mode(&self) -> Result<(), JsValue> {
+        Terminal::activate_ai_mode(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EmbedCall {
+    RunCommand(String),
+    SetTheme(String),
+    ActivateAiMode,
+}
+
+thread_local! {
+    static TARGET: RefCell<Option<Rc<dyn EmbedTarget>>> = RefCell::new(None);
+    static READY: Cell<bool> = const { Cell::new(false) };
+    static PENDING: RefCell<Vec<EmbedCall>> = const { RefCell::new(Vec::new()) };
+    static READY_CALLBACKS: RefCell<Vec<js_sys::Function>> = const { RefCell::new(Vec::new()) };
+    static EMBED_TOKEN: RefCell<Option<EmbedToken>> = const { RefCell::new(None) };
+}
+
+// Short-lived proof, minted by the server for allowlisted pages, that AI
+// requests come from a sanctioned embed. The partner page fetches it and
+// posts it into the iframe; `origin` is the poster's, as the browser saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmbedToken {
+    token: String,
+    expires_at: i64,
+    origin: String,
+}
+
+impl EmbedToken {
+    fn is_fresh(&self, now_secs: i64) -> bool {
+        self.expires_at - EMBED_TOKEN_REFRESH_MARGIN_SECS > now_secs
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    token: String,
+    expires_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbedCredentials {
+    origin: String,
+    token: Option<String>,
+}
+
+fn accept_token_message(origin: &str, message: TokenMessage) -> Option<EmbedToken> {
+    let origin = origin_of(origin)?;
+    let token = message.token.trim();
+    (message.kind == TOKEN_MESSAGE && !token.is_empty()).then(|| EmbedToken {
+        token: token.to_string(),
+        expires_at: message.expires_at,
+        origin,
+    })
+}
+
+fn origin_of(url: &str) -> Option<String> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next()?;
+    (matches!(scheme, "http" | "https") && !host.is_empty()).then(|| format!("{scheme}://{host}"))
+}
+
+// `window.self !== window.top`; reading `top` is allowed across origins.
+pub fn is_embedded() -> bool {
+    let Some(window) = utils::window() else {
+        return false;
+    };
+    match window.top() {
+        Ok(Some(top)) => !js_sys::Object::is(&window, &top),
+        _ => false,
+    }
+}
+
+// Only messages from the embedding page count; the iframe asks it for a
+// first token straight away.
+pub fn listen_for_tokens() -> Result<(), JsValue> {
+    if !is_embedded() {
+        return Ok(());
+    }
+    let window = utils::window().ok_or_else(|| JsValue::from_str("window unavailable"))?;
+    let closure = Closure::wrap(Box::new(move |event: MessageEvent| {
+        let from_parent = match (
+            event.source(),
+            utils::window().and_then(|w| w.parent().ok()),
+        ) {
+            (Some(source), Some(Some(parent))) => js_sys::Object::is(&source, &parent),
+            _ => false,
+        };
+        if from_parent {
+            receive_token(&event.origin(), event.data());
+        }
+    }) as Box<dyn FnMut(_)>);
+    window.add_event_listener_with_callback("message", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    request_token();
+    Ok(())
+}
+
+fn receive_token(origin: &str, data: JsValue) {
+    let Ok(message) = serde_wasm_bindgen::from_value::<TokenMessage>(data) else {
+        return;
+    };
+    if let Some(token) = accept_token_message(origin, message) {
+        EMBED_TOKEN.with(|slot| *slot.borrow_mut() = Some(token));
+    }
+}
+
+fn request_token() {
+    let Some(Ok(Some(parent))) = utils::window().map(|window| window.parent()) else {
+        return;
+    };
+    let message = js_sys::Object::new();
+    let posted = js_sys::Reflect::set(
+        &message,
+        &JsValue::from_str("type"),
+        &JsValue::from_str(TOKEN_REQUEST_MESSAGE),
+    )
+    .and_then(|_| parent.post_message(&message, "*"));
+    if let Err(err) = posted {
+        utils::log(&format!(
+            "Failed to ask the host page for an embed token: {:?}",
+            err
+        ));
+    }
+}
+
+fn fresh_credentials(now_secs: i64) -> Option<EmbedCredentials> {
+    EMBED_TOKEN.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .filter(|token| token.is_fresh(now_secs))
+            .map(|token| EmbedCredentials {
+                origin: token.origin.clone(),
+                token: Some(token.token.clone()),
+            })
+    })
+}
+
+fn now_secs() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+// Top-level visits send nothing. Inside an iframe a stale token is requested
+// again from the host page; if none arrives in time the request still names
+// the host (from the referrer) and the server decides.
+pub async fn ai_request_credentials() -> Option<EmbedCredentials> {
+    if !is_embedded() {
+        return None;
+    }
+    if let Some(credentials) = fresh_credentials(now_secs()) {
+        return Some(credentials);
+    }
+    request_token();
+    for _ in 0..TOKEN_WAIT_MS / TOKEN_POLL_MS {
+        TimeoutFuture::new(TOKEN_POLL_MS).await;
+        if let Some(credentials) = fresh_credentials(now_secs()) {
+            return Some(credentials);
+        }
+    }
+    let referrer = utils::window()?.document()?.referrer();
+    origin_of(&referrer).map(|origin| EmbedCredentials {
+        origin,
+        token: None,
+    })
+}
+
+pub fn apply_headers(
+    headers: &Headers,
+    credentials: Option<&EmbedCredentials>,
+) -> Result<(), JsValue> {
+    let Some(credentials) = credentials else {
+        return Ok(());
+    };
+    headers.set(EMBED_ORIGIN_HEADER, &credentials.origin)?;
+    match credentials.token.as_deref() {
+        Some(token) => headers.set(EMBED_TOKEN_HEADER, token),
+        None => Ok(()),
+    }
+}
+
+pub fn attach(target: Rc<dyn EmbedTarget>) {
+    TARGET.with(|slot| *slot.borrow_mut() = Some(target));
+}
+
+pub fn mark_ready() {
+    READY.with(|ready| ready.set(true));
+    let pending = PENDING.with(|queue| std::mem::take(&mut *queue.borrow_mut()));
+    for call in pending {
+        if let Err(err) = dispatch(&call) {
+            utils::log(&format!("Queued embed call {call:?} failed: {:?}", err));
+        }
+    }
+    let callbacks = READY_CALLBACKS.with(|queue| std::mem::take(&mut *queue.borrow_mut()));
+    for callback in callbacks {
+        notify(&callback);
+    }
+}
+
+fn is_ready() -> bool {
+    READY.with(Cell::get) && TARGET.with(|slot| slot.borrow().is_some())
+}
+
+fn submit(call: EmbedCall) -> Result<(), JsValue> {
+    if is_ready() {
+        dispatch(&call)
+    } else {
+        PENDING.with(|queue| queue.borrow_mut().push(call));
+        Ok(())
+    }
+}
+
+fn dispatch(call: &EmbedCall) -> Result<(), JsValue> {
+    let Some(target) = TARGET.with(|slot| slot.borrow().clone()) else {
+        return Ok(());
+    };
+    match call {
+        EmbedCall::RunCommand(command) => target.run_command(command),
+        EmbedCall::SetTheme(name) => target.set_theme(name),
+        EmbedCall::ActivateAiMode => target.activate_ai_mode(),
+    }
+}
+
+fn notify(callback: &js_sys::Function) {
+    if let Err(err) = callback.call0(&JsValue::NULL) {
+        utils::log(&format!("Embed on_ready callback failed: {:?}", err));
+    }
+}
+
+#[wasm_bindgen]
+pub fn run_command(cmd: &str) -> Result<(), JsValue> {
+    let command = cmd.trim();
+    if command.is_empty() {
+        return Ok(());
+    }
+    submit(EmbedCall::RunCommand(command.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn set_theme(name: &str) -> Result<(), JsValue> {
+    submit(EmbedCall::SetTheme(name.trim().to_ascii_lowercase()))
+}
+
+#[wasm_bindgen]
+pub fn activate_ai_mode() -> Result<(), JsValue> {
+    submit(EmbedCall::ActivateAiMode)
+}
+
+#[wasm_bindgen]
+pub fn on_ready(callback: js_sys::Function) {
+    if is_ready() {
+        notify(&callback);
+    } else {
+        READY_CALLBACKS.with(|queue| queue.borrow_mut().push(callback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: RefCell<Vec<EmbedCall>>,
+    }
+
+    impl EmbedTarget for Recorder {
+        fn run_command(&self, command: &str) -> Result<(), JsValue> {
+            self.calls
+                .borrow_mut()
+                .push(EmbedCall::RunCommand(command.to_string()));
+            Ok(())
+        }
+
+        fn set_theme(&self, name: &str) -> Result<(), JsValue> {
+            self.calls
+                .borrow_mut()
+                .push(EmbedCall::SetTheme(name.to_string()));
+            Ok(())
+        }
+
+        fn activate_ai_mode(&self) -> Result<(), JsValue> {
+            self.calls.borrow_mut().push(EmbedCall::ActivateAiMode);
+            Ok(())
+        }
+    }
+
+    fn reset() -> Rc<Recorder> {
+        READY.with(|ready| ready.set(false));
+        PENDING.with(|queue| queue.borrow_mut().clear());
+        READY_CALLBACKS.with(|queue| queue.borrow_mut().clear());
+        let recorder = Rc::new(Recorder::default());
+        attach(recorder.clone());
+        recorder
+    }
+
+    fn token_message(json: &str) -> TokenMessage {
+        serde_json::from_str(json).expect("token message parses")
+    }
+
+    #[test]
+    fn embed_tokens_are_refreshed_ahead_of_expiry() {
+        let token = accept_token_message(
+            "https://partner.example",
+            token_message(
+                r#"{"type":"zqs-embed-token","token":"1750000600.abcd","expires_at":1750000600}"#,
+            ),
+        )
+        .expect("token accepted");
+        assert!(token.is_fresh(1_750_000_000));
+        assert!(token.is_fresh(1_750_000_539));
+        assert!(
+            !token.is_fresh(1_750_000_540),
+            "Tokens within a minute of expiry are fetched again"
+        );
+        assert!(!token.is_fresh(1_750_000_600));
+    }
+
+    #[test]
+    fn token_messages_are_bound_to_the_posting_origin() {
+        let message =
+            r#"{"type":"zqs-embed-token","token":" 1750000600.abcd ","expires_at":1750000600}"#;
+        assert_eq!(
+            accept_token_message("https://partner.example", token_message(message)),
+            Some(EmbedToken {
+                token: "1750000600.abcd".to_string(),
+                expires_at: 1_750_000_600,
+                origin: "https://partner.example".to_string(),
+            })
+        );
+        assert_eq!(accept_token_message("null", token_message(message)), None);
+        let other = r#"{"type":"something-else","token":"abc","expires_at":1}"#;
+        assert_eq!(
+            accept_token_message("https://partner.example", token_message(other)),
+            None
+        );
+        let blank = r#"{"type":"zqs-embed-token","token":"  ","expires_at":1}"#;
+        assert_eq!(
+            accept_token_message("https://partner.example", token_message(blank)),
+            None
+        );
+    }
+
+    #[test]
+    fn referrers_reduce_to_their_origin() {
+        assert_eq!(
+            origin_of("https://partner.example/cv?tab=ai#top").as_deref(),
+            Some("https://partner.example")
+        );
+        assert_eq!(
+            origin_of("http://localhost:8080").as_deref(),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(origin_of(""), None);
+        assert_eq!(origin_of("file:///tmp/cv.html"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_runner_page_is_not_embedded() {
+        assert!(!is_embedded());
+    }
+
+    #[wasm_bindgen_test]
+    fn posted_tokens_become_request_credentials() {
+        EMBED_TOKEN.with(|slot| slot.borrow_mut().take());
+        let now = now_secs();
+        let data = js_sys::JSON::parse(&format!(
+            r#"{{"type":"zqs-embed-token","token":"{}.abcd","expires_at":{}}}"#,
+            now + 600,
+            now + 600
+        ))
+        .expect("message data");
+        receive_token("https://partner.example", data);
+        let credentials = fresh_credentials(now).expect("fresh token");
+        assert_eq!(credentials.origin, "https://partner.example");
+        assert!(
+            fresh_credentials(now + 600).is_none(),
+            "Expired tokens are dropped"
+        );
+
+        let headers = Headers::new().expect("headers");
+        apply_headers(&headers, None).expect("no credentials");
+        assert_eq!(headers.get(EMBED_ORIGIN_HEADER).expect("get"), None);
+        apply_headers(&headers, Some(&credentials)).expect("credentials");
+        assert_eq!(
+            headers.get("x-embed-origin").expect("get").as_deref(),
+            Some("https://partner.example")
+        );
+        assert_eq!(
+            headers.get("x-embed-token").expect("get"),
+            credentials.token
+        );
+
+        let unsigned = EmbedCredentials {
+            origin: "https://partner.example".to_string(),
+            token: None,
+        };
+        let headers = Headers::new().expect("headers");
+        apply_headers(&headers, Some(&unsigned)).expect("origin only");
+        assert_eq!(headers.get(EMBED_TOKEN_HEADER).expect("get"), None);
+        assert!(headers.get(EMBED_ORIGIN_HEADER).expect("get").is_some());
+        EMBED_TOKEN.with(|slot| slot.borrow_mut().take());
+    }
+
+    #[wasm_bindgen_test]
+    fn calls_before_ready_are_queued_and_replayed_in_order() {
+        let recorder = reset();
+        run_command("  projects ").expect("queued");
+        set_theme("Matrix").expect("queued");
+        activate_ai_mode().expect("queued");
+        run_command("   ").expect("blank commands are ignored");
+        assert!(
+            recorder.calls.borrow().is_empty(),
+            "Nothing runs before data"
+        );
+
+        mark_ready();
+        assert_eq!(
+            *recorder.calls.borrow(),
+            [
+                EmbedCall::RunCommand("projects".to_string()),
+                EmbedCall::SetTheme("matrix".to_string()),
+                EmbedCall::ActivateAiMode,
+            ]
+        );
+
+        run_command("skills").expect("direct");
+        assert_eq!(
+            recorder.calls.borrow().last(),
+            Some(&EmbedCall::RunCommand("skills".to_string())),
+            "Calls after ready go straight through"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn on_ready_callbacks_fire_once_data_is_loaded() {
+        reset();
+        let global = js_sys::global();
+        let counter = JsValue::from_str("__zqsEmbedReadyCount");
+        js_sys::Reflect::set(&global, &counter, &JsValue::from_f64(0.0)).expect("reset");
+        let callback = js_sys::Function::new_no_args(
+            "globalThis.__zqsEmbedReadyCount = globalThis.__zqsEmbedReadyCount + 1;",
+        );
+        let count = || {
+            js_sys::Reflect::get(&global, &counter)
+                .expect("counter")
+                .as_f64()
+        };
+
+        on_ready(callback.clone());
+        assert_eq!(count(), Some(0.0));
+        mark_ready();
+        assert_eq!(count(), Some(1.0));
+        on_ready(callback);
+        assert_eq!(count(), Some(2.0), "Late subscribers are called at once");
+    }
+}
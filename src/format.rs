@@ -0,0 +1,343 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+}
+
+const FR_GROUP_SEPARATOR: char = '\u{202F}';
+const READING_WORDS_PER_MINUTE: usize = 200;
+pub const READING_TIME_DEFAULT_MIN_WORDS: usize = 150;
+const RELATIVE_NOW_THRESHOLD_SECS: u64 = 45;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+const DURATION_UNITS: [(Unit, u64); 4] = [
+    (Unit::Day, DAY),
+    (Unit::Hour, HOUR),
+    (Unit::Minute, MINUTE),
+    (Unit::Second, 1),
+];
+
+const RELATIVE_UNITS: [(Unit, u64); 6] = [
+    (Unit::Year, YEAR),
+    (Unit::Month, MONTH),
+    (Unit::Day, DAY),
+    (Unit::Hour, HOUR),
+    (Unit::Minute, MINUTE),
+    (Unit::Second, 1),
+];
+
+pub fn format_count(n: u64, locale: Locale) -> String {
+    let separator = match locale {
+        Locale::En => ',',
+        Locale::Fr => FR_GROUP_SEPARATOR,
+    };
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+pub fn format_percent(n: u64, locale: Locale) -> String {
+    match locale {
+        Locale::En => format!("{}%", format_count(n, locale)),
+        Locale::Fr => format!("{}\u{00A0}%", format_count(n, locale)),
+    }
+}
+
+pub fn format_duration(secs: u64, locale: Locale) -> String {
+    let mut parts = Vec::new();
+    let mut remaining = secs;
+    for (unit, size) in DURATION_UNITS {
+        let amount = remaining / size;
+        remaining %= size;
+        if amount > 0 {
+            parts.push(quantity(amount, unit, locale));
+        }
+        if parts.len() == 2 {
+            break;
+        }
+    }
+    if parts.is_empty() {
+        return quantity(0, Unit::Second, locale);
+    }
+    parts.join(" ")
+}
+
+pub fn format_relative(past_ms: f64, now_ms: f64, locale: Locale) -> String {
+    let elapsed_secs = if now_ms > past_ms {
+        ((now_ms - past_ms) / 1000.0).floor() as u64
+    } else {
+        0
+    };
+    if elapsed_secs < RELATIVE_NOW_THRESHOLD_SECS {
+        return match locale {
+            Locale::En => "just now".to_string(),
+            Locale::Fr => "à l’instant".to_string(),
+        };
+    }
+    let (unit, size) = RELATIVE_UNITS
+        .iter()
+        .copied()
+        .find(|(_, size)| elapsed_secs >= *size)
+        .unwrap_or((Unit::Second, 1));
+    let amount = quantity(elapsed_secs / size, unit, locale);
+    match locale {
+        Locale::En => format!("{amount} ago"),
+        Locale::Fr => format!("il y a {amount}"),
+    }
+}
+
+// Whitespace per Unicode (so NBSP and the narrow NBSP used in French count as
+// separators); tokens without any letter or digit, like "—" or "•", are not
+// words.
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+pub fn reading_time_minutes(words: usize) -> usize {
+    words.div_ceil(READING_WORDS_PER_MINUTE).max(1)
+}
+
+// Returns None below `min_words` so short outputs stay clean.
+pub fn reading_time(text: &str, min_words: usize, locale: Locale) -> Option<String> {
+    let words = word_count(text);
+    if words < min_words {
+        return None;
+    }
+    let count = format_count(words as u64, locale);
+    let minutes = reading_time_minutes(words);
+    Some(match locale {
+        Locale::En => format!("· {count} words · ~{minutes} min read"),
+        Locale::Fr => format!("· {count} mots · ~{minutes}\u{00A0}min de lecture"),
+    })
+}
+
+fn quantity(amount: u64, unit: Unit, locale: Locale) -> String {
+    let plural = match locale {
+        Locale::En => amount != 1,
+        Locale::Fr => amount > 1,
+    };
+    format!(
+        "{} {}",
+        format_count(amount, locale),
+        unit_label(unit, locale, plural)
+    )
+}
+
+fn unit_label(unit: Unit, locale: Locale, plural: bool) -> &'static str {
+    match (locale, unit, plural) {
+        (Locale::En, Unit::Second, false) => "second",
+        (Locale::En, Unit::Second, true) => "seconds",
+        (Locale::En, Unit::Minute, false) => "minute",
+        (Locale::En, Unit::Minute, true) => "minutes",
+        (Locale::En, Unit::Hour, false) => "hour",
+        (Locale::En, Unit::Hour, true) => "hours",
+        (Locale::En, Unit::Day, false) => "day",
+        (Locale::En, Unit::Day, true) => "days",
+        (Locale::En, Unit::Month, false) => "month",
+        (Locale::En, Unit::Month, true) => "months",
+        (Locale::En, Unit::Year, false) => "year",
+        (Locale::En, Unit::Year, true) => "years",
+        (Locale::Fr, Unit::Second, false) => "seconde",
+        (Locale::Fr, Unit::Second, true) => "secondes",
+        (Locale::Fr, Unit::Minute, false) => "minute",
+        (Locale::Fr, Unit::Minute, true) => "minutes",
+        (Locale::Fr, Unit::Hour, false) => "heure",
+        (Locale::Fr, Unit::Hour, true) => "heures",
+        (Locale::Fr, Unit::Day, false) => "jour",
+        (Locale::Fr, Unit::Day, true) => "jours",
+        (Locale::Fr, Unit::Month, _) => "mois",
+        (Locale::Fr, Unit::Year, false) => "an",
+        (Locale::Fr, Unit::Year, true) => "ans",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NNBSP: char = '\u{202F}';
+
+    #[test]
+    fn locale_tags_round_trip() {
+        assert_eq!(Locale::from_tag("fr-FR"), Some(Locale::Fr));
+        assert_eq!(Locale::from_tag(" EN_us "), Some(Locale::En));
+        assert_eq!(Locale::from_tag("de"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        assert_eq!(Locale::default(), Locale::En);
+    }
+
+    #[test]
+    fn count_groups_thousands_per_locale() {
+        assert_eq!(format_count(0, Locale::En), "0");
+        assert_eq!(format_count(999, Locale::En), "999");
+        assert_eq!(format_count(1_000, Locale::En), "1,000");
+        assert_eq!(format_count(1_234_567, Locale::En), "1,234,567");
+        assert_eq!(format_count(999, Locale::Fr), "999");
+        assert_eq!(
+            format_count(1_234_567, Locale::Fr),
+            format!("1{NNBSP}234{NNBSP}567")
+        );
+        assert_eq!(format_count(100_000, Locale::Fr), format!("100{NNBSP}000"));
+    }
+
+    #[test]
+    fn word_count_splits_on_unicode_whitespace() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count("Rust — WebAssembly • Tokio"), 3);
+        assert_eq!(
+            word_count("Développeur\u{00A0}backend chez\u{202F}PlayStation"),
+            4
+        );
+        assert_eq!(word_count("l’expérience  d’équipe\n\t2024"), 3);
+        assert_eq!(word_count("Ingénieur — 東京 ✨"), 2);
+    }
+
+    #[test]
+    fn reading_time_rounds_up_and_respects_the_threshold() {
+        assert_eq!(reading_time_minutes(0), 1);
+        assert_eq!(reading_time_minutes(200), 1);
+        assert_eq!(reading_time_minutes(201), 2);
+        assert_eq!(reading_time("short answer", 150, Locale::En), None);
+        let long = "word ".repeat(420);
+        assert_eq!(
+            reading_time(&long, 150, Locale::En).as_deref(),
+            Some("· 420 words · ~3 min read")
+        );
+        let french = "mot ".repeat(1_250);
+        assert_eq!(
+            reading_time(&french, 150, Locale::Fr),
+            Some(format!("· 1{NNBSP}250 mots · ~7\u{00A0}min de lecture"))
+        );
+    }
+
+    #[test]
+    fn percent_follows_locale_spacing() {
+        assert_eq!(format_percent(42, Locale::En), "42%");
+        assert_eq!(format_percent(42, Locale::Fr), "42\u{00A0}%");
+    }
+
+    #[test]
+    fn duration_keeps_two_largest_units() {
+        assert_eq!(format_duration(0, Locale::En), "0 seconds");
+        assert_eq!(format_duration(1, Locale::En), "1 second");
+        assert_eq!(format_duration(59, Locale::En), "59 seconds");
+        assert_eq!(format_duration(61, Locale::En), "1 minute 1 second");
+        assert_eq!(format_duration(3_600, Locale::En), "1 hour");
+        assert_eq!(format_duration(3_725, Locale::En), "1 hour 2 minutes");
+        assert_eq!(
+            format_duration(2 * DAY + 3 * HOUR + 5, Locale::En),
+            "2 days 3 hours"
+        );
+        assert_eq!(format_duration(DAY + 30, Locale::En), "1 day 30 seconds");
+    }
+
+    #[test]
+    fn duration_uses_french_pluralization() {
+        assert_eq!(format_duration(0, Locale::Fr), "0 seconde");
+        assert_eq!(format_duration(1, Locale::Fr), "1 seconde");
+        assert_eq!(format_duration(2, Locale::Fr), "2 secondes");
+        assert_eq!(format_duration(3_725, Locale::Fr), "1 heure 2 minutes");
+        assert_eq!(
+            format_duration(2 * DAY + HOUR, Locale::Fr),
+            "2 jours 1 heure"
+        );
+    }
+
+    #[test]
+    fn relative_time_in_english() {
+        let now = 10_000_000_000.0;
+        let ago = |secs: u64| now - secs as f64 * 1000.0;
+        assert_eq!(format_relative(now, now, Locale::En), "just now");
+        assert_eq!(format_relative(ago(44), now, Locale::En), "just now");
+        assert_eq!(format_relative(ago(45), now, Locale::En), "45 seconds ago");
+        assert_eq!(format_relative(ago(60), now, Locale::En), "1 minute ago");
+        assert_eq!(
+            format_relative(ago(2 * HOUR), now, Locale::En),
+            "2 hours ago"
+        );
+        assert_eq!(format_relative(ago(DAY), now, Locale::En), "1 day ago");
+        assert_eq!(format_relative(ago(3 * DAY), now, Locale::En), "3 days ago");
+        assert_eq!(
+            format_relative(ago(2 * MONTH), now, Locale::En),
+            "2 months ago"
+        );
+        assert_eq!(format_relative(ago(YEAR), now, Locale::En), "1 year ago");
+        assert_eq!(
+            format_relative(now + 5_000.0, now, Locale::En),
+            "just now",
+            "Future timestamps should clamp to now"
+        );
+    }
+
+    #[test]
+    fn relative_time_in_french() {
+        let now = 10_000_000_000.0;
+        let ago = |secs: u64| now - secs as f64 * 1000.0;
+        assert_eq!(format_relative(ago(10), now, Locale::Fr), "à l’instant");
+        assert_eq!(format_relative(ago(60), now, Locale::Fr), "il y a 1 minute");
+        assert_eq!(
+            format_relative(ago(3 * DAY), now, Locale::Fr),
+            "il y a 3 jours"
+        );
+        assert_eq!(
+            format_relative(ago(MONTH), now, Locale::Fr),
+            "il y a 1 mois"
+        );
+        assert_eq!(
+            format_relative(ago(5 * MONTH), now, Locale::Fr),
+            "il y a 5 mois"
+        );
+        assert_eq!(format_relative(ago(YEAR), now, Locale::Fr), "il y a 1 an");
+        assert_eq!(
+            format_relative(ago(3 * YEAR), now, Locale::Fr),
+            "il y a 3 ans"
+        );
+    }
+}
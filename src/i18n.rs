@@ -0,0 +1,328 @@
+use crate::format::Locale;
+
+// Fixed UI strings. Both catalogs match on every key without a wildcard, so a
+// new key does not compile until it has an English and a French entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    AiStatusActive,
+    AiStatusDeactivated,
+    AiStatusBusy,
+    AiActivatedInfo,
+    AiDeactivatedInfo,
+    AiDataLoading,
+    AiError,
+    AiHelpLabel,
+    AiHelpLabelWithModel,
+    AiLoaderLabel,
+    AiResumePrompt,
+    AiResumeDeclined,
+    ConfirmDeclined,
+    PinsKept,
+    BootSequence,
+    WelcomeHelp,
+    WelcomeQuickActions,
+    CommandNotFound,
+    UpdateAvailable,
+    UpdateDismissLabel,
+    UpdateReloading,
+    AchievementsReset,
+    AchievementsModalTitle,
+    AchievementsRevealSpoilers,
+    AchievementsHideSpoilers,
+    AchievementsResetAction,
+    AchievementsClose,
+    AchievementsCloseLabel,
+    AchievementProgressLabel,
+    AchievementShawTitle,
+    AchievementShawDescription,
+    AchievementShawHint,
+    AchievementPokemonTitle,
+    AchievementPokemonDescription,
+    AchievementPokemonHint,
+    AchievementCookieTitle,
+    AchievementCookieDescription,
+    AchievementCookieHint,
+    AchievementKonamiTitle,
+    AchievementKonamiDescription,
+    AchievementKonamiHint,
+    AchievementShutdownTitle,
+    AchievementShutdownDescription,
+    AchievementShutdownHint,
+    AchievementPlatinumTitle,
+    AchievementPlatinumDescription,
+    AchievementPlatinumHint,
+}
+
+impl Message {
+    #[cfg(test)]
+    const ALL: [Message; 47] = [
+        Message::AiStatusActive,
+        Message::AiStatusDeactivated,
+        Message::AiStatusBusy,
+        Message::AiActivatedInfo,
+        Message::AiDeactivatedInfo,
+        Message::AiDataLoading,
+        Message::AiError,
+        Message::AiHelpLabel,
+        Message::AiHelpLabelWithModel,
+        Message::AiLoaderLabel,
+        Message::AiResumePrompt,
+        Message::AiResumeDeclined,
+        Message::ConfirmDeclined,
+        Message::PinsKept,
+        Message::BootSequence,
+        Message::WelcomeHelp,
+        Message::WelcomeQuickActions,
+        Message::CommandNotFound,
+        Message::UpdateAvailable,
+        Message::UpdateDismissLabel,
+        Message::UpdateReloading,
+        Message::AchievementsReset,
+        Message::AchievementsModalTitle,
+        Message::AchievementsRevealSpoilers,
+        Message::AchievementsHideSpoilers,
+        Message::AchievementsResetAction,
+        Message::AchievementsClose,
+        Message::AchievementsCloseLabel,
+        Message::AchievementProgressLabel,
+        Message::AchievementShawTitle,
+        Message::AchievementShawDescription,
+        Message::AchievementShawHint,
+        Message::AchievementPokemonTitle,
+        Message::AchievementPokemonDescription,
+        Message::AchievementPokemonHint,
+        Message::AchievementCookieTitle,
+        Message::AchievementCookieDescription,
+        Message::AchievementCookieHint,
+        Message::AchievementKonamiTitle,
+        Message::AchievementKonamiDescription,
+        Message::AchievementKonamiHint,
+        Message::AchievementShutdownTitle,
+        Message::AchievementShutdownDescription,
+        Message::AchievementShutdownHint,
+        Message::AchievementPlatinumTitle,
+        Message::AchievementPlatinumDescription,
+        Message::AchievementPlatinumHint,
+    ];
+}
+
+pub fn t(key: Message, locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => en(key),
+        Locale::Fr => fr(key).unwrap_or_else(|| en(key)),
+    }
+}
+
+// Fills `{name}` placeholders; unknown placeholders are left as written.
+pub fn t_with(key: Message, locale: Locale, args: &[(&str, &str)]) -> String {
+    let mut message = t(key, locale).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), value);
+    }
+    message
+}
+
+fn en(key: Message) -> &'static str {
+    match key {
+        Message::AiStatusActive => "AI Mode: Activated",
+        Message::AiStatusDeactivated => "AI Mode: Deactivated",
+        Message::AiStatusBusy => "AI Mode: Activated — Synthesizing…",
+        Message::AiActivatedInfo => {
+            "🤖 AI Mode activated. Ask anything about Alexandre DO-O ALMEIDA's profile."
+        }
+        Message::AiDeactivatedInfo => "📟 AI Mode deactivated. Classic terminal helpers restored.",
+        Message::AiDataLoading => "AI knowledge base still loading. Please try again shortly.",
+        Message::AiError => "AI error: {error}",
+        Message::AiHelpLabel => "AI help",
+        Message::AiHelpLabelWithModel => "AI help ({model})",
+        Message::AiLoaderLabel => "Synthesizing answer",
+        Message::AiResumePrompt => "Resume previous conversation? [y/N]",
+        Message::AiResumeDeclined => "Starting a fresh conversation.",
+        Message::ConfirmDeclined => "Cancelled.",
+        Message::PinsKept => "📌 Pinned outputs kept.",
+        Message::BootSequence => "Welcome to the ZQSDev interactive terminal!",
+        Message::WelcomeHelp => "Type `help` to view all available commands.",
+        Message::WelcomeQuickActions => {
+            "Use the quick actions below to jump to key sections instantly."
+        }
+        Message::CommandNotFound => {
+            "Command not found: `{command}`\nType `help` to list available commands."
+        }
+        Message::UpdateAvailable => {
+            "A newer version of this terminal is available — press {key} or run {command} to reload."
+        }
+        Message::UpdateDismissLabel => "Dismiss update notice",
+        Message::UpdateReloading => "🔄 Reloading the latest version…",
+        Message::AchievementsReset => "🏆 Achievements reset. Happy hunting!",
+        Message::AchievementsModalTitle => "Easter Eggs",
+        Message::AchievementsRevealSpoilers => "Reveal spoilers",
+        Message::AchievementsHideSpoilers => "Hide spoilers",
+        Message::AchievementsResetAction => "Reset",
+        Message::AchievementsClose => "Close",
+        Message::AchievementsCloseLabel => "Close Easter Eggs panel",
+        Message::AchievementProgressLabel => "Progress: {percent}%",
+        Message::AchievementShawTitle => "Shaw!",
+        Message::AchievementShawDescription => "Could she be... a Hunter?",
+        Message::AchievementShawHint => "Hornet shouts can be heard in the terminal.",
+        Message::AchievementPokemonTitle => "Who's that Pokemon?",
+        Message::AchievementPokemonDescription => "It's Pikachu!",
+        Message::AchievementPokemonHint => "Gotta catch 'em all!",
+        Message::AchievementCookieTitle => "Cookie Storm!",
+        Message::AchievementCookieDescription => "Summoned the sweetest downpour.",
+        Message::AchievementCookieHint => "Tap into the cookie zone.",
+        Message::AchievementKonamiTitle => "Kamehameha!",
+        Message::AchievementKonamiDescription => "And this... is to go even further beyond!",
+        Message::AchievementKonamiHint => "Konami",
+        Message::AchievementShutdownTitle => "AAAAAAAAAAAAAH",
+        Message::AchievementShutdownDescription => "Why would you do that?!",
+        Message::AchievementShutdownHint => "Why would you remove my files?",
+        Message::AchievementPlatinumTitle => "Platinum Trophy",
+        Message::AchievementPlatinumDescription => "Unlocked every Easter egg in the terminal.",
+        Message::AchievementPlatinumHint => {
+            "Claim every other Easter egg to reveal the rarest trophy."
+        }
+    }
+}
+
+// `None` falls back to the English entry.
+fn fr(key: Message) -> Option<&'static str> {
+    let text = match key {
+        Message::AiStatusActive => "Mode IA : activé",
+        Message::AiStatusDeactivated => "Mode IA : désactivé",
+        Message::AiStatusBusy => "Mode IA : activé — synthèse en cours…",
+        Message::AiActivatedInfo => {
+            "🤖 Mode IA activé. Posez n'importe quelle question sur le profil d'Alexandre DO-O ALMEIDA."
+        }
+        Message::AiDeactivatedInfo => {
+            "📟 Mode IA désactivé. Les raccourcis du terminal classique sont de retour."
+        }
+        Message::AiDataLoading => {
+            "La base de connaissances de l'IA est encore en chargement. Réessayez dans un instant."
+        }
+        Message::AiError => "Erreur IA : {error}",
+        Message::AiHelpLabel => "Aide IA",
+        Message::AiHelpLabelWithModel => "Aide IA ({model})",
+        Message::AiLoaderLabel => "Synthèse de la réponse",
+        Message::AiResumePrompt => "Reprendre la conversation précédente ? [y/N]",
+        Message::AiResumeDeclined => "Nouvelle conversation.",
+        Message::ConfirmDeclined => "Annulé.",
+        Message::PinsKept => "📌 Sorties épinglées conservées.",
+        Message::BootSequence => "Bienvenue dans le terminal interactif ZQSDev !",
+        Message::WelcomeHelp => "Tapez `help` pour voir toutes les commandes disponibles.",
+        Message::WelcomeQuickActions => {
+            "Utilisez les raccourcis ci-dessous pour accéder directement aux sections clés."
+        }
+        Message::CommandNotFound => {
+            "Commande introuvable : `{command}`\nTapez `help` pour lister les commandes disponibles."
+        }
+        Message::UpdateAvailable => {
+            "Une nouvelle version de ce terminal est disponible — appuyez sur {key} ou lancez {command} pour recharger."
+        }
+        Message::UpdateDismissLabel => "Masquer l'avis de mise à jour",
+        Message::UpdateReloading => "🔄 Chargement de la dernière version…",
+        Message::AchievementsReset => "🏆 Succès réinitialisés. Bonne chasse !",
+        Message::AchievementsModalTitle => "Easter eggs",
+        Message::AchievementsRevealSpoilers => "Afficher les indices",
+        Message::AchievementsHideSpoilers => "Masquer les indices",
+        Message::AchievementsResetAction => "Réinitialiser",
+        Message::AchievementsClose => "Fermer",
+        Message::AchievementsCloseLabel => "Fermer le panneau des easter eggs",
+        Message::AchievementProgressLabel => "Progression : {percent} %",
+        Message::AchievementShawTitle => "Shaw !",
+        Message::AchievementShawDescription => "Serait-elle... une chasseuse ?",
+        Message::AchievementShawHint => "On entend les cris de Hornet dans le terminal.",
+        Message::AchievementPokemonTitle => "Quel est ce Pokémon ?",
+        Message::AchievementPokemonDescription => "C'est Pikachu !",
+        Message::AchievementPokemonHint => "Attrapez-les tous !",
+        Message::AchievementCookieTitle => "Tempête de cookies !",
+        Message::AchievementCookieDescription => "La plus douce des averses est tombée.",
+        Message::AchievementCookieHint => "Entrez dans la zone cookie.",
+        Message::AchievementKonamiTitle => "Kamehameha !",
+        Message::AchievementKonamiDescription => "Et ça... c'est pour aller encore plus loin !",
+        Message::AchievementKonamiHint => "Konami",
+        Message::AchievementShutdownTitle => "AAAAAAAAAAAAAH",
+        Message::AchievementShutdownDescription => "Pourquoi avoir fait ça ?!",
+        Message::AchievementShutdownHint => "Pourquoi supprimer mes fichiers ?",
+        Message::AchievementPlatinumTitle => "Trophée platine",
+        Message::AchievementPlatinumDescription => {
+            "Tous les easter eggs du terminal sont débloqués."
+        }
+        Message::AchievementPlatinumHint => {
+            "Débloquez tous les autres easter eggs pour révéler le trophée le plus rare."
+        }
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_key_has_both_translations_with_the_same_placeholders() {
+        for key in Message::ALL {
+            let english = en(key);
+            let french = fr(key).unwrap_or_else(|| panic!("{key:?} has no French entry"));
+            assert!(!english.trim().is_empty(), "{key:?} English is empty");
+            assert!(!french.trim().is_empty(), "{key:?} French is empty");
+            assert_eq!(
+                placeholders(english),
+                placeholders(french),
+                "{key:?} placeholders differ"
+            );
+        }
+    }
+
+    #[test]
+    fn lookups_follow_the_locale() {
+        assert_eq!(t(Message::AiStatusActive, Locale::En), "AI Mode: Activated");
+        assert_eq!(t(Message::AiStatusActive, Locale::Fr), "Mode IA : activé");
+        assert_eq!(t(Message::AchievementKonamiHint, Locale::Fr), "Konami");
+    }
+
+    #[test]
+    fn placeholders_are_filled_per_locale() {
+        assert_eq!(
+            t_with(
+                Message::AiHelpLabelWithModel,
+                Locale::En,
+                &[("model", "groq")]
+            ),
+            "AI help (groq)"
+        );
+        assert_eq!(
+            t_with(
+                Message::AiHelpLabelWithModel,
+                Locale::Fr,
+                &[("model", "gpt-4o-mini")]
+            ),
+            "Aide IA (gpt-4o-mini)"
+        );
+        assert_eq!(
+            t_with(Message::CommandNotFound, Locale::Fr, &[("command", "lss")]),
+            "Commande introuvable : `lss`\nTapez `help` pour lister les commandes disponibles."
+        );
+        assert_eq!(
+            t_with(Message::AiError, Locale::En, &[("error", "timeout")]),
+            "AI error: timeout"
+        );
+    }
+
+    #[test]
+    fn missing_arguments_leave_the_placeholder_visible() {
+        assert_eq!(
+            t_with(Message::AiError, Locale::Fr, &[("model", "groq")]),
+            "Erreur IA : {error}"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::terminal::{HistoryDirection, Terminal};
 use crate::utils;
+use std::cell::RefCell;
 use std::rc::Rc;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::*;
@@ -9,6 +10,46 @@ use web_sys::{
     InputEvent, KeyboardEvent, MouseEvent, PointerEvent, TouchEvent,
 };
 
+const AI_TOGGLE_SHORTCUT_KEY: &str = "i";
+const UPDATE_SHORTCUT_KEY: &str = "u";
+
+#[derive(Debug, Default)]
+struct LineSelection {
+    anchor: Option<usize>,
+    range: Option<(usize, usize)>,
+}
+
+impl LineSelection {
+    fn press(&mut self, index: usize, extend: bool) {
+        match (extend, self.anchor) {
+            (true, Some(anchor)) => self.range = Some(ordered_range(anchor, index)),
+            _ => {
+                self.anchor = Some(index);
+                self.range = None;
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) {
+        if let Some(anchor) = self.anchor {
+            if anchor != index {
+                self.range = Some(ordered_range(anchor, index));
+            }
+        }
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.range
+            .map(|(start, end)| (start..=end).contains(&index))
+            .unwrap_or(false)
+    }
+
+    fn clear(&mut self) -> bool {
+        self.anchor = None;
+        self.range.take().is_some()
+    }
+}
+
 pub fn install_listeners(terminal: Rc<Terminal>) -> Result<(), JsValue> {
     let document = utils::document()?;
     let prompt_line = document
@@ -61,7 +102,14 @@ pub fn install_listeners(terminal: Rc<Terminal>) -> Result<(), JsValue> {
     let ai_activation_terminal = Rc::clone(&terminal);
     let composition_terminal = Rc::clone(&terminal);
 
+    let selection = Rc::new(RefCell::new(LineSelection::default()));
+    let keydown_selection = Rc::clone(&selection);
     let keydown_closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if event.key() == "Escape" && keydown_selection.borrow_mut().clear() {
+            event.prevent_default();
+            keydown_terminal.highlight_output_lines(None);
+            return;
+        }
         handle_keydown(&keydown_terminal, event);
     }) as Box<dyn FnMut(_)>);
 
@@ -80,6 +128,8 @@ pub fn install_listeners(terminal: Rc<Terminal>) -> Result<(), JsValue> {
         .add_event_listener_with_callback("click", click_closure.as_ref().unchecked_ref())?;
     click_closure.forget();
 
+    install_line_selection(&document, &terminal, &selection)?;
+
     let paste_closure = Closure::wrap(Box::new(move |event: ClipboardEvent| {
         handle_paste(&paste_terminal, event);
     }) as Box<dyn FnMut(_)>);
@@ -117,6 +167,123 @@ pub fn install_listeners(terminal: Rc<Terminal>) -> Result<(), JsValue> {
         .add_event_listener_with_callback("click", ai_activate_click.as_ref().unchecked_ref())?;
     ai_activate_click.forget();
 
+    let collapse_click_terminal = Rc::clone(&terminal);
+    let collapse_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(toggle) = lookup_collapse_toggle(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = collapse_click_terminal.toggle_output_collapse(&toggle) {
+                utils::log(&format!("Failed to toggle collapsed output: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", collapse_click.as_ref().unchecked_ref())?;
+    collapse_click.forget();
+
+    let update_dismiss_terminal = Rc::clone(&terminal);
+    let update_dismiss_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if wants_update_banner_dismissal(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = update_dismiss_terminal.dismiss_update_banner() {
+                utils::log(&format!("Failed to dismiss the update notice: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document
+        .add_event_listener_with_callback("click", update_dismiss_click.as_ref().unchecked_ref())?;
+    update_dismiss_click.forget();
+
+    let share_click_terminal = Rc::clone(&terminal);
+    let share_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_share_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = share_click_terminal.copy_share_link(&trigger) {
+                utils::log(&format!("Failed to copy command link: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", share_click.as_ref().unchecked_ref())?;
+    share_click.forget();
+
+    let pin_click_terminal = Rc::clone(&terminal);
+    let pin_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_pin_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = pin_click_terminal.toggle_output_pin(&trigger) {
+                utils::log(&format!("Failed to toggle pinned output: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", pin_click.as_ref().unchecked_ref())?;
+    pin_click.forget();
+
+    let group_click_terminal = Rc::clone(&terminal);
+    let group_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_group_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = group_click_terminal.toggle_command_group(&trigger) {
+                utils::log(&format!("Failed to toggle command group: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", group_click.as_ref().unchecked_ref())?;
+    group_click.forget();
+
+    let summary_click_terminal = Rc::clone(&terminal);
+    let summary_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_summary_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = summary_click_terminal.summarize_from_chip(&trigger) {
+                utils::log(&format!("Failed to request AI summary: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", summary_click.as_ref().unchecked_ref())?;
+    summary_click.forget();
+
+    let regenerate_click_terminal = Rc::clone(&terminal);
+    let regenerate_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_regenerate_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = regenerate_click_terminal.regenerate_from_control(&trigger) {
+                utils::log(&format!("Failed to regenerate AI answer: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document
+        .add_event_listener_with_callback("click", regenerate_click.as_ref().unchecked_ref())?;
+    regenerate_click.forget();
+
+    let toc_click_terminal = Rc::clone(&terminal);
+    let toc_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if let Some(trigger) = lookup_toc_trigger(event.target()) {
+            event.prevent_default();
+            event.stop_propagation();
+            if let Err(err) = toc_click_terminal.scroll_to_section(&trigger) {
+                utils::log(&format!("Failed to scroll to output section: {:?}", err));
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    document.add_event_listener_with_callback("click", toc_click.as_ref().unchecked_ref())?;
+    toc_click.forget();
+
+    let external_link_terminal = Rc::clone(&terminal);
+    let external_link_click = Closure::wrap(Box::new(move |event: MouseEvent| {
+        // Only record the visit; the browser still handles the navigation.
+        if let Some(href) = lookup_external_link(event.target()) {
+            external_link_terminal.record_external_link(&href);
+        }
+    }) as Box<dyn FnMut(_)>);
+    document
+        .add_event_listener_with_callback("click", external_link_click.as_ref().unchecked_ref())?;
+    external_link_click.forget();
+
     let helper_click_terminal = Rc::clone(&terminal);
     let helper_click = Closure::wrap(Box::new(move |event: MouseEvent| {
         if let Some(command) = lookup_command_trigger(event.target()) {
@@ -257,9 +424,102 @@ pub fn install_listeners(terminal: Rc<Terminal>) -> Result<(), JsValue> {
     Ok(())
 }
 
-fn handle_keydown(terminal: &Terminal, event: KeyboardEvent) {
+fn install_line_selection(
+    document: &web_sys::Document,
+    terminal: &Rc<Terminal>,
+    selection: &Rc<RefCell<LineSelection>>,
+) -> Result<(), JsValue> {
+    let output = document
+        .get_element_by_id("output")
+        .ok_or_else(|| JsValue::from_str("Missing #output element"))?;
+
+    let press_terminal = Rc::clone(terminal);
+    let press_selection = Rc::clone(selection);
+    let press_closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if event.button() != 0 || is_line_control(event.target()) {
+            return;
+        }
+        let Some(index) = lookup_output_line(&press_terminal, event.target()) else {
+            return;
+        };
+        if event.shift_key() {
+            event.prevent_default();
+        }
+        let mut selection = press_selection.borrow_mut();
+        selection.press(index, event.shift_key());
+        press_terminal.highlight_output_lines(selection.range);
+    }) as Box<dyn FnMut(_)>);
+    output.add_event_listener_with_callback("mousedown", press_closure.as_ref().unchecked_ref())?;
+    press_closure.forget();
+
+    let release_terminal = Rc::clone(terminal);
+    let release_selection = Rc::clone(selection);
+    let release_closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+        if event.button() != 0 || event.shift_key() || is_line_control(event.target()) {
+            return;
+        }
+        let Some(index) = lookup_output_line(&release_terminal, event.target()) else {
+            return;
+        };
+        let mut selection = release_selection.borrow_mut();
+        selection.release(index);
+        release_terminal.highlight_output_lines(selection.range);
+    }) as Box<dyn FnMut(_)>);
+    output.add_event_listener_with_callback("mouseup", release_closure.as_ref().unchecked_ref())?;
+    release_closure.forget();
+
+    let copy_terminal = Rc::clone(terminal);
+    let copy_selection = Rc::clone(selection);
+    let copy_closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+        let Some(index) = lookup_output_line(&copy_terminal, event.target()) else {
+            return;
+        };
+        let range = {
+            let selection = copy_selection.borrow();
+            if !selection.contains(index) {
+                return;
+            }
+            selection.range
+        };
+        let Some((start, end)) = range else {
+            return;
+        };
+        event.prevent_default();
+        copy_selection.borrow_mut().clear();
+        copy_terminal.highlight_output_lines(None);
+        if let Err(err) = copy_terminal.copy_output_lines(start, end) {
+            utils::log(&format!("Failed to copy selected lines: {:?}", err));
+        }
+    }) as Box<dyn FnMut(_)>);
+    output
+        .add_event_listener_with_callback("contextmenu", copy_closure.as_ref().unchecked_ref())?;
+    copy_closure.forget();
+
+    Ok(())
+}
+
+fn lookup_output_line(terminal: &Terminal, target: Option<EventTarget>) -> Option<usize> {
+    let element = target?.dyn_into::<Element>().ok()?;
+    terminal.output_line_index(&element)
+}
+
+fn ordered_range(a: usize, b: usize) -> (usize, usize) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+pub(crate) fn handle_keydown(terminal: &Terminal, event: KeyboardEvent) {
     let key = event.key();
 
+    if !is_modifier_key(&key) && terminal.abort_sequence() {
+        event.prevent_default();
+        event.stop_propagation();
+        return;
+    }
+
     if !event.repeat() {
         match terminal.process_konami_key(&key) {
             Ok(true) => {
@@ -274,6 +534,42 @@ fn handle_keydown(terminal: &Terminal, event: KeyboardEvent) {
         }
     }
 
+    if key != "Escape" && !is_modifier_key(&key) {
+        terminal.dismiss_active_effect();
+    }
+
+    if is_ai_toggle_shortcut(
+        &key,
+        event.ctrl_key(),
+        event.meta_key() || event.alt_key() || event.shift_key(),
+        event.is_composing(),
+        terminal.is_modal_open(),
+    ) {
+        event.prevent_default();
+        event.stop_propagation();
+        if let Err(err) = terminal.toggle_ai_mode() {
+            utils::log(&format!("Failed to toggle AI mode via shortcut: {:?}", err));
+        }
+        return;
+    }
+
+    if is_update_shortcut(
+        &key,
+        event.ctrl_key() || event.meta_key() || event.alt_key(),
+        event.is_composing(),
+    ) && terminal.update_shortcut_armed()
+    {
+        event.prevent_default();
+        event.stop_propagation();
+        if let Err(err) = terminal.reload_latest_version() {
+            utils::log(&format!(
+                "Failed to reload via the update shortcut: {:?}",
+                err
+            ));
+        }
+        return;
+    }
+
     if let Some(command) = lookup_suggestion_command(event.target()) {
         match key.as_str() {
             "Enter" | " " | "Spacebar" => {
@@ -324,6 +620,27 @@ fn handle_keydown(terminal: &Terminal, event: KeyboardEvent) {
     }
 }
 
+fn is_modifier_key(key: &str) -> bool {
+    matches!(key, "Shift" | "Control" | "Alt" | "Meta" | "CapsLock")
+}
+
+fn is_ai_toggle_shortcut(
+    key: &str,
+    ctrl: bool,
+    other_modifiers: bool,
+    composing: bool,
+    modal_open: bool,
+) -> bool {
+    ctrl && !other_modifiers
+        && !composing
+        && !modal_open
+        && key.eq_ignore_ascii_case(AI_TOGGLE_SHORTCUT_KEY)
+}
+
+fn is_update_shortcut(key: &str, modifiers: bool, composing: bool) -> bool {
+    !modifiers && !composing && key.eq_ignore_ascii_case(UPDATE_SHORTCUT_KEY)
+}
+
 fn handle_printable(terminal: &Terminal, event: &KeyboardEvent) {
     if event.ctrl_key() || event.meta_key() || event.alt_key() || event.is_composing() {
         return;
@@ -367,10 +684,122 @@ fn wants_suggestions_toggle(target: Option<EventTarget>) -> bool {
     false
 }
 
+fn wants_update_banner_dismissal(target: Option<EventTarget>) -> bool {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("update-banner__dismiss") {
+            return true;
+        }
+        current = element.parent_element();
+    }
+    false
+}
+
+fn lookup_collapse_toggle(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("output-collapse__toggle") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_share_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("command-line__share") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_pin_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("output-pin") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+// Buttons inside an output line act on their own and never start a selection.
+fn is_line_control(target: Option<EventTarget>) -> bool {
+    lookup_pin_trigger(target.clone()).is_some() || lookup_group_trigger(target).is_some()
+}
+
+fn lookup_group_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("command-group__summary") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_summary_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("output-summarize") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_regenerate_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("ai-regenerate") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_toc_trigger(target: Option<EventTarget>) -> Option<Element> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.class_list().contains("output-toc__link") {
+            return Some(element);
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
+fn lookup_external_link(target: Option<EventTarget>) -> Option<String> {
+    let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
+    while let Some(element) = current {
+        if element.tag_name().eq_ignore_ascii_case("a") {
+            if element.get_attribute("target").as_deref() != Some("_blank") {
+                return None;
+            }
+            return element
+                .get_attribute("href")
+                .filter(|href| !href.trim().is_empty());
+        }
+        current = element.parent_element();
+    }
+    None
+}
+
 fn lookup_suggestion_command(target: Option<EventTarget>) -> Option<String> {
     let mut current = target.and_then(|value| value.dyn_into::<Element>().ok());
     while let Some(element) = current {
         if element.class_list().contains("suggestion") {
+            if element.get_attribute("aria-disabled").as_deref() == Some("true") {
+                return None;
+            }
             return element
                 .get_attribute("data-command")
                 .or_else(|| element.text_content())
@@ -480,7 +909,10 @@ fn lookup_command_trigger(target: Option<EventTarget>) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_printable_character_key, sanitize_pasted_text};
+    use super::{
+        is_ai_toggle_shortcut, is_printable_character_key, is_update_shortcut,
+        sanitize_pasted_text, LineSelection,
+    };
 
     #[test]
     fn sanitize_trims_and_flattens_whitespace() {
@@ -513,4 +945,84 @@ mod tests {
         assert!(!is_printable_character_key("Dead"));
         assert!(!is_printable_character_key("Process"));
     }
+
+    #[test]
+    fn ai_toggle_shortcut_matches_ctrl_i_only() {
+        assert!(is_ai_toggle_shortcut("i", true, false, false, false));
+        assert!(is_ai_toggle_shortcut("I", true, false, false, false));
+        assert!(!is_ai_toggle_shortcut("i", false, false, false, false));
+        assert!(!is_ai_toggle_shortcut("i", true, true, false, false));
+        assert!(!is_ai_toggle_shortcut("k", true, false, false, false));
+    }
+
+    #[test]
+    fn ai_toggle_shortcut_ignored_while_composing_or_in_modal() {
+        assert!(
+            !is_ai_toggle_shortcut("i", true, false, true, false),
+            "Composition input should never toggle AI mode"
+        );
+        assert!(
+            !is_ai_toggle_shortcut("i", true, false, false, true),
+            "An open modal should swallow the AI shortcut"
+        );
+    }
+
+    #[test]
+    fn update_shortcut_matches_a_bare_u() {
+        assert!(is_update_shortcut("u", false, false));
+        assert!(is_update_shortcut("U", false, false));
+        assert!(!is_update_shortcut("u", true, false));
+        assert!(!is_update_shortcut("u", false, true));
+        assert!(!is_update_shortcut("y", false, false));
+    }
+
+    #[test]
+    fn line_selection_extends_with_shift_and_drag() {
+        let mut selection = LineSelection::default();
+        selection.press(4, false);
+        assert_eq!(selection.range, None);
+        selection.press(2, true);
+        assert_eq!(
+            selection.range,
+            Some((2, 4)),
+            "Shift-click should select upwards"
+        );
+        assert!(selection.contains(3));
+        assert!(!selection.contains(5));
+
+        selection.press(7, false);
+        assert_eq!(
+            selection.range, None,
+            "A plain click starts a new selection"
+        );
+        selection.release(7);
+        assert_eq!(
+            selection.range, None,
+            "Clicking a single line selects nothing"
+        );
+        selection.release(9);
+        assert_eq!(
+            selection.range,
+            Some((7, 9)),
+            "Dragging should select a range"
+        );
+    }
+
+    #[test]
+    fn line_selection_clears_on_escape() {
+        let mut selection = LineSelection::default();
+        selection.press(1, false);
+        selection.press(3, true);
+        assert!(
+            selection.clear(),
+            "Clearing an active selection reports a change"
+        );
+        assert_eq!(selection.range, None);
+        assert!(!selection.clear(), "Nothing left to clear");
+        selection.press(2, true);
+        assert_eq!(
+            selection.range, None,
+            "Shift-click without an anchor starts a new selection"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use crate::utils;
 use futures::{pin_mut, stream, StreamExt};
+use gloo_timers::future::TimeoutFuture;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use wasm_bindgen::JsCast;
@@ -28,6 +29,7 @@ struct KeywordPattern {
 }
 
 const ICON_PRELOAD_CONCURRENCY: usize = 4;
+const ICON_RETRY_DELAY_MS: u32 = 750;
 
 const KEYWORD_PATTERNS: &[KeywordPattern] = &[
     KeywordPattern {
@@ -568,9 +570,15 @@ fn is_keyword_char(ch: char) -> bool {
 thread_local! {
     static ICON_SOURCES: RefCell<HashMap<&'static str, String>> = RefCell::new(HashMap::new());
     static PRELOAD_STARTED: RefCell<bool> = RefCell::new(false);
+    // Icons whose preload failed; the first render of each gets one more try.
+    static PRELOAD_MISSES: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
 }
 
-pub fn preload_all_icons() -> Result<(), JsValue> {
+pub fn preload_all_icons(data_saver: bool) -> Result<(), JsValue> {
+    if data_saver {
+        utils::log("Data saver is on; keyword icons load on demand.");
+        return Ok(());
+    }
     PRELOAD_STARTED.with(|flag| {
         let mut started = flag.borrow_mut();
         if *started {
@@ -586,16 +594,43 @@ pub fn preload_all_icons() -> Result<(), JsValue> {
     Ok(())
 }
 
-pub fn icon_source(icon_path: &str) -> String {
-    ICON_SOURCES.with(|store| {
-        store
-            .borrow()
-            .get(icon_path)
-            .cloned()
-            .unwrap_or_else(|| icon_path.to_string())
+pub fn icon_source(icon_path: &'static str) -> String {
+    icon_source_with(icon_path, |icon_path| {
+        spawn_local(cache_icon_on_demand(icon_path));
     })
 }
 
+// The uncached path is returned straight away (the browser can still load it);
+// `schedule` runs at most once per preload miss so the next render hits the cache.
+fn icon_source_with(icon_path: &'static str, schedule: impl FnOnce(&'static str)) -> String {
+    if let Some(url) = ICON_SOURCES.with(|store| store.borrow().get(icon_path).cloned()) {
+        return url;
+    }
+    if PRELOAD_MISSES.with(|misses| misses.borrow_mut().remove(icon_path)) {
+        schedule(icon_path);
+    }
+    icon_path.to_string()
+}
+
+fn cache_icon(icon_path: &'static str, url: String) {
+    ICON_SOURCES.with(|store| {
+        store.borrow_mut().insert(icon_path, url);
+    });
+}
+
+async fn cache_icon_on_demand(icon_path: &'static str) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    match fetch_icon_url(&window, icon_path).await {
+        Ok(url) => cache_icon(icon_path, url),
+        Err(err) => utils::log(&format!(
+            "Failed to cache icon {icon_path} on demand: {:?}",
+            err
+        )),
+    }
+}
+
 async fn preload_icons_async() -> Result<(), JsValue> {
     let Some(window) = web_sys::window() else {
         return Ok(());
@@ -608,10 +643,8 @@ async fn preload_icons_async() -> Result<(), JsValue> {
                 if ICON_SOURCES.with(|store| store.borrow().contains_key(asset)) {
                     continue;
                 }
-                if let Ok(url) = fetch_icon_url(&window, asset).await {
-                    ICON_SOURCES.with(|store| {
-                        store.borrow_mut().insert(asset, url);
-                    });
+                if let Ok(url) = fetch_icon_url_with_retry(&window, asset).await {
+                    cache_icon(asset, url);
                 }
             }
             _ => {
@@ -637,7 +670,7 @@ async fn preload_icons_async() -> Result<(), JsValue> {
     let tasks = pending.into_iter().map(|icon_path| {
         let window = window.clone();
         async move {
-            let result = fetch_icon_url(&window, icon_path).await;
+            let result = fetch_icon_url_with_retry(&window, icon_path).await;
             (icon_path, result)
         }
     });
@@ -646,16 +679,32 @@ async fn preload_icons_async() -> Result<(), JsValue> {
     pin_mut!(stream);
     while let Some((icon_path, result)) = stream.next().await {
         match result {
-            Ok(url) => ICON_SOURCES.with(|store| {
-                store.borrow_mut().insert(icon_path, url);
-            }),
-            Err(err) => utils::log(&format!("Failed to cache icon {icon_path}: {:?}", err)),
+            Ok(url) => cache_icon(icon_path, url),
+            Err(err) => {
+                utils::log(&format!("Failed to cache icon {icon_path}: {:?}", err));
+                PRELOAD_MISSES.with(|misses| {
+                    misses.borrow_mut().insert(icon_path);
+                });
+            }
         }
     }
 
     Ok(())
 }
 
+async fn fetch_icon_url_with_retry(
+    window: &web_sys::Window,
+    icon_path: &'static str,
+) -> Result<String, JsValue> {
+    match fetch_icon_url(window, icon_path).await {
+        Ok(url) => Ok(url),
+        Err(_) => {
+            TimeoutFuture::new(ICON_RETRY_DELAY_MS).await;
+            fetch_icon_url(window, icon_path).await
+        }
+    }
+}
+
 async fn fetch_icon_url(
     window: &web_sys::Window,
     icon_path: &'static str,
@@ -694,6 +743,30 @@ async fn fetch_resource(window: &web_sys::Window, path: &str) -> Result<(), JsVa
 mod tests {
     use super::*;
 
+    #[test]
+    fn icon_missed_by_preload_is_cached_on_first_render() {
+        let icon_path = "/icons/rust-original.svg";
+        PRELOAD_MISSES.with(|misses| {
+            misses.borrow_mut().insert(icon_path);
+        });
+
+        let mut scheduled = Vec::new();
+        let first = icon_source_with(icon_path, |path| {
+            scheduled.push(path);
+            cache_icon(path, "blob:cached-rust".to_string());
+        });
+        assert_eq!(first, icon_path, "The first render falls back to the path");
+        assert_eq!(scheduled, [icon_path]);
+
+        let second = icon_source_with(icon_path, |_| panic!("no second attempt"));
+        assert_eq!(second, "blob:cached-rust");
+
+        let never_missed = icon_source_with("/icons/go-original.svg", |_| {
+            panic!("icons still preloading are not fetched twice")
+        });
+        assert_eq!(never_missed, "/icons/go-original.svg");
+    }
+
     #[test]
     fn tokenize_marks_multiword_keywords() {
         let segments = tokenize("Working with GitHub Actions and Rust.");
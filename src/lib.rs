@@ -1,43 +1,133 @@
 mod ai;
 mod build_info;
 mod commands;
+mod confirm;
+#[cfg(debug_assertions)]
+mod debug_tools;
+mod embed;
+mod format;
+mod i18n;
 mod input;
 mod keyword_icons;
 mod markdown;
 mod renderer;
+mod schedule;
 mod state;
 mod telemetry;
 mod terminal;
 mod utils;
 
 use crate::renderer::Renderer;
-use crate::state::{AppState, BackendVersionMeta, Profile, TerminalData};
+use crate::state::{AppState, BackendVersionMeta, OwnerAvailability, Profile, TerminalData};
 use crate::terminal::Terminal;
 use serde::Deserialize;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 
+type UnloadHandler = Closure<dyn FnMut(web_sys::BeforeUnloadEvent)>;
+
+thread_local! {
+    static UNLOAD_GUARD: RefCell<Option<UnloadHandler>> = const { RefCell::new(None) };
+    static PANIC_RENDERER: RefCell<Option<Rc<Renderer>>> = const { RefCell::new(None) };
+}
+
 #[wasm_bindgen(start)]
 pub fn start() -> Result<(), JsValue> {
-    console_error_panic_hook::set_once();
+    std::panic::set_hook(Box::new(report_panic));
 
     let state = Rc::new(RefCell::new(AppState::new()));
     let renderer = Rc::new(Renderer::new()?);
+    PANIC_RENDERER.with(|slot| slot.replace(Some(Rc::clone(&renderer))));
     let terminal = Rc::new(Terminal::new(Rc::clone(&state), Rc::clone(&renderer)));
 
+    utils::storage::probe();
     terminal.restore_achievements_from_storage();
+    terminal.restore_achievements_spoilers_preference();
+    terminal.restore_ai_preference_from_storage();
+    terminal.restore_suggestions_preference_from_storage();
+    terminal.restore_locale_preference();
+    terminal.restore_output_width_preference();
+    terminal.restore_data_saver_preference();
+    terminal.restore_reading_time_preference();
     terminal.initialize()?;
+    if let Err(err) = renderer.observe_compact_layout() {
+        utils::log(&format!("Compact layout observer unavailable: {:?}", err));
+    }
+    if renderer.read_only_requested() {
+        terminal.enable_read_only()?;
+    }
     terminal.push_system_message("Booting…");
+    if !utils::storage::is_persistent() {
+        terminal.push_system_message(utils::storage::UNAVAILABLE_NOTICE);
+    }
 
     input::install_listeners(Rc::clone(&terminal))?;
+    embed::attach(terminal.clone());
+    if let Err(err) = embed::listen_for_tokens() {
+        utils::log(&format!("Embed token listener unavailable: {:?}", err));
+    }
 
     spawn_local(load_terminal_data(Rc::clone(&terminal), Rc::clone(&state)));
 
     Ok(())
 }
 
+// wasm aborts on panic, so nothing unwinds back to the command loop: the hook
+// is the only place left to tell the visitor the terminal has stopped.
+fn report_panic(info: &std::panic::PanicHookInfo<'_>) {
+    console_error_panic_hook::hook(info);
+    let detail = utils::panic_message(info.payload());
+    PANIC_RENDERER.with(|slot| {
+        if let Ok(slot) = slot.try_borrow() {
+            if let Some(renderer) = slot.as_ref() {
+                terminal::render_panic_notice(renderer, &detail);
+            }
+        }
+    });
+}
+
+// The `beforeunload` listener only exists while leaving would lose work, so
+// ordinary navigation never meets the browser's confirmation dialog.
+pub(crate) fn sync_unload_guard(state: &AppState) {
+    if let Err(err) = set_unload_guard(state.should_warn_before_unload()) {
+        utils::log(&format!("Failed to update the leave-page guard: {:?}", err));
+    }
+}
+
+fn set_unload_guard(armed: bool) -> Result<(), JsValue> {
+    let Some(window) = utils::window() else {
+        return Ok(());
+    };
+    UNLOAD_GUARD.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if !armed {
+            if let Some(handler) = slot.take() {
+                window.remove_event_listener_with_callback(
+                    "beforeunload",
+                    handler.as_ref().unchecked_ref(),
+                )?;
+            }
+            return Ok(());
+        }
+        if slot.is_none() {
+            let handler = Closure::wrap(Box::new(|event: web_sys::BeforeUnloadEvent| {
+                event.prevent_default();
+                event.set_return_value("");
+            }) as Box<dyn FnMut(_)>);
+            window.add_event_listener_with_callback(
+                "beforeunload",
+                handler.as_ref().unchecked_ref(),
+            )?;
+            *slot = Some(handler);
+        }
+        Ok(())
+    })
+}
+
 async fn load_terminal_data(terminal: Rc<Terminal>, state: Rc<RefCell<AppState>>) {
     match fetch_all_data().await {
         Ok(data) => {
@@ -47,19 +137,49 @@ async fn load_terminal_data(terminal: Rc<Terminal>, state: Rc<RefCell<AppState>>
             }
             {
                 let state_clone = Rc::clone(&state);
+                let terminal_clone = Rc::clone(&terminal);
                 spawn_local(async move {
                     match fetch_backend_version().await {
-                        Ok(meta) => state_clone.borrow_mut().set_backend_version(meta),
+                        Ok(meta) => {
+                            let stale = commands::frontend_is_stale(
+                                build_info::FRONTEND_VERSION,
+                                &meta.version,
+                            );
+                            state_clone.borrow_mut().set_backend_version(meta);
+                            if stale {
+                                if let Err(err) = terminal_clone.show_update_banner() {
+                                    utils::log(&format!(
+                                        "Failed to show the update notice: {:?}",
+                                        err
+                                    ));
+                                }
+                            }
+                        }
                         Err(err) => {
                             utils::log(&format!("Failed to load backend version info: {:?}", err))
                         }
                     }
                 });
             }
+            {
+                let state_clone = Rc::clone(&state);
+                spawn_local(async move {
+                    match utils::fetch_json::<OwnerAvailability>("/api/time").await {
+                        Ok(availability) => {
+                            state_clone.borrow_mut().owner_availability = Some(availability)
+                        }
+                        Err(err) => {
+                            utils::log(&format!("Failed to load owner availability: {:?}", err))
+                        }
+                    }
+                });
+            }
             if let Err(err) = terminal.on_data_ready() {
                 utils::log(&format!("Failed to render welcome message: {:?}", err));
             }
-            if let Err(err) = keyword_icons::preload_all_icons() {
+            embed::mark_ready();
+            let data_saver = state.borrow().data_saver;
+            if let Err(err) = keyword_icons::preload_all_icons(data_saver) {
                 utils::log(&format!("Failed to preload keyword icons: {:?}", err));
             }
         }
@@ -72,9 +192,30 @@ async fn load_terminal_data(terminal: Rc<Terminal>, state: Rc<RefCell<AppState>>
     }
 }
 
+#[derive(Deserialize)]
+struct TerminalDataEnvelope {
+    #[serde(default)]
+    generated_at: Option<String>,
+    #[serde(default)]
+    source_mtime: Option<String>,
+    #[serde(default)]
+    counts: BTreeMap<String, u64>,
+    data: TerminalData,
+}
+
+impl TerminalDataEnvelope {
+    fn into_data(self) -> TerminalData {
+        let mut data = self.data;
+        data.generated_at = self.generated_at.or(data.generated_at);
+        data.source_mtime = self.source_mtime;
+        data.counts = self.counts;
+        data
+    }
+}
+
 async fn fetch_all_data() -> Result<TerminalData, JsValue> {
-    match utils::fetch_json::<TerminalData>("/api/data").await {
-        Ok(data) => Ok(data),
+    match utils::fetch_json::<TerminalDataEnvelope>("/api/data?meta=1").await {
+        Ok(envelope) => Ok(envelope.into_data()),
         Err(err) => {
             let fallback_reason = err
                 .as_string()
@@ -135,6 +276,8 @@ struct BackendVersionPayload {
     version: String,
     #[serde(default)]
     commit: Option<String>,
+    #[serde(default)]
+    built_at: Option<String>,
 }
 
 async fn fetch_backend_version() -> Result<BackendVersionMeta, JsValue> {
@@ -142,5 +285,47 @@ async fn fetch_backend_version() -> Result<BackendVersionMeta, JsValue> {
     Ok(BackendVersionMeta {
         version: payload.version,
         commit: payload.commit.unwrap_or_else(|| "unknown".to_string()),
+        built_at: payload.built_at,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{set_unload_guard, sync_unload_guard, UNLOAD_GUARD};
+    use crate::state::AppState;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn unload_is_blocked() -> bool {
+        let init = web_sys::EventInit::new();
+        init.set_cancelable(true);
+        let event = web_sys::Event::new_with_event_init_dict("beforeunload", &init)
+            .expect("beforeunload event");
+        let window = web_sys::window().expect("window");
+        !window.dispatch_event(&event).expect("dispatch")
+    }
+
+    #[wasm_bindgen_test]
+    fn unload_guard_is_added_and_removed_with_the_state() {
+        let mut state = AppState::new();
+        sync_unload_guard(&state);
+        assert!(UNLOAD_GUARD.with(|slot| slot.borrow().is_none()));
+        assert!(!unload_is_blocked());
+
+        state.ai_request_pending = true;
+        sync_unload_guard(&state);
+        sync_unload_guard(&state);
+        assert!(UNLOAD_GUARD.with(|slot| slot.borrow().is_some()));
+        assert!(unload_is_blocked());
+
+        state.ai_request_pending = false;
+        state.cookie_games_in_progress = 1;
+        sync_unload_guard(&state);
+        assert!(unload_is_blocked());
+
+        state.cookie_games_in_progress = 0;
+        sync_unload_guard(&state);
+        assert!(UNLOAD_GUARD.with(|slot| slot.borrow().is_none()));
+        assert!(!unload_is_blocked());
+        set_unload_guard(false).expect("disarming twice is a no-op");
+    }
+}
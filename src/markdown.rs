@@ -4,15 +4,63 @@ enum ListKind {
     Unordered,
 }
 
+const TRACKED_TAGS: [&str; 5] = ["p", "ol", "ul", "li", "strong"];
+// Blocks never nest and bold toggles rather than stacks, so the deepest
+// valid output is a list, an item and a strong run.
+const MAX_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownError {
+    Malformed,
+}
+
+pub fn try_to_html(input: &str) -> Result<String, MarkdownError> {
+    try_render_with(input, to_html)
+}
+
+fn try_render_with(input: &str, render: fn(&str) -> String) -> Result<String, MarkdownError> {
+    let html = render(input);
+    if is_balanced(&html) {
+        Ok(html)
+    } else {
+        Err(MarkdownError::Malformed)
+    }
+}
+
+fn is_balanced(html: &str) -> bool {
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let Some((tag, after)) = rest[start + 1..].split_once('>') else {
+            return false;
+        };
+        rest = after;
+        let (closing, name) = match tag.strip_prefix('/') {
+            Some(name) => (true, name),
+            None => (false, tag),
+        };
+        if !TRACKED_TAGS.contains(&name) {
+            return false;
+        }
+        if !closing {
+            if open.len() == MAX_DEPTH {
+                return false;
+            }
+            open.push(name);
+        } else if open.pop() != Some(name) {
+            return false;
+        }
+    }
+    open.is_empty()
+}
+
 pub fn to_html(input: &str) -> String {
     let lines: Vec<&str> = input.lines().collect();
     let mut idx = 0;
     let mut html = String::new();
 
-    while idx < lines.len() {
-        let line = lines[idx];
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
+    while let Some(line) = lines.get(idx) {
+        if line.trim().is_empty() {
             idx += 1;
             continue;
         }
@@ -57,12 +105,11 @@ pub fn to_html(input: &str) -> String {
 }
 
 fn parse_list(lines: &[&str], start: usize) -> Option<(ListKind, Vec<String>, usize)> {
-    let (kind, first) = detect_list_marker(lines[start])?;
+    let (kind, first) = detect_list_marker(lines.get(start)?)?;
     let mut items = vec![first.trim().to_string()];
     let mut idx = start + 1;
 
-    while idx < lines.len() {
-        let current = lines[idx];
+    while let Some(current) = lines.get(idx) {
         let trimmed = current.trim();
         if trimmed.is_empty() {
             idx += 1;
@@ -97,8 +144,7 @@ fn parse_paragraph(lines: &[&str], start: usize) -> (String, usize) {
     let mut content = Vec::new();
     let mut idx = start;
 
-    while idx < lines.len() {
-        let line = lines[idx];
+    while let Some(line) = lines.get(idx) {
         if line.trim().is_empty() {
             idx += 1;
             break;
@@ -115,48 +161,30 @@ fn parse_paragraph(lines: &[&str], start: usize) -> (String, usize) {
 
 fn detect_list_marker(line: &str) -> Option<(ListKind, String)> {
     let trimmed = line.trim_start();
-    if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
-        let content = trimmed[2..].to_string();
-        return Some((ListKind::Unordered, content));
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(content) = trimmed.strip_prefix(marker) {
+            return Some((ListKind::Unordered, content.to_string()));
+        }
     }
 
-    if let Some((digits_len, suffix_len)) = ordered_marker_lengths(trimmed) {
-        let start = digits_len + suffix_len;
-        let content = trimmed[start..].to_string();
-        return Some((ListKind::Ordered, content));
+    if let Some(content) = ordered_marker_content(trimmed) {
+        return Some((ListKind::Ordered, content.to_string()));
     }
 
-    if trimmed.starts_with("• ") {
-        let content = trimmed[2..].to_string();
-        return Some((ListKind::Unordered, content));
+    if let Some(content) = trimmed.strip_prefix("• ") {
+        return Some((ListKind::Unordered, content.to_string()));
     }
 
     None
 }
 
-fn ordered_marker_lengths(text: &str) -> Option<(usize, usize)> {
-    let mut digits_len = 0;
-    for ch in text.chars() {
-        if ch.is_ascii_digit() {
-            digits_len += ch.len_utf8();
-        } else {
-            break;
-        }
-    }
-
-    if digits_len == 0 || digits_len == text.len() {
+fn ordered_marker_content(text: &str) -> Option<&str> {
+    let rest = text.trim_start_matches(|ch: char| ch.is_ascii_digit());
+    if rest.len() == text.len() {
         return None;
     }
 
-    let rest = &text[digits_len..];
-    if rest.starts_with(". ") {
-        return Some((digits_len, 2));
-    }
-    if rest.starts_with(") ") {
-        return Some((digits_len, 2));
-    }
-
-    None
+    rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))
 }
 
 fn render_inline(text: &str) -> String {
@@ -184,11 +212,10 @@ fn apply_bold(text: &str) -> String {
     let mut remainder = text;
     let mut open = false;
 
-    while let Some(pos) = remainder.find("**") {
-        let (before, after) = remainder.split_at(pos);
+    while let Some((before, after)) = remainder.split_once("**") {
         result.push_str(before);
         result.push_str(if open { "</strong>" } else { "<strong>" });
-        remainder = &after[2..];
+        remainder = after;
         open = !open;
     }
 
@@ -225,6 +252,70 @@ mod tests {
         assert!(html.contains("and normal."));
     }
 
+    #[test]
+    fn bullet_markers_render_as_list_items() {
+        let html = to_html("• First\n• Second");
+        assert_eq!(html, "<ul><li>First</li><li>Second</li></ul>");
+    }
+
+    #[test]
+    fn pathological_input_renders_balanced_html() {
+        let deeply_nested = (0..500).map(|_| "- ").collect::<String>() + "deep";
+        let indented = (0..200)
+            .map(|depth| format!("{}{}. item", "  ".repeat(depth), depth))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let unbalanced = "**bold *italic __under ** ** **".repeat(50);
+        let inputs = [
+            indented.as_str(),
+            deeply_nested.as_str(),
+            unbalanced.as_str(),
+            "1. \n2)\n3.\n• \n•\n- \n*",
+            "<p><li></strong>",
+            "\u{0}\u{feff}**\r\n\r\n1.",
+            "9999999999999999999999. overflow",
+            "12\n3.\n4)x\n５. wide digit",
+            "é**ü**\n- ñ\n  • ç",
+            "",
+        ];
+        for input in inputs {
+            let html = to_html(input);
+            assert!(
+                is_balanced(&html),
+                "Unbalanced output for {input:?}: {html}"
+            );
+        }
+        assert_eq!(
+            to_html(&deeply_nested),
+            format!("<ul><li>{}deep</li></ul>", "- ".repeat(499))
+        );
+    }
+
+    #[test]
+    fn guard_reports_malformed_output() {
+        fn malformed(_: &str) -> String {
+            "<p><strong>unterminated</p>".to_string()
+        }
+        fn foreign(_: &str) -> String {
+            "<img src=x onerror=alert(1)>".to_string()
+        }
+        assert_eq!(
+            try_render_with("text", malformed),
+            Err(MarkdownError::Malformed)
+        );
+        assert_eq!(
+            try_render_with("text", foreign),
+            Err(MarkdownError::Malformed)
+        );
+        assert!(!is_balanced(
+            "<ul><li><strong><p>too deep</p></strong></li></ul>"
+        ));
+        assert_eq!(
+            try_to_html("**ok**").as_deref(),
+            Ok("<p><strong>ok</strong></p>")
+        );
+    }
+
     #[test]
     fn escapes_html() {
         let input = "<script>alert(1)</script>";
@@ -1,18 +1,49 @@
+use crate::format::{self, Locale};
+use crate::i18n::{self, Message};
 use crate::keyword_icons::{self, Segment as KeywordSegment};
 use crate::markdown;
+use crate::state::OutputWidth;
 use crate::utils;
 use gloo_timers::future::TimeoutFuture;
 use js_sys::Math;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{
     Document, DocumentFragment, Element, HtmlAudioElement, HtmlButtonElement, HtmlDivElement,
-    HtmlElement, HtmlImageElement, HtmlInputElement, HtmlSpanElement, Node, Text,
+    HtmlElement, HtmlImageElement, HtmlInputElement, HtmlSpanElement, Node, ResizeObserver,
+    ResizeObserverEntry, Text,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSummary {
+    pub total: usize,
+    pub succeeded: usize,
+}
+
+impl GroupSummary {
+    pub fn label(&self) -> String {
+        let noun = if self.total == 1 {
+            "command"
+        } else {
+            "commands"
+        };
+        format!("{} {noun}, {} succeeded", self.total, self.succeeded)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinOutcome {
+    Pinned,
+    Unpinned,
+    LimitReached,
+}
+
 const TERMINAL_ID: &str = "terminal";
 const OUTPUT_ID: &str = "output";
+const LINE_SELECTED_CLASS: &str = "line--selected";
 const PROMPT_INPUT_ID: &str = "prompt-input";
 const PROMPT_HIDDEN_INPUT_ID: &str = "prompt-hidden-input";
 const PROMPT_LABEL_ID: &str = "prompt-label";
@@ -20,10 +51,50 @@ const SUGGESTIONS_ID: &str = "suggestions";
 const AI_TOGGLE_ID: &str = "ai-mode-toggle";
 const AI_INDICATOR_ID: &str = "ai-mode-indicator";
 const AI_LOADER_ID: &str = "ai-loader";
+const ANNOUNCER_ID: &str = "sr-announcer";
+const PINNED_OUTPUT_ID: &str = "pinned-output";
+const PIN_BUTTON_CLASS: &str = "output-pin";
+const PINNED_LINE_CLASS: &str = "output-text--pinned";
+pub const PINNED_OUTPUT_LIMIT: usize = 2;
+const COMMAND_GROUP_CLASS: &str = "command-group";
+const GROUP_SUMMARY_CLASS: &str = "command-group__summary";
+const GROUP_BODY_CLASS: &str = "command-group__body";
 
 const COMPACT_SUGGESTION_VISIBLE_COUNT: usize = 4;
 const SUGGESTION_EXPAND_LABEL: &str = "Show more";
 const SUGGESTION_COLLAPSE_LABEL: &str = "Show less";
+const COLLAPSIBLE_CLASS: &str = "output-collapsible";
+const COLLAPSE_TOGGLE_CLASS: &str = "output-collapse__toggle";
+const SHARE_BUTTON_CLASS: &str = "command-line__share";
+const SHARE_COMMAND_ATTR: &str = "data-share-command";
+const TOC_ANCHOR_ATTR: &str = "data-anchor";
+const OUTPUT_MAX_WIDTH_PROPERTY: &str = "--output-max-width";
+const AI_ANSWER_CLASS: &str = "output-block--ai";
+const SUMMARY_CHIP_CLASS: &str = "output-summarize";
+const SUMMARY_SECTION_ATTR: &str = "data-summary-section";
+const SUMMARY_CHIP_LABEL: &str = "✨ Summarize with AI";
+const UPDATE_BANNER_CLASS: &str = "update-banner";
+const SUMMARY_CHIP_BUSY_LABEL: &str = "✨ Summarizing…";
+const REGENERATE_CLASS: &str = "ai-regenerate";
+const REGENERATE_MODEL_ATTR: &str = "data-regenerate-model";
+const EFFECT_PLACEHOLDER_CLASS: &str = "effect-placeholder";
+const READ_ONLY_ATTR: &str = "data-readonly";
+const THEME_CLASS_PREFIX: &str = "theme-";
+const READ_ONLY_CLASS: &str = "terminal--readonly";
+const COMPACT_CLASS: &str = "terminal--compact";
+const COMPACT_LAYOUT_MAX_WIDTH: f64 = 320.0;
+const COMPACT_PROMPT_LABEL: &str = ">$";
+const COMPACT_AI_INDICATOR: &str = "🤖";
+const SUGGESTIONS_LAYOUT_ATTR: &str = "data-layout";
+const SHAW_PLACEHOLDER: &str =
+    "🎬 Shaw bursts onto the terminal stage (media skipped to save data).";
+const POKEMON_PLACEHOLDER_ICON: &str = "⚡";
+const AI_ANSWER_SELECTOR: &str = ".output-block--ai";
+const EFFECT_LINE_SELECTOR: &str = ".shaw-effect-line, .pokemon-effect-line, .cookie-clicker-line";
+const EFFECT_LAYER_SELECTOR: &str = ".cookie-rain";
+const COLLAPSE_EXPAND_LABEL: &str = "Read more";
+const COLLAPSE_COLLAPSE_LABEL: &str = "Show less";
+const COLLAPSE_WRAP_COLUMNS: usize = 96;
 
 #[derive(Clone, Copy)]
 pub enum ScrollBehavior {
@@ -54,6 +125,7 @@ pub struct AchievementView {
     pub hint: String,
     pub unlocked: bool,
     pub tier: AchievementTier,
+    pub progress: Option<(u32, u32)>,
 }
 
 impl AchievementView {
@@ -70,8 +142,22 @@ impl AchievementView {
             hint: hint.to_string(),
             unlocked,
             tier,
+            progress: None,
         }
     }
+
+    pub fn with_progress(mut self, current: u32, target: u32) -> Self {
+        self.progress = Some((current.min(target), target));
+        self
+    }
+}
+
+fn progress_percent(current: u32, target: u32) -> u32 {
+    if target == 0 {
+        return 100;
+    }
+    let percent = u64::from(current.min(target)) * 100 / u64::from(target);
+    percent as u32
 }
 
 #[derive(Clone)]
@@ -83,6 +169,434 @@ pub struct CookieClickerView {
     pub prompt: HtmlElement,
 }
 
+// Identifies a rendered chip list so keystrokes that leave it unchanged skip
+// the DOM entirely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SuggestionSignature {
+    chips: String,
+    expanded: bool,
+    enabled: bool,
+}
+
+fn suggestion_signature(
+    items: &[(String, String)],
+    expanded: bool,
+    enabled: bool,
+) -> SuggestionSignature {
+    let chips = items
+        .iter()
+        .map(|(command, label)| format!("{command}\u{1f}{label}"))
+        .collect::<Vec<_>>()
+        .join("\u{1e}");
+    SuggestionSignature {
+        chips,
+        expanded,
+        enabled,
+    }
+}
+
+// Which of the rendered chips stay visible when `next` only filters them
+// (same chips, same order). `None` means the list must be rebuilt.
+fn suggestion_subset_mask(
+    rendered: &[(String, String)],
+    next: &[(String, String)],
+) -> Option<Vec<bool>> {
+    let mut wanted = next.iter().peekable();
+    let mask = rendered
+        .iter()
+        .map(|chip| {
+            let keep = wanted.peek().is_some_and(|next| *next == chip);
+            if keep {
+                wanted.next();
+            }
+            keep
+        })
+        .collect();
+    wanted.peek().is_none().then_some(mask)
+}
+
+struct SuggestionChips {
+    document: Document,
+    container: HtmlElement,
+    items: RefCell<Vec<HtmlSpanElement>>,
+    toggle: RefCell<Option<HtmlButtonElement>>,
+    rendered: RefCell<Vec<(String, String)>>,
+    signature: RefCell<Option<SuggestionSignature>>,
+}
+
+impl SuggestionChips {
+    fn new(document: Document, container: HtmlElement) -> Self {
+        Self {
+            document,
+            container,
+            items: RefCell::new(Vec::new()),
+            toggle: RefCell::new(None),
+            rendered: RefCell::new(Vec::new()),
+            signature: RefCell::new(None),
+        }
+    }
+
+    fn render<F>(
+        &self,
+        items: &[(String, String)],
+        enabled: bool,
+        enabling: bool,
+        expanded: bool,
+        available_while_disabled: F,
+    ) where
+        F: Fn(&str) -> bool,
+    {
+        let signature = suggestion_signature(items, expanded, enabled);
+        if !enabling && self.signature.borrow().as_ref() == Some(&signature) {
+            return;
+        }
+        let same_gating = self
+            .signature
+            .borrow()
+            .as_ref()
+            .is_some_and(|previous| previous.enabled == enabled);
+        let mask = if same_gating && !enabling {
+            suggestion_subset_mask(&self.rendered.borrow(), items)
+        } else {
+            None
+        };
+        let mask = match mask {
+            Some(mask) => mask,
+            None => {
+                self.rebuild(items);
+                vec![true; items.len()]
+            }
+        };
+
+        let visible_total = mask.iter().filter(|visible| **visible).count();
+        let has_extras = visible_total > COMPACT_SUGGESTION_VISIBLE_COUNT;
+        let mut visible_index = 0;
+        for ((span, (command, _)), visible) in self
+            .items
+            .borrow()
+            .iter()
+            .zip(self.rendered.borrow().iter())
+            .zip(mask)
+        {
+            let mut classes = String::from("suggestion");
+            if !visible {
+                classes.push_str(" suggestion--hidden");
+                span.set_class_name(&classes);
+                let _ = span.set_attribute("aria-hidden", "true");
+                let _ = span.set_attribute("tabindex", "-1");
+                continue;
+            }
+            let _ = span.remove_attribute("aria-hidden");
+            if has_extras && visible_index >= COMPACT_SUGGESTION_VISIBLE_COUNT {
+                classes.push_str(" suggestion--extra");
+            }
+            visible_index += 1;
+            let inert = !enabled && !available_while_disabled(command);
+            if inert {
+                classes.push_str(" suggestion--skeleton");
+                let _ = span.set_attribute("aria-disabled", "true");
+                let _ = span.set_attribute("tabindex", "-1");
+            } else {
+                if enabling {
+                    classes.push_str(" suggestion--enabling");
+                }
+                let _ = span.remove_attribute("aria-disabled");
+                let _ = span.set_attribute("tabindex", "0");
+            }
+            span.set_class_name(&classes);
+        }
+
+        if has_extras && self.sync_toggle(expanded) {
+            let _ = self
+                .container
+                .set_attribute("data-expanded", if expanded { "true" } else { "false" });
+            let _ = self.container.set_attribute("data-collapsible", "true");
+        } else {
+            self.hide_toggle();
+            let _ = self.container.remove_attribute("data-expanded");
+            let _ = self.container.remove_attribute("data-collapsible");
+        }
+        self.signature.replace(Some(signature));
+    }
+
+    fn rebuild(&self, items: &[(String, String)]) {
+        let mut cache = self.items.borrow_mut();
+        let total = items.len();
+        while cache.len() > total {
+            if let Some(span) = cache.pop() {
+                if let Some(parent) = span.parent_node() {
+                    let node: Node = span.into();
+                    let _ = parent.remove_child(&node);
+                }
+            }
+        }
+        while cache.len() < total {
+            let Ok(element) = self.document.create_element("span") else {
+                break;
+            };
+            if let Ok(span) = element.dyn_into::<HtmlSpanElement>() {
+                let _ = span.set_attribute("role", "button");
+                let node: Node = span.clone().into();
+                let _ = self.container.append_child(&node);
+                cache.push(span);
+            }
+        }
+        for (span, (command, label)) in cache.iter().zip(items.iter()) {
+            let _ = span.set_attribute("data-command", command);
+            span.set_text_content(Some(label));
+        }
+        self.rendered
+            .replace(items.iter().take(cache.len()).cloned().collect());
+    }
+
+    // Keeps the expand button after the last chip; returns false when it
+    // could not be created.
+    fn sync_toggle(&self, expanded: bool) -> bool {
+        let mut toggle = self.toggle.borrow_mut();
+        if toggle.is_none() {
+            let Some(button) = self
+                .document
+                .create_element("button")
+                .ok()
+                .and_then(|element| element.dyn_into::<HtmlButtonElement>().ok())
+            else {
+                return false;
+            };
+            button.set_class_name("suggestions__toggle");
+            button.set_type("button");
+            let _ = button.set_attribute("aria-controls", SUGGESTIONS_ID);
+            *toggle = Some(button);
+        }
+        let Some(button) = toggle.as_ref() else {
+            return false;
+        };
+        label_suggestion_toggle(button, expanded);
+        let is_last = self.container.last_element_child().is_some_and(|last| {
+            AsRef::<JsValue>::as_ref(&last) == AsRef::<JsValue>::as_ref(button)
+        });
+        if !is_last {
+            let node: Node = button.clone().into();
+            let _ = self.container.append_child(&node);
+        }
+        true
+    }
+
+    fn hide_toggle(&self) {
+        if let Some(button) = self.toggle.borrow().as_ref() {
+            if let Some(parent) = button.parent_node() {
+                let node: Node = button.clone().into();
+                let _ = parent.remove_child(&node);
+            }
+        }
+    }
+}
+
+struct PinnedOutput {
+    line: HtmlElement,
+    placeholder: Node,
+}
+
+// Pinned output lines live in their own region above the scrolling output.
+// A comment node keeps each line's original spot so unpinning puts it back.
+struct PinnedOutputs {
+    document: Document,
+    region: HtmlElement,
+    entries: RefCell<Vec<PinnedOutput>>,
+}
+
+impl PinnedOutputs {
+    fn new(document: Document, region: HtmlElement) -> Self {
+        region.set_hidden(true);
+        Self {
+            document,
+            region,
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    fn toggle(&self, line: &HtmlElement) -> Result<PinOutcome, JsValue> {
+        let position = self
+            .entries
+            .borrow()
+            .iter()
+            .position(|entry| entry.line == *line);
+        if let Some(position) = position {
+            let entry = self.entries.borrow_mut().remove(position);
+            self.unpin(&entry)?;
+            self.sync_region();
+            return Ok(PinOutcome::Unpinned);
+        }
+        if self.len() >= PINNED_OUTPUT_LIMIT {
+            return Ok(PinOutcome::LimitReached);
+        }
+        let placeholder: Node = self.document.create_comment("pinned output").into();
+        let node: &Node = line.unchecked_ref();
+        if let Some(parent) = node.parent_node() {
+            parent.insert_before(&placeholder, Some(node))?;
+        }
+        self.region.append_child(line)?;
+        line.class_list().add_1(PINNED_LINE_CLASS)?;
+        label_pin_button(line, true)?;
+        self.entries.borrow_mut().push(PinnedOutput {
+            line: line.clone(),
+            placeholder,
+        });
+        self.sync_region();
+        Ok(PinOutcome::Pinned)
+    }
+
+    // Lines whose original spot was cleared have nowhere to go back to.
+    fn unpin(&self, entry: &PinnedOutput) -> Result<(), JsValue> {
+        entry.line.class_list().remove_1(PINNED_LINE_CLASS)?;
+        label_pin_button(&entry.line, false)?;
+        match entry.placeholder.parent_node() {
+            Some(parent) => {
+                parent.replace_child(&entry.line, &entry.placeholder)?;
+            }
+            None => entry.line.remove(),
+        }
+        Ok(())
+    }
+
+    fn clear(&self) -> usize {
+        let entries = self.entries.take();
+        for entry in &entries {
+            entry.line.remove();
+            if let Some(parent) = entry.placeholder.parent_node() {
+                let _ = parent.remove_child(&entry.placeholder);
+            }
+        }
+        self.sync_region();
+        entries.len()
+    }
+
+    fn sync_region(&self) {
+        self.region.set_hidden(self.entries.borrow().is_empty());
+    }
+}
+
+// The button carries no text so copying or selecting output lines never
+// picks up the pin glyph, which comes from CSS.
+fn build_pin_button(document: &Document) -> Result<HtmlButtonElement, JsValue> {
+    let button = document
+        .create_element("button")?
+        .dyn_into::<HtmlButtonElement>()?;
+    button.set_type("button");
+    button.set_class_name(PIN_BUTTON_CLASS);
+    label_pin_button_element(&button, false)?;
+    Ok(button)
+}
+
+fn label_pin_button(line: &HtmlElement, pinned: bool) -> Result<(), JsValue> {
+    if let Some(button) = line.query_selector(&format!(":scope > .{PIN_BUTTON_CLASS}"))? {
+        label_pin_button_element(&button, pinned)?;
+    }
+    Ok(())
+}
+
+fn label_pin_button_element(button: &Element, pinned: bool) -> Result<(), JsValue> {
+    let label = if pinned {
+        "Unpin this output"
+    } else {
+        "Pin this output"
+    };
+    button.set_attribute("aria-label", label)?;
+    button.set_attribute("title", label)?;
+    button.set_attribute("aria-pressed", if pinned { "true" } else { "false" })
+}
+
+fn set_group_expanded(toggle: &Element, body: &Element, expanded: bool) -> Result<(), JsValue> {
+    toggle.set_attribute("aria-expanded", if expanded { "true" } else { "false" })?;
+    if expanded {
+        body.remove_attribute("hidden")
+    } else {
+        body.set_attribute("hidden", "")
+    }
+}
+
+fn is_compact_width(width: f64) -> bool {
+    // A zero width means the element is not laid out yet (hidden tab, detached).
+    width > 0.0 && width < COMPACT_LAYOUT_MAX_WIDTH
+}
+
+fn displayed_prompt_label(label: &str, compact: bool) -> &str {
+    if compact {
+        COMPACT_PROMPT_LABEL
+    } else {
+        label
+    }
+}
+
+fn apply_compact_layout(
+    root: &Element,
+    suggestions: &Element,
+    compact: bool,
+) -> Result<(), JsValue> {
+    root.class_list()
+        .toggle_with_force(COMPACT_CLASS, compact)?;
+    if compact {
+        suggestions.set_attribute(SUGGESTIONS_LAYOUT_ATTR, "scroll")
+    } else {
+        suggestions.remove_attribute(SUGGESTIONS_LAYOUT_ATTR)
+    }
+}
+
+fn ai_toggle_label(active: bool, busy: bool) -> String {
+    let action = if active {
+        "Disable AI assistant"
+    } else {
+        "Enable AI assistant"
+    };
+    if busy {
+        format!("{action} — currently answering")
+    } else {
+        action.to_string()
+    }
+}
+
+fn render_ai_indicator(indicator: &Element, text: &str, compact: bool) {
+    if compact {
+        indicator.set_text_content(Some(COMPACT_AI_INDICATOR));
+        let _ = indicator.set_attribute("title", text);
+        let _ = indicator.set_attribute("aria-label", text);
+    } else {
+        indicator.set_text_content(Some(text));
+        let _ = indicator.remove_attribute("title");
+        let _ = indicator.remove_attribute("aria-label");
+    }
+}
+
+fn observe_compact_width(
+    element: &Element,
+    on_change: impl Fn(bool) + 'static,
+) -> Result<(), JsValue> {
+    let callback = Closure::<dyn FnMut(js_sys::Array)>::new(move |entries: js_sys::Array| {
+        let width = entries
+            .get(0)
+            .dyn_into::<ResizeObserverEntry>()
+            .map(|entry| entry.content_rect().width())
+            .unwrap_or(0.0);
+        on_change(is_compact_width(width));
+    });
+    let observer = ResizeObserver::new(callback.as_ref().unchecked_ref())?;
+    observer.observe(element);
+    callback.forget();
+    Ok(())
+}
+
+fn label_suggestion_toggle(button: &HtmlButtonElement, expanded: bool) {
+    button.set_text_content(Some(if expanded {
+        SUGGESTION_COLLAPSE_LABEL
+    } else {
+        SUGGESTION_EXPAND_LABEL
+    }));
+    let _ = button.set_attribute("aria-expanded", if expanded { "true" } else { "false" });
+}
+
 pub struct Renderer {
     document: Document,
     terminal_root: HtmlElement,
@@ -91,15 +605,29 @@ pub struct Renderer {
     prompt_hidden_input: HtmlInputElement,
     prompt_label: HtmlElement,
     suggestions: HtmlElement,
-    suggestion_items: RefCell<Vec<HtmlSpanElement>>,
-    suggestion_toggle: RefCell<Option<HtmlButtonElement>>,
+    suggestion_chips: SuggestionChips,
+    suggestions_enabled: Cell<bool>,
+    suggestions_enabling: Cell<bool>,
+    suggestions_expanded: Cell<bool>,
     ai_toggle: HtmlElement,
     ai_indicator: HtmlElement,
+    ai_active: Cell<Option<bool>>,
+    ai_busy: Cell<bool>,
+    announcer: HtmlElement,
+    pins: PinnedOutputs,
+    // Body of the open `.command-group`; new lines land there instead of #output.
+    group: RefCell<Option<HtmlElement>>,
     achievement_layer: HtmlElement,
     achievements_trigger: HtmlElement,
     achievements_overlay: HtmlElement,
     achievements_modal: HtmlElement,
     last_command: RefCell<Option<HtmlElement>>,
+    data_saver: Cell<bool>,
+    reading_time: Cell<Option<(usize, Locale)>>,
+    locale: Cell<Locale>,
+    compact: Cell<bool>,
+    prompt_label_text: RefCell<String>,
+    ai_indicator_text: RefCell<String>,
 }
 
 impl Renderer {
@@ -114,6 +642,37 @@ impl Renderer {
         let suggestions = get_html_element(&document, SUGGESTIONS_ID)?;
         let ai_toggle = get_html_element(&document, AI_TOGGLE_ID)?;
         let ai_indicator = get_html_element(&document, AI_INDICATOR_ID)?;
+        ai_toggle.set_attribute("role", "switch")?;
+        let announcer = match document.get_element_by_id(ANNOUNCER_ID) {
+            Some(existing) => existing.dyn_into::<HtmlElement>()?,
+            None => {
+                let announcer = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+                announcer.set_id(ANNOUNCER_ID);
+                announcer.set_class_name("sr-only");
+                announcer.set_attribute("role", "status")?;
+                announcer.set_attribute("aria-live", "polite")?;
+                announcer.set_attribute("aria-atomic", "true")?;
+                terminal_root.append_child(&announcer)?;
+                announcer
+            }
+        };
+        let pinned_region = match document.get_element_by_id(PINNED_OUTPUT_ID) {
+            Some(existing) => existing.dyn_into::<HtmlElement>()?,
+            None => {
+                let region = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+                region.set_id(PINNED_OUTPUT_ID);
+                region.set_class_name("pinned-output");
+                region.set_attribute("role", "region")?;
+                region.set_attribute("aria-label", "Pinned output")?;
+                let output_node: &Node = output.unchecked_ref();
+                match output_node.parent_node() {
+                    Some(parent) => parent.insert_before(&region, Some(output_node))?,
+                    None => terminal_root.append_child(&region)?,
+                };
+                region
+            }
+        };
+        let pins = PinnedOutputs::new(document.clone(), pinned_region);
         let achievements_trigger = get_html_element(&document, "achievements-trigger")?;
         let achievements_overlay = get_html_element(&document, "achievements-overlay")?;
         let achievements_modal = get_html_element(&document, "achievements-modal")?;
@@ -139,6 +698,10 @@ impl Renderer {
             }
         };
 
+        let suggestion_chips = SuggestionChips::new(document.clone(), suggestions.clone());
+        let prompt_label_text = prompt_label.text_content().unwrap_or_default();
+        let ai_indicator_text = ai_indicator.text_content().unwrap_or_default();
+
         Ok(Self {
             document,
             terminal_root,
@@ -146,21 +709,61 @@ impl Renderer {
             prompt_input,
             prompt_hidden_input,
             prompt_label,
+            suggestion_chips,
             suggestions,
-            suggestion_items: RefCell::new(Vec::new()),
-            suggestion_toggle: RefCell::new(None),
+            suggestions_enabled: Cell::new(true),
+            suggestions_enabling: Cell::new(false),
+            suggestions_expanded: Cell::new(false),
             ai_toggle,
             ai_indicator,
+            ai_active: Cell::new(None),
+            ai_busy: Cell::new(false),
+            announcer,
+            pins,
+            group: RefCell::new(None),
             achievement_layer,
             achievements_trigger,
             achievements_overlay,
             achievements_modal,
             last_command: RefCell::new(None),
+            data_saver: Cell::new(false),
+            reading_time: Cell::new(None),
+            locale: Cell::new(Locale::default()),
+            compact: Cell::new(false),
+            prompt_label_text: RefCell::new(prompt_label_text),
+            ai_indicator_text: RefCell::new(ai_indicator_text),
         })
     }
 
     pub fn set_prompt_label(&self, label: &str) {
-        self.prompt_label.set_text_content(Some(label));
+        self.prompt_label_text.replace(label.to_string());
+        self.prompt_label
+            .set_text_content(Some(displayed_prompt_label(label, self.compact.get())));
+    }
+
+    // Switches to the compact layout whenever the terminal gets narrower than
+    // `COMPACT_LAYOUT_MAX_WIDTH`. The observer lives as long as the page.
+    pub fn observe_compact_layout(self: &Rc<Self>) -> Result<(), JsValue> {
+        let renderer = Rc::downgrade(self);
+        observe_compact_width(&self.terminal_root, move |compact| {
+            if let Some(renderer) = renderer.upgrade() {
+                if let Err(err) = renderer.set_compact_layout(compact) {
+                    utils::log(&format!("Failed to toggle compact layout: {:?}", err));
+                }
+            }
+        })
+    }
+
+    pub fn set_compact_layout(&self, compact: bool) -> Result<(), JsValue> {
+        if self.compact.replace(compact) == compact {
+            return Ok(());
+        }
+        apply_compact_layout(&self.terminal_root, &self.suggestions, compact)?;
+        let label = self.prompt_label_text.borrow().clone();
+        self.set_prompt_label(&label);
+        let indicator = self.ai_indicator_text.borrow().clone();
+        self.set_ai_indicator_text(&indicator);
+        Ok(())
     }
 
     pub fn update_input(&self, buffer: &str) {
@@ -170,41 +773,84 @@ impl Renderer {
         let _ = self.prompt_hidden_input.set_selection_range(end, end);
     }
 
+    pub fn input_snapshot(&self) -> (String, String) {
+        (
+            self.prompt_input.text_content().unwrap_or_default(),
+            self.prompt_hidden_input.value(),
+        )
+    }
+
     pub fn focus_terminal(&self) {
         let _ = self.prompt_hidden_input.focus();
         let end = self.prompt_hidden_input.value().encode_utf16().count() as u32;
         let _ = self.prompt_hidden_input.set_selection_range(end, end);
     }
 
-    pub fn append_command(
-        &self,
-        label: &str,
-        command: &str,
-        behavior: ScrollBehavior,
-    ) -> Result<(), JsValue> {
-        let line = self
+    fn append_line(&self, line: &Node) -> Result<(), JsValue> {
+        match self.group.borrow().as_ref() {
+            Some(body) => body.append_child(line)?,
+            None => self.output.append_child(line)?,
+        };
+        Ok(())
+    }
+
+    // Groups do not nest: a second `begin_group` keeps filling the open one.
+    pub fn begin_group(&self) -> Result<(), JsValue> {
+        if self.group.borrow().is_some() {
+            return Ok(());
+        }
+        let container = self.document.create_element("div")?;
+        container.set_class_name(&format!("line {COMMAND_GROUP_CLASS}"));
+        let body = self
             .document
             .create_element("div")?
             .dyn_into::<HtmlElement>()?;
-        line.set_class_name("line command-line");
+        body.set_class_name(GROUP_BODY_CLASS);
+        container.append_child(&body)?;
+        self.output.append_child(&container)?;
+        self.group.replace(Some(body));
+        Ok(())
+    }
 
-        let label_span = self
-            .document
-            .create_element("span")?
-            .dyn_into::<HtmlSpanElement>()?;
-        label_span.set_class_name("prompt-label");
-        label_span.set_text_content(Some(label));
+    // Closes the open group behind a summary toggle, collapsed by default.
+    pub fn end_group(&self, summary: GroupSummary) -> Result<(), JsValue> {
+        let Some(body) = self.group.borrow_mut().take() else {
+            return Ok(());
+        };
+        let Some(container) = body.parent_element() else {
+            return Ok(());
+        };
+        let toggle = self.document.create_element("button")?;
+        toggle.set_class_name(GROUP_SUMMARY_CLASS);
+        toggle.set_attribute("type", "button")?;
+        toggle.set_text_content(Some(&summary.label()));
+        container.insert_before(&toggle, Some(&body))?;
+        set_group_expanded(&toggle, &body, false)?;
+        self.scroll_to_bottom();
+        Ok(())
+    }
 
-        let command_span = self
-            .document
-            .create_element("span")?
-            .dyn_into::<HtmlSpanElement>()?;
-        command_span.set_class_name("prompt-command");
-        command_span.set_text_content(Some(command));
+    pub fn toggle_group(&self, trigger: &Element) -> Result<bool, JsValue> {
+        if !trigger.class_list().contains(GROUP_SUMMARY_CLASS) {
+            return Ok(false);
+        }
+        let Some(body) = trigger.next_element_sibling() else {
+            return Ok(false);
+        };
+        let expanded = trigger.get_attribute("aria-expanded").as_deref() == Some("true");
+        set_group_expanded(trigger, &body, !expanded)?;
+        Ok(true)
+    }
 
-        line.append_child(&label_span)?;
-        line.append_child(&command_span)?;
-        self.output.append_child(&line)?;
+    pub fn append_command(
+        &self,
+        label: &str,
+        command: &str,
+        shareable: bool,
+        behavior: ScrollBehavior,
+    ) -> Result<(), JsValue> {
+        let line = build_command_line(&self.document, label, command, shareable)?;
+        self.append_line(&line)?;
         {
             let mut anchor = self.last_command.borrow_mut();
             *anchor = Some(line.clone());
@@ -214,6 +860,10 @@ impl Renderer {
         Ok(())
     }
 
+    pub fn shared_command(&self, trigger: &Element) -> Result<Option<String>, JsValue> {
+        shared_command_for(trigger)
+    }
+
     pub fn append_spacer_line(&self, behavior: ScrollBehavior) -> Result<(), JsValue> {
         let spacer = self
             .document
@@ -222,13 +872,21 @@ impl Renderer {
         spacer.set_class_name("line spacer-line");
         spacer.set_text_content(Some("\u{00a0}"));
         spacer.set_attribute("aria-hidden", "true")?;
-        self.output.append_child(&spacer)?;
+        self.append_line(&spacer)?;
         let element: &HtmlElement = spacer.unchecked_ref();
         self.apply_scroll(element, behavior)?;
         Ok(())
     }
 
     pub fn append_output_text(&self, text: &str, behavior: ScrollBehavior) -> Result<(), JsValue> {
+        self.append_output_text_block(text, behavior).map(|_| ())
+    }
+
+    fn append_output_text_block(
+        &self,
+        text: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<HtmlElement, JsValue> {
         let wrapper = self
             .document
             .create_element("div")?
@@ -243,10 +901,12 @@ impl Renderer {
         self.render_text_with_icons(&pre, text)?;
 
         wrapper.append_child(&pre)?;
-        self.output.append_child(&wrapper)?;
-        let element: &HtmlElement = wrapper.unchecked_ref();
-        self.apply_scroll(element, behavior)?;
-        Ok(())
+        let pin = build_pin_button(&self.document)?;
+        wrapper.append_child(&pin)?;
+        self.append_line(&wrapper)?;
+        let element: HtmlElement = wrapper.unchecked_into();
+        self.apply_scroll(&element, behavior)?;
+        Ok(element)
     }
 
     pub fn show_achievements_modal(
@@ -277,7 +937,7 @@ impl Renderer {
             .dyn_into::<HtmlElement>()?;
         title_el.set_id("achievements-modal-title");
         title_el.set_class_name("achievements-modal__title");
-        title_el.set_text_content(Some("Easter Eggs"));
+        title_el.set_text_content(Some(self.t(Message::AchievementsModalTitle)));
 
         let actions = self
             .document
@@ -296,11 +956,11 @@ impl Renderer {
             "aria-pressed",
             if spoilers_enabled { "true" } else { "false" },
         )?;
-        spoiler_btn.set_text_content(Some(if spoilers_enabled {
-            "Hide spoilers"
+        spoiler_btn.set_text_content(Some(self.t(if spoilers_enabled {
+            Message::AchievementsHideSpoilers
         } else {
-            "Reveal spoilers"
-        }));
+            Message::AchievementsRevealSpoilers
+        })));
         actions.append_child(&spoiler_btn)?;
 
         let reset_btn = self
@@ -310,7 +970,7 @@ impl Renderer {
         reset_btn.set_class_name("achievements-modal__action");
         reset_btn.set_attribute("type", "button")?;
         reset_btn.set_attribute("data-role", "achievements-reset")?;
-        reset_btn.set_text_content(Some("Reset"));
+        reset_btn.set_text_content(Some(self.t(Message::AchievementsResetAction)));
         actions.append_child(&reset_btn)?;
 
         let close_btn = self
@@ -320,8 +980,8 @@ impl Renderer {
         close_btn.set_class_name("achievements-modal__action achievements-modal__close");
         close_btn.set_attribute("type", "button")?;
         close_btn.set_attribute("data-role", "achievements-close")?;
-        close_btn.set_attribute("aria-label", "Close Easter Eggs panel")?;
-        close_btn.set_text_content(Some("Close"));
+        close_btn.set_attribute("aria-label", self.t(Message::AchievementsCloseLabel))?;
+        close_btn.set_text_content(Some(self.t(Message::AchievementsClose)));
 
         header.append_child(&title_el)?;
         header.append_child(&actions)?;
@@ -453,6 +1113,16 @@ impl Renderer {
 
             item.append_child(&summary)?;
             item.append_child(&description)?;
+            // The bar gives nothing away, so it stays visible with spoilers hidden.
+            if let Some((current, target)) = achievement.progress {
+                let label = i18n::t_with(
+                    Message::AchievementProgressLabel,
+                    self.locale.get(),
+                    &[("percent", &progress_percent(current, target).to_string())],
+                );
+                let bar = build_achievement_progress(&self.document, current, target, &label)?;
+                item.append_child(&bar)?;
+            }
             list.append_child(&item)?;
         }
 
@@ -464,12 +1134,13 @@ impl Renderer {
             .set_attribute("aria-hidden", "false")?;
         self.achievements_trigger
             .set_attribute("aria-expanded", "true")?;
+        Ok(())
+    }
 
+    pub fn focus_achievements_modal(&self) {
         if let Err(err) = self.achievements_modal.focus() {
             utils::log(&format!("Failed to focus achievements modal: {:?}", err));
         }
-
-        Ok(())
     }
 
     pub fn hide_achievements_modal(&self) -> Result<(), JsValue> {
@@ -483,6 +1154,14 @@ impl Renderer {
     }
 
     pub fn append_output_html(&self, html: &str, behavior: ScrollBehavior) -> Result<(), JsValue> {
+        self.append_output_html_block(html, behavior).map(|_| ())
+    }
+
+    fn append_output_html_block(
+        &self,
+        html: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<HtmlElement, JsValue> {
         let wrapper = self
             .document
             .create_element("div")?
@@ -494,14 +1173,19 @@ impl Renderer {
             .create_element("div")?
             .dyn_into::<HtmlElement>()?;
         container.set_class_name("output-block output-block--html");
-        container.set_inner_html(html);
+        set_fragment_html(&container, html)?;
+        if self.data_saver.get() {
+            suppress_autoplay(&container)?;
+        }
         self.decorate_with_icons(&container)?;
 
         wrapper.append_child(&container)?;
-        self.output.append_child(&wrapper)?;
-        let element: &HtmlElement = wrapper.unchecked_ref();
-        self.apply_scroll(element, behavior)?;
-        Ok(())
+        let pin = build_pin_button(&self.document)?;
+        wrapper.append_child(&pin)?;
+        self.append_line(&wrapper)?;
+        let element: HtmlElement = wrapper.unchecked_into();
+        self.apply_scroll(&element, behavior)?;
+        Ok(element)
     }
 
     pub fn append_info_line(&self, message: &str, behavior: ScrollBehavior) -> Result<(), JsValue> {
@@ -511,21 +1195,95 @@ impl Renderer {
             .dyn_into::<HtmlDivElement>()?;
         line.set_class_name("line info-line");
         self.render_text_with_icons(&line, message)?;
-        self.output.append_child(&line)?;
+        self.append_line(&line)?;
+        let element: &HtmlElement = line.unchecked_ref();
+        self.apply_scroll(element, behavior)?;
+        Ok(())
+    }
+
+    pub fn flash_last_output(&self) -> Result<(), JsValue> {
+        let Some(last) = self.output.last_element_child() else {
+            return Ok(());
+        };
+        let classes = last.class_list();
+        classes.remove_1("line--flash")?;
+        if let Some(element) = last.dyn_ref::<HtmlElement>() {
+            let _ = element.offset_width();
+        }
+        classes.add_1("line--flash")?;
+        Ok(())
+    }
+
+    pub fn append_error_line(
+        &self,
+        message: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<(), JsValue> {
+        let line = self
+            .document
+            .create_element("div")?
+            .dyn_into::<HtmlDivElement>()?;
+        line.set_class_name("line info-line error-line");
+        line.set_attribute("role", "alert")?;
+        line.set_text_content(Some(message));
+        self.append_line(&line)?;
         let element: &HtmlElement = line.unchecked_ref();
         self.apply_scroll(element, behavior)?;
         Ok(())
     }
 
     pub fn append_info_html(&self, message: &str, behavior: ScrollBehavior) -> Result<(), JsValue> {
+        self.append_info_html_line("line info-line info-neutral", message, behavior)
+    }
+
+    pub fn append_update_banner(&self, message: &str) -> Result<(), JsValue> {
+        self.append_info_html_line(
+            &format!("line info-line info-neutral {UPDATE_BANNER_CLASS}"),
+            message,
+            ScrollBehavior::Bottom,
+        )
+    }
+
+    pub fn has_update_banner(&self) -> bool {
+        self.output
+            .query_selector(&format!(".{UPDATE_BANNER_CLASS}"))
+            .ok()
+            .flatten()
+            .is_some()
+    }
+
+    pub fn remove_update_banner(&self) -> Result<(), JsValue> {
+        let banners = self
+            .output
+            .query_selector_all(&format!(".{UPDATE_BANNER_CLASS}"))?;
+        for index in 0..banners.length() {
+            if let Some(banner) = banners
+                .item(index)
+                .and_then(|node| node.dyn_into::<Element>().ok())
+            {
+                banner.remove();
+            }
+        }
+        Ok(())
+    }
+
+    fn append_info_html_line(
+        &self,
+        class_name: &str,
+        message: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<(), JsValue> {
         let line = self
             .document
             .create_element("div")?
             .dyn_into::<HtmlDivElement>()?;
-        line.set_class_name("line info-line info-neutral");
-        line.set_inner_html(message);
+        line.set_class_name(class_name);
+        set_fragment_html(&line, message)?;
+        if self.data_saver.get() {
+            suppress_autoplay(&line)?;
+        }
         self.decorate_with_icons(&line)?;
-        self.output.append_child(&line)?;
+        self.append_line(&line)?;
         let element: &HtmlElement = line.unchecked_ref();
         self.apply_scroll(element, behavior)?;
         Ok(())
@@ -535,9 +1293,113 @@ impl Renderer {
         &self,
         text: &str,
         behavior: ScrollBehavior,
+    ) -> Result<HtmlElement, JsValue> {
+        let block = match markdown::try_to_html(text) {
+            Ok(html) => self.append_output_html_block(&html, behavior)?,
+            Err(err) => {
+                utils::log(&format!(
+                    "Markdown rendering failed ({err:?}); falling back to plain text"
+                ));
+                self.append_output_text_block(text, behavior)?
+            }
+        };
+        self.append_reading_time(text, behavior)?;
+        Ok(block)
+    }
+
+    pub fn append_ai_answer(
+        &self,
+        text: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<HtmlElement, JsValue> {
+        let line = self.append_output_markdown(text, behavior)?;
+        if let Some(block) = line.query_selector(".output-block")? {
+            block.class_list().add_1(AI_ANSWER_CLASS)?;
+        }
+        Ok(line)
+    }
+
+    pub fn append_summary_chip(
+        &self,
+        section: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<(), JsValue> {
+        let line = build_summary_chip(&self.document, section)?;
+        self.append_line(&line)?;
+        self.apply_scroll(&line, behavior)
+    }
+
+    // Marks the chip busy and returns its section, or None when the click
+    // did not come from an idle chip.
+    pub fn begin_summary(&self, trigger: &Element) -> Result<Option<String>, JsValue> {
+        let Some(button) = trigger.dyn_ref::<HtmlButtonElement>() else {
+            return Ok(None);
+        };
+        if button.disabled() {
+            return Ok(None);
+        }
+        let Some(section) = button.get_attribute(SUMMARY_SECTION_ATTR) else {
+            return Ok(None);
+        };
+        button.set_disabled(true);
+        button.set_text_content(Some(SUMMARY_CHIP_BUSY_LABEL));
+        Ok(Some(section))
+    }
+
+    pub fn reset_summary_chip(&self, trigger: &Element) {
+        if let Some(button) = trigger.dyn_ref::<HtmlButtonElement>() {
+            button.set_disabled(false);
+            button.set_text_content(Some(SUMMARY_CHIP_LABEL));
+        }
+    }
+
+    // The summary takes the chip's place so it sits right under the output it
+    // summarizes, even if other commands ran in the meantime.
+    pub fn replace_summary_chip(&self, trigger: &Element, summary: &str) -> Result<(), JsValue> {
+        let block = self.append_ai_answer(summary, ScrollBehavior::None)?;
+        match trigger.closest(".summary-chip-line")? {
+            Some(chip_line) => chip_line.replace_with_with_node_1(&block),
+            None => Ok(()),
+        }
+    }
+
+    pub fn remove_summary_chip(&self, trigger: &Element) -> Result<(), JsValue> {
+        if let Some(chip_line) = trigger.closest(".summary-chip-line")? {
+            chip_line.remove();
+        }
+        Ok(())
+    }
+
+    // Only the latest answer can be regenerated, so older controls go away.
+    pub fn append_regenerate_control(
+        &self,
+        model: &str,
+        behavior: ScrollBehavior,
     ) -> Result<(), JsValue> {
-        let html = markdown::to_html(text);
-        self.append_output_html(&html, behavior)
+        self.remove_regenerate_controls()?;
+        let line = build_regenerate_control(&self.document, model)?;
+        self.append_line(&line)?;
+        self.apply_scroll(&line, behavior)
+    }
+
+    pub fn regenerate_model(&self, trigger: &Element) -> Option<String> {
+        let button = trigger.dyn_ref::<HtmlButtonElement>()?;
+        if button.disabled() {
+            return None;
+        }
+        button.get_attribute(REGENERATE_MODEL_ATTR)
+    }
+
+    pub fn remove_regenerate_controls(&self) -> Result<(), JsValue> {
+        remove_lines_matching(&self.output, &format!(".{REGENERATE_CLASS}")).map(|_| ())
+    }
+
+    pub fn collapse_if_long(&self, block: &HtmlElement, max_lines: usize) -> Result<bool, JsValue> {
+        collapse_block_if_long(&self.document, block, max_lines)
+    }
+
+    pub fn toggle_collapsed_block(&self, toggle: &Element) -> Result<bool, JsValue> {
+        toggle_collapsed_block(toggle)
     }
 
     fn decorate_with_icons(&self, element: &HtmlElement) -> Result<(), JsValue> {
@@ -669,9 +1531,66 @@ impl Renderer {
         Ok(span.into())
     }
 
+    pub fn output_line_index(&self, target: &Element) -> Option<usize> {
+        child_index_containing(&self.output, target)
+    }
+
+    pub fn highlight_output_lines(&self, range: Option<(usize, usize)>) {
+        if let Err(err) = mark_selected_children(&self.output, range) {
+            utils::log(&format!("Failed to highlight selected lines: {:?}", err));
+        }
+    }
+
+    pub fn output_lines_text(&self, start: usize, end: usize) -> String {
+        children_text(&self.output, start, end)
+    }
+
     pub fn clear_output(&self) {
-        self.output.set_inner_html("");
+        self.output.set_text_content(None);
         self.last_command.borrow_mut().take();
+        self.group.borrow_mut().take();
+    }
+
+    pub fn toggle_pin(&self, trigger: &Element) -> Result<Option<PinOutcome>, JsValue> {
+        if !trigger.class_list().contains(PIN_BUTTON_CLASS) {
+            return Ok(None);
+        }
+        let Some(line) = trigger.parent_element() else {
+            return Ok(None);
+        };
+        let line = line.dyn_into::<HtmlElement>()?;
+        self.pins.toggle(&line).map(Some)
+    }
+
+    pub fn pinned_count(&self) -> usize {
+        self.pins.len()
+    }
+
+    pub fn clear_pins(&self) -> usize {
+        self.pins.clear()
+    }
+
+    pub fn remove_ai_answers(&self) -> Result<usize, JsValue> {
+        let removed = remove_lines_matching(&self.output, AI_ANSWER_SELECTOR)?;
+        self.forget_detached_command();
+        Ok(removed)
+    }
+
+    pub fn remove_effects(&self) -> Result<usize, JsValue> {
+        let removed = remove_lines_matching(&self.output, EFFECT_LINE_SELECTOR)?
+            + remove_lines_matching(&self.terminal_root, EFFECT_LAYER_SELECTOR)?;
+        self.forget_detached_command();
+        Ok(removed)
+    }
+
+    fn forget_detached_command(&self) {
+        let mut last_command = self.last_command.borrow_mut();
+        if last_command
+            .as_ref()
+            .is_some_and(|command| !command.is_connected())
+        {
+            last_command.take();
+        }
     }
 
     pub async fn type_output_text(&self, text: &str, delay_ms: u32) -> Result<(), JsValue> {
@@ -688,17 +1607,16 @@ impl Renderer {
         pre.set_class_name("output-block");
 
         wrapper.append_child(&pre)?;
-        self.output.append_child(&wrapper)?;
+        let pin = build_pin_button(&self.document)?;
+        wrapper.append_child(&pin)?;
+        self.append_line(&wrapper)?;
 
-        let mut buffer = String::new();
-        for ch in text.chars() {
-            buffer.push(ch);
-            pre.set_text_content(Some(&buffer));
+        typewrite(text, delay_ms, |typed| {
+            pre.set_text_content(Some(typed));
             self.scroll_to_bottom();
-            if delay_ms > 0 {
-                TimeoutFuture::new(delay_ms).await;
-            }
-        }
+            true
+        })
+        .await;
         self.render_text_with_icons(&pre, text)?;
         self.scroll_to_bottom();
 
@@ -709,137 +1627,112 @@ impl Renderer {
     where
         T: IntoIterator<Item = (String, String)>,
     {
-        let items: Vec<(String, String)> = suggestions.into_iter().collect();
-        let total = items.len();
-        let has_extras = total > COMPACT_SUGGESTION_VISIBLE_COUNT;
-        let mut extras_rendered = has_extras;
-        let expanded = self
-            .suggestions
-            .get_attribute("data-expanded")
-            .map(|value| value == "true")
-            .unwrap_or(false);
-
-        {
-            let mut cache = self.suggestion_items.borrow_mut();
-            let mut toggle = self.suggestion_toggle.borrow_mut();
-
-            if let Some(button) = toggle.as_ref() {
-                if let Some(parent) = button.parent_node() {
-                    let node: Node = button.clone().into();
-                    let _ = parent.remove_child(&node);
-                }
-            }
-
-            while cache.len() > total {
-                if let Some(span) = cache.pop() {
-                    if let Some(parent) = span.parent_node() {
-                        let node: Node = span.into();
-                        let _ = parent.remove_child(&node);
-                    }
-                }
-            }
-
-            while cache.len() < total {
-                if let Ok(element) = self.document.create_element("span") {
-                    if let Ok(span) = element.dyn_into::<HtmlSpanElement>() {
-                        let _ = span.set_attribute("role", "button");
-                        let _ = span.set_attribute("tabindex", "0");
-                        let node: Node = span.clone().into();
-                        let _ = self.suggestions.append_child(&node);
-                        cache.push(span);
-                    }
-                } else {
-                    break;
-                }
-            }
+        self.render_suggestions_gated(suggestions, |_| true);
+    }
 
-            for (index, (span, (command, label))) in cache.iter().zip(items.iter()).enumerate() {
-                let mut classes = String::from("suggestion");
-                if has_extras && index >= COMPACT_SUGGESTION_VISIBLE_COUNT {
-                    classes.push_str(" suggestion--extra");
-                }
-                span.set_class_name(&classes);
-                let _ = span.set_attribute("data-command", command);
-                span.set_text_content(Some(label));
-            }
+    pub fn render_suggestions_gated<T, F>(&self, suggestions: T, available_while_disabled: F)
+    where
+        T: IntoIterator<Item = (String, String)>,
+        F: Fn(&str) -> bool,
+    {
+        let items: Vec<(String, String)> = suggestions.into_iter().collect();
+        self.suggestion_chips.render(
+            &items,
+            self.suggestions_enabled.get(),
+            self.suggestions_enabling.replace(false),
+            self.suggestions_expanded.get(),
+            available_while_disabled,
+        );
+    }
 
-            if has_extras {
-                let button = if let Some(existing) = toggle.as_ref() {
-                    Some(existing.clone())
-                } else {
-                    match self.document.create_element("button") {
-                        Ok(element) => match element.dyn_into::<HtmlButtonElement>() {
-                            Ok(button) => {
-                                button.set_class_name("suggestions__toggle");
-                                button.set_type("button");
-                                let _ = button.set_attribute("aria-controls", SUGGESTIONS_ID);
-                                let clone = button.clone();
-                                *toggle = Some(clone);
-                                Some(button)
-                            }
-                            Err(_) => None,
-                        },
-                        Err(_) => None,
-                    }
-                };
-                if let Some(button) = button {
-                    button.set_text_content(Some(if expanded {
-                        SUGGESTION_COLLAPSE_LABEL
-                    } else {
-                        SUGGESTION_EXPAND_LABEL
-                    }));
-                    let _ = button
-                        .set_attribute("aria-expanded", if expanded { "true" } else { "false" });
-                    let node: Node = button.clone().into();
-                    let _ = self.suggestions.append_child(&node);
-                } else {
-                    extras_rendered = false;
-                }
-            }
+    pub fn set_suggestions_enabled(&self, enabled: bool) {
+        let previous = self.suggestions_enabled.replace(enabled);
+        self.suggestions_enabling.set(enabled && !previous);
+        if enabled {
+            let _ = self.suggestions.remove_attribute("aria-busy");
+        } else {
+            let _ = self.suggestions.set_attribute("aria-busy", "true");
         }
+    }
 
-        if extras_rendered {
+    pub fn set_suggestions_expanded(&self, expanded: bool) {
+        self.suggestions_expanded.set(expanded);
+        if self.suggestions.has_attribute("data-collapsible") {
             let _ = self
                 .suggestions
                 .set_attribute("data-expanded", if expanded { "true" } else { "false" });
-            let _ = self.suggestions.set_attribute("data-collapsible", "true");
-        } else {
-            let _ = self.suggestions.remove_attribute("data-expanded");
-            let _ = self.suggestions.remove_attribute("data-collapsible");
+        }
+        if let Some(button) = self.suggestion_chips.toggle.borrow().as_ref() {
+            label_suggestion_toggle(button, expanded);
         }
     }
 
-    pub fn toggle_suggestions_expanded(&self) {
-        {
-            let toggle = self.suggestion_toggle.borrow();
-            let has_button = toggle
-                .as_ref()
-                .and_then(|button| button.parent_node())
-                .is_some();
-            if !has_button {
-                return;
-            }
-        }
+    pub fn set_output_width(&self, width: OutputWidth) -> Result<(), JsValue> {
+        apply_output_width(&self.output, width)
+    }
 
-        let is_expanded = self
-            .suggestions
-            .get_attribute("data-expanded")
-            .map(|value| value == "true")
-            .unwrap_or(false);
-        let next_state = !is_expanded;
-        let _ = self
-            .suggestions
-            .set_attribute("data-expanded", if next_state { "true" } else { "false" });
-        let toggle = self.suggestion_toggle.borrow();
-        if let Some(button) = toggle.as_ref() {
-            button.set_text_content(Some(if next_state {
-                SUGGESTION_COLLAPSE_LABEL
-            } else {
-                SUGGESTION_EXPAND_LABEL
-            }));
-            let _ =
-                button.set_attribute("aria-expanded", if next_state { "true" } else { "false" });
+    pub fn set_data_saver(&self, enabled: bool) {
+        self.data_saver.set(enabled);
+    }
+
+    pub fn data_saver(&self) -> bool {
+        self.data_saver.get()
+    }
+
+    pub fn set_locale(&self, locale: Locale) {
+        self.locale.set(locale);
+    }
+
+    fn t(&self, key: Message) -> &'static str {
+        i18n::t(key, self.locale.get())
+    }
+
+    pub fn set_reading_time(&self, min_words: Option<usize>, locale: Locale) {
+        self.reading_time
+            .set(min_words.map(|min_words| (min_words, locale)));
+    }
+
+    // Adds the "· 420 words · ~3 min read" footer under long outputs when
+    // the preference is on; returns whether a footer was added.
+    pub fn append_reading_time(
+        &self,
+        text: &str,
+        behavior: ScrollBehavior,
+    ) -> Result<bool, JsValue> {
+        let Some((min_words, locale)) = self.reading_time.get() else {
+            return Ok(false);
+        };
+        let Some(label) = format::reading_time(text, min_words, locale) else {
+            return Ok(false);
+        };
+        let line = self
+            .document
+            .create_element("div")?
+            .dyn_into::<HtmlElement>()?;
+        line.set_class_name("line output-reading-time");
+        line.set_text_content(Some(&label));
+        self.append_line(&line)?;
+        // An anchored output keeps its anchor; only bottom-follow scrolls on.
+        if matches!(behavior, ScrollBehavior::Bottom) {
+            self.scroll_to_bottom();
         }
+        Ok(true)
+    }
+
+    pub fn apply_theme(&self, theme: &str) -> Result<(), JsValue> {
+        let body = self
+            .document
+            .body()
+            .ok_or_else(|| JsValue::from_str("Document body unavailable"))?;
+        apply_theme_class(&body, theme)
+    }
+
+    pub fn read_only_requested(&self) -> bool {
+        read_only_requested(&self.terminal_root)
+    }
+
+    pub fn mark_read_only(&self) -> Result<(), JsValue> {
+        self.terminal_root.class_list().add_1(READ_ONLY_CLASS)
     }
 
     pub fn disable_prompt_input(&self) -> Result<(), JsValue> {
@@ -851,6 +1744,13 @@ impl Renderer {
         Ok(())
     }
 
+    pub fn enable_prompt_input(&self) -> Result<(), JsValue> {
+        self.prompt_hidden_input.set_disabled(false);
+        self.prompt_hidden_input.remove_attribute("aria-disabled")?;
+        self.prompt_input.remove_attribute("data-disabled")?;
+        Ok(())
+    }
+
     pub fn play_konami_charge(&self) -> Result<(), JsValue> {
         let classes = self.terminal_root.class_list();
         let _ = classes.remove_1("ai-mode-active");
@@ -910,6 +1810,9 @@ impl Renderer {
     }
 
     pub fn render_shaw_effect(&self) -> Result<HtmlElement, JsValue> {
+        if self.data_saver.get() {
+            return self.append_effect_placeholder("shaw-effect-line", SHAW_PLACEHOLDER);
+        }
         let wrapper = self
             .document
             .create_element("div")?
@@ -948,7 +1851,7 @@ impl Renderer {
 
         let figure_node: Node = figure.into();
         wrapper.append_child(&figure_node)?;
-        self.output.append_child(&wrapper)?;
+        self.append_line(&wrapper)?;
 
         if let Err(err) = audio.play() {
             utils::log(&format!("Failed to autoplay Shaw audio: {:?}", err));
@@ -1100,7 +2003,7 @@ impl Renderer {
 
         let wrapper_node: Node = wrapper.clone().into();
         line.append_child(&wrapper_node)?;
-        self.output.append_child(&line)?;
+        self.append_line(&line)?;
 
         let line_element: HtmlElement = line.clone().dyn_into::<HtmlElement>()?;
         self.apply_scroll(&line_element, ScrollBehavior::Bottom)?;
@@ -1124,17 +2027,20 @@ impl Renderer {
 
         let drops = drops.clamp(12, 96);
         for _ in 0..drops {
-            let drop = self
-                .document
-                .create_element("img")?
-                .dyn_into::<HtmlImageElement>()?;
-            drop.set_class_name("cookie-rain__drop");
-            drop.set_src("./effects/small_cookie.webp");
-            drop.set_alt("");
-            drop.set_attribute("loading", "lazy")?;
-            drop.set_attribute("draggable", "false")?;
-
-            let drop_element: HtmlElement = drop.clone().dyn_into::<HtmlElement>()?;
+            let drop_element = if self.data_saver.get() {
+                build_emoji_cookie(&self.document)?
+            } else {
+                let drop = self
+                    .document
+                    .create_element("img")?
+                    .dyn_into::<HtmlImageElement>()?;
+                drop.set_class_name("cookie-rain__drop");
+                drop.set_src("./effects/small_cookie.webp");
+                drop.set_alt("");
+                drop.set_attribute("loading", "lazy")?;
+                drop.set_attribute("draggable", "false")?;
+                drop.dyn_into::<HtmlElement>()?
+            };
             let style = drop_element.style();
             let left = format!("{:.2}%", Math::random() * 100.0);
             let scale = 0.78 + (Math::random() * 0.42);
@@ -1145,8 +2051,7 @@ impl Renderer {
             style.set_property("animation-duration", &format!("{duration:.2}s"))?;
             style.set_property("animation-delay", &format!("{delay:.2}s"))?;
 
-            let drop_node: Node = drop.into();
-            layer.append_child(&drop_node)?;
+            layer.append_child(&drop_element)?;
         }
 
         let layer_element: HtmlElement = layer.clone().dyn_into::<HtmlElement>()?;
@@ -1162,6 +2067,10 @@ impl Renderer {
         image_alt: &str,
         audio_src: Option<&str>,
     ) -> Result<HtmlElement, JsValue> {
+        if self.data_saver.get() {
+            let text = format!("{POKEMON_PLACEHOLDER_ICON} {image_alt}.");
+            return self.append_effect_placeholder("pokemon-effect-line", &text);
+        }
         let wrapper = self
             .document
             .create_element("div")?
@@ -1206,13 +2115,24 @@ impl Renderer {
 
         let figure_node: Node = figure.into();
         wrapper.append_child(&figure_node)?;
-        self.output.append_child(&wrapper)?;
+        self.append_line(&wrapper)?;
 
         let element: HtmlElement = wrapper.clone().dyn_into::<HtmlElement>()?;
         self.apply_scroll(&element, ScrollBehavior::Bottom)?;
         Ok(element)
     }
 
+    fn append_effect_placeholder(
+        &self,
+        line_class: &str,
+        text: &str,
+    ) -> Result<HtmlElement, JsValue> {
+        let element = build_effect_placeholder(&self.document, line_class, text)?;
+        self.append_line(&element)?;
+        self.apply_scroll(&element, ScrollBehavior::Bottom)?;
+        Ok(element)
+    }
+
     pub fn remove_effect(&self, element: &HtmlElement) -> Result<(), JsValue> {
         if let Some(parent) = element.parent_node() {
             let node: Node = element.clone().into();
@@ -1232,11 +2152,20 @@ impl Renderer {
     }
 
     fn scroll_to_child(&self, child: &HtmlElement) -> Result<(), JsValue> {
-        let offset = child.offset_top();
-        self.output.set_scroll_top(offset);
+        scroll_container_to(&self.output, child);
         Ok(())
     }
 
+    pub fn scroll_to_anchor(&self, id: &str) -> Result<bool, JsValue> {
+        scroll_container_to_anchor(&self.output, id)
+    }
+
+    pub fn toc_anchor(&self, trigger: &Element) -> Option<String> {
+        trigger
+            .get_attribute(TOC_ANCHOR_ATTR)
+            .filter(|id| !id.trim().is_empty())
+    }
+
     fn apply_scroll(&self, element: &HtmlElement, behavior: ScrollBehavior) -> Result<(), JsValue> {
         match behavior {
             ScrollBehavior::None => {}
@@ -1263,13 +2192,40 @@ impl Renderer {
         }
         self.ai_toggle
             .set_attribute("aria-pressed", if active { "true" } else { "false" })?;
+        self.ai_toggle
+            .set_attribute("aria-checked", if active { "true" } else { "false" })?;
         self.ai_indicator.set_attribute("aria-busy", "false")?;
         self.set_ai_indicator_text(indicator_text);
+        // The first call restores the saved preference; only later flips are news.
+        let previous = self.ai_active.replace(Some(active));
+        if !active {
+            self.ai_busy.set(false);
+        }
+        self.update_ai_toggle_label()?;
+        if previous.is_some_and(|previous| previous != active) {
+            self.announce(if active {
+                "AI assistant enabled"
+            } else {
+                "AI assistant disabled"
+            });
+        }
         Ok(())
     }
 
+    fn update_ai_toggle_label(&self) -> Result<(), JsValue> {
+        self.ai_toggle.set_attribute(
+            "aria-label",
+            &ai_toggle_label(self.ai_active.get().unwrap_or(false), self.ai_busy.get()),
+        )
+    }
+
+    pub fn announce(&self, message: &str) {
+        self.announcer.set_text_content(Some(message));
+    }
+
     pub fn set_ai_indicator_text(&self, text: &str) {
-        self.ai_indicator.set_text_content(Some(text));
+        self.ai_indicator_text.replace(text.to_string());
+        render_ai_indicator(&self.ai_indicator, text, self.compact.get());
     }
 
     pub fn set_ai_busy(&self, busy: bool) -> Result<(), JsValue> {
@@ -1280,6 +2236,15 @@ impl Renderer {
             self.ai_toggle.class_list().remove_1("busy")?;
             self.ai_indicator.set_attribute("aria-busy", "false")?;
         }
+        let was_busy = self.ai_busy.replace(busy);
+        self.update_ai_toggle_label()?;
+        if was_busy != busy {
+            self.announce(if busy {
+                "AI assistant is answering"
+            } else {
+                "AI assistant is ready"
+            });
+        }
         Ok(())
     }
 
@@ -1306,7 +2271,7 @@ impl Renderer {
             .create_element("span")?
             .dyn_into::<HtmlSpanElement>()?;
         label.set_class_name("ai-loader__label");
-        label.set_text_content(Some("Synthesizing answer"));
+        label.set_text_content(Some(self.t(Message::AiLoaderLabel)));
 
         let dots = self
             .document
@@ -1319,7 +2284,7 @@ impl Renderer {
         wrapper.append_child(&label)?;
         wrapper.append_child(&dots)?;
 
-        self.output.append_child(&wrapper)?;
+        self.append_line(&wrapper)?;
         self.scroll_to_bottom();
         Ok(())
     }
@@ -1349,3 +2314,1061 @@ fn clear_children(element: &HtmlElement) -> Result<(), JsValue> {
     }
     Ok(())
 }
+
+fn child_index_containing(container: &Element, target: &Element) -> Option<usize> {
+    let mut current = target.clone();
+    loop {
+        let parent = current.parent_element()?;
+        if parent.is_same_node(Some(container)) {
+            break;
+        }
+        current = parent;
+    }
+    let children = container.children();
+    (0..children.length()).position(|index| {
+        children
+            .item(index)
+            .map(|child| child.is_same_node(Some(&current)))
+            .unwrap_or(false)
+    })
+}
+
+fn mark_selected_children(
+    container: &Element,
+    range: Option<(usize, usize)>,
+) -> Result<(), JsValue> {
+    let children = container.children();
+    for index in 0..children.length() {
+        let Some(child) = children.item(index) else {
+            continue;
+        };
+        let selected = range
+            .map(|(start, end)| (start..=end).contains(&(index as usize)))
+            .unwrap_or(false);
+        child
+            .class_list()
+            .toggle_with_force(LINE_SELECTED_CLASS, selected)?;
+    }
+    Ok(())
+}
+
+fn children_text(container: &Element, start: usize, end: usize) -> String {
+    let children = container.children();
+    (start..=end)
+        .filter_map(|index| children.item(index as u32))
+        .filter_map(|child| child.text_content())
+        .map(|text| text.trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collapse_block_if_long(
+    document: &Document,
+    block: &HtmlElement,
+    max_lines: usize,
+) -> Result<bool, JsValue> {
+    if block.class_list().contains(COLLAPSIBLE_CLASS) {
+        return Ok(false);
+    }
+    let lengths = rendered_line_lengths(block)?;
+    if wrapped_line_count(lengths, COLLAPSE_WRAP_COLUMNS) <= max_lines {
+        return Ok(false);
+    }
+
+    block.class_list().add_1(COLLAPSIBLE_CLASS)?;
+    block.set_attribute("data-collapsed", "true")?;
+    block
+        .style()
+        .set_property("--collapse-lines", &max_lines.to_string())?;
+
+    let button = document
+        .create_element("button")?
+        .dyn_into::<HtmlButtonElement>()?;
+    button.set_class_name(COLLAPSE_TOGGLE_CLASS);
+    button.set_type("button");
+    button.set_attribute("aria-expanded", "false")?;
+    button.set_text_content(Some(COLLAPSE_EXPAND_LABEL));
+    block.append_child(&button)?;
+    Ok(true)
+}
+
+fn build_command_line(
+    document: &Document,
+    label: &str,
+    command: &str,
+    shareable: bool,
+) -> Result<HtmlElement, JsValue> {
+    let line = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+    line.set_class_name("line command-line");
+
+    let label_span = document
+        .create_element("span")?
+        .dyn_into::<HtmlSpanElement>()?;
+    label_span.set_class_name("prompt-label");
+    label_span.set_text_content(Some(label));
+
+    let command_span = document
+        .create_element("span")?
+        .dyn_into::<HtmlSpanElement>()?;
+    command_span.set_class_name("prompt-command");
+    command_span.set_text_content(Some(command));
+
+    line.append_child(&label_span)?;
+    line.append_child(&command_span)?;
+
+    if shareable {
+        line.set_attribute(SHARE_COMMAND_ATTR, command.trim())?;
+        let button = document
+            .create_element("button")?
+            .dyn_into::<HtmlButtonElement>()?;
+        button.set_class_name(SHARE_BUTTON_CLASS);
+        button.set_type("button");
+        button.set_title("Copy link to this command");
+        button.set_attribute("aria-label", "Copy link to this command")?;
+        button.set_text_content(Some("🔗"));
+        line.append_child(&button)?;
+    }
+    Ok(line)
+}
+
+fn build_summary_chip(document: &Document, section: &str) -> Result<HtmlElement, JsValue> {
+    let line = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+    line.set_class_name("line summary-chip-line");
+    let button = document
+        .create_element("button")?
+        .dyn_into::<HtmlButtonElement>()?;
+    button.set_class_name(SUMMARY_CHIP_CLASS);
+    button.set_type("button");
+    button.set_attribute(SUMMARY_SECTION_ATTR, section)?;
+    button.set_text_content(Some(SUMMARY_CHIP_LABEL));
+    line.append_child(&button)?;
+    Ok(line)
+}
+
+fn build_regenerate_control(document: &Document, model: &str) -> Result<HtmlElement, JsValue> {
+    let line = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+    line.set_class_name("line regenerate-line");
+    let button = document
+        .create_element("button")?
+        .dyn_into::<HtmlButtonElement>()?;
+    button.set_class_name(REGENERATE_CLASS);
+    button.set_type("button");
+    button.set_attribute(REGENERATE_MODEL_ATTR, model)?;
+    button.set_text_content(Some(&format!("🔁 Try another model ({model})")));
+    line.append_child(&button)?;
+    Ok(line)
+}
+
+fn scroll_container_to(container: &HtmlElement, child: &HtmlElement) {
+    container.set_scroll_top(child.offset_top());
+}
+
+fn scroll_container_to_anchor(container: &HtmlElement, id: &str) -> Result<bool, JsValue> {
+    let Some(target) = find_anchor(container, id)? else {
+        return Ok(false);
+    };
+    scroll_container_to(container, &target);
+    Ok(true)
+}
+
+// Repeated commands render the same anchors again; the latest output wins.
+fn build_achievement_progress(
+    document: &Document,
+    current: u32,
+    target: u32,
+    label: &str,
+) -> Result<HtmlElement, JsValue> {
+    let bar = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+    bar.set_class_name("achievement-card__progress");
+    bar.set_attribute("role", "progressbar")?;
+    bar.set_attribute("aria-valuemin", "0")?;
+    bar.set_attribute("aria-valuemax", &target.to_string())?;
+    bar.set_attribute("aria-valuenow", &current.to_string())?;
+    bar.set_attribute("aria-label", label)?;
+
+    let fill = document.create_element("span")?.dyn_into::<HtmlElement>()?;
+    fill.set_class_name("achievement-card__progress-fill");
+    fill.style()
+        .set_property("width", &format!("{}%", progress_percent(current, target)))?;
+    bar.append_child(&fill)?;
+
+    let count = document.create_element("span")?.dyn_into::<HtmlElement>()?;
+    count.set_class_name("achievement-card__progress-count");
+    count.set_attribute("aria-hidden", "true")?;
+    count.set_text_content(Some(&format!("{current}/{target}")));
+    bar.append_child(&count)?;
+    Ok(bar)
+}
+
+fn find_anchor(container: &HtmlElement, id: &str) -> Result<Option<HtmlElement>, JsValue> {
+    if id.contains(['"', '\\']) {
+        return Ok(None);
+    }
+    let matches = container.query_selector_all(&format!("[id=\"{id}\"]"))?;
+    let Some(last) = matches.length().checked_sub(1) else {
+        return Ok(None);
+    };
+    Ok(matches
+        .item(last)
+        .and_then(|node| node.dyn_into::<HtmlElement>().ok()))
+}
+
+fn shared_command_for(trigger: &Element) -> Result<Option<String>, JsValue> {
+    let Some(line) = trigger.closest(&format!("[{SHARE_COMMAND_ATTR}]"))? else {
+        return Ok(None);
+    };
+    Ok(line
+        .get_attribute(SHARE_COMMAND_ATTR)
+        .filter(|command| !command.trim().is_empty()))
+}
+
+fn toggle_collapsed_block(toggle: &Element) -> Result<bool, JsValue> {
+    let Some(block) = toggle.closest(&format!(".{COLLAPSIBLE_CLASS}"))? else {
+        return Ok(false);
+    };
+    let collapsed = block
+        .get_attribute("data-collapsed")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let next_collapsed = !collapsed;
+    block.set_attribute(
+        "data-collapsed",
+        if next_collapsed { "true" } else { "false" },
+    )?;
+    toggle.set_text_content(Some(if next_collapsed {
+        COLLAPSE_EXPAND_LABEL
+    } else {
+        COLLAPSE_COLLAPSE_LABEL
+    }));
+    toggle.set_attribute(
+        "aria-expanded",
+        if next_collapsed { "false" } else { "true" },
+    )?;
+    Ok(true)
+}
+
+fn rendered_line_lengths(block: &HtmlElement) -> Result<Vec<usize>, JsValue> {
+    let mut lengths = Vec::new();
+    let nodes = block.query_selector_all("p, li, pre")?;
+    for index in 0..nodes.length() {
+        if let Some(node) = nodes.item(index) {
+            let text = node.text_content().unwrap_or_default();
+            lengths.extend(text.lines().map(|line| line.chars().count()));
+        }
+    }
+    if lengths.is_empty() {
+        let text = block.text_content().unwrap_or_default();
+        lengths.extend(text.lines().map(|line| line.chars().count()));
+    }
+    Ok(lengths)
+}
+
+fn wrapped_line_count<I>(lengths: I, columns: usize) -> usize
+where
+    I: IntoIterator<Item = usize>,
+{
+    let columns = columns.max(1);
+    lengths
+        .into_iter()
+        .map(|length| length.max(1).div_ceil(columns))
+        .sum()
+}
+
+// Removes the output line around every match (or the match itself when it is
+// not inside a line, like effect layers) and returns how many were removed.
+fn remove_lines_matching(root: &HtmlElement, selector: &str) -> Result<usize, JsValue> {
+    let matches = root.query_selector_all(selector)?;
+    let mut removed = 0;
+    for index in 0..matches.length() {
+        let Some(element) = matches
+            .item(index)
+            .and_then(|node| node.dyn_into::<Element>().ok())
+        else {
+            continue;
+        };
+        let target = element
+            .closest(".line")?
+            .filter(|line| root.contains(Some(line)))
+            .unwrap_or(element);
+        if target.is_connected() {
+            target.remove();
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn apply_output_width(output: &HtmlElement, width: OutputWidth) -> Result<(), JsValue> {
+    let style = output.style();
+    match width.css_value() {
+        Some(value) => style.set_property(OUTPUT_MAX_WIDTH_PROPERTY, &value),
+        None => style.remove_property(OUTPUT_MAX_WIDTH_PROPERTY).map(|_| ()),
+    }
+}
+
+fn apply_theme_class(body: &Element, theme: &str) -> Result<(), JsValue> {
+    let classes = body.class_list();
+    let stale = (0..classes.length())
+        .filter_map(|index| classes.item(index))
+        .filter(|class| class.starts_with(THEME_CLASS_PREFIX))
+        .collect::<Vec<_>>();
+    for class in stale {
+        classes.remove_1(&class)?;
+    }
+    classes.add_1(&format!("{THEME_CLASS_PREFIX}{theme}"))
+}
+
+// `<div id="terminal" data-readonly>` boots a showcase widget; an explicit
+// `data-readonly="false"` keeps the prompt.
+fn read_only_requested(root: &Element) -> bool {
+    root.get_attribute(READ_ONLY_ATTR)
+        .is_some_and(|value| !value.trim().eq_ignore_ascii_case("false"))
+}
+
+fn build_effect_placeholder(
+    document: &Document,
+    line_class: &str,
+    text: &str,
+) -> Result<HtmlElement, JsValue> {
+    let line: HtmlElement = document.create_element("div")?.dyn_into()?;
+    line.set_class_name(&format!(
+        "line output-text {line_class} {EFFECT_PLACEHOLDER_CLASS}"
+    ));
+    line.set_text_content(Some(text));
+    Ok(line)
+}
+
+fn build_emoji_cookie(document: &Document) -> Result<HtmlElement, JsValue> {
+    let drop: HtmlElement = document.create_element("span")?.dyn_into()?;
+    drop.set_class_name("cookie-rain__drop cookie-rain__drop--emoji");
+    drop.set_text_content(Some("🍪"));
+    Ok(drop)
+}
+
+// The typewriter loop behind the boot message and the demo's prompt typing.
+// `render` gets the growing prefix and returns false to stop early.
+pub async fn typewrite(text: &str, delay_ms: u32, mut render: impl FnMut(&str) -> bool) {
+    let mut typed = String::new();
+    for ch in text.chars() {
+        typed.push(ch);
+        if !render(&typed) {
+            return;
+        }
+        if delay_ms > 0 {
+            TimeoutFuture::new(delay_ms).await;
+        }
+    }
+}
+
+// Data saver keeps HTML outputs but stops embedded media from downloading and
+// playing on its own; the controls stay usable if the reader opts in.
+fn suppress_autoplay(container: &Element) -> Result<(), JsValue> {
+    let media = container.query_selector_all("audio, video")?;
+    for index in 0..media.length() {
+        if let Some(element) = media
+            .item(index)
+            .and_then(|node| node.dyn_into::<Element>().ok())
+        {
+            element.remove_attribute("autoplay")?;
+            element.set_attribute("preload", "none")?;
+        }
+    }
+    Ok(())
+}
+
+// Every HTML string the renderer injects goes through here, so data files
+// (or the host serving them) cannot smuggle active content into the page.
+fn set_fragment_html(element: &Element, html: &str) -> Result<(), JsValue> {
+    if cfg!(feature = "strict-html") {
+        element.set_inner_html(&utils::sanitize_fragment(html)?);
+    } else {
+        element.set_inner_html(html);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[test]
+    fn ai_toggle_label_says_what_pressing_it_does() {
+        assert_eq!(ai_toggle_label(false, false), "Enable AI assistant");
+        assert_eq!(
+            ai_toggle_label(false, true),
+            "Enable AI assistant — currently answering"
+        );
+        assert_eq!(ai_toggle_label(true, false), "Disable AI assistant");
+        assert_eq!(
+            ai_toggle_label(true, true),
+            "Disable AI assistant — currently answering"
+        );
+    }
+
+    #[test]
+    fn wrapped_line_count_accounts_for_soft_wraps() {
+        assert_eq!(wrapped_line_count([0, 10, 96], 96), 3);
+        assert_eq!(wrapped_line_count([97], 96), 2);
+        assert_eq!(wrapped_line_count(Vec::<usize>::new(), 96), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn share_button_resolves_command_hash_url() {
+        let document = utils::document().expect("document");
+        let line = build_command_line(&document, "guest@zqsdev:~$", "projects --sort date", true)
+            .expect("command line");
+        let button = line
+            .query_selector(&format!(".{SHARE_BUTTON_CLASS}"))
+            .expect("query")
+            .expect("Shareable commands should get a link button");
+        let command = shared_command_for(&button)
+            .expect("lookup")
+            .expect("The button should resolve its command");
+        assert_eq!(
+            utils::share_url("https://zqsdev.com/", &command),
+            "https://zqsdev.com/#projects%20--sort%20date"
+        );
+
+        let plain = build_command_line(&document, "guest@zqsdev:~$", "cookie", false)
+            .expect("command line");
+        assert!(plain
+            .query_selector(&format!(".{SHARE_BUTTON_CLASS}"))
+            .expect("query")
+            .is_none());
+    }
+
+    #[test]
+    fn progress_percent_floors_and_clamps() {
+        assert_eq!(progress_percent(0, 100), 0);
+        assert_eq!(progress_percent(37, 100), 37);
+        assert_eq!(progress_percent(2, 3), 66);
+        assert_eq!(progress_percent(150, 100), 100);
+        assert_eq!(progress_percent(0, 0), 100);
+        let view = AchievementView::new("Cookie", "", "", false, AchievementTier::Standard);
+        assert_eq!(view.progress, None);
+        let view = view.with_progress(120, 100);
+        assert_eq!(view.progress, Some((100, 100)));
+    }
+
+    #[wasm_bindgen_test]
+    fn achievement_progress_bar_announces_its_percentage() {
+        let document = utils::document().expect("document");
+        let bar =
+            build_achievement_progress(&document, 37, 100, "Progress: 37%").expect("progress bar");
+        assert_eq!(bar.class_name(), "achievement-card__progress");
+        assert_eq!(bar.get_attribute("role").as_deref(), Some("progressbar"));
+        assert_eq!(bar.get_attribute("aria-valuenow").as_deref(), Some("37"));
+        assert_eq!(bar.get_attribute("aria-valuemax").as_deref(), Some("100"));
+        assert_eq!(
+            bar.get_attribute("aria-label").as_deref(),
+            Some("Progress: 37%")
+        );
+        let fill = bar
+            .query_selector(".achievement-card__progress-fill")
+            .expect("query")
+            .expect("The bar holds a fill")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        assert_eq!(
+            fill.style().get_property_value("width").as_deref(),
+            Ok("37%")
+        );
+        assert_eq!(bar.text_content().as_deref(), Some("37/100"));
+    }
+
+    #[wasm_bindgen_test]
+    fn summary_chip_names_its_section() {
+        let document = utils::document().expect("document");
+        let line = build_summary_chip(&document, "experience").expect("chip line");
+        assert!(line.class_list().contains("summary-chip-line"));
+        let button = line
+            .query_selector(&format!(".{SUMMARY_CHIP_CLASS}"))
+            .expect("query")
+            .expect("The line holds the chip button");
+        assert_eq!(
+            button.get_attribute(SUMMARY_SECTION_ATTR).as_deref(),
+            Some("experience")
+        );
+        assert_eq!(button.text_content().as_deref(), Some(SUMMARY_CHIP_LABEL));
+    }
+
+    #[wasm_bindgen_test]
+    fn regenerate_control_names_its_target_model() {
+        let document = utils::document().expect("document");
+        let line = build_regenerate_control(&document, "google").expect("control line");
+        assert!(line.class_list().contains("regenerate-line"));
+        let button = line
+            .query_selector(&format!(".{REGENERATE_CLASS}"))
+            .expect("query")
+            .expect("The line holds the button");
+        assert_eq!(
+            button.get_attribute(REGENERATE_MODEL_ATTR).as_deref(),
+            Some("google")
+        );
+        assert!(button
+            .text_content()
+            .unwrap_or_default()
+            .contains("Try another model"));
+    }
+
+    fn chips(commands: &[&str]) -> Vec<(String, String)> {
+        commands
+            .iter()
+            .map(|command| (command.to_string(), format!("{command} label")))
+            .collect()
+    }
+
+    #[test]
+    fn suggestion_signature_tracks_chips_and_flags() {
+        let base = suggestion_signature(&chips(&["help", "projects"]), false, true);
+        assert_eq!(
+            base,
+            suggestion_signature(&chips(&["help", "projects"]), false, true)
+        );
+        assert_ne!(
+            base,
+            suggestion_signature(&chips(&["projects", "help"]), false, true)
+        );
+        assert_ne!(
+            base,
+            suggestion_signature(&chips(&["help", "projects"]), true, true)
+        );
+        assert_ne!(
+            base,
+            suggestion_signature(&chips(&["help", "projects"]), false, false)
+        );
+        let mut relabelled = chips(&["help", "projects"]);
+        relabelled[1].1 = "projects (3)".to_string();
+        assert_ne!(base, suggestion_signature(&relabelled, false, true));
+    }
+
+    #[test]
+    fn suggestion_subset_mask_only_accepts_ordered_filters() {
+        let rendered = chips(&["help", "projects", "profile", "publications"]);
+        assert_eq!(
+            suggestion_subset_mask(&rendered, &chips(&["projects", "publications"])),
+            Some(vec![false, true, false, true])
+        );
+        assert_eq!(
+            suggestion_subset_mask(&rendered, &rendered),
+            Some(vec![true; 4])
+        );
+        assert_eq!(suggestion_subset_mask(&rendered, &[]), Some(vec![false; 4]));
+        assert_eq!(
+            suggestion_subset_mask(&rendered, &chips(&["publications", "projects"])),
+            None,
+            "Reordered chips need a rebuild"
+        );
+        assert_eq!(
+            suggestion_subset_mask(&rendered, &chips(&["projects", "contact"])),
+            None,
+            "New chips need a rebuild"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unchanged_suggestions_keep_their_chip_nodes() {
+        let document = utils::document().expect("document");
+        let container: HtmlElement = document
+            .create_element("div")
+            .expect("container")
+            .dyn_into()
+            .expect("html element");
+        let suggestion_chips = SuggestionChips::new(document, container.clone());
+        let items = chips(&["help", "projects", "profile", "publications", "contact"]);
+
+        suggestion_chips.render(&items, true, false, false, |_| true);
+        let first = container.first_element_child().expect("first chip");
+        let toggle = container.last_element_child().expect("toggle");
+        assert_eq!(toggle.class_name(), "suggestions__toggle");
+
+        suggestion_chips.render(&items, true, false, false, |_| true);
+        assert!(container
+            .first_element_child()
+            .is_some_and(|node| node.is_same_node(Some(&first))));
+        assert!(container
+            .last_element_child()
+            .is_some_and(|node| node.is_same_node(Some(&toggle))));
+
+        suggestion_chips.render(&chips(&["projects", "profile"]), true, false, false, |_| {
+            true
+        });
+        assert!(container
+            .first_element_child()
+            .is_some_and(|node| node.is_same_node(Some(&first))));
+        assert!(first.class_list().contains("suggestion--hidden"));
+        assert_eq!(
+            container
+                .query_selector_all(".suggestion")
+                .expect("chips")
+                .length(),
+            5
+        );
+        assert!(
+            container
+                .query_selector(".suggestions__toggle")
+                .expect("query")
+                .is_none(),
+            "Two visible chips do not need the expand button"
+        );
+    }
+
+    #[test]
+    fn targeted_clear_selectors_match_their_classes() {
+        assert_eq!(AI_ANSWER_SELECTOR, format!(".{AI_ANSWER_CLASS}"));
+        for class in [
+            "shaw-effect-line",
+            "pokemon-effect-line",
+            "cookie-clicker-line",
+        ] {
+            assert!(
+                EFFECT_LINE_SELECTOR
+                    .split(", ")
+                    .any(|selector| selector == format!(".{class}")),
+                "Effect lines rendered with `{class}` must be cleared"
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn clearing_ai_answers_keeps_the_welcome_banner() {
+        let document = utils::document().expect("document");
+        let output = document
+            .create_element("div")
+            .expect("output")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        output.set_inner_html(
+            "<div class=\"line info-line\">Welcome to the ZQSDev interactive terminal!</div>\
+             <div class=\"line command-line\">ai&gt; where?</div>\
+             <div class=\"line output-text\"><div class=\"output-block output-block--html output-block--ai\">Paris</div></div>\
+             <div class=\"line output-text shaw-effect-line\"><figure class=\"shaw-effect\"></figure></div>\
+             <div class=\"line output-text\"><div class=\"output-block output-block--html output-block--ai\">Rust</div></div>",
+        );
+
+        assert_eq!(
+            remove_lines_matching(&output, AI_ANSWER_SELECTOR).unwrap(),
+            2
+        );
+        let text = output.text_content().unwrap_or_default();
+        assert!(text.contains("Welcome to the ZQSDev interactive terminal!"));
+        assert!(!text.contains("Paris") && !text.contains("Rust"));
+        assert_eq!(output.child_element_count(), 3);
+
+        assert_eq!(
+            remove_lines_matching(&output, EFFECT_LINE_SELECTOR).unwrap(),
+            1
+        );
+        assert_eq!(output.child_element_count(), 2);
+    }
+
+    #[test]
+    fn group_summaries_count_commands_and_successes() {
+        let summary = GroupSummary {
+            total: 3,
+            succeeded: 2,
+        };
+        assert_eq!(summary.label(), "3 commands, 2 succeeded");
+        let single = GroupSummary {
+            total: 1,
+            succeeded: 0,
+        };
+        assert_eq!(single.label(), "1 command, 0 succeeded");
+    }
+
+    fn pin_fixture(lines: usize) -> (HtmlElement, PinnedOutputs, Vec<HtmlElement>) {
+        let document = utils::document().expect("document");
+        let output = document
+            .create_element("div")
+            .expect("output")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        let region = document
+            .create_element("div")
+            .expect("region")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        let lines = (0..lines)
+            .map(|index| {
+                let line = document
+                    .create_element("div")
+                    .expect("line")
+                    .dyn_into::<HtmlElement>()
+                    .expect("html element");
+                line.set_class_name("line output-text");
+                line.set_inner_html(&format!("<pre class=\"output-block\">block {index}</pre>"));
+                line.append_child(&build_pin_button(&document).expect("pin button"))
+                    .expect("append pin");
+                output.append_child(&line).expect("append line");
+                line
+            })
+            .collect();
+        (output, PinnedOutputs::new(document, region), lines)
+    }
+
+    #[wasm_bindgen_test]
+    fn pinning_moves_lines_and_unpinning_restores_their_spot() {
+        let (output, pins, lines) = pin_fixture(3);
+        assert!(pins.region.hidden());
+
+        assert_eq!(pins.toggle(&lines[1]).unwrap(), PinOutcome::Pinned);
+        assert!(!pins.region.hidden());
+        assert_eq!(output.child_element_count(), 2);
+        assert_eq!(pins.region.child_element_count(), 1);
+        assert!(lines[1].class_list().contains(PINNED_LINE_CLASS));
+        let button = lines[1]
+            .query_selector(&format!(".{PIN_BUTTON_CLASS}"))
+            .unwrap()
+            .expect("pin button");
+        assert_eq!(
+            button.get_attribute("aria-pressed").as_deref(),
+            Some("true")
+        );
+
+        assert_eq!(pins.toggle(&lines[1]).unwrap(), PinOutcome::Unpinned);
+        assert!(pins.region.hidden());
+        assert_eq!(output.child_element_count(), 3);
+        assert_eq!(output.children().item(1), Some(lines[1].clone().into()));
+        assert!(!lines[1].class_list().contains(PINNED_LINE_CLASS));
+        assert_eq!(output.child_nodes().length(), 3, "placeholder is gone");
+    }
+
+    #[wasm_bindgen_test]
+    fn only_two_outputs_can_be_pinned_and_clearing_drops_them() {
+        let (output, pins, lines) = pin_fixture(3);
+        assert_eq!(pins.toggle(&lines[0]).unwrap(), PinOutcome::Pinned);
+        assert_eq!(pins.toggle(&lines[2]).unwrap(), PinOutcome::Pinned);
+        assert_eq!(pins.toggle(&lines[1]).unwrap(), PinOutcome::LimitReached);
+        assert_eq!(pins.len(), PINNED_OUTPUT_LIMIT);
+        assert_eq!(output.child_element_count(), 1);
+
+        output.set_inner_html("");
+        assert_eq!(pins.toggle(&lines[0]).unwrap(), PinOutcome::Unpinned);
+        assert!(lines[0].parent_node().is_none());
+        assert_eq!(pins.clear(), 1);
+        assert_eq!(pins.len(), 0);
+        assert_eq!(pins.region.child_element_count(), 0);
+        assert!(pins.region.hidden());
+    }
+
+    #[wasm_bindgen_test]
+    fn pin_buttons_add_no_text_to_output_lines() {
+        let (_output, _pins, lines) = pin_fixture(1);
+        assert_eq!(lines[0].text_content().as_deref(), Some("block 0"));
+        let block = lines[0]
+            .query_selector(".output-block")
+            .unwrap()
+            .expect("output block");
+        assert!(block
+            .query_selector(&format!(".{PIN_BUTTON_CLASS}"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn compact_layout_kicks_in_below_the_threshold() {
+        assert!(is_compact_width(280.0));
+        assert!(is_compact_width(319.5));
+        assert!(!is_compact_width(320.0));
+        assert!(!is_compact_width(0.0), "Unlaid-out roots stay regular");
+        assert_eq!(displayed_prompt_label("zqs@dev:~$", true), ">$");
+        assert_eq!(displayed_prompt_label("zqs@dev:~$", false), "zqs@dev:~$");
+    }
+
+    #[wasm_bindgen_test]
+    fn compact_layout_toggles_classes_and_the_indicator_badge() {
+        let document = utils::document().expect("document");
+        let root = document.create_element("div").expect("root");
+        let suggestions = document.create_element("div").expect("suggestions");
+        let indicator = document.create_element("span").expect("indicator");
+
+        apply_compact_layout(&root, &suggestions, true).expect("compact");
+        render_ai_indicator(&indicator, "AI Mode: Activated", true);
+        assert!(root.class_list().contains(COMPACT_CLASS));
+        assert_eq!(
+            suggestions
+                .get_attribute(SUGGESTIONS_LAYOUT_ATTR)
+                .as_deref(),
+            Some("scroll")
+        );
+        assert_eq!(
+            indicator.text_content().as_deref(),
+            Some(COMPACT_AI_INDICATOR)
+        );
+        assert_eq!(
+            indicator.get_attribute("title").as_deref(),
+            Some("AI Mode: Activated")
+        );
+
+        apply_compact_layout(&root, &suggestions, false).expect("regular");
+        render_ai_indicator(&indicator, "AI Mode: Activated", false);
+        assert!(!root.class_list().contains(COMPACT_CLASS));
+        assert!(!suggestions.has_attribute(SUGGESTIONS_LAYOUT_ATTR));
+        assert_eq!(
+            indicator.text_content().as_deref(),
+            Some("AI Mode: Activated")
+        );
+        assert!(!indicator.has_attribute("title"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn resize_observer_reports_narrow_fixtures_as_compact() {
+        let document = utils::document().expect("document");
+        let body = document.body().expect("body");
+        let fixture = document
+            .create_element("div")
+            .expect("fixture")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        fixture
+            .style()
+            .set_property("width", "280px")
+            .expect("width");
+        body.append_child(&fixture).expect("attach");
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        observe_compact_width(&fixture, move |compact| sink.borrow_mut().push(compact))
+            .expect("observer");
+        TimeoutFuture::new(50).await;
+        assert_eq!(seen.borrow().last(), Some(&true));
+
+        fixture
+            .style()
+            .set_property("width", "640px")
+            .expect("width");
+        TimeoutFuture::new(50).await;
+        assert_eq!(seen.borrow().last(), Some(&false));
+        fixture.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn applying_a_theme_replaces_the_previous_theme_class() {
+        let document = utils::document().expect("document");
+        let body = document.create_element("body").expect("body");
+        body.set_class_name("theme-midnight ai-ready");
+        apply_theme_class(&body, "matrix").expect("theme");
+        assert_eq!(body.class_name(), "ai-ready theme-matrix");
+    }
+
+    #[wasm_bindgen_test]
+    fn read_only_attribute_is_detected_on_the_terminal_root() {
+        let document = utils::document().expect("document");
+        let root = document.create_element("div").expect("root");
+        assert!(!read_only_requested(&root));
+        root.set_attribute(READ_ONLY_ATTR, "").expect("attr");
+        assert!(read_only_requested(&root));
+        root.set_attribute(READ_ONLY_ATTR, "FALSE").expect("attr");
+        assert!(!read_only_requested(&root));
+    }
+
+    #[wasm_bindgen_test]
+    fn data_saver_placeholders_render_text_without_media() {
+        let document = utils::document().expect("document");
+        let placeholder = build_effect_placeholder(&document, "shaw-effect-line", SHAW_PLACEHOLDER)
+            .expect("placeholder");
+        assert!(placeholder.class_list().contains(EFFECT_PLACEHOLDER_CLASS));
+        assert_eq!(
+            placeholder.text_content().as_deref(),
+            Some(SHAW_PLACEHOLDER)
+        );
+        assert!(placeholder
+            .query_selector("img, audio")
+            .expect("query")
+            .is_none());
+
+        let cookie = build_emoji_cookie(&document).expect("cookie");
+        assert_eq!(cookie.tag_name(), "SPAN");
+        assert_eq!(cookie.text_content().as_deref(), Some("🍪"));
+    }
+
+    #[wasm_bindgen_test]
+    fn data_saver_strips_autoplay_from_html_outputs() {
+        let document = utils::document().expect("document");
+        let container = document.create_element("div").expect("container");
+        container.set_inner_html(
+            r#"<p>Answer</p><audio src="a.mp3" autoplay preload="auto"></audio><video autoplay></video>"#,
+        );
+        suppress_autoplay(&container).expect("suppress");
+        assert!(container
+            .query_selector("[autoplay]")
+            .expect("query")
+            .is_none());
+        let audio = container
+            .query_selector("audio")
+            .expect("query")
+            .expect("audio kept");
+        assert_eq!(audio.get_attribute("preload").as_deref(), Some("none"));
+        assert_eq!(
+            container
+                .query_selector("p")
+                .expect("query")
+                .map(|p| p.text_content()),
+            Some(Some("Answer".to_string()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn output_width_sets_the_max_width_variable() {
+        let document = utils::document().expect("document");
+        let output = document
+            .create_element("div")
+            .expect("output")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        let value = |output: &HtmlElement| {
+            output
+                .style()
+                .get_property_value(OUTPUT_MAX_WIDTH_PROPERTY)
+                .expect("read property")
+        };
+
+        apply_output_width(&output, OutputWidth::Columns(100)).expect("columns");
+        assert_eq!(value(&output), "100ch");
+        apply_output_width(&output, OutputWidth::Pixels(960)).expect("pixels");
+        assert_eq!(value(&output), "960px");
+        apply_output_width(&output, OutputWidth::Auto).expect("auto");
+        assert_eq!(value(&output), "");
+    }
+
+    #[wasm_bindgen_test]
+    fn scrolling_to_anchor_targets_latest_heading() {
+        let document = utils::document().expect("document");
+        let output = document
+            .create_element("div")
+            .expect("output")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        output
+            .set_attribute(
+                "style",
+                "position: relative; height: 100px; overflow-y: auto;",
+            )
+            .expect("style");
+        output.set_inner_html(
+            "<div style=\"height: 400px\"></div>\
+             <h2 id=\"section-awards\">Awards</h2>\
+             <div style=\"height: 400px\"></div>\
+             <h2 id=\"section-awards\">Awards</h2>\
+             <div style=\"height: 400px\"></div>",
+        );
+        document
+            .body()
+            .expect("body")
+            .append_child(&output)
+            .expect("attach output");
+
+        assert!(scroll_container_to_anchor(&output, "section-awards").expect("scroll"));
+        let target = find_anchor(&output, "section-awards")
+            .expect("query")
+            .expect("The anchor should resolve");
+        assert!(
+            target.offset_top() > 400,
+            "The latest heading should be targeted"
+        );
+        assert_eq!(output.scroll_top(), target.offset_top());
+        assert!(!scroll_container_to_anchor(&output, "section-missing").expect("scroll"));
+
+        output.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn long_block_collapses_and_expands_on_toggle() {
+        let document = utils::document().expect("document");
+        let block = document
+            .create_element("div")
+            .expect("block")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        let paragraphs: String = (0..30)
+            .map(|index| format!("<p>Line {index}</p>"))
+            .collect();
+        block.set_inner_html(&paragraphs);
+
+        let collapsed = collapse_block_if_long(&document, &block, 12).expect("collapse");
+        assert!(
+            collapsed,
+            "A 30-line block should collapse with a 12-line budget"
+        );
+        assert_eq!(
+            block.get_attribute("data-collapsed").as_deref(),
+            Some("true")
+        );
+
+        let toggle = block
+            .query_selector(&format!(".{COLLAPSE_TOGGLE_CLASS}"))
+            .expect("query")
+            .expect("Read more toggle should be appended");
+        assert_eq!(
+            toggle.text_content().as_deref(),
+            Some(COLLAPSE_EXPAND_LABEL)
+        );
+
+        assert!(toggle_collapsed_block(&toggle).expect("toggle"));
+        assert_eq!(
+            block.get_attribute("data-collapsed").as_deref(),
+            Some("false")
+        );
+        assert_eq!(
+            toggle.text_content().as_deref(),
+            Some(COLLAPSE_COLLAPSE_LABEL)
+        );
+        assert!(
+            block.inner_html().contains("<p>Line 29</p>"),
+            "Expanding should reveal the full stored content"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn short_block_is_left_untouched() {
+        let document = utils::document().expect("document");
+        let block = document
+            .create_element("div")
+            .expect("block")
+            .dyn_into::<HtmlElement>()
+            .expect("html element");
+        block.set_inner_html("<p>Short answer.</p>");
+        let collapsed = collapse_block_if_long(&document, &block, 12).expect("collapse");
+        assert!(!collapsed, "Short answers should not be collapsed");
+        assert!(block.get_attribute("data-collapsed").is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn selected_lines_are_highlighted_and_joined() {
+        let document = utils::document().expect("document");
+        let output = document.create_element("div").expect("output");
+        output.set_inner_html(
+            "<div class=\"line\">zqs@dev:~$ about</div>\
+             <div class=\"line\"><span>Alexandre</span> builds games</div>\
+             <div class=\"line\">Third line</div>",
+        );
+        let nested = output
+            .query_selector("span")
+            .expect("query")
+            .expect("nested span");
+        assert_eq!(child_index_containing(&output, &nested), Some(1));
+
+        mark_selected_children(&output, Some((0, 1))).expect("highlight");
+        assert_eq!(
+            output
+                .query_selector_all(&format!(".{LINE_SELECTED_CLASS}"))
+                .expect("query")
+                .length(),
+            2
+        );
+        assert_eq!(
+            children_text(&output, 0, 1),
+            "zqs@dev:~$ about\nAlexandre builds games"
+        );
+
+        mark_selected_children(&output, None).expect("clear");
+        assert_eq!(
+            output
+                .query_selector_all(&format!(".{LINE_SELECTED_CLASS}"))
+                .expect("query")
+                .length(),
+            0
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use gloo_timers::future::TimeoutFuture;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+pub const SLEEP_COMMAND: &str = "sleep";
+pub const SLEEP_USAGE: &str =
+    "Usage: sleep <seconds> — up to 10, between chained commands (`sleep 2 && about`).";
+const MAX_SLEEP_SECS: f64 = 10.0;
+const PAUSE_TICK_MS: u32 = 100;
+
+const DEMO_COMMANDS: [&str; 4] = ["about", "skills", "projects", "testimonials"];
+const DEMO_LEAD_IN_MS: u32 = 600;
+const DEMO_BEFORE_ENTER_MS: u32 = 350;
+const DEMO_BETWEEN_COMMANDS_MS: u32 = 2200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Type(String),
+    Run(String),
+    Sleep { line: String, ms: u32 },
+    Pause(u32),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Rc<Cell<bool>>);
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.0.set(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.get()
+    }
+}
+
+pub struct Sequence {
+    steps: VecDeque<Step>,
+    cancellation: Cancellation,
+}
+
+impl Sequence {
+    pub fn new(steps: impl IntoIterator<Item = Step>) -> Self {
+        Self {
+            steps: steps.into_iter().collect(),
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    pub fn cancellation(&self) -> Cancellation {
+        self.cancellation.clone()
+    }
+
+    pub fn next_step(&mut self) -> Option<Step> {
+        if self.cancellation.is_cancelled() {
+            self.steps.clear();
+            return None;
+        }
+        self.steps.pop_front()
+    }
+}
+
+// `None` when the line is not a sleep at all.
+pub fn parse_sleep(line: &str) -> Option<Result<u32, String>> {
+    let mut words = line.split_whitespace();
+    if !words.next()?.eq_ignore_ascii_case(SLEEP_COMMAND) {
+        return None;
+    }
+    let seconds = match (words.next(), words.next()) {
+        (Some(raw), None) => raw
+            .strip_suffix('s')
+            .unwrap_or(raw)
+            .parse::<f64>()
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs > 0.0 && *secs <= MAX_SLEEP_SECS),
+        _ => None,
+    };
+    Some(
+        seconds
+            .map(|secs| (secs * 1000.0).round() as u32)
+            .ok_or_else(|| SLEEP_USAGE.to_string()),
+    )
+}
+
+// Invalid sleeps run as commands so they print the usage.
+pub fn chain_steps(lines: &[&str]) -> Vec<Step> {
+    lines
+        .iter()
+        .map(|line| match parse_sleep(line) {
+            Some(Ok(ms)) => Step::Sleep {
+                line: line.to_string(),
+                ms,
+            },
+            _ => Step::Run(line.to_string()),
+        })
+        .collect()
+}
+
+pub fn has_sleep(steps: &[Step]) -> bool {
+    steps.iter().any(|step| matches!(step, Step::Sleep { .. }))
+}
+
+pub fn demo_steps() -> Vec<Step> {
+    let mut steps = vec![Step::Pause(DEMO_LEAD_IN_MS)];
+    for (index, command) in DEMO_COMMANDS.iter().enumerate() {
+        if index > 0 {
+            steps.push(Step::Pause(DEMO_BETWEEN_COMMANDS_MS));
+        }
+        steps.push(Step::Type(command.to_string()));
+        steps.push(Step::Pause(DEMO_BEFORE_ENTER_MS));
+        steps.push(Step::Run(command.to_string()));
+    }
+    steps
+}
+
+fn pause_slices(ms: u32) -> impl Iterator<Item = u32> {
+    let full = ms / PAUSE_TICK_MS;
+    let rest = ms % PAUSE_TICK_MS;
+    std::iter::repeat_n(PAUSE_TICK_MS, full as usize).chain((rest > 0).then_some(rest))
+}
+
+// Returns whether the full pause elapsed.
+pub async fn pause(ms: u32, cancellation: &Cancellation) -> bool {
+    for slice in pause_slices(ms) {
+        if cancellation.is_cancelled() {
+            return false;
+        }
+        TimeoutFuture::new(slice).await;
+    }
+    !cancellation.is_cancelled()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_yields_steps_in_order() {
+        let mut sequence = Sequence::new([
+            Step::Run("about".to_string()),
+            Step::Pause(200),
+            Step::Run("skills".to_string()),
+        ]);
+        assert_eq!(sequence.steps.len(), 3);
+        assert_eq!(sequence.next_step(), Some(Step::Run("about".to_string())));
+        assert_eq!(sequence.next_step(), Some(Step::Pause(200)));
+        assert_eq!(sequence.next_step(), Some(Step::Run("skills".to_string())));
+        assert_eq!(sequence.next_step(), None);
+    }
+
+    #[test]
+    fn cancelling_through_any_handle_drops_the_rest_of_the_queue() {
+        let mut sequence = Sequence::new(demo_steps());
+        let handle = sequence.cancellation();
+        assert!(sequence.next_step().is_some());
+        assert!(!handle.is_cancelled());
+
+        handle.clone().cancel();
+        assert!(sequence.cancellation().is_cancelled());
+        assert_eq!(sequence.next_step(), None);
+        assert!(sequence.steps.is_empty());
+        assert_eq!(sequence.next_step(), None);
+    }
+
+    #[test]
+    fn sleep_accepts_seconds_up_to_the_cap() {
+        assert_eq!(parse_sleep("sleep 2"), Some(Ok(2000)));
+        assert_eq!(parse_sleep("  SLEEP 0.5 "), Some(Ok(500)));
+        assert_eq!(parse_sleep("sleep 3s"), Some(Ok(3000)));
+        assert_eq!(parse_sleep("sleep 10"), Some(Ok(10_000)));
+    }
+
+    #[test]
+    fn sleep_rejects_missing_invalid_and_excessive_durations() {
+        for line in [
+            "sleep",
+            "sleep 0",
+            "sleep -1",
+            "sleep 10.5",
+            "sleep 60",
+            "sleep NaN",
+            "sleep inf",
+            "sleep two",
+            "sleep 1 2",
+        ] {
+            assert_eq!(
+                parse_sleep(line),
+                Some(Err(SLEEP_USAGE.to_string())),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_sleep_lines_are_parsed_as_sleeps() {
+        assert_eq!(parse_sleep("about"), None);
+        assert_eq!(parse_sleep("sleepy 2"), None);
+        assert_eq!(parse_sleep(""), None);
+    }
+
+    #[test]
+    fn chains_turn_valid_sleeps_into_sleep_steps() {
+        let steps = chain_steps(&["sleep 2", "about", "sleep 99"]);
+        assert_eq!(
+            steps,
+            vec![
+                Step::Sleep {
+                    line: "sleep 2".to_string(),
+                    ms: 2000
+                },
+                Step::Run("about".to_string()),
+                Step::Run("sleep 99".to_string()),
+            ]
+        );
+        assert!(has_sleep(&steps));
+        assert!(!has_sleep(&chain_steps(&["help", "faq", "sleep 99"])));
+    }
+
+    #[test]
+    fn demo_types_each_command_before_running_it() {
+        let steps = demo_steps();
+        let runs: Vec<&str> = steps
+            .iter()
+            .filter_map(|step| match step {
+                Step::Run(line) => Some(line.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(runs, DEMO_COMMANDS);
+
+        for (index, step) in steps.iter().enumerate() {
+            if let Step::Run(line) = step {
+                let typed = steps[..index]
+                    .iter()
+                    .rev()
+                    .find(|earlier| !matches!(earlier, Step::Pause(_)));
+                assert_eq!(typed, Some(&Step::Type(line.clone())), "{line}");
+            }
+        }
+        assert!(matches!(steps.first(), Some(Step::Pause(_))));
+        assert!(matches!(steps.last(), Some(Step::Run(_))));
+    }
+
+    #[test]
+    fn pauses_are_split_into_short_slices() {
+        assert_eq!(pause_slices(250).collect::<Vec<_>>(), vec![100, 100, 50]);
+        assert_eq!(pause_slices(200).collect::<Vec<_>>(), vec![100, 100]);
+        assert_eq!(pause_slices(0).count(), 0);
+        assert_eq!(pause_slices(10_000).sum::<u32>(), 10_000);
+    }
+}
@@ -1,5 +1,13 @@
+use crate::build_info;
+use crate::confirm::PendingConfirmation;
+use crate::format::{Locale, READING_TIME_DEFAULT_MIN_WORDS};
+use crate::utils::random::{BrowserRandom, RandomSource};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use std::collections::BTreeMap;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use web_sys::HtmlElement;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProfileLinks {
@@ -46,6 +54,10 @@ pub struct Education {
     pub school: String,
     pub years: Option<String>,
     pub location: Option<String>,
+    #[serde(default)]
+    pub details: Vec<String>,
+    #[serde(default)]
+    pub link: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -116,6 +128,12 @@ pub struct TerminalData {
     pub projects: ProjectsCollection,
     pub testimonials: Vec<Testimonial>,
     pub faqs: Vec<FaqEntry>,
+    #[serde(default)]
+    pub generated_at: Option<String>,
+    #[serde(default)]
+    pub source_mtime: Option<String>,
+    #[serde(default)]
+    pub counts: BTreeMap<String, u64>,
 }
 
 impl TerminalData {
@@ -136,14 +154,164 @@ impl TerminalData {
             projects,
             testimonials,
             faqs,
+            generated_at: None,
+            source_mtime: None,
+            counts: BTreeMap::new(),
         }
     }
+
+    pub fn freshness_timestamp(&self) -> Option<&str> {
+        self.source_mtime
+            .as_deref()
+            .or(self.generated_at.as_deref())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BackendVersionMeta {
     pub version: String,
     pub commit: String,
+    pub built_at: Option<String>,
+}
+
+// Owner's local clock and working-hours window, as reported by `/api/time`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct OwnerAvailability {
+    pub local_time: String,
+    pub utc_offset: String,
+    pub working_hours: String,
+    pub within_working_hours: bool,
+}
+
+pub const AI_HISTORY_LIMIT: usize = 20;
+pub const AI_SESSION_UNDO_MS: f64 = 5.0 * 60.0 * 1000.0;
+pub const DEFAULT_THEME: &str = "midnight";
+pub const OUTPUT_WIDTH_COLUMNS: (u16, u16) = (40, 240);
+pub const OUTPUT_WIDTH_PIXELS: (u16, u16) = (320, 4000);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputWidth {
+    #[default]
+    Auto,
+    Columns(u16),
+    Pixels(u16),
+}
+
+impl OutputWidth {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        if raw == "auto" {
+            return Some(OutputWidth::Auto);
+        }
+        let within = |value: u16, (min, max): (u16, u16)| (min..=max).contains(&value);
+        if let Some(pixels) = raw.strip_suffix("px") {
+            return pixels
+                .parse()
+                .ok()
+                .filter(|value| within(*value, OUTPUT_WIDTH_PIXELS))
+                .map(OutputWidth::Pixels);
+        }
+        raw.strip_suffix("ch")
+            .unwrap_or(&raw)
+            .parse()
+            .ok()
+            .filter(|value| within(*value, OUTPUT_WIDTH_COLUMNS))
+            .map(OutputWidth::Columns)
+    }
+
+    pub fn css_value(self) -> Option<String> {
+        match self {
+            OutputWidth::Auto => None,
+            OutputWidth::Columns(columns) => Some(format!("{columns}ch")),
+            OutputWidth::Pixels(pixels) => Some(format!("{pixels}px")),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            OutputWidth::Auto => "auto".to_string(),
+            OutputWidth::Columns(columns) => columns.to_string(),
+            OutputWidth::Pixels(pixels) => format!("{pixels}px"),
+        }
+    }
+}
+
+// `Auto` follows the browser's Save-Data hint; `settings datasaver on|off`
+// pins the choice regardless of the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataSaverSetting {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl DataSaverSetting {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "on" => Some(Self::On),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn resolve(self, save_data_hint: bool) -> bool {
+        match self {
+            Self::Auto => save_data_hint,
+            Self::On => true,
+            Self::Off => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiExchange {
+    pub question: String,
+    pub answer: String,
+    pub model: Option<String>,
+    pub backend: Option<String>,
+}
+
+// What `quit` dropped from AI mode, kept for a few minutes so an accidental
+// quit can be undone when AI mode comes back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AiSessionSnapshot {
+    pub turns: Vec<AiExchange>,
+    pub model: Option<String>,
+    pub saved_at: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    pub external_links: Vec<String>,
+}
+
+impl SessionStats {
+    pub fn record_external_link(&mut self, url: &str) {
+        self.external_links.push(url.to_string());
+    }
+}
+
+// Overlays of the non-destructive effects (Shaw, Pokémon, cookie rain) that a
+// key press may cut short. Their timers release them once they clean up.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveEffect {
+    elements: Vec<HtmlElement>,
+}
+
+impl ActiveEffect {
+    pub fn elements(&self) -> &[HtmlElement] {
+        &self.elements
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -156,10 +324,27 @@ pub struct AppState {
     pub initialized: bool,
     pub ai_mode: bool,
     pub ai_model: Option<String>,
+    pub ai_preferred_model: Option<String>,
+    pub ai_request_generation: u64,
+    pub ai_request_pending: bool,
+    pub cookie_games_in_progress: u32,
+    pub ai_available: bool,
+    pub pending_confirmation: Option<PendingConfirmation>,
+    pub active_effect: Option<ActiveEffect>,
+    pub session_stats: SessionStats,
+    pub ai_history: Vec<AiExchange>,
+    pub ai_session_snapshot: Option<AiSessionSnapshot>,
+    pub output_width: OutputWidth,
+    pub data_saver_setting: DataSaverSetting,
+    pub data_saver: bool,
+    pub reading_time_min_words: Option<usize>,
     pub input_disabled: bool,
+    pub read_only: bool,
+    pub theme: String,
     pub konami_index: usize,
     pub konami_triggered: bool,
     pub pokemon_capture_chance: u8,
+    pub cookie_best_count: u32,
     pub achievement_shaw_unlocked: bool,
     pub achievement_pokemon_unlocked: bool,
     pub achievement_cookie_unlocked: bool,
@@ -168,7 +353,13 @@ pub struct AppState {
     pub achievement_platinum_unlocked: bool,
     pub achievements_modal_open: bool,
     pub achievements_spoilers_enabled: bool,
+    pub suggestions_expanded: bool,
+    pub last_submission: Option<(String, f64)>,
+    pub clock: fn() -> f64,
+    pub random: Rc<dyn RandomSource>,
+    pub locale: Locale,
     pub backend_version: Option<BackendVersionMeta>,
+    pub owner_availability: Option<OwnerAvailability>,
 }
 
 impl AppState {
@@ -182,10 +373,27 @@ impl AppState {
             initialized: false,
             ai_mode: false,
             ai_model: None,
+            ai_preferred_model: None,
+            ai_request_generation: 0,
+            ai_request_pending: false,
+            cookie_games_in_progress: 0,
+            ai_available: true,
+            pending_confirmation: None,
+            active_effect: None,
+            session_stats: SessionStats::default(),
+            ai_history: Vec::new(),
+            ai_session_snapshot: None,
+            output_width: OutputWidth::Auto,
+            data_saver_setting: DataSaverSetting::Auto,
+            data_saver: false,
+            reading_time_min_words: Some(READING_TIME_DEFAULT_MIN_WORDS),
             input_disabled: false,
+            read_only: false,
+            theme: DEFAULT_THEME.to_string(),
             konami_index: 0,
             konami_triggered: false,
             pokemon_capture_chance: 1,
+            cookie_best_count: 0,
             achievement_shaw_unlocked: false,
             achievement_pokemon_unlocked: false,
             achievement_cookie_unlocked: false,
@@ -194,7 +402,13 @@ impl AppState {
             achievement_platinum_unlocked: false,
             achievements_modal_open: false,
             achievements_spoilers_enabled: false,
+            suggestions_expanded: false,
+            last_submission: None,
+            clock: js_sys::Date::now,
+            random: Rc::new(BrowserRandom),
+            locale: Locale::default(),
             backend_version: None,
+            owner_availability: None,
         }
     }
 
@@ -210,14 +424,111 @@ impl AppState {
         self.history_index = None;
     }
 
+    pub fn record_ai_exchange(&mut self, exchange: AiExchange) {
+        self.ai_history.push(exchange);
+        if self.ai_history.len() > AI_HISTORY_LIMIT {
+            let overflow = self.ai_history.len() - AI_HISTORY_LIMIT;
+            self.ai_history.drain(..overflow);
+        }
+    }
+
+    // Drops the conversation and the model that answered it, keeping a copy
+    // for `AI_SESSION_UNDO_MS`. Empty sessions leave nothing to resume.
+    pub fn end_ai_session(&mut self) {
+        let turns = std::mem::take(&mut self.ai_history);
+        self.ai_session_snapshot = (!turns.is_empty()).then(|| AiSessionSnapshot {
+            turns,
+            model: self.ai_model.clone(),
+            saved_at: self.now_ms(),
+        });
+    }
+
+    pub fn resumable_ai_session(&mut self) -> Option<&AiSessionSnapshot> {
+        let now = self.now_ms();
+        if self
+            .ai_session_snapshot
+            .as_ref()
+            .is_some_and(|snapshot| now - snapshot.saved_at > AI_SESSION_UNDO_MS)
+        {
+            self.ai_session_snapshot = None;
+        }
+        self.ai_session_snapshot.as_ref()
+    }
+
+    // Returns how many turns came back. The offer was already checked against
+    // the expiry, so a slow reply to the prompt still restores.
+    pub fn restore_ai_session(&mut self) -> Option<usize> {
+        let snapshot = self.ai_session_snapshot.take()?;
+        let restored = snapshot.turns.len();
+        self.ai_history = snapshot.turns;
+        self.ai_model = snapshot.model;
+        Some(restored)
+    }
+
+    pub fn discard_ai_session(&mut self) {
+        self.ai_session_snapshot = None;
+    }
+
+    pub fn track_effect(&mut self, element: HtmlElement) {
+        self.active_effect
+            .get_or_insert_with(ActiveEffect::default)
+            .elements
+            .push(element);
+    }
+
+    pub fn release_effect(&mut self, element: &HtmlElement) {
+        let Some(effect) = self.active_effect.as_mut() else {
+            return;
+        };
+        let target: &JsValue = element.as_ref();
+        effect
+            .elements
+            .retain(|tracked| AsRef::<JsValue>::as_ref(tracked) != target);
+        if effect.elements.is_empty() {
+            self.active_effect = None;
+        }
+    }
+
+    pub fn take_active_effect(&mut self) -> Option<ActiveEffect> {
+        self.active_effect.take()
+    }
+
     pub fn set_ai_mode(&mut self, active: bool) {
         self.ai_mode = active;
     }
 
+    pub fn cancel_pending_ai_requests(&mut self) {
+        self.ai_request_generation = self.ai_request_generation.wrapping_add(1);
+        self.ai_request_pending = false;
+    }
+
+    // Closing the tab now would drop a pending answer or a half-baked cookie
+    // game, neither of which survives a reload.
+    pub fn should_warn_before_unload(&self) -> bool {
+        self.ai_request_pending || self.cookie_games_in_progress > 0
+    }
+
+    pub fn is_current_ai_request(&self, generation: u64) -> bool {
+        self.ai_request_generation == generation
+    }
+
     pub fn set_ai_model(&mut self, model: Option<String>) {
         self.ai_model = model;
     }
 
+    pub fn set_ai_preferred_model(&mut self, model: Option<String>) {
+        self.ai_preferred_model = model;
+    }
+
+    pub fn now_ms(&self) -> f64 {
+        (self.clock)()
+    }
+
+    pub fn toggle_suggestions_expanded(&mut self) -> bool {
+        self.suggestions_expanded = !self.suggestions_expanded;
+        self.suggestions_expanded
+    }
+
     pub fn set_backend_version(&mut self, info: BackendVersionMeta) {
         self.backend_version = Some(info);
     }
@@ -234,6 +545,57 @@ impl AppState {
         self.input_disabled
     }
 
+    // Read-only embeds keep running commands from suggestion chips; only the
+    // keyboard prompt is locked.
+    pub fn accepts_typing(&self) -> bool {
+        !self.input_disabled && !self.read_only
+    }
+
+    // Pasteable into bug reports: settings, counts and versions only. The
+    // prompt buffer, history entries, AI exchanges and visited links stay out.
+    pub fn debug_snapshot(&self) -> Value {
+        let achievements = [
+            self.achievement_shaw_unlocked,
+            self.achievement_pokemon_unlocked,
+            self.achievement_cookie_unlocked,
+            self.achievement_konami_unlocked,
+            self.achievement_shutdown_unlocked,
+            self.achievement_platinum_unlocked,
+        ];
+        json!({
+            "version": {
+                "frontend": build_info::FRONTEND_VERSION,
+                "commit": build_info::frontend_commit(),
+                "backend": self.backend_version.as_ref().map(|meta| &meta.version),
+                "backend_commit": self.backend_version.as_ref().map(|meta| &meta.commit),
+            },
+            "ai_mode": self.ai_mode,
+            "ai_model": self.ai_model,
+            "ai_preferred_model": self.ai_preferred_model,
+            "ai_request_pending": self.ai_request_pending,
+            "locale": self.locale.tag(),
+            "theme": self.theme,
+            "counts": {
+                "commands": self.command_history.len(),
+                "ai_exchanges": self.ai_history.len(),
+                "external_links": self.session_stats.external_links.len(),
+                "achievements_unlocked": achievements.iter().filter(|flag| **flag).count(),
+                "data": self.data.as_ref().map(|data| &data.counts),
+            },
+            "flags": {
+                "data_loaded": self.data.is_some(),
+                "data_saver": self.data_saver,
+                "data_saver_setting": self.data_saver_setting.label(),
+                "reading_time_min_words": self.reading_time_min_words,
+                "read_only": self.read_only,
+                "input_disabled": self.input_disabled,
+                "suggestions_expanded": self.suggestions_expanded,
+                "achievements_spoilers": self.achievements_spoilers_enabled,
+                "output_width": self.output_width.label(),
+            },
+        })
+    }
+
     pub fn pokemon_capture_chance(&self) -> u8 {
         self.pokemon_capture_chance
     }
@@ -242,6 +604,16 @@ impl AppState {
         self.pokemon_capture_chance = chance.clamp(1, 100);
     }
 
+    // Keeps the furthest an unfinished cookie game got, for the achievements
+    // progress bar; returns whether the record moved.
+    pub fn record_cookie_count(&mut self, count: u32) -> bool {
+        if self.achievement_cookie_unlocked || count <= self.cookie_best_count {
+            return false;
+        }
+        self.cookie_best_count = count;
+        true
+    }
+
     pub fn unlock_shaw_celebration(&mut self) -> bool {
         Self::unlock_flag(&mut self.achievement_shaw_unlocked)
     }
@@ -277,6 +649,21 @@ impl AppState {
         Self::unlock_flag(&mut self.achievement_platinum_unlocked)
     }
 
+    // Spoiler visibility is a display preference, not progress, so it
+    // survives a reset.
+    pub fn reset_achievements(&mut self) {
+        self.achievement_shaw_unlocked = false;
+        self.achievement_pokemon_unlocked = false;
+        self.achievement_cookie_unlocked = false;
+        self.achievement_konami_unlocked = false;
+        self.achievement_shutdown_unlocked = false;
+        self.achievement_platinum_unlocked = false;
+        self.konami_triggered = false;
+        self.konami_index = 0;
+        self.pokemon_capture_chance = 1;
+        self.cookie_best_count = 0;
+    }
+
     fn unlock_flag(flag: &mut bool) -> bool {
         if *flag {
             false
@@ -289,7 +676,208 @@ impl AppState {
 
 #[cfg(test)]
 mod tests {
-    use super::AppState;
+    use super::{
+        AiExchange, AppState, BackendVersionMeta, DataSaverSetting, OutputWidth, AI_HISTORY_LIMIT,
+        AI_SESSION_UNDO_MS,
+    };
+
+    #[test]
+    fn output_width_parses_columns_pixels_and_auto() {
+        assert_eq!(OutputWidth::parse("auto"), Some(OutputWidth::Auto));
+        assert_eq!(OutputWidth::parse("100"), Some(OutputWidth::Columns(100)));
+        assert_eq!(OutputWidth::parse("100ch"), Some(OutputWidth::Columns(100)));
+        assert_eq!(OutputWidth::parse("960PX"), Some(OutputWidth::Pixels(960)));
+        assert_eq!(OutputWidth::parse("10"), None);
+        assert_eq!(OutputWidth::parse("99999px"), None);
+        assert_eq!(OutputWidth::parse("wide"), None);
+        for width in [
+            OutputWidth::Auto,
+            OutputWidth::Columns(80),
+            OutputWidth::Pixels(960),
+        ] {
+            assert_eq!(OutputWidth::parse(&width.label()), Some(width));
+        }
+    }
+
+    #[test]
+    fn debug_snapshot_reports_versions_without_private_data() {
+        let mut state = AppState::new();
+        state.input_buffer = "my password is hunter2".to_string();
+        state.remember_command("contact --email me@example.com");
+        state
+            .session_stats
+            .record_external_link("https://example.com/?token=abc");
+        state.set_backend_version(BackendVersionMeta {
+            version: "9.9.9".to_string(),
+            commit: "abc1234".to_string(),
+            built_at: None,
+        });
+
+        let snapshot = state.debug_snapshot();
+        assert_eq!(
+            snapshot["version"]["frontend"],
+            crate::build_info::FRONTEND_VERSION
+        );
+        assert_eq!(snapshot["version"]["backend"], "9.9.9");
+        assert_eq!(snapshot["counts"]["commands"], 1);
+        assert_eq!(snapshot["theme"], "midnight");
+
+        let raw = snapshot.to_string().to_ascii_lowercase();
+        for leaked in [
+            "hunter2",
+            "me@example.com",
+            "example.com",
+            "token",
+            "secret",
+            "password",
+            "api_key",
+        ] {
+            assert!(!raw.contains(leaked), "`{leaked}` leaked into {raw}");
+        }
+    }
+
+    #[test]
+    fn read_only_blocks_typing_but_not_commands() {
+        let mut state = AppState::new();
+        assert!(state.accepts_typing());
+        state.read_only = true;
+        assert!(!state.accepts_typing());
+        assert!(
+            !state.input_disabled(),
+            "Suggestion chips still run commands"
+        );
+    }
+
+    #[test]
+    fn data_saver_override_beats_the_connection_hint() {
+        assert_eq!(DataSaverSetting::parse(" ON "), Some(DataSaverSetting::On));
+        assert_eq!(DataSaverSetting::parse("maybe"), None);
+        assert!(DataSaverSetting::Auto.resolve(true));
+        assert!(!DataSaverSetting::Auto.resolve(false));
+        assert!(DataSaverSetting::On.resolve(false));
+        assert!(!DataSaverSetting::Off.resolve(true));
+    }
+
+    #[test]
+    fn ai_history_keeps_only_the_latest_exchanges() {
+        let mut state = AppState::new();
+        for index in 0..AI_HISTORY_LIMIT + 3 {
+            state.record_ai_exchange(AiExchange {
+                question: format!("question {index}"),
+                answer: String::new(),
+                model: None,
+                backend: None,
+            });
+        }
+        assert_eq!(state.ai_history.len(), AI_HISTORY_LIMIT);
+        assert_eq!(state.ai_history[0].question, "question 3");
+    }
+
+    #[test]
+    fn quitting_keeps_the_ai_session_resumable_for_five_minutes() {
+        thread_local! {
+            static NOW: std::cell::Cell<f64> = const { std::cell::Cell::new(1_000.0) };
+        }
+        fn clock() -> f64 {
+            NOW.with(|now| now.get())
+        }
+        let advance = |ms: f64| NOW.with(|now| now.set(now.get() + ms));
+
+        let mut state = AppState::new();
+        state.clock = clock;
+        state.end_ai_session();
+        assert!(
+            state.resumable_ai_session().is_none(),
+            "Nothing to resume before any question"
+        );
+
+        let exchange = AiExchange {
+            question: "Where does Alexandre work?".to_string(),
+            answer: "At zqsdev.".to_string(),
+            model: Some("groq".to_string()),
+            backend: None,
+        };
+        state.record_ai_exchange(exchange.clone());
+        state.set_ai_model(Some("groq".to_string()));
+        state.end_ai_session();
+        assert!(
+            state.ai_history.is_empty(),
+            "Quitting drops the conversation"
+        );
+        advance(AI_SESSION_UNDO_MS - 1.0);
+        assert_eq!(
+            state
+                .resumable_ai_session()
+                .map(|snapshot| snapshot.turns.len()),
+            Some(1)
+        );
+        state.set_ai_model(None);
+        assert_eq!(state.restore_ai_session(), Some(1));
+        assert_eq!(state.ai_history, vec![exchange]);
+        assert_eq!(state.ai_model.as_deref(), Some("groq"));
+        assert_eq!(state.restore_ai_session(), None, "Restoring is one-shot");
+
+        state.end_ai_session();
+        state.discard_ai_session();
+        assert!(state.resumable_ai_session().is_none());
+
+        state.record_ai_exchange(AiExchange {
+            question: "Skills?".to_string(),
+            answer: "Rust.".to_string(),
+            model: None,
+            backend: None,
+        });
+        state.end_ai_session();
+        advance(AI_SESSION_UNDO_MS + 1.0);
+        assert!(state.resumable_ai_session().is_none(), "Snapshots expire");
+        assert!(state.ai_session_snapshot.is_none());
+        assert_eq!(state.restore_ai_session(), None);
+    }
+
+    #[test]
+    fn reset_clears_progress_but_keeps_spoiler_preference() {
+        let mut state = AppState::new();
+        state.unlock_shaw_celebration();
+        state.unlock_pokemon_master();
+        state.unlock_cookie_rain();
+        state.unlock_konami_secret();
+        state.unlock_shutdown_protocol();
+        assert!(state.unlock_platinum_trophy());
+        state.konami_triggered = true;
+        state.set_pokemon_capture_chance(40);
+        state.cookie_best_count = 64;
+        state.achievements_spoilers_enabled = true;
+
+        state.reset_achievements();
+        assert!(!state.achievement_shaw_unlocked);
+        assert!(!state.achievement_pokemon_unlocked);
+        assert!(!state.achievement_cookie_unlocked);
+        assert!(!state.achievement_konami_unlocked);
+        assert!(!state.achievement_shutdown_unlocked);
+        assert!(!state.achievement_platinum_unlocked);
+        assert!(!state.konami_triggered);
+        assert_eq!(state.pokemon_capture_chance(), 1);
+        assert_eq!(state.cookie_best_count, 0);
+        assert!(state.achievements_spoilers_enabled);
+        assert!(
+            state.unlock_shaw_celebration(),
+            "Achievements can be earned again after a reset"
+        );
+    }
+
+    #[test]
+    fn cookie_record_only_moves_forward_while_locked() {
+        let mut state = AppState::new();
+        assert!(state.record_cookie_count(40));
+        assert!(
+            !state.record_cookie_count(25),
+            "A shorter game keeps the record"
+        );
+        assert_eq!(state.cookie_best_count, 40);
+        state.unlock_cookie_rain();
+        assert!(!state.record_cookie_count(60));
+        assert_eq!(state.cookie_best_count, 40);
+    }
 
     #[test]
     fn platinum_requires_every_base_achievement() {
@@ -321,4 +909,34 @@ mod tests {
         assert!(state.unlock_platinum_trophy());
         assert!(!state.unlock_platinum_trophy());
     }
+
+    #[test]
+    fn cancelling_ai_requests_marks_in_flight_answers_stale() {
+        let mut state = AppState::new();
+        let generation = state.ai_request_generation;
+        assert!(state.is_current_ai_request(generation));
+
+        state.cancel_pending_ai_requests();
+        assert!(
+            !state.is_current_ai_request(generation),
+            "An answer started before the toggle should be discarded"
+        );
+        assert!(state.is_current_ai_request(state.ai_request_generation));
+    }
+
+    #[test]
+    fn leaving_the_page_warns_only_while_work_would_be_lost() {
+        let mut state = AppState::new();
+        assert!(!state.should_warn_before_unload());
+
+        state.ai_request_pending = true;
+        assert!(state.should_warn_before_unload());
+        state.cancel_pending_ai_requests();
+        assert!(!state.should_warn_before_unload());
+
+        state.cookie_games_in_progress = 2;
+        assert!(state.should_warn_before_unload());
+        state.cookie_games_in_progress = 0;
+        assert!(!state.should_warn_before_unload());
+    }
 }
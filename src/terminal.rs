@@ -1,9 +1,18 @@
 use crate::ai;
-use crate::commands::{self, CommandAction, CommandError, PokemonAttemptOutcome};
-use crate::renderer::{AchievementTier, AchievementView, Renderer, ScrollBehavior};
-use crate::state::AppState;
+use crate::commands::{self, ClearScope, CommandAction, CommandError, PokemonAttemptOutcome};
+use crate::confirm::{ConfirmAction, ConfirmOutcome, PendingConfirmation};
+use crate::format::{self, Locale};
+use crate::i18n::{self, Message};
+use crate::keyword_icons;
+use crate::renderer::{
+    self, AchievementTier, AchievementView, GroupSummary, PinOutcome, Renderer, ScrollBehavior,
+    PINNED_OUTPUT_LIMIT,
+};
+use crate::schedule::{self, Cancellation, Sequence, Step};
+use crate::state::{AiExchange, AppState, DataSaverSetting, OutputWidth, SessionStats};
 use crate::telemetry::{self, CommandLogMode};
-use crate::utils;
+use crate::utils::debounce::{BrowserScheduler, Debouncer, Scheduler};
+use crate::utils::{self, storage};
 use gloo_timers::future::TimeoutFuture;
 use serde::{Deserialize, Serialize};
 use std::cell::{Cell, RefCell};
@@ -12,14 +21,35 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::HtmlElement;
+use web_sys::{Element, HtmlElement};
+use zqs_model::ReasonCode;
 
 pub type SharedState = Rc<RefCell<AppState>>;
 pub type SharedRenderer = Rc<Renderer>;
 
+// Every field is shared, so a clone is a cheap handle for async sequences.
+#[derive(Clone)]
 pub struct Terminal {
     state: SharedState,
     renderer: SharedRenderer,
+    typing: TypingBatch,
+    suggestion_refresh: Rc<Debouncer>,
+    sequence: Rc<RefCell<Option<Cancellation>>>,
+}
+
+// Keystrokes landing in the same macrotask are merged into one buffer update.
+#[derive(Clone)]
+struct TypingBatch {
+    pending: Rc<RefCell<String>>,
+    scheduler: Rc<dyn Scheduler>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SequenceKind {
+    // A `&&` chain with sleeps: rendered as one collapsible group.
+    Chain,
+    // The `demo` tour: each command is typed into the prompt first.
+    Demo,
 }
 
 pub enum HistoryDirection {
@@ -28,22 +58,39 @@ pub enum HistoryDirection {
 }
 
 const WELCOME_TYPE_DELAY_MS: u32 = 18;
+const DEMO_TYPE_DELAY_MS: u32 = 70;
+const DEMO_STARTED: &str = "🎬 Demo started — press any key to stop it.";
+const DEMO_FINISHED: &str = "🎬 Demo finished. The prompt is all yours.";
+const SEQUENCE_STOPPED: &str = "⏹️ Stopped. The prompt is all yours.";
+const SEQUENCE_BUSY: &str = "⏳ A scripted sequence is already running — press any key to stop it.";
+const SUGGESTION_REFRESH_DEBOUNCE_MS: u32 = 80;
+const AI_PENDING_LINK_PROMPT: &str =
+    "An AI answer is still generating — open the link anyway? [y/N]";
 const AI_HELP_COMMAND: &str = "help";
 const AI_QUIT_COMMAND: &str = "quit";
 const AI_QUIT_LABEL: &str = "Quit AI";
-const AI_STATUS_ACTIVE: &str = "AI Mode: Activated";
-const AI_STATUS_DEACTIVATED: &str = "AI Mode: Deactivated";
-const AI_STATUS_BUSY: &str = "AI Mode: Activated — Synthesizing…";
-const AI_ACTIVATED_INFO: &str =
-    "🤖 AI Mode activated. Ask anything about Alexandre DO-O ALMEIDA's profile.";
-const AI_DEACTIVATED_INFO: &str = "📟 AI Mode deactivated. Classic terminal helpers restored.";
-const AI_HELP_MESSAGE: &str = "🤖 AI Mode help:\nYou're chatting with an assistant that only uses Alexandre's résumé data.\nAsk a question or type `quit` to exit AI Mode.";
-const AI_DATA_LOADING: &str = "AI knowledge base still loading. Please try again shortly.";
-const BOOT_SEQUENCE_MESSAGE: &str = "Welcome to the ZQSDev interactive terminal!";
-const WELCOME_GUIDANCE_LINES: [&str; 2] = [
-    "Type `help` to view all available commands.",
-    "Use the quick actions below to jump to key sections instantly.",
-];
+const AI_MODEL_COMMAND: &str = "model";
+const AI_HISTORY_COMMAND: &str = "ai --history";
+const AI_MODEL_AUTO: &str = "auto";
+const AI_MODEL_CHOICES: [&str; 3] = ["groq", "google", "openai"];
+const READING_TIME_COMMANDS: [&str; 2] = ["about", "experience"];
+const AI_MODEL_STORAGE_KEY: &str = "zqs_terminal_ai_model";
+const SUGGESTIONS_EXPANDED_STORAGE_KEY: &str = "zqs_terminal_suggestions_expanded";
+const LOCALE_STORAGE_KEY: &str = "zqs_terminal_lang";
+const OUTPUT_WIDTH_STORAGE_KEY: &str = "zqs_terminal_output_width";
+const DATA_SAVER_STORAGE_KEY: &str = "zqs_terminal_data_saver";
+const READING_TIME_STORAGE_KEY: &str = "zqs_terminal_reading_time";
+const THEMES: &[&str] = &["midnight", "matrix"];
+const ACHIEVEMENTS_SPOILERS_STORAGE_KEY: &str = "zqs_terminal_achievements_spoilers";
+const AI_HELP_MESSAGE: &str = "🤖 AI Mode help:\nYou're chatting with an assistant that only uses Alexandre's résumé data.\nAsk a question, type `model <groq|google|openai|auto>` to pick the preferred backend, `ai --history` to review this session's answers, or type `quit` to exit AI Mode.";
+const ISSUE_TRACKER_URL: &str = "https://github.com/Aleqsd/zqsdev.com/issues";
+const AI_ANSWER_MAX_LINES: usize = 18;
+const COMMAND_COOLDOWN_MS: f64 = 400.0;
+const COOLDOWN_EXEMPT_COMMANDS: [&str; 1] = ["clear"];
+const WELCOME_GUIDANCE_LINES: [Message; 2] = [Message::WelcomeHelp, Message::WelcomeQuickActions];
+const UPDATE_COMMAND_CHIP_HTML: &str =
+    r#"<button type="button" class="command-chip" data-command="update">update</button>"#;
+const WELCOME_AI_CTA_HTML: &str = r#"Prefer to talk with an AI? <button type="button" class="ai-mode-cta" data-action="activate-ai-mode">Ask the AI assistant</button>"#;
 const TV_OFF_COMMAND: &str = "rm -rf";
 const TV_OFF_WARNING: &str = "⚠️ `rm -rf` sequence detected. Powering down terminal…";
 const KONAMI_CODE: [&str; 10] = [
@@ -76,48 +123,47 @@ const KAMEHAMEHA_MEDIA_HTML: &str = r#"
     ></audio>
 </figure>
 "#;
+const KAMEHAMEHA_PLACEHOLDER_HTML: &str = r#"<div class="konami-kamehameha effect-placeholder">💥 Goku unleashes a Kamehameha beam (media skipped to save data).</div>"#;
 const GOKU_FINISHER_HTML: &str =
     r#"<div class="konami-message konami-message--goku">Goku: "KAMEHAMEHA!" 💥</div>"#;
 const TERMINAL_EXPLODED_HTML: &str = r#"<div class="konami-message konami-message--terminal">💥 The terminal has exploded. Refresh the page to revive it.</div>"#;
 const KAMEHAMEHA_PROMPT_LABEL: &str = "⚡ KI>$";
-const ACHIEVEMENT_SHAW_TITLE: &str = "Shaw!";
-const ACHIEVEMENT_SHAW_DESCRIPTION: &str = "Could she be... a Hunter?";
-const ACHIEVEMENT_POKEMON_TITLE: &str = "Who's that Pokemon?";
-const ACHIEVEMENT_POKEMON_DESCRIPTION: &str = "It's Pikachu!";
-const ACHIEVEMENT_COOKIE_TITLE: &str = "Cookie Storm!";
-const ACHIEVEMENT_COOKIE_DESCRIPTION: &str = "Summoned the sweetest downpour.";
-const ACHIEVEMENT_KONAMI_TITLE: &str = "Kamehameha!";
-const ACHIEVEMENT_KONAMI_DESCRIPTION: &str = "And this... is to go even further beyond!";
-const ACHIEVEMENT_SHUTDOWN_TITLE: &str = "AAAAAAAAAAAAAH";
-const ACHIEVEMENT_SHUTDOWN_DESCRIPTION: &str = "Why would you do that?!";
-const ACHIEVEMENT_PLATINUM_TITLE: &str = "Platinum Trophy";
-const ACHIEVEMENT_PLATINUM_DESCRIPTION: &str = "Unlocked every Easter egg in the terminal.";
 const ACHIEVEMENTS_STORAGE_KEY: &str = "zqs_terminal_achievements";
 const ACHIEVEMENTS_STORAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
-const ACHIEVEMENT_SHAW_HINT: &str = "Hornet shouts can be heard in the terminal.";
-const ACHIEVEMENT_POKEMON_HINT: &str = "Gotta catch 'em all!";
-const ACHIEVEMENT_COOKIE_HINT: &str = "Tap into the cookie zone.";
-const ACHIEVEMENT_KONAMI_HINT: &str = "Konami";
-const ACHIEVEMENT_SHUTDOWN_HINT: &str = "Why would you remove my files?";
-const ACHIEVEMENT_PLATINUM_HINT: &str = "Claim every other Easter egg to reveal the rarest trophy.";
+const COOKIE_CLICKER_GOAL: u32 = 100;
+const POKEMON_MAX_CAPTURE_CHANCE: u32 = 100;
 
 impl Terminal {
     pub fn new(state: SharedState, renderer: SharedRenderer) -> Self {
-        Self { state, renderer }
+        let scheduler: Rc<dyn Scheduler> = Rc::new(BrowserScheduler);
+        Self {
+            state,
+            renderer,
+            typing: TypingBatch {
+                pending: Rc::new(RefCell::new(String::new())),
+                scheduler: Rc::clone(&scheduler),
+            },
+            suggestion_refresh: Rc::new(Debouncer::new(SUGGESTION_REFRESH_DEBOUNCE_MS, scheduler)),
+            sequence: Rc::new(RefCell::new(None)),
+        }
     }
 
     pub fn initialize(&self) -> Result<(), JsValue> {
-        let (prompt_label, input_buffer, ai_mode) = {
+        let (prompt_label, input_buffer, ai_mode, data_ready, suggestions_expanded) = {
             let state = self.state.borrow();
             (
                 state.prompt_label.clone(),
                 state.input_buffer.clone(),
                 state.ai_mode,
+                state.data.is_some(),
+                state.suggestions_expanded,
             )
         };
 
         self.renderer.set_prompt_label(&prompt_label);
-        self.renderer.update_input(&input_buffer);
+        self.set_buffer(&input_buffer, false);
+        self.renderer.set_suggestions_enabled(data_ready);
+        self.renderer.set_suggestions_expanded(suggestions_expanded);
         self.refresh_suggestions();
         self.renderer.apply_ai_mode(ai_mode)?;
         self.renderer.focus_terminal();
@@ -125,7 +171,7 @@ impl Terminal {
     }
 
     pub fn focus(&self) {
-        if self.input_disabled() {
+        if !self.accepts_typing() {
             return;
         }
         self.renderer.focus_terminal();
@@ -140,6 +186,20 @@ impl Terminal {
         }
     }
 
+    pub fn restore_achievements_spoilers_preference(&self) {
+        match read_achievements_spoilers() {
+            Ok(enabled) => {
+                self.state.borrow_mut().achievements_spoilers_enabled = enabled;
+            }
+            Err(err) => {
+                utils::log(&format!(
+                    "Failed to restore achievements spoilers preference: {:?}",
+                    err
+                ));
+            }
+        }
+    }
+
     pub fn open_achievements_modal(&self) -> Result<(), JsValue> {
         let achievements = self.collect_achievement_views();
         let spoilers_enabled = self.achievements_spoilers_enabled();
@@ -149,15 +209,22 @@ impl Terminal {
             let mut state = self.state.borrow_mut();
             state.achievements_modal_open = true;
         }
+        self.settle_focus();
         Ok(())
     }
 
+    pub fn is_modal_open(&self) -> bool {
+        self.state.borrow().achievements_modal_open
+    }
+
     pub fn close_achievements_modal(&self) -> Result<(), JsValue> {
         {
             let mut state = self.state.borrow_mut();
             state.achievements_modal_open = false;
         }
-        self.renderer.hide_achievements_modal()
+        self.renderer.hide_achievements_modal()?;
+        self.settle_focus();
+        Ok(())
     }
 
     pub fn toggle_achievements_spoilers(&self) -> Result<(), JsValue> {
@@ -165,52 +232,49 @@ impl Terminal {
             let mut state = self.state.borrow_mut();
             state.achievements_spoilers_enabled = !state.achievements_spoilers_enabled;
         }
-        self.persist_achievements_state();
-        self.refresh_achievements_modal_if_visible()
-    }
-
-    pub fn reset_achievements(&self) -> Result<(), JsValue> {
-        {
-            let mut state = self.state.borrow_mut();
-            state.achievement_shaw_unlocked = false;
-            state.achievement_pokemon_unlocked = false;
-            state.achievement_cookie_unlocked = false;
-            state.achievement_konami_unlocked = false;
-            state.achievement_shutdown_unlocked = false;
-            state.achievement_platinum_unlocked = false;
-            state.achievements_spoilers_enabled = false;
-            state.konami_triggered = false;
-            state.konami_index = 0;
-            state.pokemon_capture_chance = 1;
-        }
-        if let Err(err) = self.clear_achievements_storage() {
+        let enabled = self.achievements_spoilers_enabled();
+        if let Err(err) = write_achievements_spoilers(enabled) {
             utils::log(&format!(
-                "Failed to clear achievements storage during reset: {:?}",
+                "Failed to persist achievements spoilers preference: {:?}",
                 err
             ));
         }
-        self.persist_achievements_state();
         self.refresh_achievements_modal_if_visible()
     }
 
+    pub fn reset_achievements(&self) -> Result<(), JsValue> {
+        Self::reset_achievements_shared(&self.state);
+        self.refresh_achievements_modal_if_visible()?;
+        self.renderer
+            .append_info_line(self.t(Message::AchievementsReset), ScrollBehavior::Bottom)
+    }
+
+    fn reset_achievements_shared(state: &SharedState) {
+        state.borrow_mut().reset_achievements();
+        storage::remove(ACHIEVEMENTS_STORAGE_KEY);
+        Self::persist_achievements_snapshot_shared(state);
+    }
+
     pub fn handle_escape(&self) {
         if self.close_achievements_modal_if_open() {
             return;
         }
+        if self.dismiss_active_effect() {
+            return;
+        }
         self.clear_input();
     }
 
+    pub fn dismiss_active_effect(&self) -> bool {
+        dismiss_active_effect(&mut self.state.borrow_mut())
+    }
+
     pub fn overwrite_input(&self, value: &str) {
-        if self.input_disabled() {
+        if !self.accepts_typing() {
             return;
         }
-        {
-            let mut state = self.state.borrow_mut();
-            state.input_buffer = value.to_string();
-            state.history_index = None;
-        }
-        self.refresh_input();
-        self.refresh_suggestions();
+        self.set_buffer(value, true);
+        self.schedule_suggestion_refresh();
     }
 
     pub fn push_system_message(&self, message: &str) {
@@ -220,30 +284,59 @@ impl Terminal {
     }
 
     pub fn submit_command(&self) -> Result<(), JsValue> {
+        if !self.accepts_typing() {
+            return Ok(());
+        }
+        self.run_input_buffer()
+    }
+
+    fn run_input_buffer(&self) -> Result<(), JsValue> {
         if self.input_disabled() {
             return Ok(());
         }
+        self.flush_typing();
         let input = {
             let state = self.state.borrow();
             state.input_buffer.clone()
         };
+        let pending = self.state.borrow_mut().pending_confirmation.take();
+        if let Some(pending) = pending {
+            return self.resolve_confirmation(pending, &input);
+        }
 
         let display_line = input.clone();
         let mut state_mut = self.state.borrow_mut();
         let prompt_label = state_mut.prompt_label.clone();
         let trimmed = input.trim().to_string();
+        if !trimmed.is_empty() {
+            let now = state_mut.now_ms();
+            let previous = state_mut
+                .last_submission
+                .as_ref()
+                .map(|(command, at)| (command.as_str(), *at));
+            if within_command_cooldown(previous, &trimmed, now, state_mut.ai_mode) {
+                drop(state_mut);
+                self.set_buffer("", false);
+                self.refresh_suggestions();
+                if let Err(err) = self.renderer.flash_last_output() {
+                    utils::log(&format!("Failed to flash previous output: {:?}", err));
+                }
+                return Ok(());
+            }
+            state_mut.last_submission = Some((trimmed.clone(), now));
+        }
         state_mut.remember_command(&trimmed);
-        state_mut.input_buffer.clear();
         drop(state_mut);
 
-        self.refresh_input();
+        self.set_buffer("", false);
         self.refresh_suggestions();
 
         let ai_mode_active = self.ai_mode_active();
         let command_scroll = ScrollBehavior::Anchor;
         self.renderer.append_spacer_line(ScrollBehavior::None)?;
+        let shareable = !ai_mode_active && commands::is_shareable(&trimmed);
         self.renderer
-            .append_command(&prompt_label, &display_line, command_scroll)?;
+            .append_command(&prompt_label, &display_line, shareable, command_scroll)?;
 
         if trimmed.is_empty() {
             return Ok(());
@@ -253,15 +346,15 @@ impl Terminal {
             telemetry::log_command_submission(&trimmed, CommandLogMode::Classic);
         }
 
-        if Self::is_shutdown_command(&trimmed) {
+        if Self::is_shutdown_command(&trimmed, ai_mode_active) {
             let celebrate = {
                 let mut state = self.state.borrow_mut();
                 state.unlock_shutdown_protocol()
             };
             if celebrate {
                 self.trigger_achievement_popup(
-                    ACHIEVEMENT_SHUTDOWN_TITLE,
-                    ACHIEVEMENT_SHUTDOWN_DESCRIPTION,
+                    Message::AchievementShutdownTitle,
+                    Message::AchievementShutdownDescription,
                     AchievementTier::Standard,
                 )?;
                 self.finalize_achievement_unlock()?;
@@ -275,20 +368,220 @@ impl Terminal {
             return self.handle_ai_mode_submission(trimmed);
         }
 
-        let args: Vec<&str> = trimmed.split_whitespace().collect();
+        match chained_steps(&trimmed) {
+            Some(steps) => {
+                let scheduled = schedule::chain_steps(&steps);
+                if schedule::has_sleep(&scheduled) {
+                    self.start_sequence(scheduled, SequenceKind::Chain)
+                } else {
+                    self.run_chain(&prompt_label, &steps)
+                }
+            }
+            None => self.execute_classic(&trimmed).map(|_| ()),
+        }
+    }
+
+    fn start_demo(&self) -> Result<(), JsValue> {
+        if self.sequence.borrow().is_some() {
+            return self
+                .renderer
+                .append_info_line(SEQUENCE_BUSY, ScrollBehavior::Bottom);
+        }
+        self.renderer
+            .append_info_line(DEMO_STARTED, ScrollBehavior::Bottom)?;
+        self.start_sequence(schedule::demo_steps(), SequenceKind::Demo)
+    }
+
+    // Input stays disabled while the steps play; any key cancels them (see
+    // `abort_sequence`) and the runner hands the prompt back.
+    fn start_sequence(&self, steps: Vec<Step>, kind: SequenceKind) -> Result<(), JsValue> {
+        if self.sequence.borrow().is_some() {
+            return self
+                .renderer
+                .append_info_line(SEQUENCE_BUSY, ScrollBehavior::Bottom);
+        }
+        let sequence = Sequence::new(steps);
+        self.sequence.replace(Some(sequence.cancellation()));
+        self.state.borrow_mut().set_input_disabled(true);
+        self.renderer.disable_prompt_input()?;
+        if kind == SequenceKind::Chain {
+            self.renderer.begin_group()?;
+        }
+        let terminal = self.clone();
+        spawn_local(async move {
+            terminal.play_sequence(sequence, kind).await;
+        });
+        Ok(())
+    }
+
+    async fn play_sequence(&self, mut sequence: Sequence, kind: SequenceKind) {
+        let cancellation = sequence.cancellation();
+        let prompt_label = self.state.borrow().prompt_label.clone();
+        let mut summary = GroupSummary {
+            total: 0,
+            succeeded: 0,
+        };
+        while let Some(step) = sequence.next_step() {
+            let result = match step {
+                Step::Type(line) => {
+                    self.type_into_prompt(&line, &cancellation).await;
+                    Ok(())
+                }
+                Step::Pause(ms) => {
+                    schedule::pause(ms, &cancellation).await;
+                    Ok(())
+                }
+                Step::Sleep { line, ms } => {
+                    summary.total += 1;
+                    let echoed = self.echo_sequence_step(&prompt_label, &line, kind);
+                    if schedule::pause(ms, &cancellation).await {
+                        summary.succeeded += 1;
+                    }
+                    echoed
+                }
+                Step::Run(line) => {
+                    summary.total += 1;
+                    self.set_buffer("", false);
+                    self.echo_sequence_step(&prompt_label, &line, kind)
+                        .and_then(|_| self.execute_classic(&line))
+                        .map(|succeeded| {
+                            if succeeded {
+                                summary.succeeded += 1;
+                            }
+                        })
+                }
+            };
+            if let Err(err) = result {
+                utils::log(&format!("Scripted step failed: {:?}", err));
+            }
+        }
+        if let Err(err) = self.finish_sequence(kind, summary, cancellation.is_cancelled()) {
+            utils::log(&format!("Failed to finish scripted sequence: {:?}", err));
+        }
+    }
+
+    fn echo_sequence_step(
+        &self,
+        prompt_label: &str,
+        line: &str,
+        kind: SequenceKind,
+    ) -> Result<(), JsValue> {
+        let shareable = commands::is_shareable(line);
+        match kind {
+            SequenceKind::Chain => {
+                self.renderer
+                    .append_command(prompt_label, line, shareable, ScrollBehavior::None)
+            }
+            SequenceKind::Demo => {
+                self.renderer.append_spacer_line(ScrollBehavior::None)?;
+                self.renderer
+                    .append_command(prompt_label, line, shareable, ScrollBehavior::Anchor)
+            }
+        }
+    }
+
+    async fn type_into_prompt(&self, line: &str, cancellation: &Cancellation) {
+        let delay_ms = if utils::prefers_reduced_motion() {
+            0
+        } else {
+            DEMO_TYPE_DELAY_MS
+        };
+        renderer::typewrite(line, delay_ms, |typed| {
+            if cancellation.is_cancelled() {
+                return false;
+            }
+            self.set_buffer(typed, false);
+            true
+        })
+        .await;
+    }
+
+    fn finish_sequence(
+        &self,
+        kind: SequenceKind,
+        summary: GroupSummary,
+        cancelled: bool,
+    ) -> Result<(), JsValue> {
+        self.sequence.replace(None);
+        if kind == SequenceKind::Chain {
+            self.renderer.end_group(summary)?;
+        }
+        self.state.borrow_mut().set_input_disabled(false);
+        self.renderer.enable_prompt_input()?;
+        self.set_buffer("", true);
+        self.refresh_suggestions();
+        let closing = match (cancelled, kind) {
+            (true, _) => Some(SEQUENCE_STOPPED),
+            (false, SequenceKind::Demo) => Some(DEMO_FINISHED),
+            (false, SequenceKind::Chain) => None,
+        };
+        if let Some(message) = closing {
+            self.renderer
+                .append_info_line(message, ScrollBehavior::Bottom)?;
+        }
+        self.settle_focus();
+        Ok(())
+    }
+
+    // Called for every keydown: while a sequence plays, the key only stops it.
+    pub fn abort_sequence(&self) -> bool {
+        match self.sequence.borrow().as_ref() {
+            Some(cancellation) => {
+                cancellation.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Runs each `a && b` step inside one collapsible group. Every step runs
+    // even after a failure; the summary reports how many succeeded.
+    fn run_chain(&self, prompt_label: &str, steps: &[&str]) -> Result<(), JsValue> {
+        self.renderer.begin_group()?;
+        let mut succeeded = 0;
+        for step in steps {
+            let shareable = commands::is_shareable(step);
+            self.renderer
+                .append_command(prompt_label, step, shareable, ScrollBehavior::None)?;
+            match self.execute_classic(step) {
+                Ok(true) => succeeded += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    utils::log(&format!("Chained command `{step}` failed: {:?}", err));
+                }
+            }
+        }
+        self.renderer.end_group(GroupSummary {
+            total: steps.len(),
+            succeeded,
+        })
+    }
+
+    // Dispatches one classic-mode command line; `Ok(false)` means it ran but
+    // reported an error (unknown command or bad arguments).
+    fn execute_classic(&self, line: &str) -> Result<bool, JsValue> {
+        let args: Vec<&str> = line.split_whitespace().collect();
         let command = args.first().cloned().unwrap_or_default();
         let extra = if args.is_empty() { &[][..] } else { &args[1..] };
 
+        let output_scroll = ScrollBehavior::Bottom;
+
         let action = {
             let state = self.state.borrow();
             commands::execute(command, &state, extra)
         };
 
-        let output_scroll = ScrollBehavior::Bottom;
-
+        let succeeded = action.is_ok();
         match action {
             Ok(CommandAction::Output(text)) => {
                 self.renderer.append_output_text(&text, output_scroll)?;
+                if READING_TIME_COMMANDS
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(command))
+                {
+                    self.renderer.append_reading_time(&text, output_scroll)?;
+                }
+                self.offer_summary(command, &text);
             }
             Ok(CommandAction::OutputHtml(html)) => {
                 self.renderer.append_output_html(&html, output_scroll)?;
@@ -302,50 +595,94 @@ impl Terminal {
             Ok(CommandAction::CookieClicker) => {
                 self.start_cookie_clicker(output_scroll)?;
             }
-            Ok(CommandAction::Clear) => {
-                self.renderer.clear_output();
+            Ok(CommandAction::Banner) => {
+                self.replay_banner();
+            }
+            Ok(CommandAction::Demo) => {
+                self.start_demo()?;
+            }
+            Ok(CommandAction::Update) => {
+                self.reload_latest_version()?;
+            }
+            Ok(CommandAction::Clear(scope)) => {
+                self.clear_scope(scope, output_scroll)?;
+            }
+            Ok(CommandAction::SetOutputWidth(width)) => {
+                self.apply_output_width(width);
+                write_output_width(width);
+                let message = format!("📏 Output width set to {}.", width.label());
+                self.renderer.append_info_line(&message, output_scroll)?;
+            }
+            Ok(CommandAction::SetLocale(choice)) => {
+                write_locale_preference(choice);
+                let locale = choice
+                    .or_else(|| read_locale_preference().ok().flatten())
+                    .unwrap_or_default();
+                self.apply_locale(locale);
+                let message = format!("🌐 Language set to {}.", self.state.borrow().locale.tag());
+                self.renderer.append_info_line(&message, output_scroll)?;
+            }
+            Ok(CommandAction::SetDataSaver(setting)) => {
+                let enabled = self.apply_data_saver(setting);
+                write_data_saver(setting);
+                if !enabled {
+                    if let Err(err) = keyword_icons::preload_all_icons(false) {
+                        utils::log(&format!("Failed to preload keyword icons: {:?}", err));
+                    }
+                }
+                let message = format!(
+                    "📶 Data saver: {}.",
+                    commands::describe_data_saver(setting, enabled)
+                );
+                self.renderer.append_info_line(&message, output_scroll)?;
+            }
+            Ok(CommandAction::SetReadingTime(min_words)) => {
+                self.apply_reading_time(min_words);
+                write_reading_time(min_words);
+                let message = format!(
+                    "⏱️ Reading time: {}.",
+                    commands::describe_reading_time(min_words)
+                );
+                self.renderer.append_info_line(&message, output_scroll)?;
+            }
+            Ok(CommandAction::AiEstimate(question)) => {
+                self.queue_ai_estimate(question)?;
+            }
+            #[cfg(debug_assertions)]
+            Ok(CommandAction::Debug(action)) => {
+                crate::debug_tools::dispatch(self, action)?;
             }
             Ok(CommandAction::Download(url)) => {
-                utils::open_link(&url);
-                let confirmation = format!("Opening résumé at {url}");
-                self.renderer
-                    .append_info_line(&confirmation, output_scroll)?;
+                if self.state.borrow().ai_request_pending {
+                    let pending = PendingConfirmation::new(
+                        AI_PENDING_LINK_PROMPT,
+                        ConfirmAction::OpenLink(url),
+                    );
+                    self.renderer
+                        .append_info_line(&pending.prompt, output_scroll)?;
+                    self.state.borrow_mut().pending_confirmation = Some(pending);
+                } else {
+                    self.open_download(&url)?;
+                }
             }
             Err(CommandError::NotFound { command }) => {
-                self.handle_unknown_command(&command)?;
+                self.handle_unknown_command(&command, line)?;
             }
             Err(CommandError::Message(message)) => {
                 self.renderer.append_output_text(&message, output_scroll)?;
             }
         }
 
-        Ok(())
+        Ok(succeeded)
     }
 
     pub fn process_konami_key(&self, key: &str) -> Result<bool, JsValue> {
-        let Some(normalized) = Self::normalize_konami_key(key) else {
-            self.reset_konami_progress();
-            return Ok(false);
-        };
-
-        let triggered = {
-            let mut state = self.state.borrow_mut();
-            if state.konami_triggered {
-                false
-            } else if KONAMI_CODE[state.konami_index] == normalized {
-                state.konami_index += 1;
-                if state.konami_index == KONAMI_CODE.len() {
-                    state.konami_index = 0;
-                    state.konami_triggered = true;
-                    true
-                } else {
-                    false
-                }
-            } else {
-                state.konami_index = if normalized == KONAMI_CODE[0] { 1 } else { 0 };
-                false
-            }
-        };
+        // The sequence may only start from an empty prompt, so settle any
+        // batched keystrokes before looking at the buffer.
+        if key == KONAMI_CODE[0] {
+            self.flush_typing();
+        }
+        let triggered = Self::advance_konami(&mut self.state.borrow_mut(), key);
 
         if triggered {
             let celebrate = {
@@ -355,8 +692,8 @@ impl Terminal {
             self.start_kamehameha_sequence()?;
             if celebrate {
                 self.trigger_achievement_popup(
-                    ACHIEVEMENT_KONAMI_TITLE,
-                    ACHIEVEMENT_KONAMI_DESCRIPTION,
+                    Message::AchievementKonamiTitle,
+                    Message::AchievementKonamiDescription,
                     AchievementTier::Standard,
                 )?;
                 self.finalize_achievement_unlock()?;
@@ -379,12 +716,51 @@ impl Terminal {
         self.update_ai_mode(true, true)
     }
 
-    fn handle_unknown_command(&self, command: &str) -> Result<(), JsValue> {
-        let message =
-            format!("Command not found: `{command}`\nType `help` to list available commands.");
+    fn locale(&self) -> Locale {
+        self.state.borrow().locale
+    }
+
+    fn t(&self, key: Message) -> &'static str {
+        i18n::t(key, self.locale())
+    }
+
+    fn handle_unknown_command(&self, command: &str, line: &str) -> Result<(), JsValue> {
+        let matches = {
+            let state = self.state.borrow();
+            // The typo itself was just remembered; skip it.
+            let history = state
+                .command_history
+                .split_last()
+                .map(|(_, earlier)| earlier)
+                .unwrap_or_default();
+            commands::fuzzy_matches(line, history)
+        };
+        if let Some(correction) = commands::auto_correction(line, &matches) {
+            return self.execute_suggestion(correction);
+        }
+
+        let message = i18n::t_with(
+            Message::CommandNotFound,
+            self.locale(),
+            &[("command", command)],
+        );
         let info_scroll = ScrollBehavior::Bottom;
         self.renderer
             .append_output_text(&message, info_scroll.clone())?;
+        if !matches.is_empty() {
+            let chips = matches
+                .iter()
+                .map(|found| {
+                    let escaped = utils::escape_html(&found.command);
+                    format!(
+                        r#"<button type="button" class="command-chip" data-command="{escaped}">{escaped}</button>"#
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.renderer
+                .append_info_html(&format!("Did you mean {chips}?"), info_scroll)?;
+        }
         let html = r#"Need a hand? <button type="button" class="ai-mode-cta" data-action="activate-ai-mode">Ask the AI assistant</button>"#;
         self.renderer.append_info_html(html, info_scroll)?;
         Ok(())
@@ -394,12 +770,7 @@ impl Terminal {
         if self.input_disabled() {
             return;
         }
-        {
-            let mut state = self.state.borrow_mut();
-            state.input_buffer.clear();
-            state.history_index = None;
-        }
-        self.refresh_input();
+        self.set_buffer("", true);
         self.refresh_suggestions();
     }
 
@@ -408,61 +779,59 @@ impl Terminal {
     }
 
     pub fn append_text(&self, value: &str) {
-        if self.input_disabled() || value.is_empty() {
+        if !self.accepts_typing() || value.is_empty() {
             return;
         }
-        {
-            let mut state = self.state.borrow_mut();
-            state.input_buffer.push_str(value);
-            state.history_index = None;
-        }
-        self.refresh_input();
-        self.refresh_suggestions();
+        queue_typed_text(
+            &self.state,
+            &self.typing,
+            &self.suggestion_refresh,
+            value,
+            self.suggestion_refresher(),
+        );
+        self.renderer.update_input(&self.displayed_buffer());
+        self.debug_assert_input_synced();
     }
 
     pub fn delete_last_character(&self) {
-        if self.input_disabled() {
+        if !self.accepts_typing() {
             return;
         }
-        {
-            let mut state = self.state.borrow_mut();
-            state.input_buffer.pop();
-            state.history_index = None;
-        }
-        self.refresh_input();
-        self.refresh_suggestions();
+        self.flush_typing();
+        let mut buffer = self.state.borrow().input_buffer.clone();
+        buffer.pop();
+        self.set_buffer(&buffer, true);
+        self.schedule_suggestion_refresh();
     }
 
     pub fn navigate_history(&self, direction: HistoryDirection) {
-        if self.input_disabled() {
+        if !self.accepts_typing() {
             return;
         }
+        self.flush_typing();
         let new_buffer = {
             let mut state = self.state.borrow_mut();
             select_history_entry(&mut state, direction)
         };
 
         if let Some(buffer) = new_buffer {
-            self.renderer.update_input(&buffer);
+            self.set_buffer(&buffer, false);
             self.refresh_suggestions();
         }
     }
 
     pub fn autocomplete(&self) {
-        if self.input_disabled() {
+        if !self.accepts_typing() {
             return;
         }
+        self.flush_typing();
         let suggestion = {
             let state = self.state.borrow();
             commands::autocomplete(&state.input_buffer).map(|value| value.to_string())
         };
 
         if let Some(text) = suggestion {
-            {
-                let mut state = self.state.borrow_mut();
-                state.input_buffer = text;
-            }
-            self.refresh_input();
+            self.set_buffer(&text, false);
             self.refresh_suggestions();
         }
     }
@@ -471,78 +840,96 @@ impl Terminal {
         if self.input_disabled() {
             return Ok(());
         }
-        {
-            let mut state = self.state.borrow_mut();
-            state.input_buffer = command.to_string();
-            state.history_index = None;
-        }
-        self.refresh_input();
-        self.submit_command()
+        self.set_buffer(command, true);
+        let result = self.run_input_buffer();
+        // The chip that triggered the command is usually re-rendered away.
+        self.settle_focus();
+        result
     }
 
-    pub fn on_data_ready(&self) -> Result<(), JsValue> {
-        let profile_name = {
-            let state = self.state.borrow();
-            let name = state.data.as_ref().map(|data| data.profile.name.clone());
-            name
-        };
+    pub fn on_data_ready(self: &Rc<Self>) -> Result<(), JsValue> {
+        let deep_link =
+            utils::deep_link_command().filter(|command| commands::is_shareable(command));
+
+        self.renderer.set_suggestions_enabled(true);
+        self.refresh_suggestions();
 
         let renderer = Rc::clone(&self.renderer);
+        let state = Rc::clone(&self.state);
+        let terminal = Rc::clone(self);
         spawn_local(async move {
-            if let Err(err) = renderer
-                .type_output_text(BOOT_SEQUENCE_MESSAGE, WELCOME_TYPE_DELAY_MS)
-                .await
-            {
-                utils::log(&format!("Failed to animate welcome message: {:?}", err));
-                if let Err(err) =
-                    renderer.append_output_text(BOOT_SEQUENCE_MESSAGE, ScrollBehavior::Bottom)
-                {
-                    utils::log(&format!(
-                        "Failed to render welcome message fallback: {:?}",
-                        err
-                    ));
-                }
-            }
+            render_welcome(&renderer, &state).await;
 
-            if let Some(name) = profile_name {
-                let profile_line = profile_loaded_line(&name);
-                if let Err(err) = renderer.append_output_text(&profile_line, ScrollBehavior::Bottom)
-                {
+            if let Some(command) = deep_link {
+                if let Err(err) = terminal.execute_suggestion(&command) {
                     utils::log(&format!(
-                        "Failed to append profile line `{profile_line}`: {:?}",
+                        "Failed to run deep-linked command `{command}`: {:?}",
                         err
                     ));
                 }
             }
+        });
 
-            for guidance in WELCOME_GUIDANCE_LINES {
-                if let Err(err) = renderer.append_info_line(guidance, ScrollBehavior::Bottom) {
-                    utils::log(&format!(
-                        "Failed to append guidance line `{guidance}`: {:?}",
-                        err
-                    ));
-                }
-            }
+        Ok(())
+    }
 
-            let ai_cta_html = r#"Prefer to talk with an AI? <button type="button" class="ai-mode-cta" data-action="activate-ai-mode">Ask the AI assistant</button>"#;
-            if let Err(err) = renderer.append_info_html(ai_cta_html, ScrollBehavior::Bottom) {
-                utils::log(&format!(
-                    "Failed to append AI assistant call-to-action: {:?}",
-                    err
-                ));
-            }
+    pub fn replay_banner(&self) {
+        let renderer = Rc::clone(&self.renderer);
+        let state = Rc::clone(&self.state);
+        spawn_local(async move {
+            render_welcome(&renderer, &state).await;
         });
+    }
+
+    pub fn show_update_banner(&self) -> Result<(), JsValue> {
+        if self.renderer.has_update_banner() {
+            return Ok(());
+        }
+        let locale = self.locale();
+        let message = i18n::t_with(
+            Message::UpdateAvailable,
+            locale,
+            &[
+                ("key", "<kbd>u</kbd>"),
+                ("command", UPDATE_COMMAND_CHIP_HTML),
+            ],
+        );
+        let dismiss = utils::escape_html(i18n::t(Message::UpdateDismissLabel, locale));
+        self.renderer.append_update_banner(&format!(
+            r#"{message} <button type="button" class="update-banner__dismiss" aria-label="{dismiss}" title="{dismiss}">×</button>"#
+        ))
+    }
 
+    pub fn dismiss_update_banner(&self) -> Result<(), JsValue> {
+        self.renderer.remove_update_banner()?;
+        self.settle_focus();
         Ok(())
     }
 
+    // `u` only reloads while the banner is on screen and nothing is being typed,
+    // so it never swallows the first letter of a command or an AI question.
+    pub fn update_shortcut_armed(&self) -> bool {
+        let state = self.state.borrow();
+        !state.ai_mode
+            && state.input_buffer.is_empty()
+            && !state.achievements_modal_open
+            && state.accepts_typing()
+            && self.renderer.has_update_banner()
+    }
+
+    pub fn reload_latest_version(&self) -> Result<(), JsValue> {
+        self.renderer.remove_update_banner()?;
+        self.push_system_message(self.t(Message::UpdateReloading));
+        utils::reload_bypassing_cache()
+    }
+
     fn trigger_shutdown_sequence(&self, delay_ms: u32) -> Result<(), JsValue> {
         if self.ensure_input_disabled() {
             return Ok(());
         }
 
         self.renderer.disable_prompt_input()?;
-        self.renderer.update_input("");
+        self.set_buffer("", true);
         self.renderer
             .render_suggestions(std::iter::empty::<(String, String)>());
 
@@ -573,14 +960,17 @@ impl Terminal {
         outcome: &PokemonAttemptOutcome,
         behavior: ScrollBehavior,
     ) -> Result<(), JsValue> {
-        {
+        let locale = {
             let mut state = self.state.borrow_mut();
             state.set_pokemon_capture_chance(outcome.next_chance);
-        }
+            state.locale
+        };
+        // Persisted right away so a reload mid-animation keeps the new odds.
+        self.persist_achievements_state();
 
         let chance_message = format!(
-            "You have a {chance}% chance of catching Pikachu!",
-            chance = outcome.current_chance
+            "You have a {chance} chance of catching Pikachu!",
+            chance = format::format_percent(outcome.current_chance.into(), locale)
         );
         self.renderer
             .append_output_text(&chance_message, behavior)?;
@@ -597,8 +987,8 @@ impl Terminal {
             };
             if celebrate {
                 self.trigger_achievement_popup(
-                    ACHIEVEMENT_POKEMON_TITLE,
-                    ACHIEVEMENT_POKEMON_DESCRIPTION,
+                    Message::AchievementPokemonTitle,
+                    Message::AchievementPokemonDescription,
                     AchievementTier::Standard,
                 )?;
                 self.finalize_achievement_unlock()?;
@@ -616,8 +1006,10 @@ impl Terminal {
             let next = state.pokemon_capture_chance();
             drop(state);
             if next > outcome.current_chance {
-                let encouragement =
-                    format!("Your next capture chance rises to {next}%.", next = next);
+                let encouragement = format!(
+                    "Your next capture chance rises to {next}.",
+                    next = format::format_percent(next.into(), locale)
+                );
                 self.renderer
                     .append_info_line(&encouragement, ScrollBehavior::Bottom)?;
             }
@@ -643,6 +1035,7 @@ impl Terminal {
         let renderer = Rc::clone(&self.renderer);
 
         let counter_cell = Rc::new(Cell::new(0u32));
+        let started_at = Rc::new(Cell::new(None::<f64>));
         let finished = Rc::new(Cell::new(false));
 
         let counter_el = view.counter.clone();
@@ -655,26 +1048,60 @@ impl Terminal {
             let state = Rc::clone(&state);
             let renderer = Rc::clone(&renderer);
             let counter_cell = Rc::clone(&counter_cell);
+            let started_at = Rc::clone(&started_at);
             let finished = Rc::clone(&finished);
             Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
                 event.prevent_default();
                 if finished.get() {
                     return;
                 }
-                let next = counter_cell.get().saturating_add(1).min(100);
+                let next = counter_cell
+                    .get()
+                    .saturating_add(1)
+                    .min(COOKIE_CLICKER_GOAL);
                 counter_cell.set(next);
+                let improved = state.borrow_mut().record_cookie_count(next);
+                if improved && next < COOKIE_CLICKER_GOAL {
+                    Terminal::persist_achievements_snapshot_shared(&state);
+                }
 
-                let label = format!("{next} / 100");
+                let (locale, now) = {
+                    let state_ref = state.borrow();
+                    (state_ref.locale, state_ref.now_ms())
+                };
+                if started_at.get().is_none() {
+                    started_at.set(Some(now));
+                    state.borrow_mut().cookie_games_in_progress += 1;
+                    crate::sync_unload_guard(&state.borrow());
+                }
+                let label = Terminal::cookie_counter_label(next, locale);
                 counter_el.set_text_content(Some(&label));
                 Terminal::apply_cookie_counter_tier(&counter_el, next);
                 Terminal::apply_cookie_wrapper_state(&wrapper_el, next);
                 prompt_el.set_text_content(Some(Terminal::cookie_prompt_for(next)));
 
-                if next == 100 {
+                if next == COOKIE_CLICKER_GOAL {
                     finished.set(true);
+                    {
+                        let mut state_mut = state.borrow_mut();
+                        state_mut.cookie_games_in_progress =
+                            state_mut.cookie_games_in_progress.saturating_sub(1);
+                    }
+                    crate::sync_unload_guard(&state.borrow());
                     let _ = button_el.set_attribute("disabled", "true");
+                    let elapsed_secs =
+                        ((now - started_at.get().unwrap_or(now)) / 1000.0).max(0.0) as u64;
+                    let summary = format!(
+                        "🍪 Baked {} cookies in {}.",
+                        format::format_count(u64::from(COOKIE_CLICKER_GOAL), locale),
+                        format::format_duration(elapsed_secs, locale)
+                    );
+                    if let Err(err) = renderer.append_info_line(&summary, ScrollBehavior::Bottom) {
+                        utils::log(&format!("Failed to append cookie summary: {:?}", err));
+                    }
                     Terminal::celebrate_cookie_unlock(Rc::clone(&state), Rc::clone(&renderer));
                     Terminal::launch_cookie_rain_sequence(
+                        Rc::clone(&state),
                         Rc::clone(&renderer),
                         line_el.clone(),
                         Some(wrapper_el.clone()),
@@ -692,7 +1119,9 @@ impl Terminal {
 
     fn dismiss_pokemon_effect_after_delay(&self, element: &HtmlElement, delay_ms: u32) {
         let renderer = Rc::clone(&self.renderer);
+        let state = Rc::clone(&self.state);
         let element = element.clone();
+        state.borrow_mut().track_effect(element.clone());
         spawn_local(async move {
             TimeoutFuture::new(delay_ms).await;
 
@@ -712,18 +1141,19 @@ impl Terminal {
                     err
                 ));
             }
+            state.borrow_mut().release_effect(&element);
         });
     }
 
     fn trigger_achievement_popup(
         &self,
-        title: &str,
-        description: &str,
+        title: Message,
+        description: Message,
         tier: AchievementTier,
     ) -> Result<(), JsValue> {
-        let toast = self
-            .renderer
-            .render_achievement_toast(title, description, tier)?;
+        let toast =
+            self.renderer
+                .render_achievement_toast(self.t(title), self.t(description), tier)?;
         Self::schedule_toast_dismissal(Rc::clone(&self.renderer), toast, 5200);
         Ok(())
     }
@@ -741,8 +1171,8 @@ impl Terminal {
         };
         if celebrate {
             self.trigger_achievement_popup(
-                ACHIEVEMENT_PLATINUM_TITLE,
-                ACHIEVEMENT_PLATINUM_DESCRIPTION,
+                Message::AchievementPlatinumTitle,
+                Message::AchievementPlatinumDescription,
                 AchievementTier::Platinum,
             )?;
         }
@@ -758,9 +1188,10 @@ impl Terminal {
             return;
         }
 
+        let locale = state.borrow().locale;
         match renderer.render_achievement_toast(
-            ACHIEVEMENT_PLATINUM_TITLE,
-            ACHIEVEMENT_PLATINUM_DESCRIPTION,
+            i18n::t(Message::AchievementPlatinumTitle, locale),
+            i18n::t(Message::AchievementPlatinumDescription, locale),
             AchievementTier::Platinum,
         ) {
             Ok(toast) => {
@@ -798,6 +1229,25 @@ impl Terminal {
         });
     }
 
+    fn settle_focus(&self) {
+        Self::settle_focus_for_shared(&self.state, &self.renderer);
+    }
+
+    // Where keyboard focus goes once the UI settles: an open overlay keeps it,
+    // otherwise it returns to the hidden prompt input. Read-only embeds never
+    // grab focus from the host page.
+    fn settle_focus_for_shared(state: &SharedState, renderer: &SharedRenderer) {
+        let (modal_open, read_only) = {
+            let state = state.borrow();
+            (state.achievements_modal_open, state.read_only)
+        };
+        if modal_open {
+            renderer.focus_achievements_modal();
+        } else if !read_only {
+            renderer.focus_terminal();
+        }
+    }
+
     fn refresh_achievements_modal_if_visible(&self) -> Result<(), JsValue> {
         Self::refresh_achievements_modal_for_shared(&self.state, &self.renderer)
     }
@@ -825,60 +1275,84 @@ impl Terminal {
             let state_ref = state.borrow();
             Self::build_achievement_views(&state_ref)
         };
-        renderer.show_achievements_modal(&achievements, spoilers_enabled)
+        renderer.show_achievements_modal(&achievements, spoilers_enabled)?;
+        Self::settle_focus_for_shared(state, renderer);
+        Ok(())
     }
 
     fn build_achievement_views(state: &AppState) -> Vec<AchievementView> {
         let mut unlocked = Vec::new();
         let mut locked = Vec::new();
 
+        // Only the counter-driven eggs have progress to show, and only until
+        // they hatch.
+        let pokemon_progress = (
+            u32::from(state.pokemon_capture_chance()),
+            POKEMON_MAX_CAPTURE_CHANCE,
+        );
+        let cookie_progress = (state.cookie_best_count, COOKIE_CLICKER_GOAL);
         let entries = [
             (
                 state.achievement_shaw_unlocked,
-                ACHIEVEMENT_SHAW_TITLE,
-                ACHIEVEMENT_SHAW_DESCRIPTION,
-                ACHIEVEMENT_SHAW_HINT,
+                Message::AchievementShawTitle,
+                Message::AchievementShawDescription,
+                Message::AchievementShawHint,
                 AchievementTier::Standard,
+                None,
             ),
             (
                 state.achievement_pokemon_unlocked,
-                ACHIEVEMENT_POKEMON_TITLE,
-                ACHIEVEMENT_POKEMON_DESCRIPTION,
-                ACHIEVEMENT_POKEMON_HINT,
+                Message::AchievementPokemonTitle,
+                Message::AchievementPokemonDescription,
+                Message::AchievementPokemonHint,
                 AchievementTier::Standard,
+                Some(pokemon_progress),
             ),
             (
                 state.achievement_cookie_unlocked,
-                ACHIEVEMENT_COOKIE_TITLE,
-                ACHIEVEMENT_COOKIE_DESCRIPTION,
-                ACHIEVEMENT_COOKIE_HINT,
+                Message::AchievementCookieTitle,
+                Message::AchievementCookieDescription,
+                Message::AchievementCookieHint,
                 AchievementTier::Standard,
+                Some(cookie_progress),
             ),
             (
                 state.achievement_konami_unlocked,
-                ACHIEVEMENT_KONAMI_TITLE,
-                ACHIEVEMENT_KONAMI_DESCRIPTION,
-                ACHIEVEMENT_KONAMI_HINT,
+                Message::AchievementKonamiTitle,
+                Message::AchievementKonamiDescription,
+                Message::AchievementKonamiHint,
                 AchievementTier::Standard,
+                None,
             ),
             (
                 state.achievement_shutdown_unlocked,
-                ACHIEVEMENT_SHUTDOWN_TITLE,
-                ACHIEVEMENT_SHUTDOWN_DESCRIPTION,
-                ACHIEVEMENT_SHUTDOWN_HINT,
+                Message::AchievementShutdownTitle,
+                Message::AchievementShutdownDescription,
+                Message::AchievementShutdownHint,
                 AchievementTier::Standard,
+                None,
             ),
             (
                 state.achievement_platinum_unlocked,
-                ACHIEVEMENT_PLATINUM_TITLE,
-                ACHIEVEMENT_PLATINUM_DESCRIPTION,
-                ACHIEVEMENT_PLATINUM_HINT,
+                Message::AchievementPlatinumTitle,
+                Message::AchievementPlatinumDescription,
+                Message::AchievementPlatinumHint,
                 AchievementTier::Platinum,
+                None,
             ),
         ];
 
-        for (is_unlocked, title, description, hint, tier) in entries {
-            let view = AchievementView::new(title, description, hint, is_unlocked, tier);
+        for (is_unlocked, title, description, hint, tier, progress) in entries {
+            let mut view = AchievementView::new(
+                i18n::t(title, state.locale),
+                i18n::t(description, state.locale),
+                i18n::t(hint, state.locale),
+                is_unlocked,
+                tier,
+            );
+            if let Some((current, target)) = progress.filter(|_| !is_unlocked) {
+                view = view.with_progress(current, target);
+            }
             if is_unlocked {
                 unlocked.push(view);
             } else {
@@ -914,27 +1388,19 @@ impl Terminal {
     }
 
     fn try_restore_achievements_from_storage(&self) -> Result<(), JsValue> {
-        let Some(window) = utils::window() else {
+        let Some(raw) = storage::get(ACHIEVEMENTS_STORAGE_KEY) else {
             return Ok(());
         };
-        let storage = match window.local_storage()? {
-            Some(storage) => storage,
-            None => return Ok(()),
-        };
-        let raw = match storage.get_item(ACHIEVEMENTS_STORAGE_KEY)? {
-            Some(value) => value,
-            None => return Ok(()),
-        };
         let data: StoredAchievements = match serde_json::from_str(&raw) {
             Ok(data) => data,
             Err(err) => {
                 utils::log(&format!("Discarding corrupt achievements cache: {err}"));
-                let _ = storage.remove_item(ACHIEVEMENTS_STORAGE_KEY);
+                storage::remove(ACHIEVEMENTS_STORAGE_KEY);
                 return Ok(());
             }
         };
         if data.version != ACHIEVEMENTS_STORAGE_VERSION {
-            let _ = storage.remove_item(ACHIEVEMENTS_STORAGE_KEY);
+            storage::remove(ACHIEVEMENTS_STORAGE_KEY);
             return Ok(());
         }
         {
@@ -946,23 +1412,12 @@ impl Terminal {
             state.achievement_shutdown_unlocked = data.shutdown;
             state.achievement_platinum_unlocked =
                 data.platinum || state.all_base_achievements_unlocked();
-            state.achievements_spoilers_enabled = data.spoilers_enabled;
+            state.set_pokemon_capture_chance(data.pokemon_chance);
+            state.cookie_best_count = data.cookie_best.min(COOKIE_CLICKER_GOAL);
         }
         Ok(())
     }
 
-    fn clear_achievements_storage(&self) -> Result<(), JsValue> {
-        let Some(window) = utils::window() else {
-            return Ok(());
-        };
-        let storage = match window.local_storage()? {
-            Some(storage) => storage,
-            None => return Ok(()),
-        };
-        storage.remove_item(ACHIEVEMENTS_STORAGE_KEY)?;
-        Ok(())
-    }
-
     fn persist_achievements_snapshot_shared(state: &SharedState) {
         let payload = {
             let state_ref = state.borrow();
@@ -982,22 +1437,16 @@ impl Terminal {
             konami: state.achievement_konami_unlocked,
             shutdown: state.achievement_shutdown_unlocked,
             platinum: state.achievement_platinum_unlocked,
-            spoilers_enabled: state.achievements_spoilers_enabled,
+            pokemon_chance: state.pokemon_capture_chance(),
+            cookie_best: state.cookie_best_count,
         }
     }
 
     fn write_achievements_payload(payload: &StoredAchievements) -> Result<(), JsValue> {
-        let Some(window) = utils::window() else {
-            return Ok(());
-        };
-        let storage = match window.local_storage()? {
-            Some(storage) => storage,
-            None => return Ok(()),
-        };
         let serialized = serde_json::to_string(payload).map_err(|err| {
             JsValue::from_str(&format!("Failed to serialize achievements payload: {err}"))
         })?;
-        storage.set_item(ACHIEVEMENTS_STORAGE_KEY, &serialized)?;
+        storage::set(ACHIEVEMENTS_STORAGE_KEY, &serialized);
         Ok(())
     }
 
@@ -1010,9 +1459,10 @@ impl Terminal {
             return;
         }
 
+        let locale = state.borrow().locale;
         match renderer.render_achievement_toast(
-            ACHIEVEMENT_COOKIE_TITLE,
-            ACHIEVEMENT_COOKIE_DESCRIPTION,
+            i18n::t(Message::AchievementCookieTitle, locale),
+            i18n::t(Message::AchievementCookieDescription, locale),
             AchievementTier::Standard,
         ) {
             Ok(toast) => {
@@ -1038,6 +1488,7 @@ impl Terminal {
     }
 
     fn launch_cookie_rain_sequence(
+        state: SharedState,
         renderer: SharedRenderer,
         line: HtmlElement,
         wrapper: Option<HtmlElement>,
@@ -1049,7 +1500,10 @@ impl Terminal {
         }
 
         let rain = match renderer.render_cookie_rain(120) {
-            Ok(layer) => Some(layer),
+            Ok(layer) => {
+                state.borrow_mut().track_effect(layer.clone());
+                Some(layer)
+            }
             Err(err) => {
                 utils::log(&format!("Failed to render cookie rain: {:?}", err));
                 None
@@ -1091,6 +1545,7 @@ impl Terminal {
                 if let Err(err) = cleanup_renderer.remove_effect(&rain_layer) {
                     utils::log(&format!("Failed to remove cookie rain layer: {:?}", err));
                 }
+                state.borrow_mut().release_effect(&rain_layer);
             }
             if let Err(err) = cleanup_renderer.remove_effect(&cleanup_line) {
                 utils::log(&format!("Failed to remove cookie clicker line: {:?}", err));
@@ -1110,6 +1565,14 @@ impl Terminal {
         }
     }
 
+    fn cookie_counter_label(count: u32, locale: Locale) -> String {
+        format!(
+            "{} / {}",
+            format::format_count(count.into(), locale),
+            format::format_count(100, locale)
+        )
+    }
+
     fn apply_cookie_counter_tier(counter: &HtmlElement, count: u32) {
         let class_list = counter.class_list();
         for tier in 0..=5 {
@@ -1172,8 +1635,8 @@ impl Terminal {
 
         if celebrate {
             self.trigger_achievement_popup(
-                ACHIEVEMENT_SHAW_TITLE,
-                ACHIEVEMENT_SHAW_DESCRIPTION,
+                Message::AchievementShawTitle,
+                Message::AchievementShawDescription,
                 AchievementTier::Standard,
             )?;
             self.finalize_achievement_unlock()?;
@@ -1182,6 +1645,7 @@ impl Terminal {
         self.renderer.force_scroll_to_bottom();
 
         let renderer = Rc::clone(&self.renderer);
+        let state = Rc::clone(&self.state);
         spawn_local(async move {
             // Allow the terminal to settle at the bottom before showing the effect.
             TimeoutFuture::new(120).await;
@@ -1193,6 +1657,7 @@ impl Terminal {
                     return;
                 }
             };
+            state.borrow_mut().track_effect(effect.clone());
 
             renderer.force_scroll_to_bottom();
 
@@ -1210,6 +1675,7 @@ impl Terminal {
             if let Err(err) = renderer.remove_effect(&effect) {
                 utils::log(&format!("Failed to remove Shaw effect: {:?}", err));
             }
+            state.borrow_mut().release_effect(&effect);
         });
 
         Ok(())
@@ -1231,14 +1697,10 @@ impl Terminal {
         }
 
         self.renderer.disable_prompt_input()?;
-        self.renderer.update_input("");
+        self.set_buffer("", true);
         self.renderer
             .render_suggestions(std::iter::empty::<(String, String)>());
-        {
-            let mut state = self.state.borrow_mut();
-            state.prompt_label = KAMEHAMEHA_PROMPT_LABEL.to_string();
-            state.input_buffer.clear();
-        }
+        self.state.borrow_mut().prompt_label = KAMEHAMEHA_PROMPT_LABEL.to_string();
         self.renderer.set_prompt_label(KAMEHAMEHA_PROMPT_LABEL);
         self.renderer.play_konami_charge()?;
 
@@ -1262,9 +1724,12 @@ impl Terminal {
 
             renderer.force_scroll_to_bottom();
 
-            if let Err(err) =
-                renderer.append_output_html(KAMEHAMEHA_MEDIA_HTML, ScrollBehavior::Bottom)
-            {
+            let media = if renderer.data_saver() {
+                KAMEHAMEHA_PLACEHOLDER_HTML
+            } else {
+                KAMEHAMEHA_MEDIA_HTML
+            };
+            if let Err(err) = renderer.append_output_html(media, ScrollBehavior::Bottom) {
                 utils::log(&format!("Failed to render Goku media: {:?}", err));
             }
 
@@ -1313,33 +1778,273 @@ impl Terminal {
         self.state.borrow().input_disabled()
     }
 
-    fn is_shutdown_command(input: &str) -> bool {
-        let mut normalized = String::new();
-        for part in input.split_whitespace() {
-            if !normalized.is_empty() {
-                normalized.push(' ');
+    fn accepts_typing(&self) -> bool {
+        self.state.borrow().accepts_typing()
+    }
+
+    pub fn set_theme(&self, name: &str) -> Result<(), JsValue> {
+        if !THEMES.contains(&name) {
+            return Err(JsValue::from_str(&format!(
+                "Unknown theme `{name}` (available: {}).",
+                THEMES.join(", ")
+            )));
+        }
+        self.renderer.apply_theme(name)?;
+        self.state.borrow_mut().theme = name.to_string();
+        Ok(())
+    }
+
+    pub fn enable_read_only(&self) -> Result<(), JsValue> {
+        self.state.borrow_mut().read_only = true;
+        self.renderer.disable_prompt_input()?;
+        self.renderer.mark_read_only()
+    }
+
+    // Only a classic-mode command line that *is* `rm -rf …` powers the TV off;
+    // AI questions that merely mention it go to the model like any other.
+    fn is_shutdown_command(input: &str, ai_mode_active: bool) -> bool {
+        if ai_mode_active {
+            return false;
+        }
+        let mut tokens = input.split_whitespace();
+        TV_OFF_COMMAND.split_whitespace().all(|expected| {
+            tokens
+                .next()
+                .is_some_and(|token| token.eq_ignore_ascii_case(expected))
+        })
+    }
+
+    // Every programmatic buffer change goes through here so the state, the
+    // visible prompt and the hidden <input> cannot drift apart. Characters
+    // still waiting in the typing batch are dropped: the new value wins.
+    fn set_buffer(&self, value: &str, reset_history: bool) {
+        self.typing.pending.borrow_mut().clear();
+        {
+            let mut state = self.state.borrow_mut();
+            state.input_buffer = value.to_string();
+            if reset_history {
+                state.history_index = None;
             }
-            normalized.push_str(&part.to_ascii_lowercase());
         }
-        normalized.contains(TV_OFF_COMMAND)
+        self.renderer.update_input(value);
+        self.debug_assert_input_synced();
+    }
+
+    fn displayed_buffer(&self) -> String {
+        format!(
+            "{}{}",
+            self.state.borrow().input_buffer,
+            self.typing.pending.borrow()
+        )
     }
 
-    fn refresh_input(&self) {
-        let buffer = { self.state.borrow().input_buffer.clone() };
-        self.renderer.update_input(&buffer);
+    fn debug_assert_input_synced(&self) {
+        if cfg!(debug_assertions) {
+            let expected = self.displayed_buffer();
+            let (visible, hidden) = self.renderer.input_snapshot();
+            debug_assert_eq!(visible, expected, "Visible prompt drifted from the buffer");
+            debug_assert_eq!(hidden, expected, "Hidden input drifted from the buffer");
+        }
     }
 
     fn refresh_suggestions(&self) {
-        if self.input_disabled() {
-            self.renderer
-                .render_suggestions(std::iter::empty::<(String, String)>());
-            return;
+        self.suggestion_refresh.cancel();
+        refresh_suggestions_now(&self.state, &self.renderer);
+    }
+
+    fn suggestion_refresher(&self) -> Rc<dyn Fn()> {
+        let state = Rc::clone(&self.state);
+        let renderer = Rc::clone(&self.renderer);
+        Rc::new(move || refresh_suggestions_now(&state, &renderer))
+    }
+
+    fn schedule_suggestion_refresh(&self) {
+        let refresh = self.suggestion_refresher();
+        self.suggestion_refresh.call(move || refresh());
+    }
+
+    fn flush_typing(&self) {
+        if flush_typed_text(&self.state, &self.typing.pending) {
+            let buffer = self.state.borrow().input_buffer.clone();
+            self.set_buffer(&buffer, false);
         }
-        render_current_suggestions(&self.state, &self.renderer);
     }
 
     pub fn toggle_suggestions_expanded(&self) {
-        self.renderer.toggle_suggestions_expanded();
+        let expanded = self.state.borrow_mut().toggle_suggestions_expanded();
+        self.renderer.set_suggestions_expanded(expanded);
+        if let Err(err) = write_suggestions_expanded(expanded) {
+            utils::log(&format!(
+                "Failed to persist suggestions expansion preference: {:?}",
+                err
+            ));
+        }
+    }
+
+    pub fn restore_suggestions_preference_from_storage(&self) {
+        match read_suggestions_expanded() {
+            Ok(expanded) => {
+                self.state.borrow_mut().suggestions_expanded = expanded;
+            }
+            Err(err) => {
+                utils::log(&format!(
+                    "Failed to restore suggestions expansion preference: {:?}",
+                    err
+                ));
+            }
+        }
+    }
+
+    pub fn restore_output_width_preference(&self) {
+        if let Some(width) = read_output_width() {
+            self.apply_output_width(width);
+        }
+    }
+
+    pub fn restore_data_saver_preference(&self) {
+        self.apply_data_saver(read_data_saver().unwrap_or_default());
+    }
+
+    fn apply_data_saver(&self, setting: DataSaverSetting) -> bool {
+        let enabled = setting.resolve(utils::save_data_requested());
+        {
+            let mut state = self.state.borrow_mut();
+            state.data_saver_setting = setting;
+            state.data_saver = enabled;
+        }
+        self.renderer.set_data_saver(enabled);
+        enabled
+    }
+
+    pub fn restore_reading_time_preference(&self) {
+        let min_words =
+            read_reading_time().unwrap_or_else(|| self.state.borrow().reading_time_min_words);
+        self.apply_reading_time(min_words);
+    }
+
+    fn apply_reading_time(&self, min_words: Option<usize>) {
+        let locale = {
+            let mut state = self.state.borrow_mut();
+            state.reading_time_min_words = min_words;
+            state.locale
+        };
+        self.renderer.set_reading_time(min_words, locale);
+    }
+
+    fn apply_output_width(&self, width: OutputWidth) {
+        self.state.borrow_mut().output_width = width;
+        if let Err(err) = self.renderer.set_output_width(width) {
+            utils::log(&format!("Failed to apply output width: {:?}", err));
+        }
+    }
+
+    pub fn restore_locale_preference(&self) {
+        match read_locale_preference() {
+            Ok(Some(locale)) => self.apply_locale(locale),
+            Ok(None) => {}
+            Err(err) => {
+                utils::log(&format!("Failed to restore locale preference: {:?}", err));
+            }
+        }
+    }
+
+    fn apply_locale(&self, locale: Locale) {
+        self.state.borrow_mut().locale = locale;
+        self.renderer.set_locale(locale);
+    }
+
+    pub fn output_line_index(&self, target: &Element) -> Option<usize> {
+        self.renderer.output_line_index(target)
+    }
+
+    pub fn highlight_output_lines(&self, range: Option<(usize, usize)>) {
+        self.renderer.highlight_output_lines(range);
+    }
+
+    pub fn copy_output_lines(&self, start: usize, end: usize) -> Result<(), JsValue> {
+        let text = self.renderer.output_lines_text(start, end);
+        utils::copy_to_clipboard(&text)?;
+        let count = end - start + 1;
+        let locale = self.state.borrow().locale;
+        let message = format!(
+            "📋 Copied {} {} to the clipboard.",
+            format::format_count(count as u64, locale),
+            if count == 1 { "line" } else { "lines" }
+        );
+        self.renderer
+            .append_info_line(&message, ScrollBehavior::Bottom)
+    }
+
+    pub fn copy_share_link(&self, trigger: &Element) -> Result<bool, JsValue> {
+        let Some(command) = self.renderer.shared_command(trigger)? else {
+            return Ok(false);
+        };
+        let base = utils::page_base_url().unwrap_or_default();
+        let url = utils::share_url(&base, &command);
+        utils::copy_to_clipboard(&url)?;
+        self.renderer
+            .append_info_line(&format!("🔗 Link copied: {url}"), ScrollBehavior::Bottom)?;
+        Ok(true)
+    }
+
+    fn offer_summary(&self, command: &str, output: &str) {
+        if !self.state.borrow().ai_available {
+            return;
+        }
+        let Some(section) = ai::summarizable_section(command, output) else {
+            return;
+        };
+        if let Err(err) = self
+            .renderer
+            .append_summary_chip(section, ScrollBehavior::Bottom)
+        {
+            utils::log(&format!("Failed to render summary chip: {:?}", err));
+        }
+    }
+
+    pub fn summarize_from_chip(&self, trigger: &Element) -> Result<bool, JsValue> {
+        let Some(section) = self.renderer.begin_summary(trigger)? else {
+            return Ok(false);
+        };
+        let renderer = Rc::clone(&self.renderer);
+        let shared_state = Rc::clone(&self.state);
+        let chip = trigger.clone();
+        spawn_local(async move {
+            let rendered = match ai::summarize_section(&section).await {
+                Ok(payload) if payload.ai_enabled && payload.reason.is_none() => {
+                    shared_state.borrow_mut().set_ai_model(payload.model);
+                    renderer.replace_summary_chip(&chip, &payload.summary)
+                }
+                Ok(payload) => {
+                    if !payload.ai_enabled {
+                        shared_state.borrow_mut().ai_available = false;
+                    }
+                    renderer.remove_summary_chip(&chip).and_then(|_| {
+                        renderer.append_info_line(&payload.summary, ScrollBehavior::Bottom)
+                    })
+                }
+                Err(error) => {
+                    renderer.reset_summary_chip(&chip);
+                    let message = ai_error_message(&error, shared_state.borrow().locale);
+                    renderer.append_output_text(&message, ScrollBehavior::Bottom)
+                }
+            };
+            if let Err(err) = rendered {
+                utils::log(&format!("Failed to render AI summary: {:?}", err));
+            }
+        });
+        Ok(true)
+    }
+
+    pub fn scroll_to_section(&self, trigger: &Element) -> Result<bool, JsValue> {
+        let Some(anchor) = self.renderer.toc_anchor(trigger) else {
+            return Ok(false);
+        };
+        self.renderer.scroll_to_anchor(&anchor)
+    }
+
+    pub fn toggle_output_collapse(&self, toggle: &Element) -> Result<bool, JsValue> {
+        self.renderer.toggle_collapsed_block(toggle)
     }
 
     fn handle_ai_mode_submission(&self, input: String) -> Result<(), JsValue> {
@@ -1352,20 +2057,297 @@ impl Terminal {
         }
         if normalized == "quit" {
             telemetry::log_command_submission(&input, CommandLogMode::Ai);
+            self.state.borrow_mut().end_ai_session();
             return self.update_ai_mode(false, true);
         }
-        self.queue_ai_answer(input)
+        if normalized == AI_HISTORY_COMMAND {
+            telemetry::log_command_submission(&input, CommandLogMode::Ai);
+            let history = commands::render_ai_history(&self.state.borrow());
+            return self
+                .renderer
+                .append_output_text(&history, ScrollBehavior::Bottom);
+        }
+        if let Some(argument) = normalized
+            .strip_prefix(AI_MODEL_COMMAND)
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        {
+            telemetry::log_command_submission(&input, CommandLogMode::Ai);
+            return self.handle_ai_model_command(argument.trim());
+        }
+        let preferred_model = self.state.borrow().ai_preferred_model.clone();
+        self.queue_ai_answer(input, preferred_model)
+    }
+
+    // Asks the latest question again through the backend named on the
+    // control; the server applies its rate limits and budget as usual.
+    pub fn regenerate_from_control(&self, trigger: &Element) -> Result<bool, JsValue> {
+        let Some(model) = self.renderer.regenerate_model(trigger) else {
+            return Ok(false);
+        };
+        let question = {
+            let state = self.state.borrow();
+            if state.ai_request_pending || !state.ai_available {
+                return Ok(false);
+            }
+            match state.ai_history.last() {
+                Some(exchange) => exchange.question.clone(),
+                None => return Ok(false),
+            }
+        };
+        self.renderer.remove_regenerate_controls()?;
+        self.renderer.append_info_line(
+            &format!("🔁 Asking {model} instead…"),
+            ScrollBehavior::Bottom,
+        )?;
+        self.queue_ai_answer(question, Some(model))?;
+        Ok(true)
+    }
+
+    fn handle_ai_model_command(&self, argument: &str) -> Result<(), JsValue> {
+        if argument.is_empty() {
+            let current = self.state.borrow().ai_preferred_model.clone();
+            let message = match current {
+                Some(model) => format!(
+                    "🤖 Preferred AI backend: {model}. Other backends are used if it is unavailable.\nOptions: {}, {AI_MODEL_AUTO}.",
+                    AI_MODEL_CHOICES.join(", ")
+                ),
+                None => format!(
+                    "🤖 Preferred AI backend: {AI_MODEL_AUTO} (server default order).\nOptions: {}, {AI_MODEL_AUTO}.",
+                    AI_MODEL_CHOICES.join(", ")
+                ),
+            };
+            return self
+                .renderer
+                .append_output_text(&message, ScrollBehavior::Bottom);
+        }
+
+        let preferred = match parse_preferred_model(argument) {
+            Ok(preferred) => preferred,
+            Err(message) => {
+                return self
+                    .renderer
+                    .append_output_text(&message, ScrollBehavior::Bottom);
+            }
+        };
+
+        {
+            let mut state = self.state.borrow_mut();
+            state.set_ai_preferred_model(preferred.map(str::to_string));
+        }
+        if let Err(err) = write_preferred_model(preferred) {
+            utils::log(&format!("Failed to persist preferred AI model: {:?}", err));
+        }
+        self.renderer.set_ai_indicator_text(&ai_indicator_text(
+            &self.state.borrow(),
+            Message::AiStatusActive,
+        ));
+
+        let message = match preferred {
+            Some(model) => format!(
+                "🤖 Preferred AI backend set to {model}. If it is a paid backend, the server may still ask free ones first and will say so."
+            ),
+            None => "🤖 Preferred AI backend reset to the server default order.".to_string(),
+        };
+        self.renderer
+            .append_info_line(&message, ScrollBehavior::Bottom)
+    }
+
+    pub fn restore_ai_preference_from_storage(&self) {
+        match read_preferred_model() {
+            Ok(preferred) => {
+                self.state.borrow_mut().set_ai_preferred_model(preferred);
+            }
+            Err(err) => {
+                utils::log(&format!(
+                    "Failed to restore preferred AI model from storage: {:?}",
+                    err
+                ));
+            }
+        }
+    }
+
+    fn clear_scope(&self, scope: ClearScope, behavior: ScrollBehavior) -> Result<(), JsValue> {
+        let message = match scope {
+            ClearScope::Screen => {
+                self.renderer.clear_output();
+                self.abandon_cookie_games();
+                return self.offer_pin_clear();
+            }
+            ClearScope::Ai => {
+                let removed = self.renderer.remove_ai_answers()?;
+                format!("🧹 Removed {removed} AI answer(s).")
+            }
+            ClearScope::Effects => {
+                let removed = self.renderer.remove_effects()?;
+                self.state.borrow_mut().active_effect = None;
+                self.abandon_cookie_games();
+                format!("🧹 Removed {removed} effect(s).")
+            }
+            ClearScope::All => {
+                self.renderer.remove_effects()?;
+                self.renderer.clear_output();
+                let mut state = self.state.borrow_mut();
+                state.active_effect = None;
+                state.session_stats = SessionStats::default();
+                state.ai_history.clear();
+                state.discard_ai_session();
+                drop(state);
+                self.abandon_cookie_games();
+                return self.offer_pin_clear();
+            }
+        };
+        self.renderer.append_info_line(&message, behavior)
+    }
+
+    // Pins sit outside the scrolling output, so clearing the screen leaves
+    // them alone unless the visitor confirms.
+    fn offer_pin_clear(&self) -> Result<(), JsValue> {
+        let pinned = self.renderer.pinned_count();
+        let mut state = self.state.borrow_mut();
+        if pinned == 0 || state.pending_confirmation.is_some() {
+            return Ok(());
+        }
+        let noun = if pinned == 1 { "output" } else { "outputs" };
+        let pending = PendingConfirmation::new(
+            format!("📌 Also clear the {pinned} pinned {noun}? [y/N]"),
+            ConfirmAction::ClearPins,
+        );
+        let prompt = pending.prompt.clone();
+        state.pending_confirmation = Some(pending);
+        drop(state);
+        self.renderer
+            .append_info_line(&prompt, ScrollBehavior::Bottom)
+    }
+
+    pub fn toggle_command_group(&self, trigger: &Element) -> Result<bool, JsValue> {
+        self.renderer.toggle_group(trigger)
+    }
+
+    pub fn toggle_output_pin(&self, trigger: &Element) -> Result<bool, JsValue> {
+        let Some(outcome) = self.renderer.toggle_pin(trigger)? else {
+            return Ok(false);
+        };
+        match outcome {
+            PinOutcome::Pinned => self.renderer.announce("Output pinned."),
+            PinOutcome::Unpinned => self.renderer.announce("Output unpinned."),
+            PinOutcome::LimitReached => self.renderer.append_info_line(
+                &format!(
+                    "📌 Only {PINNED_OUTPUT_LIMIT} outputs can be pinned at once. Unpin one first."
+                ),
+                ScrollBehavior::Bottom,
+            )?,
+        }
+        Ok(true)
+    }
+
+    // Cleared cookie games can no longer be clicked, so their progress is
+    // already gone and no longer worth a leave-page warning.
+    fn abandon_cookie_games(&self) {
+        self.state.borrow_mut().cookie_games_in_progress = 0;
+        crate::sync_unload_guard(&self.state.borrow());
+    }
+
+    fn resolve_confirmation(
+        &self,
+        pending: PendingConfirmation,
+        reply: &str,
+    ) -> Result<(), JsValue> {
+        self.set_buffer("", false);
+        let prompt_label = self.state.borrow().prompt_label.clone();
+        self.refresh_suggestions();
+        self.renderer
+            .append_command(&prompt_label, reply, false, ScrollBehavior::Anchor)?;
+
+        let resuming = pending.action == ConfirmAction::ResumeAiSession;
+        let clearing_pins = pending.action == ConfirmAction::ClearPins;
+        match pending.resolve(reply) {
+            ConfirmOutcome::Accepted(ConfirmAction::OpenLink(url)) => self.open_download(&url),
+            ConfirmOutcome::Accepted(ConfirmAction::ResumeAiSession) => self.resume_ai_session(),
+            ConfirmOutcome::Accepted(ConfirmAction::ClearPins) => {
+                let cleared = self.renderer.clear_pins();
+                self.renderer.append_info_line(
+                    &format!("🧹 Cleared {cleared} pinned output(s)."),
+                    ScrollBehavior::Bottom,
+                )
+            }
+            ConfirmOutcome::Declined if clearing_pins => self
+                .renderer
+                .append_info_line(self.t(Message::PinsKept), ScrollBehavior::Bottom),
+            ConfirmOutcome::Declined if resuming => {
+                self.state.borrow_mut().discard_ai_session();
+                self.renderer
+                    .append_info_line(self.t(Message::AiResumeDeclined), ScrollBehavior::Bottom)
+            }
+            ConfirmOutcome::Declined => self
+                .renderer
+                .append_info_line(self.t(Message::ConfirmDeclined), ScrollBehavior::Bottom),
+        }
+    }
+
+    fn offer_ai_session_resume(&self) -> Result<(), JsValue> {
+        let mut state = self.state.borrow_mut();
+        if state.pending_confirmation.is_some() || state.resumable_ai_session().is_none() {
+            return Ok(());
+        }
+        let prompt = i18n::t(Message::AiResumePrompt, state.locale);
+        state.pending_confirmation = Some(PendingConfirmation::new(
+            prompt,
+            ConfirmAction::ResumeAiSession,
+        ));
+        drop(state);
+        self.renderer
+            .append_info_line(prompt, ScrollBehavior::Bottom)
+    }
+
+    // Earlier answers stay where they are on screen; only the state comes back.
+    fn resume_ai_session(&self) -> Result<(), JsValue> {
+        let Some(turns) = self.state.borrow_mut().restore_ai_session() else {
+            return self
+                .renderer
+                .append_info_line(self.t(Message::AiResumeDeclined), ScrollBehavior::Bottom);
+        };
+        let noun = if turns == 1 { "exchange" } else { "exchanges" };
+        self.renderer.append_info_line(
+            &format!("↩️ Previous conversation resumed ({turns} {noun})."),
+            ScrollBehavior::Bottom,
+        )
+    }
+
+    fn open_download(&self, url: &str) -> Result<(), JsValue> {
+        self.record_external_link(url);
+        utils::open_link(url);
+        let confirmation = format!("Opening résumé at {url}");
+        self.renderer
+            .append_info_line(&confirmation, ScrollBehavior::Bottom)
     }
 
-    fn queue_ai_answer(&self, question: String) -> Result<(), JsValue> {
+    pub fn record_external_link(&self, href: &str) {
+        self.state
+            .borrow_mut()
+            .session_stats
+            .record_external_link(href);
+    }
+
+    fn queue_ai_answer(
+        &self,
+        question: String,
+        preferred_model: Option<String>,
+    ) -> Result<(), JsValue> {
         let data_ready = { self.state.borrow().data.is_some() };
         if !data_ready {
             self.renderer
-                .append_info_line(AI_DATA_LOADING, ScrollBehavior::Bottom)?;
+                .append_info_line(self.t(Message::AiDataLoading), ScrollBehavior::Bottom)?;
             return Ok(());
         }
 
-        self.renderer.set_ai_indicator_text(AI_STATUS_BUSY);
+        let generation = {
+            let state = self.state.borrow();
+            self.renderer
+                .set_ai_indicator_text(&ai_indicator_text(&state, Message::AiStatusBusy));
+            state.ai_request_generation
+        };
+        self.state.borrow_mut().ai_request_pending = true;
+        crate::sync_unload_guard(&self.state.borrow());
         if let Err(err) = self.renderer.set_ai_busy(true) {
             utils::log(&format!("Failed to flag AI busy state: {:?}", err));
         }
@@ -1377,32 +2359,123 @@ impl Terminal {
         let shared_state = Rc::clone(&self.state);
 
         spawn_local(async move {
-            let result = ai::ask_ai(&question).await;
+            let result = ai::ask_ai(&question, preferred_model.as_deref()).await;
+            if !shared_state.borrow().is_current_ai_request(generation) {
+                utils::log("Discarding AI answer received after AI mode was toggled off");
+                return;
+            }
 
             match result {
                 Ok(payload) => {
                     if payload.ai_enabled {
                         {
                             let mut state = shared_state.borrow_mut();
-                            state.set_ai_model(payload.model.clone());
+                            // FAQ hits never reached a model, so the indicator
+                            // keeps naming the last real one.
+                            if !ai::answered_from_faq(&payload) {
+                                state.set_ai_model(payload.model.clone());
+                            }
+                            state.record_ai_exchange(AiExchange {
+                                question: question.clone(),
+                                answer: payload.answer.clone(),
+                                model: payload.model.clone(),
+                                backend: payload.backend.clone(),
+                            });
                         }
                         render_current_suggestions(&shared_state, &renderer);
-                        renderer.set_ai_indicator_text(AI_STATUS_ACTIVE);
-                        if let Err(err) =
-                            renderer.append_output_markdown(&payload.answer, ScrollBehavior::Bottom)
+                        renderer.set_ai_indicator_text(&ai_indicator_text(
+                            &shared_state.borrow(),
+                            Message::AiStatusActive,
+                        ));
+                        match renderer.append_ai_answer(&payload.answer, ScrollBehavior::Bottom) {
+                            Ok(block) => {
+                                if let Err(err) =
+                                    renderer.collapse_if_long(&block, AI_ANSWER_MAX_LINES)
+                                {
+                                    utils::log(&format!(
+                                        "Failed to collapse long AI answer: {:?}",
+                                        err
+                                    ));
+                                }
+                            }
+                            Err(err) => {
+                                utils::log(&format!("Failed to render AI answer: {:?}", err));
+                            }
+                        }
+                        if let Some(notice) = ai::truncation_notice(&payload) {
+                            if let Err(err) =
+                                renderer.append_info_line(notice, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!(
+                                    "Failed to render AI truncation notice: {:?}",
+                                    err
+                                ));
+                            }
+                        }
+                        if let Some(notice) =
+                            ai::preference_notice(&payload, preferred_model.as_deref())
                         {
-                            utils::log(&format!("Failed to render AI answer: {:?}", err));
+                            if let Err(err) =
+                                renderer.append_info_line(&notice, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!(
+                                    "Failed to render AI preference notice: {:?}",
+                                    err
+                                ));
+                            }
+                        }
+                        if let Some(badge) = ai::faq_badge(&payload) {
+                            if let Err(err) =
+                                renderer.append_info_line(badge, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!("Failed to render AI FAQ badge: {:?}", err));
+                            }
+                        }
+                        if let Some(badge) = ai::topic_badge(&payload) {
+                            if let Err(err) =
+                                renderer.append_info_line(&badge, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!("Failed to render AI topic badge: {:?}", err));
+                            }
+                        }
+                        if let Some(summary) = ai::failure_summary(&payload.failures) {
+                            if let Err(err) =
+                                renderer.append_info_line(&summary, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!(
+                                    "Failed to render AI failure summary: {:?}",
+                                    err
+                                ));
+                            }
+                        }
+                        if let Some(alternate) = alternate_model(
+                            payload.backend.as_deref(),
+                            preferred_model.as_deref(),
+                            &payload.backend_order,
+                        ) {
+                            if let Err(err) = renderer
+                                .append_regenerate_control(alternate, ScrollBehavior::Bottom)
+                            {
+                                utils::log(&format!(
+                                    "Failed to render regenerate control: {:?}",
+                                    err
+                                ));
+                            }
                         }
                     } else {
                         {
                             let mut state = shared_state.borrow_mut();
                             state.set_ai_model(payload.model.clone());
                             state.set_ai_mode(false);
+                            state.ai_available = false;
                         }
                         if let Err(err) = renderer.apply_ai_mode(false) {
                             utils::log(&format!("Failed to revert AI mode visuals: {:?}", err));
                         }
-                        renderer.set_ai_indicator_text(AI_STATUS_DEACTIVATED);
+                        renderer.set_ai_indicator_text(&ai_indicator_text(
+                            &shared_state.borrow(),
+                            Message::AiStatusDeactivated,
+                        ));
                         render_current_suggestions(&shared_state, &renderer);
                         let mut notice = payload.answer.clone();
                         if let Some(reason) = payload.reason.as_ref() {
@@ -1415,7 +2488,7 @@ impl Terminal {
                     }
                 }
                 Err(error) => {
-                    let message = format!("AI error: {error}");
+                    let message = ai_error_message(&error, shared_state.borrow().locale);
                     if let Err(err) = renderer.append_output_text(&message, ScrollBehavior::Bottom)
                     {
                         utils::log(&format!("Failed to render AI error: {:?}", err));
@@ -1423,6 +2496,8 @@ impl Terminal {
                 }
             }
 
+            shared_state.borrow_mut().ai_request_pending = false;
+            crate::sync_unload_guard(&shared_state.borrow());
             if let Err(err) = renderer.set_ai_busy(false) {
                 utils::log(&format!("Failed to reset AI busy state: {:?}", err));
             }
@@ -1430,41 +2505,88 @@ impl Terminal {
                 utils::log(&format!("Failed to remove AI loader: {:?}", err));
             }
 
-            let status = if shared_state.borrow().ai_mode {
-                AI_STATUS_ACTIVE
-            } else {
-                AI_STATUS_DEACTIVATED
+            let status = {
+                let state = shared_state.borrow();
+                let status = if state.ai_mode {
+                    Message::AiStatusActive
+                } else {
+                    Message::AiStatusDeactivated
+                };
+                ai_indicator_text(&state, status)
             };
-            renderer.set_ai_indicator_text(status);
+            renderer.set_ai_indicator_text(&status);
         });
 
         Ok(())
     }
 
-    fn update_ai_mode(&self, active: bool, announce: bool) -> Result<(), JsValue> {
-        let previous = {
-            let mut state = self.state.borrow_mut();
-            let prev = state.ai_mode;
-            state.set_ai_mode(active);
-            prev
+    fn queue_ai_estimate(&self, question: String) -> Result<(), JsValue> {
+        let (preferred_model, locale) = {
+            let state = self.state.borrow();
+            (state.ai_preferred_model.clone(), state.locale)
+        };
+        let renderer = Rc::clone(&self.renderer);
+        spawn_local(async move {
+            let message = match ai::estimate_ai(&question, preferred_model.as_deref()).await {
+                Ok(payload) => {
+                    let notice = ai::preference_notice(&payload, preferred_model.as_deref());
+                    let message = match payload.estimate.as_ref() {
+                        Some(estimate) => {
+                            ai_estimate_message(estimate, payload.model.as_deref(), locale)
+                        }
+                        None => payload.answer,
+                    };
+                    match notice {
+                        Some(notice) => format!("{message}\n{notice}"),
+                        None => message,
+                    }
+                }
+                Err(error) => ai_error_message(&error, locale),
+            };
+            if let Err(err) = renderer.append_output_text(&message, ScrollBehavior::Bottom) {
+                utils::log(&format!("Failed to render AI estimate: {:?}", err));
+            }
+        });
+        Ok(())
+    }
+
+    fn update_ai_mode(&self, active: bool, announce: bool) -> Result<(), JsValue> {
+        // Scripted sequences only run classic commands.
+        if active {
+            self.abort_sequence();
+        }
+        let previous = {
+            let mut state = self.state.borrow_mut();
+            let prev = state.ai_mode;
+            state.set_ai_mode(active);
+            prev
         };
 
-        self.renderer.apply_ai_mode(active)?;
-        self.renderer.set_ai_indicator_text(if active {
-            AI_STATUS_ACTIVE
-        } else {
-            AI_STATUS_DEACTIVATED
-        });
-        if let Err(err) = self.renderer.set_ai_busy(false) {
-            utils::log(&format!("Failed to reset AI busy flag: {:?}", err));
-        }
-
+        self.renderer.apply_ai_mode(active)?;
+        let status = if active {
+            Message::AiStatusActive
+        } else {
+            Message::AiStatusDeactivated
+        };
+        self.renderer
+            .set_ai_indicator_text(&ai_indicator_text(&self.state.borrow(), status));
+        if let Err(err) = self.renderer.set_ai_busy(false) {
+            utils::log(&format!("Failed to reset AI busy flag: {:?}", err));
+        }
+        if previous && !active {
+            self.state.borrow_mut().cancel_pending_ai_requests();
+            crate::sync_unload_guard(&self.state.borrow());
+            if let Err(err) = self.renderer.hide_ai_loader() {
+                utils::log(&format!("Failed to remove AI loader: {:?}", err));
+            }
+        }
+
         if announce && previous != active {
-            let message = if active {
-                AI_ACTIVATED_INFO
+            let message = self.t(if active {
+                Message::AiActivatedInfo
             } else {
-                AI_DEACTIVATED_INFO
-            };
+                Message::AiDeactivatedInfo
+            });
             self.renderer
                 .append_info_line(message, ScrollBehavior::Bottom)?;
         }
@@ -1472,6 +2594,9 @@ impl Terminal {
         if previous != active {
             self.refresh_suggestions();
         }
+        if active && !previous {
+            self.offer_ai_session_resume()?;
+        }
 
         Ok(())
     }
@@ -1480,8 +2605,37 @@ impl Terminal {
         self.state.borrow().ai_mode
     }
 
-    fn reset_konami_progress(&self) {
-        let mut state = self.state.borrow_mut();
+    fn advance_konami(state: &mut AppState, key: &str) -> bool {
+        // Arrow keys browse history all the time in AI mode; never let them
+        // blow up an ongoing conversation.
+        if state.ai_mode || state.ai_request_pending {
+            Self::reset_konami_progress(state);
+            return false;
+        }
+        let Some(normalized) = Self::normalize_konami_key(key) else {
+            Self::reset_konami_progress(state);
+            return false;
+        };
+        if state.konami_triggered {
+            return false;
+        }
+
+        let can_start = normalized == KONAMI_CODE[0] && state.input_buffer.is_empty();
+        let continues = state.konami_index > 0 || can_start;
+        if continues && KONAMI_CODE[state.konami_index] == normalized {
+            state.konami_index += 1;
+            if state.konami_index == KONAMI_CODE.len() {
+                state.konami_index = 0;
+                state.konami_triggered = true;
+                return true;
+            }
+        } else {
+            state.konami_index = if can_start { 1 } else { 0 };
+        }
+        false
+    }
+
+    fn reset_konami_progress(state: &mut AppState) {
         if !state.konami_triggered {
             state.konami_index = 0;
         }
@@ -1500,6 +2654,78 @@ impl Terminal {
     }
 }
 
+#[cfg(debug_assertions)]
+impl crate::debug_tools::DebugEffects for Terminal {
+    fn debug_toast(&self) -> Result<(), JsValue> {
+        let toast = self.renderer.render_achievement_toast(
+            "Debug toast",
+            "Rendered by the debug command.",
+            AchievementTier::Standard,
+        )?;
+        Self::schedule_toast_dismissal(Rc::clone(&self.renderer), toast, 5200);
+        Ok(())
+    }
+
+    fn debug_rain(&self, drops: usize) -> Result<(), JsValue> {
+        let rain = self.renderer.render_cookie_rain(drops)?;
+        schedule_effect_removal(Rc::clone(&self.renderer), rain, 5000);
+        Ok(())
+    }
+
+    fn debug_shaw(&self) -> Result<(), JsValue> {
+        let effect = self.renderer.render_shaw_effect()?;
+        schedule_effect_removal(Rc::clone(&self.renderer), effect, 3000);
+        Ok(())
+    }
+
+    fn debug_explode(&self) -> Result<(), JsValue> {
+        self.renderer.trigger_terminal_explosion()
+    }
+
+    fn debug_markdown(&self, source: &str) -> Result<(), JsValue> {
+        self.renderer
+            .append_output_markdown(source, ScrollBehavior::Bottom)
+            .map(|_| ())
+    }
+
+    fn debug_fill(&self, lines: usize) -> Result<(), JsValue> {
+        for line in 1..=lines {
+            self.renderer
+                .append_output_text(&format!("debug line {line}/{lines}"), ScrollBehavior::None)?;
+        }
+        self.renderer.force_scroll_to_bottom();
+        Ok(())
+    }
+
+    fn debug_seed(&self, seed: u64) -> Result<(), JsValue> {
+        self.state.borrow_mut().random = Rc::new(crate::utils::random::SeededRandom::new(seed));
+        self.renderer.append_info_line(
+            &format!("Random rolls now follow seed {seed} until reload."),
+            ScrollBehavior::Bottom,
+        )
+    }
+}
+
+#[cfg(debug_assertions)]
+fn schedule_effect_removal(renderer: SharedRenderer, effect: HtmlElement, delay_ms: u32) {
+    spawn_local(async move {
+        TimeoutFuture::new(delay_ms).await;
+        if let Err(err) = renderer.remove_effect(&effect) {
+            utils::log(&format!("Failed to remove debug effect: {:?}", err));
+        }
+    });
+}
+
+fn dismiss_active_effect(state: &mut AppState) -> bool {
+    let Some(effect) = state.take_active_effect() else {
+        return false;
+    };
+    for element in effect.elements() {
+        element.remove();
+    }
+    true
+}
+
 fn select_history_entry(state: &mut AppState, direction: HistoryDirection) -> Option<String> {
     if state.command_history.is_empty() {
         return None;
@@ -1532,12 +2758,13 @@ fn select_history_entry(state: &mut AppState, direction: HistoryDirection) -> Op
     Some(buffer)
 }
 
-const HIDDEN_HELPER_COMMANDS: [&str; 3] = ["shaw", "pokemon", "cookie"];
+const HIDDEN_HELPER_COMMANDS: [&str; 6] = ["shaw", "pokemon", "cookie", "width", "lang", "sleep"];
 
 fn is_hidden_helper(command: &str) -> bool {
-    HIDDEN_HELPER_COMMANDS
-        .iter()
-        .any(|hidden| hidden.eq_ignore_ascii_case(command))
+    command.eq_ignore_ascii_case(commands::DEBUG_COMMAND)
+        || HIDDEN_HELPER_COMMANDS
+            .iter()
+            .any(|hidden| hidden.eq_ignore_ascii_case(command))
 }
 
 fn default_suggestions() -> Vec<&'static str> {
@@ -1557,16 +2784,181 @@ fn default_suggestions() -> Vec<&'static str> {
     names
 }
 
-fn ai_help_label(model: Option<&str>) -> String {
+fn ai_help_label(model: Option<&str>, locale: Locale) -> String {
     match model {
-        Some(name) if !name.trim().is_empty() => format!("AI help ({name})"),
-        _ => "AI help".to_string(),
+        Some(name) if !name.trim().is_empty() => {
+            i18n::t_with(Message::AiHelpLabelWithModel, locale, &[("model", name)])
+        }
+        _ => i18n::t(Message::AiHelpLabel, locale).to_string(),
+    }
+}
+
+fn ai_indicator_text(state: &AppState, status: Message) -> String {
+    let label = i18n::t(status, state.locale);
+    match state.ai_preferred_model.as_deref() {
+        Some(model) if status != Message::AiStatusDeactivated => format!("{label} · {model}"),
+        _ => label.to_string(),
+    }
+}
+
+// Picks the next backend after the one that answered, among those the server
+// says can answer first. Older servers do not report the backend, so the
+// requested one stands in for it; without an order there is nothing to offer.
+fn alternate_model<'a>(
+    answered: Option<&str>,
+    requested: Option<&str>,
+    order: &'a [String],
+) -> Option<&'a str> {
+    let previous = answered.or(requested);
+    let start = previous
+        .and_then(|name| order.iter().position(|candidate| candidate == name))
+        .map_or(0, |index| index + 1);
+    order
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(order.len())
+        .map(String::as_str)
+        .find(|candidate| Some(*candidate) != previous)
+}
+
+fn parse_preferred_model(argument: &str) -> Result<Option<&'static str>, String> {
+    let normalized = argument.trim().to_ascii_lowercase();
+    if normalized == AI_MODEL_AUTO || normalized == "default" {
+        return Ok(None);
+    }
+    let alias = match normalized.as_str() {
+        "gemini" => "google",
+        "gpt" => "openai",
+        other => other,
+    };
+    AI_MODEL_CHOICES
+        .iter()
+        .find(|choice| **choice == alias)
+        .map(|choice| Some(*choice))
+        .ok_or_else(|| {
+            format!(
+                "Unknown AI backend `{argument}`. Options: {}, {AI_MODEL_AUTO}.",
+                AI_MODEL_CHOICES.join(", ")
+            )
+        })
+}
+
+fn read_preferred_model() -> Result<Option<String>, JsValue> {
+    let stored = storage::get(AI_MODEL_STORAGE_KEY);
+    Ok(stored
+        .as_deref()
+        .and_then(|value| parse_preferred_model(value).ok().flatten())
+        .map(str::to_string))
+}
+
+fn write_preferred_model(model: Option<&str>) -> Result<(), JsValue> {
+    match model {
+        Some(model) => storage::set(AI_MODEL_STORAGE_KEY, model),
+        None => storage::remove(AI_MODEL_STORAGE_KEY),
+    }
+    Ok(())
+}
+
+fn parse_stored_flag(value: Option<&str>) -> bool {
+    matches!(value.map(str::trim), Some("true"))
+}
+
+fn read_locale_preference() -> Result<Option<Locale>, JsValue> {
+    let Some(window) = utils::window() else {
+        return Ok(None);
+    };
+    if let Some(locale) = storage::get(LOCALE_STORAGE_KEY)
+        .as_deref()
+        .and_then(Locale::from_tag)
+    {
+        return Ok(Some(locale));
+    }
+    Ok(window
+        .navigator()
+        .language()
+        .as_deref()
+        .and_then(Locale::from_tag))
+}
+
+fn write_locale_preference(choice: Option<Locale>) {
+    match choice {
+        Some(locale) => storage::set(LOCALE_STORAGE_KEY, locale.tag()),
+        None => storage::remove(LOCALE_STORAGE_KEY),
+    }
+}
+
+fn read_suggestions_expanded() -> Result<bool, JsValue> {
+    let stored = storage::get(SUGGESTIONS_EXPANDED_STORAGE_KEY);
+    Ok(parse_stored_flag(stored.as_deref()))
+}
+
+fn write_suggestions_expanded(expanded: bool) -> Result<(), JsValue> {
+    storage::set(
+        SUGGESTIONS_EXPANDED_STORAGE_KEY,
+        if expanded { "true" } else { "false" },
+    );
+    Ok(())
+}
+
+fn read_output_width() -> Option<OutputWidth> {
+    storage::get(OUTPUT_WIDTH_STORAGE_KEY)
+        .as_deref()
+        .and_then(OutputWidth::parse)
+}
+
+fn write_output_width(width: OutputWidth) {
+    match width {
+        OutputWidth::Auto => storage::remove(OUTPUT_WIDTH_STORAGE_KEY),
+        _ => storage::set(OUTPUT_WIDTH_STORAGE_KEY, &width.label()),
+    }
+}
+
+fn read_reading_time() -> Option<Option<usize>> {
+    storage::get(READING_TIME_STORAGE_KEY)
+        .as_deref()
+        .and_then(commands::parse_reading_time)
+}
+
+fn write_reading_time(min_words: Option<usize>) {
+    match min_words {
+        Some(words) if words == format::READING_TIME_DEFAULT_MIN_WORDS => {
+            storage::remove(READING_TIME_STORAGE_KEY)
+        }
+        Some(words) => storage::set(READING_TIME_STORAGE_KEY, &words.to_string()),
+        None => storage::set(READING_TIME_STORAGE_KEY, "off"),
+    }
+}
+
+fn read_data_saver() -> Option<DataSaverSetting> {
+    storage::get(DATA_SAVER_STORAGE_KEY)
+        .as_deref()
+        .and_then(DataSaverSetting::parse)
+}
+
+fn write_data_saver(setting: DataSaverSetting) {
+    match setting {
+        DataSaverSetting::Auto => storage::remove(DATA_SAVER_STORAGE_KEY),
+        _ => storage::set(DATA_SAVER_STORAGE_KEY, setting.label()),
     }
 }
 
-fn ai_mode_suggestions(filter: &str, model: Option<&str>) -> Vec<(String, String)> {
+fn read_achievements_spoilers() -> Result<bool, JsValue> {
+    let stored = storage::get(ACHIEVEMENTS_SPOILERS_STORAGE_KEY);
+    Ok(parse_stored_flag(stored.as_deref()))
+}
+
+fn write_achievements_spoilers(enabled: bool) -> Result<(), JsValue> {
+    storage::set(
+        ACHIEVEMENTS_SPOILERS_STORAGE_KEY,
+        if enabled { "true" } else { "false" },
+    );
+    Ok(())
+}
+
+fn ai_mode_suggestions(filter: &str, model: Option<&str>, locale: Locale) -> Vec<(String, String)> {
     let commands = [
-        (AI_HELP_COMMAND, ai_help_label(model)),
+        (AI_HELP_COMMAND, ai_help_label(model, locale)),
         (AI_QUIT_COMMAND, AI_QUIT_LABEL.to_string()),
     ];
 
@@ -1577,19 +2969,68 @@ fn ai_mode_suggestions(filter: &str, model: Option<&str>) -> Vec<(String, String
         .collect()
 }
 
+fn refresh_suggestions_now(state: &SharedState, renderer: &SharedRenderer) {
+    if state.borrow().input_disabled() {
+        renderer.render_suggestions(std::iter::empty::<(String, String)>());
+        return;
+    }
+    render_current_suggestions(state, renderer);
+}
+
+fn queue_typed_text(
+    state: &SharedState,
+    typing: &TypingBatch,
+    suggestions: &Rc<Debouncer>,
+    value: &str,
+    refresh: Rc<dyn Fn()>,
+) {
+    let first_in_batch = {
+        let mut pending = typing.pending.borrow_mut();
+        let first = pending.is_empty();
+        pending.push_str(value);
+        first
+    };
+    if !first_in_batch {
+        return;
+    }
+    let state = Rc::clone(state);
+    let pending = Rc::clone(&typing.pending);
+    let suggestions = Rc::clone(suggestions);
+    typing.scheduler.schedule(
+        0,
+        Box::new(move || {
+            if flush_typed_text(&state, &pending) {
+                suggestions.call(move || refresh());
+            }
+        }),
+    );
+}
+
+fn flush_typed_text(state: &SharedState, pending: &RefCell<String>) -> bool {
+    let text = std::mem::take(&mut *pending.borrow_mut());
+    if text.is_empty() {
+        return false;
+    }
+    let mut state = state.borrow_mut();
+    state.input_buffer.push_str(&text);
+    state.history_index = None;
+    true
+}
+
 fn render_current_suggestions(state: &SharedState, renderer: &SharedRenderer) {
-    let (buffer, ai_mode, ai_model) = {
+    let (buffer, ai_mode, ai_model, locale) = {
         let state = state.borrow();
         (
             state.input_buffer.clone(),
             state.ai_mode,
             state.ai_model.clone(),
+            state.locale,
         )
     };
     let trimmed = buffer.trim().to_ascii_lowercase();
 
     let suggestions: Vec<(String, String)> = if ai_mode {
-        ai_mode_suggestions(&trimmed, ai_model.as_deref())
+        ai_mode_suggestions(&trimmed, ai_model.as_deref(), locale)
     } else {
         let names: Vec<String> = if trimmed.is_empty() {
             default_suggestions()
@@ -1614,7 +3055,171 @@ fn render_current_suggestions(state: &SharedState, renderer: &SharedRenderer) {
             .collect()
     };
 
-    renderer.render_suggestions(suggestions);
+    if ai_mode {
+        renderer.render_suggestions(suggestions);
+    } else {
+        renderer.render_suggestions_gated(suggestions, chip_available_before_data);
+    }
+}
+
+const DATA_INDEPENDENT_COMMANDS: [&str; 5] = ["help", "clear", "ai", "banner", "update"];
+
+fn chip_available_before_data(command: &str) -> bool {
+    DATA_INDEPENDENT_COMMANDS
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(command.trim()))
+}
+
+fn ai_error_message(error: &impl std::fmt::Display, locale: Locale) -> String {
+    i18n::t_with(Message::AiError, locale, &[("error", &error.to_string())])
+}
+
+fn ai_estimate_message(
+    estimate: &ai::AiCostEstimate,
+    model: Option<&str>,
+    locale: Locale,
+) -> String {
+    let mut message = format!(
+        "💶 Estimated cost: ~{} input + {} output tokens ≈ €{:.4}",
+        format::format_count(estimate.input_tokens, locale),
+        format::format_count(estimate.output_tokens, locale),
+        estimate.cost_eur
+    );
+    if let Some(model) = model {
+        message.push_str(&format!(" via {model}"));
+    }
+    message.push('.');
+    if estimate.allowed {
+        message.push_str("\n✅ The usage limiter would accept this question.");
+    } else {
+        let reason = estimate
+            .limit_reason
+            .map_or("limit reached", ReasonCode::as_str);
+        message.push_str(&format!(
+            "\n⛔ The usage limiter would block this question right now ({reason})."
+        ));
+    }
+    message
+}
+
+// Classic-mode `help && faq` splits into steps; a single command is not a chain.
+fn chained_steps(line: &str) -> Option<Vec<&str>> {
+    let steps: Vec<&str> = line
+        .split("&&")
+        .map(str::trim)
+        .filter(|step| !step.is_empty())
+        .collect();
+    (line.contains("&&") && steps.len() > 1).then_some(steps)
+}
+
+fn within_command_cooldown(
+    previous: Option<(&str, f64)>,
+    command: &str,
+    now_ms: f64,
+    ai_mode: bool,
+) -> bool {
+    if ai_mode {
+        return false;
+    }
+    let normalized = command.trim();
+    if COOLDOWN_EXEMPT_COMMANDS
+        .iter()
+        .any(|exempt| exempt.eq_ignore_ascii_case(normalized))
+    {
+        return false;
+    }
+    match previous {
+        Some((last, at)) => last.trim() == normalized && now_ms - at < COMMAND_COOLDOWN_MS,
+        None => false,
+    }
+}
+
+// Runs from the panic hook: wasm has already aborted, so nothing will handle
+// input again and the notice only asks the visitor to reload.
+pub(crate) fn render_panic_notice(renderer: &Renderer, detail: &str) {
+    let notice = format!(
+        "💥 The terminal crashed ({detail}) and has stopped. Please reload the page, and report it at {ISSUE_TRACKER_URL}."
+    );
+    if let Err(err) = renderer.append_error_line(&notice, ScrollBehavior::Bottom) {
+        utils::log(&format!("Failed to render the crash notice: {:?}", err));
+    }
+}
+
+// Shared by the boot sequence and the `banner` command so both stay identical.
+async fn render_welcome(renderer: &Renderer, state: &SharedState) {
+    let (profile_name, freshness, locale) = {
+        let state = state.borrow();
+        let name = state.data.as_ref().map(|data| data.profile.name.clone());
+        let generated_at = state
+            .data
+            .as_ref()
+            .and_then(|data| data.freshness_timestamp());
+        let freshness = generated_at
+            .and_then(commands::data_freshness_line)
+            .map(|line| {
+                let generated_ms = generated_at.map(js_sys::Date::parse).unwrap_or(f64::NAN);
+                if generated_ms.is_nan() {
+                    line
+                } else {
+                    let relative =
+                        format::format_relative(generated_ms, state.now_ms(), state.locale);
+                    format!("{line} ({relative})")
+                }
+            });
+        (name, freshness, state.locale)
+    };
+
+    let boot_message = i18n::t(Message::BootSequence, locale);
+    let delay_ms = welcome_type_delay_ms(utils::prefers_reduced_motion());
+    if let Err(err) = renderer.type_output_text(boot_message, delay_ms).await {
+        utils::log(&format!("Failed to animate welcome message: {:?}", err));
+        if let Err(err) = renderer.append_output_text(boot_message, ScrollBehavior::Bottom) {
+            utils::log(&format!(
+                "Failed to render welcome message fallback: {:?}",
+                err
+            ));
+        }
+    }
+
+    if let Some(name) = profile_name {
+        let profile_line = profile_loaded_line(&name);
+        if let Err(err) = renderer.append_output_text(&profile_line, ScrollBehavior::Bottom) {
+            utils::log(&format!(
+                "Failed to append profile line `{profile_line}`: {:?}",
+                err
+            ));
+        }
+    }
+
+    if let Some(freshness) = freshness {
+        if let Err(err) = renderer.append_info_line(&freshness, ScrollBehavior::Bottom) {
+            utils::log(&format!("Failed to append data freshness line: {:?}", err));
+        }
+    }
+
+    for guidance in WELCOME_GUIDANCE_LINES.map(|key| i18n::t(key, locale)) {
+        if let Err(err) = renderer.append_info_line(guidance, ScrollBehavior::Bottom) {
+            utils::log(&format!(
+                "Failed to append guidance line `{guidance}`: {:?}",
+                err
+            ));
+        }
+    }
+
+    if let Err(err) = renderer.append_info_html(WELCOME_AI_CTA_HTML, ScrollBehavior::Bottom) {
+        utils::log(&format!(
+            "Failed to append AI assistant call-to-action: {:?}",
+            err
+        ));
+    }
+}
+
+fn welcome_type_delay_ms(reduced_motion: bool) -> u32 {
+    if reduced_motion {
+        0
+    } else {
+        WELCOME_TYPE_DELAY_MS
+    }
 }
 
 fn profile_loaded_line(name: &str) -> String {
@@ -1632,7 +3237,14 @@ struct StoredAchievements {
     shutdown: bool,
     #[serde(default)]
     platinum: bool,
-    spoilers_enabled: bool,
+    #[serde(default = "default_pokemon_chance")]
+    pokemon_chance: u8,
+    #[serde(default)]
+    cookie_best: u32,
+}
+
+fn default_pokemon_chance() -> u8 {
+    1
 }
 
 #[cfg(test)]
@@ -1642,8 +3254,437 @@ mod tests {
         Education, Experience, FaqEntry, Profile, ProfileLinks, ProjectsCollection, ResumeVariant,
         TerminalData, Testimonial,
     };
+    use crate::utils::debounce::manual::ManualScheduler;
     use wasm_bindgen_test::wasm_bindgen_test;
 
+    #[wasm_bindgen_test]
+    fn burst_typing_fills_the_buffer_with_few_suggestion_refreshes() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        state.borrow_mut().history_index = Some(0);
+        let scheduler = Rc::new(ManualScheduler::default());
+        let typing = TypingBatch {
+            pending: Rc::new(RefCell::new(String::new())),
+            scheduler: scheduler.clone(),
+        };
+        let suggestions = Rc::new(Debouncer::new(
+            SUGGESTION_REFRESH_DEBOUNCE_MS,
+            scheduler.clone(),
+        ));
+        let refreshes = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&refreshes);
+        let refresh: Rc<dyn Fn()> = Rc::new(move || counter.set(counter.get() + 1));
+
+        let burst = "the quick brown fox jumps over";
+        assert_eq!(burst.len(), 30);
+        for (index, ch) in burst.chars().enumerate() {
+            queue_typed_text(
+                &state,
+                &typing,
+                &suggestions,
+                &ch.to_string(),
+                Rc::clone(&refresh),
+            );
+            if index % 10 == 9 {
+                scheduler.advance(5);
+            }
+        }
+        scheduler.advance(SUGGESTION_REFRESH_DEBOUNCE_MS.into());
+
+        let state = state.borrow();
+        assert_eq!(state.input_buffer, burst);
+        assert_eq!(state.history_index, None);
+        assert!(typing.pending.borrow().is_empty());
+        assert_eq!(refreshes.get(), 1, "Suggestions render once per burst");
+    }
+
+    fn mount_prompt_fixture() -> SharedRenderer {
+        let document = utils::document().expect("document");
+        if document.get_element_by_id("terminal").is_none() {
+            let body = document.body().expect("body");
+            for (tag, id) in [
+                ("div", "terminal"),
+                ("div", "output"),
+                ("span", "prompt-input"),
+                ("input", "prompt-hidden-input"),
+                ("span", "prompt-label"),
+                ("div", "suggestions"),
+                ("button", "ai-mode-toggle"),
+                ("span", "ai-mode-indicator"),
+                ("button", "achievements-trigger"),
+                ("div", "achievements-overlay"),
+                ("div", "achievements-modal"),
+            ] {
+                let element = document.create_element(tag).expect("element");
+                element.set_id(id);
+                body.append_child(&element).expect("attach");
+            }
+        }
+        Rc::new(Renderer::new().expect("renderer"))
+    }
+
+    async fn wait_for_welcome(output: &Element) {
+        for _ in 0..200 {
+            if output
+                .query_selector(".ai-mode-cta")
+                .ok()
+                .flatten()
+                .is_some()
+            {
+                return;
+            }
+            TimeoutFuture::new(20).await;
+        }
+        panic!("welcome banner never finished rendering");
+    }
+
+    #[wasm_bindgen_test]
+    async fn banner_replays_the_same_welcome_as_boot() {
+        let state: SharedState = Rc::new(RefCell::new(make_state_with_data()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Rc::new(Terminal::new(Rc::clone(&state), Rc::clone(&renderer)));
+        let output = utils::document()
+            .expect("document")
+            .get_element_by_id("output")
+            .expect("output");
+
+        output.set_inner_html("");
+        terminal.on_data_ready().expect("boot sequence");
+        wait_for_welcome(&output).await;
+        let boot = output.inner_html();
+
+        output.set_inner_html("");
+        terminal.replay_banner();
+        wait_for_welcome(&output).await;
+        let banner = output.inner_html();
+
+        assert!(
+            boot.contains(i18n::t(Message::BootSequence, Locale::En)),
+            "{boot}"
+        );
+        assert!(boot.contains("Profile loaded for Alex."), "{boot}");
+        assert_eq!(banner, boot, "banner should rebuild the boot DOM exactly");
+    }
+
+    #[test]
+    fn only_real_chains_split_into_steps() {
+        assert_eq!(super::chained_steps("help"), None);
+        assert_eq!(super::chained_steps("help &&"), None);
+        assert_eq!(super::chained_steps(" && "), None);
+        assert_eq!(
+            super::chained_steps("help && faq"),
+            Some(vec!["help", "faq"])
+        );
+        assert_eq!(
+            super::chained_steps("about&&skills --cloud&& && faq"),
+            Some(vec!["about", "skills --cloud", "faq"])
+        );
+    }
+
+    fn submit(terminal: &Terminal, line: &str) -> Element {
+        let output = utils::document()
+            .expect("document")
+            .get_element_by_id("output")
+            .expect("output");
+        output.set_inner_html("");
+        terminal.overwrite_input(line);
+        terminal.submit_command().expect("submit");
+        output
+    }
+
+    async fn wait_for_prompt(state: &SharedState) {
+        for _ in 0..200 {
+            if !state.borrow().input_disabled() {
+                return;
+            }
+            TimeoutFuture::new(20).await;
+        }
+        panic!("scripted sequence never handed the prompt back");
+    }
+
+    #[wasm_bindgen_test]
+    async fn sleeping_chains_pause_inside_their_group_then_release_the_prompt() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+
+        let output = submit(&terminal, "sleep 0.2 && help");
+        assert!(state.borrow().input_disabled(), "input waits for the chain");
+        let group = output
+            .query_selector(".command-group")
+            .unwrap()
+            .expect("scheduled chains still group their output");
+
+        wait_for_prompt(&state).await;
+        let summary = group
+            .query_selector(".command-group__summary")
+            .unwrap()
+            .expect("summary toggle");
+        assert_eq!(
+            summary.text_content().as_deref(),
+            Some("2 commands, 2 succeeded")
+        );
+        assert!(!terminal.abort_sequence(), "nothing left to abort");
+        assert!(state.borrow().accepts_typing());
+    }
+
+    #[wasm_bindgen_test]
+    async fn a_keypress_stops_the_demo_and_hands_back_the_prompt() {
+        let state: SharedState = Rc::new(RefCell::new(make_state_with_data()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+
+        let output = submit(&terminal, "demo");
+        assert!(output
+            .text_content()
+            .unwrap_or_default()
+            .contains(DEMO_STARTED));
+        assert!(state.borrow().input_disabled());
+        assert!(terminal.abort_sequence(), "the first key stops the demo");
+
+        wait_for_prompt(&state).await;
+        let text = output.text_content().unwrap_or_default();
+        assert!(text.contains(SEQUENCE_STOPPED), "{text}");
+        assert!(!text.contains(DEMO_FINISHED), "{text}");
+        assert_eq!(
+            output.query_selector_all(".command-line").unwrap().length(),
+            1,
+            "only the `demo` line itself was echoed"
+        );
+        assert!(state.borrow().input_buffer.is_empty());
+        assert!(!terminal.abort_sequence());
+    }
+
+    #[wasm_bindgen_test]
+    fn chained_commands_render_inside_a_collapsed_group() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+
+        let output = submit(
+            &terminal,
+            "help && definitely-not-a-command && clear effects",
+        );
+        let group = output
+            .query_selector(".command-group")
+            .unwrap()
+            .expect("chained submission opens a group");
+        let summary = group
+            .query_selector(".command-group__summary")
+            .unwrap()
+            .expect("summary toggle");
+        assert_eq!(
+            summary.text_content().as_deref(),
+            Some("3 commands, 2 succeeded")
+        );
+        assert_eq!(
+            summary.get_attribute("aria-expanded").as_deref(),
+            Some("false")
+        );
+        let body = summary.next_element_sibling().expect("group body");
+        assert!(body.has_attribute("hidden"), "groups start collapsed");
+        assert_eq!(
+            body.query_selector_all(".command-line").unwrap().length(),
+            3
+        );
+
+        assert!(terminal.toggle_command_group(&summary).unwrap());
+        assert!(!body.has_attribute("hidden"));
+        assert_eq!(
+            summary.get_attribute("aria-expanded").as_deref(),
+            Some("true")
+        );
+
+        let after = renderer.append_output_text("next", ScrollBehavior::None);
+        assert!(after.is_ok());
+        assert_eq!(
+            output.last_element_child().map(|line| line.class_name()),
+            Some("line output-text".to_string()),
+            "lines after end_group go straight to #output"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn single_commands_are_not_grouped() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+
+        let output = submit(&terminal, "help");
+        assert!(output.query_selector(".command-group").unwrap().is_none());
+        let classes: Vec<String> = (0..output.children().length())
+            .filter_map(|index| output.children().item(index))
+            .map(|line| line.class_name())
+            .collect();
+        assert_eq!(classes.len(), 3, "{classes:?}");
+        assert!(classes[0].contains("spacer-line"), "{classes:?}");
+        assert!(classes[2].contains("output-text"), "{classes:?}");
+    }
+
+    #[test]
+    fn reduced_motion_types_the_welcome_instantly() {
+        assert_eq!(super::welcome_type_delay_ms(true), 0);
+        assert_eq!(
+            super::welcome_type_delay_ms(false),
+            super::WELCOME_TYPE_DELAY_MS
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn prompt_representations_stay_in_sync_after_mutations() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        state.borrow_mut().command_history = vec!["help".to_string(), "faq".to_string()];
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+        let check = |label: &str| {
+            let expected = format!(
+                "{}{}",
+                state.borrow().input_buffer,
+                terminal.typing.pending.borrow()
+            );
+            assert_eq!(
+                renderer.input_snapshot(),
+                (expected.clone(), expected),
+                "after {label}"
+            );
+        };
+
+        terminal.overwrite_input("proj");
+        check("overwrite");
+        terminal.append_text("ec");
+        check("typing");
+        terminal.autocomplete();
+        check("autocomplete");
+        terminal.delete_last_character();
+        check("delete");
+        terminal.navigate_history(HistoryDirection::Older);
+        check("history");
+        assert_eq!(state.borrow().input_buffer, "faq");
+        terminal.append_text("x");
+        terminal.overwrite_input("skills");
+        check("overwrite over pending typing");
+        assert_eq!(state.borrow().input_buffer, "skills");
+        terminal.clear_input();
+        check("clear");
+        assert!(state.borrow().input_buffer.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn ai_toggle_exposes_its_state_to_assistive_tech() {
+        let renderer = mount_prompt_fixture();
+        let document = utils::document().expect("document");
+        let toggle = document
+            .get_element_by_id("ai-mode-toggle")
+            .expect("toggle");
+        let attr = |name: &str| toggle.get_attribute(name).unwrap_or_default();
+        let announced = || {
+            document
+                .get_element_by_id("sr-announcer")
+                .and_then(|announcer| announcer.text_content())
+                .unwrap_or_default()
+        };
+        assert_eq!(attr("role"), "switch");
+        renderer.announce("");
+
+        renderer.apply_ai_mode(false).expect("initial mode");
+        renderer.set_ai_busy(false).expect("idle");
+        assert_eq!(attr("aria-label"), "Enable AI assistant");
+        assert_eq!(attr("aria-checked"), "false");
+        assert_eq!(announced(), "", "Restoring the initial mode stays quiet");
+
+        renderer.set_ai_busy(true).expect("busy");
+        assert_eq!(
+            attr("aria-label"),
+            "Enable AI assistant — currently answering"
+        );
+        assert_eq!(attr("aria-checked"), "false");
+        renderer.set_ai_busy(false).expect("idle");
+
+        renderer.apply_ai_mode(true).expect("enable");
+        assert_eq!(attr("aria-label"), "Disable AI assistant");
+        assert_eq!(attr("aria-checked"), "true");
+        assert_eq!(attr("aria-pressed"), "true");
+        assert_eq!(announced(), "AI assistant enabled");
+
+        renderer.set_ai_busy(true).expect("busy");
+        assert_eq!(
+            attr("aria-label"),
+            "Disable AI assistant — currently answering"
+        );
+        assert_eq!(announced(), "AI assistant is answering");
+
+        renderer.set_ai_busy(false).expect("idle");
+        assert_eq!(attr("aria-label"), "Disable AI assistant");
+        assert_eq!(announced(), "AI assistant is ready");
+
+        renderer.set_ai_busy(true).expect("busy");
+        renderer.apply_ai_mode(false).expect("disable");
+        assert_eq!(attr("aria-label"), "Enable AI assistant");
+        assert_eq!(attr("aria-checked"), "false");
+        assert_eq!(announced(), "AI assistant disabled");
+    }
+
+    #[wasm_bindgen_test]
+    fn keyboard_focus_settles_on_the_prompt_after_chips_and_overlays() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Rc::new(Terminal::new(Rc::clone(&state), Rc::clone(&renderer)));
+        let document = utils::document().expect("document");
+        let active_id = || {
+            document
+                .active_element()
+                .map(|element| element.id())
+                .unwrap_or_default()
+        };
+
+        let chip: HtmlElement = document
+            .create_element("span")
+            .expect("chip")
+            .dyn_into()
+            .expect("html element");
+        chip.set_class_name("suggestion");
+        chip.set_attribute("data-command", "help")
+            .expect("chip command");
+        chip.set_tab_index(0);
+        document
+            .get_element_by_id("suggestions")
+            .expect("suggestions")
+            .append_child(&chip)
+            .expect("attach chip");
+        let keydown_terminal = Rc::clone(&terminal);
+        let keydown = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            crate::input::handle_keydown(&keydown_terminal, event);
+        }) as Box<dyn FnMut(_)>);
+        chip.add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())
+            .expect("keydown listener");
+        chip.focus().expect("focus chip");
+
+        let init = web_sys::KeyboardEventInit::new();
+        init.set_key("Enter");
+        init.set_bubbles(true);
+        let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+            .expect("keyboard event");
+        chip.dispatch_event(&event).expect("dispatch keydown");
+        assert_eq!(active_id(), "prompt-hidden-input", "after a chip runs");
+
+        let modal = document
+            .get_element_by_id("achievements-modal")
+            .expect("modal");
+        modal.set_attribute("tabindex", "-1").expect("tabindex");
+        terminal.open_achievements_modal().expect("open modal");
+        assert_eq!(
+            active_id(),
+            "achievements-modal",
+            "while the overlay is open"
+        );
+        terminal.close_achievements_modal().expect("close modal");
+        assert_eq!(
+            active_id(),
+            "prompt-hidden-input",
+            "after the overlay closes"
+        );
+        chip.remove();
+    }
+
     fn make_state_with_data() -> AppState {
         use std::collections::BTreeMap;
 
@@ -1682,10 +3723,188 @@ mod tests {
         state
     }
 
+    #[wasm_bindgen_test]
+    fn dismissing_an_effect_removes_it_and_clears_the_handle() {
+        let document = utils::document().expect("document");
+        let body = document.body().expect("body");
+        let overlay: HtmlElement = document
+            .create_element("div")
+            .expect("overlay")
+            .dyn_into()
+            .expect("html element");
+        body.append_child(&overlay).expect("attach overlay");
+
+        let mut state = AppState::new();
+        assert!(!dismiss_active_effect(&mut state), "Nothing to dismiss yet");
+        state.track_effect(overlay.clone());
+        assert!(dismiss_active_effect(&mut state));
+        assert!(state.active_effect.is_none());
+        assert!(
+            !overlay.is_connected(),
+            "The overlay is removed immediately"
+        );
+
+        state.track_effect(overlay.clone());
+        state.release_effect(&overlay);
+        assert!(
+            state.active_effect.is_none(),
+            "Timers release effects they cleaned up themselves"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn reset_achievements_persists_the_cleared_state() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        {
+            let mut state = state.borrow_mut();
+            state.unlock_shaw_celebration();
+            state.unlock_cookie_rain();
+            state.unlock_shutdown_protocol();
+            state.achievements_spoilers_enabled = true;
+        }
+        Terminal::persist_achievements_snapshot_shared(&state);
+
+        Terminal::reset_achievements_shared(&state);
+        let raw = storage::get(ACHIEVEMENTS_STORAGE_KEY).expect("cleared state is persisted");
+        let stored: StoredAchievements = serde_json::from_str(&raw).expect("valid payload");
+        assert_eq!(stored.version, ACHIEVEMENTS_STORAGE_VERSION);
+        assert!(
+            !(stored.shaw
+                || stored.pokemon
+                || stored.cookie
+                || stored.konami
+                || stored.shutdown
+                || stored.platinum),
+            "Every unlocked flag should be cleared: {raw}"
+        );
+        assert!(
+            state.borrow().achievements_spoilers_enabled,
+            "The spoiler toggle survives a reset"
+        );
+        assert!(!state.borrow().achievement_shaw_unlocked);
+    }
+
+    #[wasm_bindgen_test]
+    fn pokemon_capture_chance_is_persisted_with_achievements() {
+        let state: SharedState = Rc::new(RefCell::new(AppState::new()));
+        state.borrow_mut().set_pokemon_capture_chance(37);
+        Terminal::persist_achievements_snapshot_shared(&state);
+
+        let raw = storage::get(ACHIEVEMENTS_STORAGE_KEY).expect("snapshot is persisted");
+        let stored: StoredAchievements = serde_json::from_str(&raw).expect("valid payload");
+        assert_eq!(stored.pokemon_chance, 37);
+
+        let legacy: StoredAchievements = serde_json::from_str(
+            r#"{"version":"0","shaw":false,"pokemon":false,"konami":false,"shutdown":false}"#,
+        )
+        .expect("older payloads still parse");
+        assert_eq!(
+            legacy.pokemon_chance, 1,
+            "Older caches start from the base odds"
+        );
+        assert_eq!(legacy.cookie_best, 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn achievements_spoilers_preference_round_trips() {
+        storage::remove(ACHIEVEMENTS_SPOILERS_STORAGE_KEY);
+        assert!(
+            !read_achievements_spoilers().unwrap(),
+            "Spoilers start hidden"
+        );
+
+        write_achievements_spoilers(true).unwrap();
+        assert_eq!(
+            storage::get(ACHIEVEMENTS_SPOILERS_STORAGE_KEY).as_deref(),
+            Some("true")
+        );
+        assert!(read_achievements_spoilers().unwrap());
+
+        write_achievements_spoilers(false).unwrap();
+        assert!(!read_achievements_spoilers().unwrap());
+    }
+
+    fn enter_konami(state: &mut AppState) -> bool {
+        let mut triggered = false;
+        for key in KONAMI_CODE {
+            triggered = Terminal::advance_konami(state, key);
+            if matches!(key, "a" | "b") {
+                state.input_buffer.push_str(key);
+            }
+        }
+        triggered
+    }
+
+    #[test]
+    fn shutdown_triggers_only_for_classic_rm_rf_commands() {
+        assert!(Terminal::is_shutdown_command("rm -rf /", false));
+        assert!(Terminal::is_shutdown_command("  RM   -RF  ", false));
+        assert!(!Terminal::is_shutdown_command("explain rm -rf", true));
+        assert!(!Terminal::is_shutdown_command("rm -rf /", true));
+        assert!(!Terminal::is_shutdown_command("explain rm -rf", false));
+        assert!(!Terminal::is_shutdown_command("rm -rfv", false));
+        assert!(!Terminal::is_shutdown_command("rm", false));
+    }
+
+    #[test]
+    fn konami_code_triggers_once_from_an_empty_prompt() {
+        let mut state = AppState::new();
+        assert!(enter_konami(&mut state));
+        assert!(state.konami_triggered);
+        state.input_buffer.clear();
+        assert!(!enter_konami(&mut state), "The sequence only fires once");
+    }
+
+    #[test]
+    fn konami_wrong_key_restarts_the_sequence() {
+        let mut state = AppState::new();
+        Terminal::advance_konami(&mut state, "ArrowUp");
+        Terminal::advance_konami(&mut state, "ArrowUp");
+        assert_eq!(state.konami_index, 2);
+        Terminal::advance_konami(&mut state, "Enter");
+        assert_eq!(state.konami_index, 0);
+        Terminal::advance_konami(&mut state, "ArrowLeft");
+        assert_eq!(state.konami_index, 0);
+    }
+
+    #[test]
+    fn konami_is_ignored_in_ai_mode_and_while_an_answer_is_pending() {
+        let mut state = AppState::new();
+        Terminal::advance_konami(&mut state, "ArrowUp");
+        Terminal::advance_konami(&mut state, "ArrowUp");
+        state.set_ai_mode(true);
+        Terminal::advance_konami(&mut state, "ArrowDown");
+        assert_eq!(state.konami_index, 0, "AI mode resets progress");
+        assert!(!enter_konami(&mut state));
+
+        let mut state = AppState::new();
+        state.ai_request_pending = true;
+        assert!(!enter_konami(&mut state));
+        assert_eq!(state.konami_index, 0);
+        assert!(!state.konami_triggered);
+    }
+
+    #[test]
+    fn konami_must_start_from_an_empty_input_buffer() {
+        let mut state = AppState::new();
+        state.input_buffer = "skills".to_string();
+        assert!(!enter_konami(&mut state));
+        assert_eq!(state.konami_index, 0);
+
+        // Arrow keys fill the buffer from history mid-sequence; that's fine.
+        state.input_buffer.clear();
+        Terminal::advance_konami(&mut state, "ArrowUp");
+        state.input_buffer = "projects".to_string();
+        for key in &KONAMI_CODE[1..] {
+            Terminal::advance_konami(&mut state, key);
+        }
+        assert!(state.konami_triggered);
+    }
+
     #[test]
     fn boot_sequence_matches_spec() {
         assert_eq!(
-            super::BOOT_SEQUENCE_MESSAGE,
+            i18n::t(Message::BootSequence, Locale::En),
             "Welcome to the ZQSDev interactive terminal!"
         );
     }
@@ -1693,7 +3912,7 @@ mod tests {
     #[test]
     fn guidance_lines_match_spec() {
         assert_eq!(
-            super::WELCOME_GUIDANCE_LINES,
+            super::WELCOME_GUIDANCE_LINES.map(|key| i18n::t(key, Locale::En)),
             [
                 "Type `help` to view all available commands.",
                 "Use the quick actions below to jump to key sections instantly."
@@ -1749,7 +3968,7 @@ mod tests {
 
     #[test]
     fn ai_help_label_includes_model_name() {
-        let label = super::ai_help_label(Some("gpt-4o-mini"));
+        let label = super::ai_help_label(Some("gpt-4o-mini"), Locale::En);
         assert!(
             label.contains("gpt-4o-mini"),
             "AI help label should include the model name: {label}"
@@ -1758,7 +3977,7 @@ mod tests {
 
     #[test]
     fn ai_mode_suggestions_label_help_with_model() {
-        let suggestions = super::ai_mode_suggestions("", Some("llama-3.1-8b-instant"));
+        let suggestions = super::ai_mode_suggestions("", Some("llama-3.1-8b-instant"), Locale::En);
         let help = suggestions
             .iter()
             .find(|(command, _)| command == "help")
@@ -1771,7 +3990,7 @@ mod tests {
 
     #[test]
     fn ai_mode_suggestions_filter_by_prefix() {
-        let suggestions = super::ai_mode_suggestions("q", Some("gpt"));
+        let suggestions = super::ai_mode_suggestions("q", Some("gpt"), Locale::En);
         assert_eq!(
             suggestions.len(),
             1,
@@ -1780,6 +3999,228 @@ mod tests {
         assert_eq!(suggestions[0].0, "quit");
     }
 
+    #[wasm_bindgen_test]
+    fn lang_preference_is_saved_and_cleared() {
+        super::write_locale_preference(Some(Locale::Fr));
+        assert_eq!(
+            storage::get(super::LOCALE_STORAGE_KEY).as_deref(),
+            Some("fr")
+        );
+        assert_eq!(super::read_locale_preference(), Ok(Some(Locale::Fr)));
+
+        super::write_locale_preference(None);
+        assert_eq!(storage::get(super::LOCALE_STORAGE_KEY), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn panic_notice_asks_for_a_reload() {
+        let renderer = mount_prompt_fixture();
+        let document = utils::document().expect("document");
+        let output = document.get_element_by_id("output").expect("output");
+        output.set_inner_html("");
+
+        super::render_panic_notice(&renderer, "formatter exploded");
+
+        let notice = output
+            .query_selector(".error-line")
+            .unwrap()
+            .expect("crash notice");
+        let text = notice.text_content().unwrap_or_default();
+        assert!(
+            text.contains("formatter exploded")
+                && text.contains("reload the page")
+                && text.contains(super::ISSUE_TRACKER_URL),
+            "Crash notice should carry the detail, ask for a reload and link the tracker: {text}"
+        );
+    }
+
+    #[test]
+    fn only_data_independent_chips_are_available_before_data_loads() {
+        for command in ["help", "clear", "ai", " AI ", "update"] {
+            assert!(
+                super::chip_available_before_data(command),
+                "`{command}` should stay clickable while résumé data loads"
+            );
+        }
+        for command in ["about", "projects", "resume", "contact", "faq", ""] {
+            assert!(
+                !super::chip_available_before_data(command),
+                "`{command}` should render as a skeleton until data is ready"
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn suggestions_expansion_survives_rerenders_and_reloads() {
+        storage::remove(super::SUGGESTIONS_EXPANDED_STORAGE_KEY);
+        let state: SharedState = Rc::new(RefCell::new(make_state_with_data()));
+        let renderer = mount_prompt_fixture();
+        let terminal = Terminal::new(Rc::clone(&state), Rc::clone(&renderer));
+        let suggestions = utils::document()
+            .expect("document")
+            .get_element_by_id("suggestions")
+            .expect("suggestions");
+        let expanded_attr = || suggestions.get_attribute("data-expanded");
+
+        super::render_current_suggestions(&state, &renderer);
+        assert_eq!(expanded_attr().as_deref(), Some("false"));
+
+        terminal.toggle_suggestions_expanded();
+        state.borrow_mut().input_buffer = "pro".to_string();
+        super::render_current_suggestions(&state, &renderer);
+        state.borrow_mut().input_buffer.clear();
+        super::render_current_suggestions(&state, &renderer);
+        assert_eq!(
+            expanded_attr().as_deref(),
+            Some("true"),
+            "Re-rendering the chips should keep them expanded"
+        );
+        assert_eq!(super::read_suggestions_expanded(), Ok(true));
+
+        let reloaded: SharedState = Rc::new(RefCell::new(make_state_with_data()));
+        Terminal::new(Rc::clone(&reloaded), Rc::clone(&renderer))
+            .restore_suggestions_preference_from_storage();
+        assert!(
+            reloaded.borrow().suggestions_expanded,
+            "A reload should restore the persisted expansion preference"
+        );
+        storage::remove(super::SUGGESTIONS_EXPANDED_STORAGE_KEY);
+    }
+
+    #[test]
+    fn ai_estimate_message_reports_cost_and_limiter_verdict() {
+        let mut estimate = crate::ai::AiCostEstimate {
+            input_tokens: 1_250,
+            output_tokens: 384,
+            cost_eur: 0.0015,
+            allowed: true,
+            limit_reason: None,
+        };
+        let message = super::ai_estimate_message(&estimate, Some("gpt-4o-mini"), Locale::En);
+        assert!(
+            message.contains("~1,250 input + 384 output tokens ≈ €0.0015 via gpt-4o-mini."),
+            "Unexpected estimate summary: {message}"
+        );
+        assert!(
+            message.contains("would accept"),
+            "Missing verdict: {message}"
+        );
+
+        estimate.allowed = false;
+        estimate.limit_reason = Some(ReasonCode::MinuteBudget);
+        let message = super::ai_estimate_message(&estimate, None, Locale::En);
+        assert!(
+            message.contains("would block this question right now (minute_budget)"),
+            "Blocked estimates should explain why: {message}"
+        );
+    }
+
+    #[test]
+    fn cooldown_blocks_only_rapid_repeats_of_the_same_command() {
+        let previous = Some(("projects", 1_000.0));
+        assert!(super::within_command_cooldown(
+            previous, "projects", 1_399.0, false
+        ));
+        assert!(
+            !super::within_command_cooldown(previous, "projects", 1_400.0, false),
+            "The cooldown window should end exactly at the threshold"
+        );
+        assert!(!super::within_command_cooldown(
+            previous, "skills", 1_050.0, false
+        ));
+        assert!(!super::within_command_cooldown(
+            None, "projects", 1_050.0, false
+        ));
+    }
+
+    #[test]
+    fn cooldown_exempts_clear_and_ai_questions() {
+        assert!(!super::within_command_cooldown(
+            Some(("clear", 1_000.0)),
+            "clear",
+            1_010.0,
+            false
+        ));
+        assert!(!super::within_command_cooldown(
+            Some(("Who is Alex?", 1_000.0)),
+            "Who is Alex?",
+            1_010.0,
+            true
+        ));
+    }
+
+    #[test]
+    fn cooldown_uses_injected_clock() {
+        let mut state = AppState::new();
+        state.clock = || 42.0;
+        assert_eq!(state.now_ms(), 42.0);
+    }
+
+    #[test]
+    fn regenerate_targets_a_different_backend() {
+        let order: Vec<String> = ["groq", "google", "openrouter"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        for answered in &order {
+            let alternate = super::alternate_model(Some(answered), Some(answered), &order);
+            assert_ne!(alternate, Some(answered.as_str()));
+            assert!(order.iter().any(|name| Some(name.as_str()) == alternate));
+        }
+        // The backend that actually answered wins over the one requested.
+        assert_eq!(
+            super::alternate_model(Some("google"), Some("groq"), &order),
+            Some("openrouter")
+        );
+        assert_eq!(
+            super::alternate_model(None, Some("openrouter"), &order),
+            Some("groq")
+        );
+        assert_eq!(
+            super::alternate_model(Some("mock"), None, &order),
+            Some("groq")
+        );
+        // A held-back paid backend is missing from the order, so a single
+        // leader leaves nothing to offer, and neither does an older server.
+        let single = vec!["groq".to_string()];
+        assert_eq!(
+            super::alternate_model(Some("groq"), Some("openai"), &single),
+            None
+        );
+        assert_eq!(super::alternate_model(Some("groq"), None, &[]), None);
+    }
+
+    #[test]
+    fn parse_preferred_model_accepts_known_backends() {
+        assert_eq!(super::parse_preferred_model("Groq"), Ok(Some("groq")));
+        assert_eq!(super::parse_preferred_model("gemini"), Ok(Some("google")));
+        assert_eq!(super::parse_preferred_model("auto"), Ok(None));
+        let error = super::parse_preferred_model("claude").expect_err("unknown backend");
+        assert!(
+            error.contains("claude"),
+            "Unknown backend error should echo the input: {error}"
+        );
+    }
+
+    #[test]
+    fn ai_indicator_reflects_preferred_model() {
+        let mut state = AppState::new();
+        assert_eq!(
+            super::ai_indicator_text(&state, Message::AiStatusActive),
+            "AI Mode: Activated"
+        );
+        state.set_ai_preferred_model(Some("openai".to_string()));
+        let label = super::ai_indicator_text(&state, Message::AiStatusActive);
+        assert!(
+            label.contains("openai"),
+            "Indicator should mention the preferred backend: {label}"
+        );
+        assert_eq!(
+            super::ai_indicator_text(&state, Message::AiStatusDeactivated),
+            "AI Mode: Deactivated"
+        );
+    }
+
     #[test]
     fn achievement_views_include_platinum_entry() {
         let state = AppState::new();
@@ -1790,7 +4231,37 @@ mod tests {
             achievements
                 .last()
                 .map(|achievement| achievement.title.as_str()),
-            Some(super::ACHIEVEMENT_PLATINUM_TITLE)
+            Some(i18n::t(Message::AchievementPlatinumTitle, Locale::En))
+        );
+    }
+
+    #[test]
+    fn achievement_views_carry_counter_progress_until_unlocked() {
+        let mut state = AppState::new();
+        state.set_pokemon_capture_chance(37);
+        state.record_cookie_count(64);
+        let progress_of = |state: &AppState, title: Message| {
+            super::Terminal::build_achievement_views(state)
+                .into_iter()
+                .find(|view| view.title == i18n::t(title, Locale::En))
+                .expect("achievement view")
+                .progress
+        };
+        assert_eq!(
+            progress_of(&state, Message::AchievementPokemonTitle),
+            Some((37, 100))
+        );
+        assert_eq!(
+            progress_of(&state, Message::AchievementCookieTitle),
+            Some((64, 100))
+        );
+        assert_eq!(progress_of(&state, Message::AchievementShawTitle), None);
+
+        state.unlock_cookie_rain();
+        assert_eq!(
+            progress_of(&state, Message::AchievementCookieTitle),
+            None,
+            "Unlocked eggs drop their bar"
         );
     }
 
@@ -1809,12 +4280,13 @@ mod tests {
             achievements
                 .first()
                 .map(|achievement| achievement.title.as_str()),
-            Some(super::ACHIEVEMENT_SHAW_TITLE)
+            Some(i18n::t(Message::AchievementShawTitle, Locale::En))
         );
         assert!(achievements
             .iter()
             .take_while(|achievement| achievement.unlocked)
-            .any(|achievement| achievement.title == super::ACHIEVEMENT_PLATINUM_TITLE));
+            .any(|achievement| achievement.title
+                == i18n::t(Message::AchievementPlatinumTitle, Locale::En)));
     }
 
     #[wasm_bindgen_test]
@@ -1,9 +1,15 @@
+pub mod debounce;
+pub mod random;
+pub mod storage;
+
 use serde::de::DeserializeOwned;
 use serde_wasm_bindgen::from_value;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{console, Document, Request, RequestInit, RequestMode, Response};
+use web_sys::{
+    console, Document, Element, HtmlTemplateElement, Request, RequestInit, RequestMode, Response,
+};
 
 pub fn document() -> Result<Document, JsValue> {
     window()
@@ -46,6 +52,120 @@ pub fn open_link(url: &str) {
     }
 }
 
+pub fn copy_to_clipboard(text: &str) -> Result<(), JsValue> {
+    let window = window().ok_or_else(|| JsValue::from_str("Window unavailable"))?;
+    let promise = window.navigator().clipboard().write_text(text);
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = JsFuture::from(promise).await {
+            log(&format!("Failed to write to clipboard: {:?}", err));
+        }
+    });
+    Ok(())
+}
+
+pub fn share_url(base: &str, command_line: &str) -> String {
+    let base = base.split('#').next().unwrap_or_default();
+    let mut url = String::with_capacity(base.len() + command_line.len() + 1);
+    url.push_str(base);
+    url.push('#');
+    for byte in command_line.trim().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    url
+}
+
+pub fn save_data_requested() -> bool {
+    window().is_some_and(|win| navigator_requests_save_data(&win.navigator()))
+}
+
+// `navigator.connection` is Chromium-only and missing from web-sys' stable
+// bindings, so it is read reflectively; absent means "no hint".
+fn navigator_requests_save_data(navigator: &JsValue) -> bool {
+    js_sys::Reflect::get(navigator, &JsValue::from_str("connection"))
+        .ok()
+        .filter(|connection| connection.is_object())
+        .and_then(|connection| {
+            js_sys::Reflect::get(&connection, &JsValue::from_str("saveData")).ok()
+        })
+        .and_then(|flag| flag.as_bool())
+        .unwrap_or(false)
+}
+
+pub fn prefers_reduced_motion() -> bool {
+    window()
+        .and_then(|win| win.match_media("(prefers-reduced-motion: reduce)").ok())
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+pub fn page_base_url() -> Option<String> {
+    let location = window()?.location();
+    let origin = location.origin().ok()?;
+    let path = location.pathname().unwrap_or_default();
+    Some(format!("{origin}{path}"))
+}
+
+pub fn deep_link_command() -> Option<String> {
+    let location = window()?.location();
+    let search = location.search().unwrap_or_default();
+    let encoded = match query_command(&search) {
+        Some(value) => value.replace('+', " "),
+        None => location.hash().ok()?.strip_prefix('#')?.to_string(),
+    };
+    let decoded = js_sys::decode_uri_component(&encoded).ok()?.as_string()?;
+    let trimmed = decoded.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+// Browsers ignore the old `location.reload(true)` flag, so a fresh query
+// parameter is what forces the page past a cached copy of the previous deploy.
+pub fn reload_bypassing_cache() -> Result<(), JsValue> {
+    let Some(window) = window() else {
+        return Ok(());
+    };
+    let location = window.location();
+    let href = location.href()?;
+    let stamp = format!("{:x}", js_sys::Date::now() as u64);
+    location.replace(&cache_busted_url(&href, &stamp))
+}
+
+const CACHE_BUST_PARAM: &str = "_r";
+
+fn cache_busted_url(href: &str, stamp: &str) -> String {
+    let (base, fragment) = match href.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (href, None),
+    };
+    let (path, query) = base.split_once('?').unwrap_or((base, ""));
+    let fresh = format!("{CACHE_BUST_PARAM}={stamp}");
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(CACHE_BUST_PARAM))
+        .chain(std::iter::once(fresh.as_str()))
+        .collect::<Vec<_>>()
+        .join("&");
+    let mut url = format!("{path}?{params}");
+    if let Some(fragment) = fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    url
+}
+
+// Crawlers drop URL fragments, so indexed pages link to `?cmd=<section>`.
+fn query_command(search: &str) -> Option<&str> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("cmd="))
+        .filter(|value| !value.is_empty())
+}
+
 pub fn escape_html(input: &str) -> String {
     let mut escaped = String::with_capacity(input.len());
     for ch in input.chars() {
@@ -61,6 +181,200 @@ pub fn escape_html(input: &str) -> String {
     escaped
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagPolicy {
+    Keep,
+    Unwrap,
+    Drop,
+}
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "audio",
+    "b",
+    "blockquote",
+    "br",
+    "button",
+    "caption",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "section",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+    "video",
+];
+// Dropped together with their content; any other unknown tag is unwrapped
+// so its text survives.
+const DROPPED_TAGS: &[&str] = &[
+    "applet", "base", "embed", "form", "frame", "frameset", "iframe", "input", "link", "math",
+    "meta", "noscript", "object", "script", "select", "style", "svg", "template", "textarea",
+];
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "alt",
+    "autoplay",
+    "class",
+    "colspan",
+    "controls",
+    "datetime",
+    "dir",
+    "download",
+    "height",
+    "href",
+    "id",
+    "lang",
+    "loading",
+    "loop",
+    "muted",
+    "open",
+    "playsinline",
+    "poster",
+    "preload",
+    "rel",
+    "role",
+    "rowspan",
+    "scope",
+    "src",
+    "start",
+    "tabindex",
+    "target",
+    "title",
+    "type",
+    "width",
+];
+const URL_ATTRIBUTES: &[&str] = &["href", "poster", "src"];
+const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+fn tag_policy(tag: &str) -> TagPolicy {
+    let tag = tag.to_ascii_lowercase();
+    if ALLOWED_TAGS.contains(&tag.as_str()) {
+        TagPolicy::Keep
+    } else if DROPPED_TAGS.contains(&tag.as_str()) {
+        TagPolicy::Drop
+    } else {
+        TagPolicy::Unwrap
+    }
+}
+
+fn attribute_allowed(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if name.starts_with("on") {
+        return false;
+    }
+    if name.starts_with("data-") || name.starts_with("aria-") {
+        return true;
+    }
+    ALLOWED_ATTRIBUTES.contains(&name.as_str())
+        && (!URL_ATTRIBUTES.contains(&name.as_str()) || is_safe_url(value))
+}
+
+// Browsers ignore whitespace and control characters inside a scheme, so
+// `java\tscript:` must be caught as well.
+fn is_safe_url(value: &str) -> bool {
+    let compact: String = value
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && !ch.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match compact.find([':', '/', '?', '#']) {
+        Some(index) if compact[index..].starts_with(':') => {
+            SAFE_URL_SCHEMES.contains(&&compact[..index])
+        }
+        _ => true,
+    }
+}
+
+pub fn sanitize_fragment(html: &str) -> Result<String, JsValue> {
+    let template = document()?
+        .create_element("template")?
+        .dyn_into::<HtmlTemplateElement>()?;
+    template.set_inner_html(html);
+    // A static list in document order: children of an unwrapped element are
+    // still visited after it moves them up.
+    let elements = template.content().query_selector_all("*")?;
+    for index in 0..elements.length() {
+        let Some(element) = elements
+            .item(index)
+            .and_then(|node| node.dyn_into::<Element>().ok())
+        else {
+            continue;
+        };
+        match tag_policy(&element.local_name()) {
+            TagPolicy::Keep => strip_attributes(&element)?,
+            TagPolicy::Unwrap => unwrap_element(&element)?,
+            TagPolicy::Drop => element.remove(),
+        }
+    }
+    Ok(template.inner_html())
+}
+
+fn strip_attributes(element: &Element) -> Result<(), JsValue> {
+    for name in element.get_attribute_names().iter() {
+        let Some(name) = name.as_string() else {
+            continue;
+        };
+        let value = element.get_attribute(&name).unwrap_or_default();
+        if !attribute_allowed(&name, &value) {
+            element.remove_attribute(&name)?;
+        }
+    }
+    Ok(())
+}
+
+fn unwrap_element(element: &Element) -> Result<(), JsValue> {
+    if let Some(parent) = element.parent_node() {
+        while let Some(child) = element.first_child() {
+            parent.insert_before(&child, Some(element))?;
+        }
+    }
+    element.remove();
+    Ok(())
+}
+
 pub fn tag_resume_source(url: &str) -> String {
     const CV_HOSTS: &[&str] = &[
         "cv.zqsdev.com",
@@ -148,9 +462,20 @@ pub fn window() -> Option<web_sys::Window> {
     web_sys::window()
 }
 
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
 
     #[test]
     fn escape_html_encodes_special_characters() {
@@ -163,6 +488,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_busted_url_replaces_the_previous_stamp() {
+        assert_eq!(
+            cache_busted_url("https://zqsdev.com/", "1a"),
+            "https://zqsdev.com/?_r=1a"
+        );
+        assert_eq!(
+            cache_busted_url("https://zqsdev.com/?cmd=projects&_r=1a#faq", "2b"),
+            "https://zqsdev.com/?cmd=projects&_r=2b#faq"
+        );
+        assert_eq!(
+            cache_busted_url("https://zqsdev.com/?_run=1", "3c"),
+            "https://zqsdev.com/?_run=1&_r=3c"
+        );
+    }
+
+    #[test]
+    fn query_command_reads_cmd_parameter() {
+        assert_eq!(query_command("?cmd=projects"), Some("projects"));
+        assert_eq!(
+            query_command("?utm_source=x&cmd=education+--detail"),
+            Some("education+--detail")
+        );
+        assert_eq!(query_command("?cmd="), None);
+        assert_eq!(query_command("?command=faq"), None);
+        assert_eq!(query_command(""), None);
+    }
+
+    #[test]
+    fn share_url_encodes_command_into_hash() {
+        assert_eq!(
+            share_url("https://zqsdev.com/", "projects"),
+            "https://zqsdev.com/#projects"
+        );
+        assert_eq!(
+            share_url("https://zqsdev.com/#faq", " education --school Paris "),
+            "https://zqsdev.com/#education%20--school%20Paris"
+        );
+        assert_eq!(
+            share_url("http://localhost:8765/index.html", "skills"),
+            "http://localhost:8765/index.html#skills"
+        );
+    }
+
     #[test]
     fn tag_resume_source_appends_param_without_query() {
         let url = "https://founding.zqsdev.com/";
@@ -208,4 +577,170 @@ mod tests {
             "https://founding.zqsdev.com/?lang=en&from=interactive#top"
         );
     }
+
+    #[wasm_bindgen_test]
+    fn panic_message_extracts_str_and_string_payloads() {
+        let static_payload: Box<dyn std::any::Any + Send> = Box::new("static boom");
+        assert_eq!(panic_message(static_payload.as_ref()), "static boom");
+
+        let owned_payload: Box<dyn std::any::Any + Send> = Box::new(format!("owned {}", 42));
+        assert_eq!(panic_message(owned_payload.as_ref()), "owned 42");
+
+        let opaque_payload: Box<dyn std::any::Any + Send> = Box::new(7_u8);
+        assert_eq!(
+            panic_message(opaque_payload.as_ref()),
+            "unknown panic payload"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn save_data_hint_is_read_from_a_mocked_navigator() {
+        let navigator = js_sys::Object::new();
+        assert!(!navigator_requests_save_data(&navigator));
+
+        let connection = js_sys::Object::new();
+        js_sys::Reflect::set(&navigator, &"connection".into(), &connection).unwrap();
+        assert!(!navigator_requests_save_data(&navigator));
+
+        js_sys::Reflect::set(&connection, &"saveData".into(), &JsValue::TRUE).unwrap();
+        assert!(navigator_requests_save_data(&navigator));
+
+        js_sys::Reflect::set(&connection, &"saveData".into(), &JsValue::FALSE).unwrap();
+        assert!(!navigator_requests_save_data(&navigator));
+    }
+
+    #[test]
+    fn tag_policy_keeps_markup_drops_active_content_and_unwraps_the_rest() {
+        for tag in ["p", "a", "STRONG", "article", "audio", "img", "kbd"] {
+            assert_eq!(tag_policy(tag), TagPolicy::Keep, "{tag}");
+        }
+        for tag in [
+            "script", "IFRAME", "object", "embed", "style", "svg", "form",
+        ] {
+            assert_eq!(tag_policy(tag), TagPolicy::Drop, "{tag}");
+        }
+        for tag in ["blink", "marquee", "custom-element", "font"] {
+            assert_eq!(tag_policy(tag), TagPolicy::Unwrap, "{tag}");
+        }
+    }
+
+    #[test]
+    fn attribute_policy_rejects_handlers_styles_and_unknown_names() {
+        assert!(attribute_allowed("class", "entry"));
+        assert!(attribute_allowed("data-command", "projects"));
+        assert!(attribute_allowed("aria-label", "Sections"));
+        assert!(attribute_allowed("href", "https://example.com"));
+        assert!(!attribute_allowed("onclick", "alert(1)"));
+        assert!(!attribute_allowed("ONERROR", "alert(1)"));
+        assert!(!attribute_allowed("onpointerenter", ""));
+        assert!(!attribute_allowed("style", "position:fixed"));
+        assert!(!attribute_allowed("formaction", "https://example.com"));
+        assert!(!attribute_allowed("srcdoc", "<script></script>"));
+    }
+
+    #[test]
+    fn url_policy_allows_web_and_relative_links_only() {
+        for url in [
+            "https://cv.zqsdev.com/?from=interactive",
+            "http://example.com",
+            "mailto:alex@example.com",
+            "tel:+33123456789",
+            "./effects/kamehameha.gif",
+            "/files/resume.pdf",
+            "#section-awards",
+            "?cmd=projects",
+            "docs/a:b",
+            "",
+        ] {
+            assert!(is_safe_url(url), "{url}");
+        }
+        for url in [
+            "javascript:alert(1)",
+            "JavaScript:alert(1)",
+            "  javascript:alert(1)",
+            "java\tscript:alert(1)",
+            "java\nscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "vbscript:msgbox(1)",
+            "data:text/html,<script>alert(1)</script>",
+        ] {
+            assert!(!is_safe_url(url), "{url:?}");
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_removes_scripts_and_embedded_frames() {
+        let html = sanitize_fragment(
+            "<p>Hi</p><script>alert(1)</script><iframe src=\"https://evil.example\"></iframe>\
+             <object data=\"x.swf\"></object><embed src=\"x.swf\"><style>body{display:none}</style>",
+        )
+        .unwrap();
+        assert_eq!(html, "<p>Hi</p>");
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_strips_event_handlers_and_styles() {
+        let html = sanitize_fragment(
+            "<img src=\"./a.png\" alt=\"a\" onerror=\"alert(1)\">\
+             <div class=\"card\" onmouseover=\"alert(1)\" style=\"position:fixed\">Card</div>",
+        )
+        .unwrap();
+        assert_eq!(
+            html,
+            "<img src=\"./a.png\" alt=\"a\"><div class=\"card\">Card</div>"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_drops_javascript_urls_but_keeps_the_link_text() {
+        let html = sanitize_fragment(
+            "<a href=\"javascript:alert(1)\">one</a>\
+             <a href=\"jav&#x61;script:alert(1)\">two</a>\
+             <a href=\" JAVA&#9;SCRIPT:alert(1)\">three</a>\
+             <a href=\"data:text/html,x\">four</a>\
+             <img src=\"javascript:alert(1)\">",
+        )
+        .unwrap();
+        assert_eq!(html, "<a>one</a><a>two</a><a>three</a><a>four</a><img>");
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_unwraps_unknown_tags_and_cleans_their_children() {
+        let html = sanitize_fragment(
+            "<marquee><b onclick=\"x()\">bold</b> text<script>alert(1)</script></marquee>",
+        )
+        .unwrap();
+        assert_eq!(html, "<b>bold</b> text");
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_drops_svg_and_nested_hostile_markup() {
+        let html = sanitize_fragment(
+            "<svg><script>alert(1)</script><a href=\"javascript:alert(1)\">x</a></svg>\
+             <div><div><span><img src=x onerror=alert(1)></span></div></div>\
+             <template><img src=x onerror=alert(1)></template>",
+        )
+        .unwrap();
+        assert_eq!(html, "<div><div><span><img src=\"x\"></span></div></div>");
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_survives_broken_markup_without_reviving_it() {
+        let html = sanitize_fragment(
+            "<p>open <scr<script>ipt>alert(1)</script> <img src=x onerror=alert(1)//",
+        )
+        .unwrap();
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("onerror"), "{html}");
+        assert!(html.starts_with("<p>open "), "{html}");
+    }
+
+    #[wasm_bindgen_test]
+    fn sanitize_fragment_keeps_text_and_allowed_markup_untouched() {
+        let original = "<p>Plain &amp; <strong>bold</strong> with <kbd>u</kbd></p>\
+             <button type=\"button\" class=\"chip\" data-command=\"update\" aria-label=\"Update\">update</button>\
+             <audio class=\"clip\" src=\"./effects/kamehameha.mp3\" preload=\"auto\" autoplay=\"\"></audio>";
+        assert_eq!(sanitize_fragment(original).unwrap(), original);
+        assert_eq!(sanitize_fragment("").unwrap(), "");
+    }
 }
@@ -0,0 +1,137 @@
+use gloo_timers::callback::Timeout;
+use std::cell::Cell;
+use std::rc::Rc;
+
+pub type Task = Box<dyn FnOnce()>;
+
+// Timer seam so debouncing and batching can be driven by hand in tests.
+pub trait Scheduler {
+    fn schedule(&self, delay_ms: u32, task: Task);
+}
+
+pub struct BrowserScheduler;
+
+impl Scheduler for BrowserScheduler {
+    fn schedule(&self, delay_ms: u32, task: Task) {
+        Timeout::new(delay_ms, task).forget();
+    }
+}
+
+// Trailing-edge debounce: only the last call within `delay_ms` runs. Stale
+// timers stay scheduled but find their generation outdated and do nothing.
+pub struct Debouncer {
+    delay_ms: u32,
+    generation: Rc<Cell<u64>>,
+    scheduler: Rc<dyn Scheduler>,
+}
+
+impl Debouncer {
+    pub fn new(delay_ms: u32, scheduler: Rc<dyn Scheduler>) -> Self {
+        Self {
+            delay_ms,
+            generation: Rc::new(Cell::new(0)),
+            scheduler,
+        }
+    }
+
+    pub fn call(&self, task: impl FnOnce() + 'static) {
+        let ticket = self.generation.get() + 1;
+        self.generation.set(ticket);
+        let generation = Rc::clone(&self.generation);
+        self.scheduler.schedule(
+            self.delay_ms,
+            Box::new(move || {
+                if generation.get() == ticket {
+                    task();
+                }
+            }),
+        );
+    }
+
+    pub fn cancel(&self) {
+        self.generation.set(self.generation.get() + 1);
+    }
+}
+
+#[cfg(test)]
+pub mod manual {
+    use super::{Scheduler, Task};
+    use std::cell::{Cell, RefCell};
+
+    // Runs scheduled tasks only when the test advances the clock.
+    #[derive(Default)]
+    pub struct ManualScheduler {
+        now_ms: Cell<u64>,
+        queue: RefCell<Vec<(u64, Task)>>,
+    }
+
+    impl ManualScheduler {
+        pub fn advance(&self, ms: u64) {
+            let target = self.now_ms.get() + ms;
+            loop {
+                let next = {
+                    let mut queue = self.queue.borrow_mut();
+                    let due = queue
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, (at, _))| *at <= target)
+                        .min_by_key(|(_, (at, _))| *at)
+                        .map(|(index, _)| index);
+                    due.map(|index| queue.remove(index))
+                };
+                let Some((at, task)) = next else {
+                    break;
+                };
+                self.now_ms.set(at);
+                task();
+            }
+            self.now_ms.set(target);
+        }
+
+        pub fn pending(&self) -> usize {
+            self.queue.borrow().len()
+        }
+    }
+
+    impl Scheduler for ManualScheduler {
+        fn schedule(&self, delay_ms: u32, task: Task) {
+            let at = self.now_ms.get() + u64::from(delay_ms);
+            self.queue.borrow_mut().push((at, task));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::manual::ManualScheduler;
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn only_the_last_call_in_a_burst_runs() {
+        let scheduler = Rc::new(ManualScheduler::default());
+        let debouncer = Debouncer::new(80, scheduler.clone());
+        let runs = Rc::new(RefCell::new(Vec::new()));
+        for value in 0..5 {
+            let runs = Rc::clone(&runs);
+            debouncer.call(move || runs.borrow_mut().push(value));
+            scheduler.advance(20);
+        }
+        assert!(runs.borrow().is_empty(), "Still inside the quiet window");
+        scheduler.advance(80);
+        assert_eq!(*runs.borrow(), [4]);
+    }
+
+    #[test]
+    fn cancel_drops_the_pending_call() {
+        let scheduler = Rc::new(ManualScheduler::default());
+        let debouncer = Debouncer::new(80, scheduler.clone());
+        let ran = Rc::new(Cell::new(false));
+        let flag = Rc::clone(&ran);
+        debouncer.call(move || flag.set(true));
+        debouncer.cancel();
+        scheduler.advance(200);
+        assert!(!ran.get());
+        assert_eq!(scheduler.pending(), 0);
+    }
+}
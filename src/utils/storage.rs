@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use web_sys::Storage;
+
+const PROBE_KEY: &str = "__zqs_storage_probe__";
+
+pub const UNAVAILABLE_NOTICE: &str =
+    "💾 Browser storage is unavailable (private mode?). Preferences and achievements won't be saved this session.";
+
+enum Backend {
+    Local(Storage),
+    Memory(HashMap<String, String>),
+}
+
+thread_local! {
+    static BACKEND: RefCell<Option<Backend>> = const { RefCell::new(None) };
+}
+
+pub fn probe() {
+    let storage = super::window().and_then(|window| window.local_storage().ok().flatten());
+    install(storage);
+}
+
+pub fn is_persistent() -> bool {
+    with_backend(|backend| matches!(backend, Backend::Local(_)))
+}
+
+pub fn get(key: &str) -> Option<String> {
+    with_backend(|backend| match backend {
+        Backend::Local(storage) => storage.get_item(key).unwrap_or_else(|err| {
+            super::log(&format!("Failed to read {key} from storage: {:?}", err));
+            None
+        }),
+        Backend::Memory(values) => values.get(key).cloned(),
+    })
+}
+
+pub fn set(key: &str, value: &str) {
+    with_backend(|backend| {
+        if let Backend::Local(storage) = backend {
+            match storage.set_item(key, value) {
+                Ok(()) => return,
+                Err(err) => {
+                    // Quota or privacy errors can start mid-session; keep the
+                    // rest of the session consistent in memory.
+                    super::log(&format!(
+                        "Failed to write {key} to storage, keeping it in memory: {:?}",
+                        err
+                    ));
+                    *backend = Backend::Memory(HashMap::new());
+                }
+            }
+        }
+        if let Backend::Memory(values) = backend {
+            values.insert(key.to_string(), value.to_string());
+        }
+    });
+}
+
+pub fn remove(key: &str) {
+    with_backend(|backend| match backend {
+        Backend::Local(storage) => {
+            if let Err(err) = storage.remove_item(key) {
+                super::log(&format!("Failed to remove {key} from storage: {:?}", err));
+            }
+        }
+        Backend::Memory(values) => {
+            values.remove(key);
+        }
+    });
+}
+
+fn install(storage: Option<Storage>) -> bool {
+    let backend = match storage.filter(storage_is_writable) {
+        Some(storage) => Backend::Local(storage),
+        None => Backend::Memory(HashMap::new()),
+    };
+    let persistent = matches!(backend, Backend::Local(_));
+    BACKEND.with(|cell| *cell.borrow_mut() = Some(backend));
+    persistent
+}
+
+fn storage_is_writable(storage: &Storage) -> bool {
+    storage
+        .set_item(PROBE_KEY, PROBE_KEY)
+        .and_then(|_| storage.remove_item(PROBE_KEY))
+        .is_ok()
+}
+
+fn with_backend<T>(operation: impl FnOnce(&mut Backend) -> T) -> T {
+    if BACKEND.with(|cell| cell.borrow().is_none()) {
+        probe();
+    }
+    BACKEND.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        let backend = slot.get_or_insert_with(|| Backend::Memory(HashMap::new()));
+        operation(backend)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn storage_shim(body: &str) -> Storage {
+        js_sys::Function::new_no_args(body)
+            .call0(&wasm_bindgen::JsValue::NULL)
+            .expect("shim should build")
+            .unchecked_into::<Storage>()
+    }
+
+    #[test]
+    fn missing_storage_falls_back_to_memory() {
+        assert!(!install(None));
+        assert!(!is_persistent());
+        assert_eq!(get("locale"), None);
+        set("locale", "fr");
+        assert_eq!(get("locale").as_deref(), Some("fr"));
+        remove("locale");
+        assert_eq!(get("locale"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn throwing_storage_is_detected_by_the_probe() {
+        let shim = storage_shim(
+            "return { getItem() { return null; }, \
+                      setItem() { throw new Error('QuotaExceededError'); }, \
+                      removeItem() {} };",
+        );
+        assert!(!install(Some(shim)), "A throwing setItem means no storage");
+        assert!(!is_persistent());
+        set("achievements", "{}");
+        assert_eq!(get("achievements").as_deref(), Some("{}"));
+    }
+
+    #[wasm_bindgen_test]
+    fn working_storage_is_used_until_a_write_throws() {
+        let shim = storage_shim(
+            "const values = {}; let full = false; \
+             return { values, fill() { full = true; }, \
+                      getItem(key) { return key in values ? values[key] : null; }, \
+                      setItem(key, value) { if (full) { throw new Error('QuotaExceededError'); } values[key] = String(value); }, \
+                      removeItem(key) { delete values[key]; } };",
+        );
+        assert!(install(Some(shim.clone())));
+        set("locale", "fr");
+        assert_eq!(
+            shim.get_item("locale").expect("read"),
+            Some("fr".to_string())
+        );
+        assert_eq!(get("locale").as_deref(), Some("fr"));
+
+        let fill = js_sys::Reflect::get(&shim, &"fill".into())
+            .expect("fill")
+            .unchecked_into::<js_sys::Function>();
+        fill.call0(&shim).expect("fill storage");
+        set("achievements", "{}");
+        assert!(!is_persistent(), "A failing write degrades to memory");
+        assert_eq!(get("achievements").as_deref(), Some("{}"));
+    }
+}